@@ -23,6 +23,7 @@ pub enum TaskType {
     Fix,
     Test,
     Review,
+    Document,
     Custom(String),
 }
 
@@ -42,6 +43,28 @@ pub struct AgentContext {
 }
 
 impl AgentContext {
+    /// Copia de `task` con el `context` recortado al `max_context_tokens` del modelo primario
+    /// (ver `agents::context_budget`). Sin límite configurado se devuelve tal cual.
+    pub fn fit_task_to_model(&self, task: &Task) -> Task {
+        let (Some(max_tokens), Some(context)) = (self.config.primary_model.max_context_tokens, &task.context) else {
+            return task.clone();
+        };
+        let budget = max_tokens
+            .saturating_sub(crate::agents::context_budget::RESERVED_TOKENS)
+            .saturating_sub(crate::agents::context_budget::estimate_tokens(&task.description));
+        let fitted = crate::agents::context_budget::fit_context(context, budget);
+        if fitted.trimmed_tokens > 0 {
+            log::debug!(
+                "Contexto de la tarea {} recortado: ~{} tokens y {} archivo(s) omitidos (max_context_tokens = {})",
+                task.id,
+                fitted.trimmed_tokens,
+                fitted.dropped_sections,
+                max_tokens
+            );
+        }
+        Task { context: Some(fitted.context), ..task.clone() }
+    }
+
     pub fn build_rag_context(&self, file_path: &std::path::Path) -> String {
         let mut ctx = String::new();
         if let Some(ref db) = self.index_db {
@@ -74,6 +97,8 @@ impl AgentContext {
 
     /// Builds a project-wide architectural context block from the SQLite index.
     /// Used by the Review handler to give the LLM structural insight beyond code samples.
+    /// Includes the imports between controller/service/repository files and flags the ones
+    /// that break that order (see `index::layers`).
     /// Returns empty string if the index is empty or unavailable.
     pub fn build_architectural_context(&self) -> String {
         let Some(ref db) = self.index_db else {
@@ -83,8 +108,9 @@ impl AgentContext {
         let symbols = db.get_symbols(200);
         let calls = db.get_call_graph(100);
         let imports = db.get_import_usage(100);
+        let layer_deps = db.layer_dependencies();
 
-        if symbols.is_empty() && calls.is_empty() && imports.is_empty() {
+        if symbols.is_empty() && calls.is_empty() && imports.is_empty() && layer_deps.is_empty() {
             return String::new();
         }
 
@@ -114,6 +140,26 @@ impl AgentContext {
             }
         }
 
+        if !layer_deps.is_empty() {
+            ctx.push_str(&format!("\nDependencias entre capas controller → service → repository ({}):\n", layer_deps.len()));
+            for dep in layer_deps.iter().take(100) {
+                ctx.push_str(&format!(
+                    "  {} [{}] → {} [{}]\n",
+                    dep.from,
+                    dep.from_layer.as_str(),
+                    dep.to,
+                    dep.to_layer.as_str()
+                ));
+            }
+            let violations: Vec<_> = layer_deps.iter().filter_map(|dep| Some((dep, dep.violation()?))).collect();
+            if !violations.is_empty() {
+                ctx.push_str(&format!("\nViolaciones de capas ({}):\n", violations.len()));
+                for (dep, reason) in violations {
+                    ctx.push_str(&format!("  {} importa {} ({})\n", dep.from, dep.to, reason));
+                }
+            }
+        }
+
         ctx
     }
 }
@@ -127,3 +173,44 @@ pub trait Agent: Send + Sync {
     /// Ejecuta una tarea asignada al agente
     async fn execute(&self, task: &Task, context: &AgentContext) -> anyhow::Result<TaskResult>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_architectural_context_flags_controller_importing_repository() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        {
+            let conn = db.lock();
+            for file in ["src/users/user.controller.ts", "src/users/user.service.ts", "src/users/user.repository.ts"] {
+                conn.execute("INSERT INTO file_index (file_path, content_hash) VALUES (?, 'h')", [file]).unwrap();
+            }
+            for (file, name, src) in [
+                ("src/users/user.controller.ts", "UserService", "./user.service"),
+                ("src/users/user.controller.ts", "UserRepository", "./user.repository"),
+                ("src/users/user.service.ts", "UserRepository", "./user.repository"),
+            ] {
+                conn.execute(
+                    "INSERT INTO import_usage (file_path, import_name, import_src) VALUES (?, ?, ?)",
+                    [file, name, src],
+                )
+                .unwrap();
+            }
+        }
+        let ctx = AgentContext {
+            config: Arc::new(SentinelConfig::default()),
+            stats: Arc::new(Mutex::new(SentinelStats::default())),
+            project_root: tmp.path().to_path_buf(),
+            index_db: Some(Arc::new(db)),
+        };
+
+        let text = ctx.build_architectural_context();
+        assert!(text.contains("src/users/user.controller.ts [controller] → src/users/user.repository.ts [repository]"), "{}", text);
+        assert!(text.contains("src/users/user.service.ts [service] → src/users/user.repository.ts [repository]"));
+        let violations = text.split("Violaciones de capas (1):").nth(1).expect("violations section");
+        assert!(violations.contains("src/users/user.controller.ts importa src/users/user.repository.ts (se salta la capa service)"));
+        assert!(!violations.contains("user.service.ts importa"));
+    }
+}
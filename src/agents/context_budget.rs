@@ -0,0 +1,99 @@
+//! Ajuste del `context` de una tarea al `max_context_tokens` del modelo.
+//!
+//! Los tokens se estiman como en `ai::client` (~4 caracteres por token). Si el contexto
+//! está formado por secciones `=== archivo ===` (review/audit), se descartan primero las
+//! últimas, que son las de menor prioridad; si aun así no cabe, se recorta el texto
+//! restante en un salto de línea y se deja una marca.
+
+/// Tokens reservados para las instrucciones del prompt y la respuesta del modelo.
+pub const RESERVED_TOKENS: usize = 5_000;
+
+const SECTION_MARKER: &str = "\n\n=== ";
+
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Resultado de `fit_context`.
+#[derive(Debug, PartialEq)]
+pub struct FittedContext {
+    pub context: String,
+    pub trimmed_tokens: usize,
+    pub dropped_sections: usize,
+}
+
+/// Recorta `context` para que quepa en `budget_tokens`.
+pub fn fit_context(context: &str, budget_tokens: usize) -> FittedContext {
+    let original = estimate_tokens(context);
+    if original <= budget_tokens {
+        return FittedContext { context: context.to_string(), trimmed_tokens: 0, dropped_sections: 0 };
+    }
+
+    let notice = |tokens: usize| format!("\n[… contexto recortado: ~{} tokens omitidos …]", tokens);
+    // La marca final también consume presupuesto
+    let budget_tokens = budget_tokens.saturating_sub(estimate_tokens(&notice(original)));
+
+    // Secciones por archivo, en orden de prioridad (la primera es la más importante)
+    let mut starts: Vec<usize> = context.match_indices(SECTION_MARKER).map(|(i, _)| i).collect();
+    let mut dropped_sections = 0;
+    let mut kept = context;
+    while starts.len() > 1 && estimate_tokens(kept) > budget_tokens {
+        let last = starts.pop().unwrap_or(0);
+        kept = &context[..last];
+        dropped_sections += 1;
+    }
+
+    let mut fitted = kept.to_string();
+    if estimate_tokens(&fitted) > budget_tokens {
+        let max_chars = budget_tokens.saturating_mul(4);
+        let mut cut = max_chars.min(fitted.len());
+        while !fitted.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let cut = fitted[..cut].rfind('\n').unwrap_or(cut);
+        fitted.truncate(cut);
+    }
+    let trimmed_tokens = original.saturating_sub(estimate_tokens(&fitted));
+    fitted.push_str(&notice(trimmed_tokens));
+    FittedContext { context: fitted, trimmed_tokens, dropped_sections }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(name: &str, lines: usize) -> String {
+        let body: Vec<String> = (0..lines).map(|i| format!("const {}_{} = {};", name, i, i)).collect();
+        format!("\n\n=== src/{}.ts ===\n{}", name, body.join("\n"))
+    }
+
+    #[test]
+    fn test_fit_context_drops_lowest_priority_sections_first() {
+        let context = format!("{}{}{}", section("core", 20), section("auth", 20), section("jobs", 20));
+        let per_section = estimate_tokens(&section("core", 20));
+
+        // Cabe entero: sin cambios
+        let untouched = fit_context(&context, estimate_tokens(&context));
+        assert_eq!((untouched.trimmed_tokens, untouched.dropped_sections), (0, 0));
+        assert_eq!(untouched.context, context);
+
+        // Presupuesto para 2 secciones y la marca: se descarta la última (jobs)
+        let budget = per_section * 2 + 20;
+        let fitted = fit_context(&context, budget);
+        assert_eq!(fitted.dropped_sections, 1);
+        assert!(fitted.context.contains("=== src/core.ts ===") && fitted.context.contains("=== src/auth.ts ==="));
+        assert!(!fitted.context.contains("jobs"));
+        assert!(estimate_tokens(&fitted.context) <= budget);
+        assert!(fitted.trimmed_tokens > 0);
+        assert!(fitted.context.ends_with("omitidos …]"));
+    }
+
+    #[test]
+    fn test_fit_context_truncates_single_block_at_line_boundary() {
+        let context = section("core", 200);
+        let fitted = fit_context(&context, 100);
+        assert!(estimate_tokens(&fitted.context) <= 100, "got {}", estimate_tokens(&fitted.context));
+        let body = fitted.context.rsplit_once("\n[…").unwrap().0;
+        assert!(body.ends_with(';'), "must cut after a full line: {:?}", &body[body.len() - 20..]);
+    }
+}
@@ -0,0 +1,340 @@
+//! DocAgent: docstring de un símbolo concreto (JSDoc, comentario de Go o `///` de Rust)
+//! insertado encima de su declaración.
+//!
+//! La IA solo redacta el texto; la localización del símbolo, el formato del comentario y
+//! la detección de docstrings existentes son deterministas, así que repetir la tarea sobre
+//! un símbolo ya documentado no cambia el archivo.
+
+use crate::agents::base::{Agent, AgentContext, Task, TaskResult, TaskType};
+use crate::ai::client::consultar_ia_dinamico;
+use async_trait::async_trait;
+use regex::Regex;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Formato de comentario de documentación según el lenguaje del archivo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocStyle {
+    JsDoc,
+    Go,
+    Rust,
+}
+
+impl DocStyle {
+    pub fn for_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::JsDoc),
+            "go" => Some(Self::Go),
+            "rs" => Some(Self::Rust),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::JsDoc => "JSDoc",
+            Self::Go => "comentario de documentación de Go",
+            Self::Rust => "doc comment de Rust (///)",
+        }
+    }
+
+    /// `true` si las líneas justo encima de `start` son un docstring de este estilo. En JS/TS
+    /// solo cuenta un bloque `/** … */`: un `/* … */` corriente no documenta el símbolo.
+    fn documents(self, lines: &[&str], start: usize) -> bool {
+        let Some(prev) = start.checked_sub(1).map(|i| lines[i].trim()) else {
+            return false;
+        };
+        match self {
+            Self::JsDoc => {
+                prev.ends_with("*/")
+                    && lines[..start]
+                        .iter()
+                        .rev()
+                        .find_map(|l| l.rfind("/*").map(|i| &l[i..]))
+                        .is_some_and(|open| open.starts_with("/**") && !open.starts_with("/**/"))
+            }
+            Self::Go => prev.starts_with("//"),
+            Self::Rust => prev.starts_with("///") || prev.starts_with("#[doc"),
+        }
+    }
+}
+
+/// Línea (0-based) donde se declara `symbol`: funciones, clases, tipos, variables,
+/// métodos de clase, `func` de Go (incluidos métodos con receiver) y `fn`/`struct` de Rust.
+pub fn find_symbol_line(content: &str, symbol: &str) -> Option<usize> {
+    let name = regex::escape(symbol);
+    let declaration = Regex::new(&format!(
+        r"^\s*(?:export\s+)?(?:default\s+)?(?:pub(?:\([^)]*\))?\s+)?(?:declare\s+|abstract\s+|async\s+|unsafe\s+)*(?:function\*?|class|interface|type|enum|const|let|var|fn|struct|trait|func)\s+(?:\([^)]*\)\s*)?{}\b",
+        name
+    ))
+    .ok()?;
+    let method = Regex::new(&format!(
+        r"^\s*(?:(?:public|private|protected|static|readonly|async|override|get|set)\s+)*{}\s*[<(]",
+        name
+    ))
+    .ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .iter()
+        .position(|l| declaration.is_match(l))
+        .or_else(|| lines.iter().position(|l| method.is_match(l) && !l.trim_end().ends_with(';')))
+}
+
+/// Primera línea del bloque de la declaración: sube por encima de decoradores (`@Get()`)
+/// y atributos (`#[derive]`), que deben quedar entre el docstring y el símbolo. En Go
+/// también por encima de las directivas (`//go:generate`, `//nolint`), que godoc no
+/// cuenta como documentación.
+fn declaration_start(lines: &[&str], symbol_line: usize, style: DocStyle) -> usize {
+    let mut start = symbol_line;
+    while start > 0 {
+        let prev = lines[start - 1].trim_start();
+        if prev.starts_with('@')
+            || (prev.starts_with("#[") && !prev.starts_with("#[doc"))
+            || (style == DocStyle::Go && is_go_directive(prev))
+        {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// `//` seguido directamente de texto (`//go:embed`, `//nolint:errcheck`, `//export F`):
+/// una directiva, no un comentario.
+fn is_go_directive(line: &str) -> bool {
+    line.strip_prefix("//")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| !c.is_whitespace() && c != '/')
+}
+
+/// Texto del docstring a partir de la respuesta de la IA: el primer bloque de código si lo
+/// hay, sin marcadores de comentario (el formato lo pone `format_docstring`).
+pub fn clean_doc_text(response: &str) -> String {
+    let raw = crate::ai::utils::extraer_codigo_opcional(response).unwrap_or_else(|| response.to_string());
+    let lines: Vec<String> = raw
+        .lines()
+        .map(|l| {
+            let t = l.trim();
+            let t = t.strip_prefix("/**").or_else(|| t.strip_prefix("///")).or_else(|| t.strip_prefix("//")).unwrap_or(t);
+            let t = t.strip_suffix("*/").unwrap_or(t).trim_end();
+            let t = t.strip_prefix('*').unwrap_or(t);
+            t.strip_prefix(' ').unwrap_or(t).to_string()
+        })
+        .collect();
+    let first = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(lines.len());
+    let last = lines.iter().rposition(|l| !l.trim().is_empty()).map_or(first, |i| i + 1);
+    lines[first..last].join("\n")
+}
+
+/// Comentario de documentación con la indentación de la declaración.
+pub fn format_docstring(style: DocStyle, indent: &str, text: &str) -> String {
+    let prefixed = |prefix: &str| -> Vec<String> {
+        text.lines()
+            .map(|l| if l.trim().is_empty() { format!("{}{}", indent, prefix) } else { format!("{}{} {}", indent, prefix, l) })
+            .collect()
+    };
+    match style {
+        DocStyle::JsDoc => {
+            let mut out = vec![format!("{}/**", indent)];
+            out.extend(prefixed(" *"));
+            out.push(format!("{} */", indent));
+            out.join("\n")
+        }
+        DocStyle::Go => prefixed("//").join("\n"),
+        DocStyle::Rust => prefixed("///").join("\n"),
+    }
+}
+
+/// `content` con el docstring `text` encima de `symbol`, o `None` si el símbolo ya está
+/// documentado. Error si el símbolo no aparece en el archivo.
+pub fn insert_docstring(content: &str, symbol: &str, style: DocStyle, text: &str) -> anyhow::Result<Option<String>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let symbol_line = find_symbol_line(content, symbol)
+        .ok_or_else(|| anyhow::anyhow!("No se encontró la declaración de '{}'", symbol))?;
+    let start = declaration_start(&lines, symbol_line, style);
+    if style.documents(&lines, start) {
+        return Ok(None);
+    }
+
+    let line = lines[symbol_line];
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let mut out: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    out.push(format_docstring(style, indent, text));
+    out.extend(lines[start..].iter().map(|l| l.to_string()));
+    let mut updated = out.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Ok(Some(updated))
+}
+
+/// Tarea de `DocAgent`: `context` lleva el nombre del símbolo; el agente lee el archivo.
+pub fn document_task(file: &str, symbol: &str) -> Task {
+    Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: format!("DOCUMENTACIÓN: docstring para '{}' en '{}'", symbol, file),
+        task_type: TaskType::Document,
+        file_path: Some(PathBuf::from(file)),
+        context: Some(symbol.to_string()),
+    }
+}
+
+#[derive(Default)]
+pub struct DocAgent;
+
+impl DocAgent {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn build_prompt(&self, file: &str, symbol: &str, style: DocStyle, content: &str, context: &AgentContext) -> String {
+        format!(
+            "Actúa como un Desarrollador Senior experto en {} que documenta código para su equipo.\n\n\
+            TAREA:\n\
+            Redacta el {} del símbolo `{}` del archivo {}.\n\n\
+            REQUISITOS:\n\
+            1. Describe qué hace y por qué existe, no cómo está implementado línea a línea.\n\
+            2. JSDoc: incluye @param por parámetro, @returns si devuelve algo y @throws si lanza errores.\n\
+            3. Go: la primera frase empieza por el nombre del símbolo.\n\
+            4. Rust: resumen de una línea; añade secciones `# Errors` o `# Panics` solo si aplican.\n\
+            5. Responde SOLO con el texto del docstring dentro de un bloque ```text, SIN marcadores de comentario (/**, *, //, ///).\n\n\
+            ARCHIVO:\n```\n{}\n```\n",
+            context.config.framework,
+            style.name(),
+            symbol,
+            file,
+            content
+        )
+    }
+}
+
+#[async_trait]
+impl Agent for DocAgent {
+    fn name(&self) -> &str {
+        "DocAgent"
+    }
+
+    fn description(&self) -> &str {
+        "Genera docstrings (JSDoc, Go, Rust) para símbolos individuales sin duplicar los existentes"
+    }
+
+    async fn execute(&self, task: &Task, context: &AgentContext) -> anyhow::Result<TaskResult> {
+        let file = task
+            .file_path
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("DocAgent necesita un archivo"))?;
+        let symbol = task
+            .context
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("DocAgent necesita el nombre del símbolo"))?;
+        let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let style = DocStyle::for_extension(ext)
+            .ok_or_else(|| anyhow::anyhow!("Lenguaje no soportado para docstrings: .{}", ext))?;
+        let path = crate::files::secure_join(&context.project_root, file).map_err(|e| anyhow::anyhow!(e))?;
+        let content = std::fs::read_to_string(&path)?;
+
+        // Comprobar antes de llamar a la IA: ya documentado o símbolo inexistente
+        if insert_docstring(&content, symbol, style, "")?.is_none() {
+            return Ok(TaskResult {
+                success: true,
+                output: format!("'{}' ya tiene docstring", symbol),
+                files_modified: vec![],
+                artifacts: vec![],
+            });
+        }
+
+        let file_label = file.display().to_string();
+        let prompt = crate::agents::prompts::custom_prompt(context, self.name(), task)
+            .unwrap_or_else(|| self.build_prompt(&file_label, symbol, style, &content, context));
+        let config_clone = context.config.clone();
+        let stats_clone = Arc::clone(&context.stats);
+        let project_root_clone = context.project_root.clone();
+        let response = tokio::task::spawn_blocking(move || {
+            consultar_ia_dinamico(
+                prompt,
+                crate::ai::client::TaskType::Light,
+                &config_clone,
+                stats_clone,
+                &project_root_clone,
+            )
+        })
+        .await??;
+
+        let text = clean_doc_text(&response);
+        if text.is_empty() {
+            anyhow::bail!("La IA no devolvió texto para el docstring");
+        }
+        let updated = insert_docstring(&content, symbol, style, &text)?.unwrap_or(content);
+        Ok(TaskResult {
+            success: true,
+            output: text,
+            files_modified: vec![file.clone()],
+            artifacts: vec![updated],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVICE: &str = "\
+import { db } from './db';
+
+export class UsersService {
+  @Cacheable()
+  async findById(id: string) {
+    return db.users.find(id);
+  }
+}
+
+export function normalizeEmail(email: string): string {
+  return email.trim().toLowerCase();
+}
+";
+
+    #[test]
+    fn test_jsdoc_is_inserted_above_symbol_once() {
+        let text = clean_doc_text("```text\nNormaliza un email para compararlo.\n\n@param email Email tal como lo escribió el usuario\n@returns Email sin espacios y en minúsculas\n```");
+        let once = insert_docstring(SERVICE, "normalizeEmail", DocStyle::JsDoc, &text).unwrap().unwrap();
+        assert!(once.contains(
+            "/**\n * Normaliza un email para compararlo.\n *\n * @param email Email tal como lo escribió el usuario\n * @returns Email sin espacios y en minúsculas\n */\nexport function normalizeEmail("
+        ));
+        assert!(once.ends_with('\n'));
+
+        // Segunda ejecución: ya documentado, no se vuelve a insertar
+        assert!(insert_docstring(&once, "normalizeEmail", DocStyle::JsDoc, &text).unwrap().is_none());
+
+        // Métodos: indentado y por encima del decorador
+        let method = insert_docstring(SERVICE, "findById", DocStyle::JsDoc, "Busca un usuario.").unwrap().unwrap();
+        assert!(method.contains("  /**\n   * Busca un usuario.\n   */\n  @Cacheable()\n  async findById("));
+
+        assert!(insert_docstring(SERVICE, "missing", DocStyle::JsDoc, "x").is_err());
+
+        // Un comentario de bloque corriente (o una licencia) no es un docstring
+        let plain = "/* eslint-disable */\nexport function f() {}\n";
+        let out = insert_docstring(plain, "f", DocStyle::JsDoc, "Hace f.").unwrap().unwrap();
+        assert!(out.contains("/* eslint-disable */\n/**\n * Hace f.\n */\nexport function f()"));
+        let multiline = "/*\n * Copyright\n */\nexport function f() {}\n";
+        assert!(insert_docstring(multiline, "f", DocStyle::JsDoc, "Hace f.").unwrap().is_some());
+        assert!(insert_docstring("/** Hace f. */\nexport function f() {}\n", "f", DocStyle::JsDoc, "x").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_go_and_rust_styles() {
+        let go = "package users\n\nfunc (s *Store) Find(id string) *User {\n\treturn nil\n}\n";
+        let out = insert_docstring(go, "Find", DocStyle::Go, "Find busca un usuario por id.").unwrap().unwrap();
+        assert!(out.contains("// Find busca un usuario por id.\nfunc (s *Store) Find("));
+
+        // Las directivas no son documentación: el docstring va encima de ellas
+        let lint = "package users\n\n//nolint:gocyclo\n//go:noinline\nfunc Parse(s string) int {\n\treturn 0\n}\n";
+        let out = insert_docstring(lint, "Parse", DocStyle::Go, "Parse lee un entero.").unwrap().unwrap();
+        assert!(out.contains("// Parse lee un entero.\n//nolint:gocyclo\n//go:noinline\nfunc Parse("), "{}", out);
+        assert!(insert_docstring(&out, "Parse", DocStyle::Go, "x").unwrap().is_none());
+
+        let rs = "#[derive(Debug)]\npub struct Config;\n";
+        let out = insert_docstring(rs, "Config", DocStyle::Rust, &clean_doc_text("/// Configuración.")).unwrap().unwrap();
+        assert_eq!(out, "/// Configuración.\n#[derive(Debug)]\npub struct Config;\n");
+    }
+}
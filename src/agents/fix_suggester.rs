@@ -89,7 +89,8 @@ impl Agent for FixSuggesterAgent {
         };
 
         let prompt_context = if rag_context.is_empty() { None } else { Some(rag_context.as_str()) };
-        let prompt = self.build_prompt(task, context, prompt_context);
+        let prompt = crate::agents::prompts::custom_prompt(context, self.name(), task)
+            .unwrap_or_else(|| self.build_prompt(task, context, prompt_context));
         
         let config_clone = context.config.clone();
         let stats_clone = Arc::clone(&context.stats);
@@ -1,6 +1,9 @@
 pub mod base;
+pub mod context_budget;
 pub mod orchestrator;
+pub mod prompts;
 pub mod workflow;
+pub mod documenter;
 pub mod fix_suggester;
 pub mod reviewer;
 pub mod tester;
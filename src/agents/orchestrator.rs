@@ -20,6 +20,13 @@ impl AgentOrchestrator {
         println!("   🤖 Agente registrado: {}", name);
     }
 
+    /// Nombres de los agentes registrados, en orden alfabético.
+    pub fn agent_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.agents.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
     pub fn get_agent(&self, name: &str) -> Option<Arc<dyn Agent>> {
         self.agents.get(name).cloned()
     }
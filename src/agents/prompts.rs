@@ -0,0 +1,124 @@
+//! Plantillas de prompt personalizadas por agente.
+//!
+//! Si existe una plantilla para el agente, reemplaza al prompt built-in. Se busca
+//! en la ruta indicada en `[prompts]` de `.sentinelrc.toml` o, si no hay entrada,
+//! en `.sentinel/prompts/<Agente>.txt`.
+//!
+//! Placeholders: `{file}`, `{content}`, `{framework}` y `{task}`. Si la plantilla no
+//! incluye `{task}`, la descripción de la tarea se añade al final para no perder
+//! las instrucciones del comando.
+
+use crate::agents::base::{AgentContext, Task};
+use std::path::{Path, PathBuf};
+
+pub const PROMPTS_DIR: &str = ".sentinel/prompts";
+
+/// Recordatorio que se añade cuando la tarea exige JSON y la plantilla no lo pide.
+const JSON_OUTPUT_REMINDER: &str = "\n⚠️  RECORDATORIO FINAL: Tu respuesta DEBE terminar con el bloque ```json\n\
+solicitado en las instrucciones anteriores. Sin ese bloque, la respuesta\n\
+es inválida y no podrá ser procesada.\n";
+
+fn template_path(context: &AgentContext, agent: &str) -> PathBuf {
+    match context.config.prompts.get(agent) {
+        Some(custom) => context.project_root.join(custom),
+        None => context.project_root.join(PROMPTS_DIR).join(format!("{}.txt", agent)),
+    }
+}
+
+fn load_template(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .filter(|t| !t.trim().is_empty())
+}
+
+/// Sustituye los placeholders y aplica las garantías mínimas del prompt.
+pub fn render_template(template: &str, task: &Task, framework: &str) -> String {
+    let file = task
+        .file_path
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+    let content = task.context.as_deref().unwrap_or("");
+
+    let mut prompt = template
+        .replace("{file}", &file)
+        .replace("{framework}", framework)
+        .replace("{task}", &task.description)
+        .replace("{content}", content);
+
+    if !template.contains("{task}") {
+        prompt.push_str(&format!("\n\nTAREA:\n{}\n", task.description));
+    }
+    if task.description.contains("JSON") && !prompt.contains("```json") {
+        prompt.push_str(JSON_OUTPUT_REMINDER);
+    }
+    prompt
+}
+
+/// Prompt personalizado para `agent`, o `None` para usar el built-in.
+pub fn custom_prompt(context: &AgentContext, agent: &str, task: &Task) -> Option<String> {
+    let template = load_template(&template_path(context, agent))?;
+    Some(render_template(&template, task, &context.config.framework))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::base::TaskType;
+    use crate::config::SentinelConfig;
+    use crate::stats::SentinelStats;
+    use std::sync::{Arc, Mutex};
+
+    fn context(root: &Path, config: SentinelConfig) -> AgentContext {
+        AgentContext {
+            config: Arc::new(config),
+            stats: Arc::new(Mutex::new(SentinelStats::default())),
+            project_root: root.to_path_buf(),
+            index_db: None,
+        }
+    }
+
+    fn task(description: &str) -> Task {
+        Task {
+            id: "t".to_string(),
+            description: description.to_string(),
+            task_type: TaskType::Review,
+            file_path: Some(PathBuf::from("src/patients.service.ts")),
+            context: Some("export class PatientsService {}".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_custom_template_is_loaded_and_substituted() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(PROMPTS_DIR)).unwrap();
+        std::fs::write(
+            tmp.path().join(PROMPTS_DIR).join("ReviewerAgent.txt"),
+            "Proyecto HIPAA en {framework}. Revisa {file}:\n{content}\n{task}",
+        )
+        .unwrap();
+        let config = SentinelConfig { framework: "NestJS".to_string(), ..Default::default() };
+        let ctx = context(tmp.path(), config);
+
+        let prompt = custom_prompt(&ctx, "ReviewerAgent", &task("Busca fugas de PHI")).unwrap();
+        assert_eq!(
+            prompt,
+            "Proyecto HIPAA en NestJS. Revisa src/patients.service.ts:\nexport class PatientsService {}\nBusca fugas de PHI"
+        );
+        assert!(custom_prompt(&ctx, "TesterAgent", &task("x")).is_none(), "no template → built-in");
+    }
+
+    #[test]
+    fn test_config_path_and_json_guard() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("review.txt"), "Solo revisa {file}").unwrap();
+        let mut config = SentinelConfig::default();
+        config.prompts.insert("ReviewerAgent".to_string(), "review.txt".to_string());
+        let ctx = context(tmp.path(), config);
+
+        let prompt = custom_prompt(&ctx, "ReviewerAgent", &task("Responde en JSON con los hallazgos")).unwrap();
+        assert!(prompt.starts_with("Solo revisa src/patients.service.ts"));
+        assert!(prompt.contains("TAREA:\nResponde en JSON"), "task must be appended when {{task}} is missing");
+        assert!(prompt.contains("```json"), "JSON reminder must be appended");
+    }
+}
@@ -80,6 +80,7 @@ impl Agent for ReviewerAgent {
     async fn execute(&self, task: &Task, context: &AgentContext) -> anyhow::Result<TaskResult> {
         println!("   🧐 ReviewerAgent: Iniciando revisión del proyecto...");
 
+        let task = &context.fit_task_to_model(task);
         let rag_context = if let Some(path) = &task.file_path {
             context.build_rag_context(path)
         } else {
@@ -91,7 +92,8 @@ impl Agent for ReviewerAgent {
         } else {
             Some(rag_context.as_str())
         };
-        let prompt = self.build_prompt(task, context, prompt_context);
+        let prompt = crate::agents::prompts::custom_prompt(context, self.name(), task)
+            .unwrap_or_else(|| self.build_prompt(task, context, prompt_context));
 
         let config_clone = context.config.clone();
         let stats_clone = Arc::clone(&context.stats);
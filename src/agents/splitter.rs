@@ -400,6 +400,19 @@ impl SplitterAgent {
         }
     }
 
+    /// Bloque de código con la ruta como comentario en la primera línea.
+    fn path_block(fence: &str, comment: &str, path: &Path, code: &str) -> String {
+        let path = path.display().to_string().replace('\\', "/");
+        let code = code.trim();
+        // Si la IA ya puso la ruta como primera línea no se duplica
+        let first = code.lines().next().unwrap_or("").trim();
+        if first.starts_with(comment) && first.trim_start_matches(comment).trim() == path {
+            format!("```{}\n{}\n```", fence, code)
+        } else {
+            format!("```{}\n{} {}\n{}\n```", fence, comment, path, code)
+        }
+    }
+
     // ─── TODO comment para el archivo original ────────────────────────────────
 
     fn build_todo_comment(
@@ -526,6 +539,11 @@ impl Agent for SplitterAgent {
             .unwrap_or_else(|| context.project_root.clone());
 
         let content_lines: Vec<&str> = content.lines().collect();
+        let comment = if language.eq_ignore_ascii_case("python") { "#" } else { "//" };
+        let fence = language.to_lowercase();
+        // Bloques `(ruta, código)` con la ruta en la primera línea: el handler los
+        // extrae con `extraer_todos_bloques` y los escribe con los guardas de apply
+        let mut blocks: Vec<String> = Vec::new();
         let mut new_files: Vec<std::path::PathBuf> = Vec::new();
         let mut todo_entries: Vec<(String, String, String, Vec<String>)> = Vec::new();
         let mut output_lines: Vec<String> = Vec::new();
@@ -560,69 +578,54 @@ impl Agent for SplitterAgent {
                 Self::generate_new_file(filename, &class_name, &extracted_code, context).await;
 
             let file_path = base_dir.join(filename);
-            match std::fs::write(&file_path, &file_content) {
-                Ok(_) => {
-                    println!("   📄 Creado: {}", file_path.display());
-                    new_files.push(file_path);
-
-                    let fn_list = assigned.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
-                    output_lines.push(format!(
-                        "  📤 [{}] → {}",
-                        fn_list.join(", "),
-                        filename
-                    ));
-                    todo_entries.push((
-                        filename.clone(),
-                        class_name,
-                        field_name,
-                        fn_list,
-                    ));
-                }
-                Err(e) => {
-                    println!("   ❌ No se pudo escribir '{}': {}", filename, e);
-                }
-            }
+            blocks.push(Self::path_block(&fence, comment, &file_path, &file_content));
+            new_files.push(file_path);
+
+            let fn_list = assigned.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+            output_lines.push(format!(
+                "  📤 [{}] → {}",
+                fn_list.join(", "),
+                filename
+            ));
+            todo_entries.push((
+                filename.clone(),
+                class_name,
+                field_name,
+                fn_list,
+            ));
         }
 
         if new_files.is_empty() {
             return Ok(TaskResult {
                 success: false,
-                output: "No se pudo crear ningún archivo nuevo.".to_string(),
+                output: "No se pudo generar ningún archivo nuevo.".to_string(),
                 files_modified: vec![],
                 artifacts: vec![],
             });
         }
 
-        // ── Fase 4: Escribir TODO comment en el archivo original ──────────────
+        // ── Fase 4: TODO comment al inicio del archivo original ───────────────
         let todo_comment = Self::build_todo_comment(&todo_entries, language);
-        let original_path = task.file_path.as_ref();
-
-        if let Some(path) = original_path {
+        if let Some(path) = task.file_path.as_ref() {
             let updated = format!("{}\n{}", todo_comment, content);
-            match std::fs::write(path, &updated) {
-                Ok(_) => println!("   📝 TODO comment añadido al original."),
-                Err(e) => println!("   ⚠️  No se pudo actualizar el original: {}", e),
-            }
+            blocks.push(Self::path_block(&fence, comment, path, &updated));
         }
 
-        println!(
-            "   ✅ {} archivo(s) generado(s). Revisa el TODO al inicio del original.",
-            new_files.len()
-        );
+        println!("   ✅ {} archivo(s) propuesto(s).", new_files.len());
 
         let output = format!(
-            "ARCHIVOS GENERADOS:\n{}\n\n\
-             Se añadió un bloque TODO al inicio de tu archivo original con\n\
-             las instrucciones exactas para completar la migración manualmente.\n\
-             El archivo original NO fue modificado estructuralmente.",
-            output_lines.join("\n")
+            "ARCHIVOS PROPUESTOS:\n{}\n\n\
+             El archivo original recibe un bloque TODO con las instrucciones\n\
+             exactas para completar la migración manualmente.\n\n{}",
+            output_lines.join("\n"),
+            blocks.join("\n\n")
         );
 
         Ok(TaskResult {
             success: true,
             output,
-            files_modified: new_files,
-            artifacts: vec![],
+            files_modified: vec![],
+            artifacts: new_files.iter().map(|p| p.display().to_string()).collect(),
         })
     }
 }
@@ -2,8 +2,50 @@ use crate::agents::base::{Agent, AgentContext, Task, TaskResult};
 use crate::ai::client::{consultar_ia_dinamico, TaskType};
 use crate::ai::utils::extraer_codigo;
 use async_trait::async_trait;
+use std::path::PathBuf;
 use std::sync::Arc;
 
+/// Salida del runner que se reenvía al agente (la cola, donde están los fallos).
+const MAX_FAILURE_CHARS: usize = 4000;
+
+/// Intento anterior que no pasó: el test generado y lo que imprimió el runner.
+pub struct FailedAttempt<'a> {
+    pub test_code: &'a str,
+    pub output: &'a str,
+}
+
+/// Tarea de `TesterAgent` para escribir `test_file` sobre `source_file` (`source`: su
+/// contenido). Con `previous`, pide corregir ese test a partir de la salida del runner.
+pub fn test_task(source_file: &str, source: &str, test_file: &str, previous: Option<&FailedAttempt>) -> Task {
+    let mut description = format!(
+        "Genera el archivo de tests `{}` para `{}`. Importa el módulo con la ruta relativa correcta desde el test.",
+        test_file, source_file
+    );
+    let mut context = format!("CONTENIDO DE {}:\n```\n{}\n```", source_file, source);
+    if let Some(attempt) = previous {
+        description.push_str(
+            "\n\nEl test anterior FALLA al ejecutarse. Corrígelo: ajusta imports, mocks y expectativas \
+            al comportamiento real del código (no modifiques el código fuente).",
+        );
+        let output = attempt.output.trim();
+        let tail_start = output.len().saturating_sub(MAX_FAILURE_CHARS);
+        let tail_start = (tail_start..output.len()).find(|&i| output.is_char_boundary(i)).unwrap_or(0);
+        context.push_str(&format!(
+            "\n\nTEST ANTERIOR ({}):\n```\n{}\n```\n\nSALIDA DEL RUNNER:\n```\n{}\n```",
+            test_file,
+            attempt.test_code,
+            &output[tail_start..]
+        ));
+    }
+    Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description,
+        task_type: crate::agents::base::TaskType::Test,
+        file_path: Some(PathBuf::from(source_file)),
+        context: Some(context),
+    }
+}
+
 pub struct TesterAgent;
 
 impl TesterAgent {
@@ -71,6 +113,7 @@ impl Agent for TesterAgent {
             .unwrap_or_else(|| "archivo".to_string());
         println!("   🧪 TesterAgent: generando test para '{}'...", file_hint);
 
+        let task = &context.fit_task_to_model(task);
         let rag_context = if let Some(path) = &task.file_path {
             context.build_rag_context(path)
         } else {
@@ -78,7 +121,8 @@ impl Agent for TesterAgent {
         };
 
         let prompt_context = if rag_context.is_empty() { None } else { Some(rag_context.as_str()) };
-        let prompt = self.build_prompt(task, context, prompt_context);
+        let prompt = crate::agents::prompts::custom_prompt(context, self.name(), task)
+            .unwrap_or_else(|| self.build_prompt(task, context, prompt_context));
 
         let config_clone = context.config.clone();
         let stats_clone = Arc::clone(&context.stats);
@@ -2,6 +2,7 @@ use crate::agents::base::{AgentContext, Task, TaskResult, TaskType};
 use crate::agents::orchestrator::AgentOrchestrator;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use colored::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,15 +16,95 @@ pub struct Workflow {
 pub struct WorkflowStep {
     pub name: String,
     pub agent: String, // Nombre del agente a usar (e.g., "CoderAgent")
+    #[serde(flatten)]
     pub task_template: TaskTemplate, // Plantilla para crear la tarea
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskTemplate {
     pub description: String,
+    #[serde(rename = "taskType", alias = "task_type")]
     pub task_type: TaskType,
 }
 
+impl Workflow {
+    /// Agentes de los pasos que `orchestrator` no tiene registrados, sin repetir.
+    pub fn missing_agents(&self, orchestrator: &AgentOrchestrator) -> Vec<&str> {
+        let mut missing: Vec<&str> = Vec::new();
+        for step in &self.steps {
+            if orchestrator.get_agent(&step.agent).is_none() && !missing.contains(&step.agent.as_str()) {
+                missing.push(&step.agent);
+            }
+        }
+        missing
+    }
+}
+
+/// Un workflow ejecutable y de dónde sale: `None` si es predefinido, o su archivo YAML.
+#[derive(Debug, Clone)]
+pub struct AvailableWorkflow {
+    pub workflow: Workflow,
+    pub path: Option<PathBuf>,
+}
+
+fn step(name: &str, agent: &str, task_type: TaskType, description: &str) -> WorkflowStep {
+    WorkflowStep {
+        name: name.to_string(),
+        agent: agent.to_string(),
+        task_template: TaskTemplate { description: description.to_string(), task_type },
+    }
+}
+
+/// Workflows incluidos en Sentinel (`fix-and-verify`, `review-security`).
+pub fn builtin_workflows() -> Vec<Workflow> {
+    vec![
+        Workflow {
+            name: "fix-and-verify".to_string(),
+            description: "Busca bugs en el archivo, aplica la corrección y genera tests de regresión".to_string(),
+            steps: vec![
+                step("Detectar bugs", "ReviewerAgent", TaskType::Analyze, "Analiza {file} y lista los bugs y casos borde que encuentres."),
+                step("Aplicar corrección", "FixSuggesterAgent", TaskType::Fix, "Corrige los bugs detectados en {file} y devuelve el archivo completo."),
+                step("Tests de regresión", "TesterAgent", TaskType::Test, "Genera tests unitarios para {file} que cubran los bugs corregidos y sus casos borde."),
+            ],
+        },
+        Workflow {
+            name: "review-security".to_string(),
+            description: "Auditoría OWASP Top 10 del archivo y código que mitiga lo encontrado".to_string(),
+            steps: vec![
+                step("Auditoría de seguridad", "ReviewerAgent", TaskType::Analyze, "Audita {file} contra el OWASP Top 10 y lista cada vulnerabilidad con su severidad."),
+                step("Mitigación", "FixSuggesterAgent", TaskType::Fix, "Aplica en {file} las mitigaciones de las vulnerabilidades encontradas y devuelve el archivo completo."),
+            ],
+        },
+    ]
+}
+
+/// Predefinidos + `.sentinel/workflows/*.yml` del proyecto, en ese orden. Un YAML reemplaza
+/// al predefinido del mismo nombre; los que no parsean se avisan por stderr y se omiten.
+pub fn available_workflows(project_root: &Path) -> Vec<AvailableWorkflow> {
+    let mut available: Vec<AvailableWorkflow> =
+        builtin_workflows().into_iter().map(|workflow| AvailableWorkflow { workflow, path: None }).collect();
+    let Ok(entries) = std::fs::read_dir(project_root.join(".sentinel/workflows")) else {
+        return available;
+    };
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yaml" | "yml")))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        match serde_yaml::from_str::<Workflow>(&content) {
+            Ok(workflow) => {
+                available.retain(|w| w.workflow.name != workflow.name);
+                available.push(AvailableWorkflow { workflow, path: Some(path) });
+            }
+            Err(e) => eprintln!("   ⚠️  Workflow inválido {}: {}", path.display(), e),
+        }
+    }
+    available
+}
+
 pub struct WorkflowContext {
     pub _shared_memory: HashMap<String, String>,
     pub step_results: Vec<TaskResult>,
@@ -40,12 +121,12 @@ impl WorkflowContext {
     }
 }
 
-pub struct WorkflowEngine {
-    orchestrator: AgentOrchestrator,
+pub struct WorkflowEngine<'a> {
+    orchestrator: &'a AgentOrchestrator,
 }
 
-impl WorkflowEngine {
-    pub fn new(orchestrator: AgentOrchestrator) -> Self {
+impl<'a> WorkflowEngine<'a> {
+    pub fn new(orchestrator: &'a AgentOrchestrator) -> Self {
         Self { orchestrator }
     }
 
@@ -198,3 +279,57 @@ impl WorkflowEngine {
         Ok(wf_context)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_workflows_lists_builtin_and_project_yaml() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".sentinel/workflows");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("ci_pipeline.yml"),
+            "name: \"Clean & Test\"\n\
+             description: \"Aplica clean code y crea smoke tests\"\n\
+             steps:\n  \
+               - name: \"Aplicar Clean Code\"\n    agent: \"FixSuggesterAgent\"\n    taskType: \"Refactor\"\n    description: \"Refactoriza {file}\"\n  \
+               - name: \"Pruebas\"\n    agent: \"TesterAgent\"\n    taskType: \"Test\"\n    description: \"Tests para {file}\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.yaml"), "name: [").unwrap();
+
+        let available = available_workflows(tmp.path());
+        let listed: Vec<(&str, usize, bool)> = available
+            .iter()
+            .map(|w| (w.workflow.name.as_str(), w.workflow.steps.len(), w.path.is_some()))
+            .collect();
+        assert_eq!(listed, vec![("fix-and-verify", 3, false), ("review-security", 2, false), ("Clean & Test", 2, true)]);
+        let yaml = &available[2].workflow;
+        assert_eq!(yaml.description, "Aplica clean code y crea smoke tests");
+        assert_eq!(yaml.steps[0].task_template.task_type, TaskType::Refactor);
+    }
+
+    #[test]
+    fn test_missing_agents_reports_unregistered_steps() {
+        let mut orchestrator = AgentOrchestrator::new();
+        orchestrator.register(std::sync::Arc::new(crate::agents::reviewer::ReviewerAgent::new()));
+        orchestrator.register(std::sync::Arc::new(crate::agents::fix_suggester::FixSuggesterAgent::new()));
+        orchestrator.register(std::sync::Arc::new(crate::agents::tester::TesterAgent::new()));
+        for workflow in builtin_workflows() {
+            assert!(workflow.missing_agents(&orchestrator).is_empty(), "{}", workflow.name);
+        }
+
+        let workflow = Workflow {
+            name: "custom".to_string(),
+            description: String::new(),
+            steps: vec![
+                step("a", "RefactorAgent", TaskType::Refactor, "{file}"),
+                step("b", "TesterAgent", TaskType::Test, "{file}"),
+                step("c", "RefactorAgent", TaskType::Refactor, "{file}"),
+            ],
+        };
+        assert_eq!(workflow.missing_agents(&orchestrator), vec!["RefactorAgent"]);
+    }
+}
@@ -1,34 +1,73 @@
 //! Sistema de caché para optimizar consultas a IA
 //!
 //! Guarda respuestas de IA en disco para evitar consultas repetidas.
-//! Usa hash del prompt como identificador del caché.
+//! Usa hash de (prompt, modelo, proveedor) como identificador del caché, de modo
+//! que cambiar de modelo no sirva respuestas generadas por otro.
 
+use crate::config::ModelConfig;
 use colored::*;
 use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
-fn obtener_cache_path(prompt: &str, project_path: &Path) -> PathBuf {
+/// Prefijo del esquema de claves actual. Las entradas antiguas (solo hash del
+/// prompt, sin prefijo) nunca coinciden y se tratan como misses.
+const CACHE_KEY_VERSION: &str = "v2";
+
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+    }
+}
+
+/// Aciertos y fallos de caché en la sesión actual.
+pub fn cache_stats() -> CacheStats {
+    CacheStats {
+        hits: CACHE_HITS.load(Ordering::Relaxed),
+        misses: CACHE_MISSES.load(Ordering::Relaxed),
+    }
+}
+
+fn obtener_cache_path(prompt: &str, model: &ModelConfig, project_path: &Path) -> PathBuf {
     let mut s = DefaultHasher::new();
-    prompt.hash(&mut s);
+    (prompt, model.name.as_str(), model.provider.as_str()).hash(&mut s);
     let hash = s.finish();
     project_path
         .join(".sentinel/cache")
-        .join(format!("{:x}.cache", hash))
+        .join(format!("{}-{:x}.cache", CACHE_KEY_VERSION, hash))
 }
 
-pub fn intentar_leer_cache(prompt: &str, project_path: &Path) -> Option<String> {
-    let path = obtener_cache_path(prompt, project_path);
-    fs::read_to_string(path).ok()
+pub fn intentar_leer_cache(prompt: &str, model: &ModelConfig, project_path: &Path) -> Option<String> {
+    let path = obtener_cache_path(prompt, model, project_path);
+    let result = fs::read_to_string(path).ok();
+    let counter = if result.is_some() { &CACHE_HITS } else { &CACHE_MISSES };
+    counter.fetch_add(1, Ordering::Relaxed);
+    result
 }
 
-pub fn guardar_en_cache(prompt: &str, respuesta: &str, project_path: &Path) -> anyhow::Result<()> {
+pub fn guardar_en_cache(
+    prompt: &str,
+    model: &ModelConfig,
+    respuesta: &str,
+    project_path: &Path,
+) -> anyhow::Result<()> {
     let cache_dir = project_path.join(".sentinel/cache");
     if !cache_dir.exists() {
         fs::create_dir_all(&cache_dir)?;
     }
-    let path = obtener_cache_path(prompt, project_path);
+    let path = obtener_cache_path(prompt, model, project_path);
     fs::write(path, respuesta)?;
     Ok(())
 }
@@ -50,3 +89,46 @@ pub fn limpiar_cache(project_path: &Path) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(name: &str, provider: &str) -> ModelConfig {
+        ModelConfig {
+            name: name.to_string(),
+            provider: provider.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_same_prompt_different_models_are_distinct_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = model("claude-3-5-sonnet", "anthropic");
+        let b = model("gpt-4o", "openai");
+
+        guardar_en_cache("explica esto", &a, "respuesta del modelo A", dir.path()).unwrap();
+        guardar_en_cache("explica esto", &b, "respuesta del modelo B", dir.path()).unwrap();
+
+        assert_eq!(fs::read_dir(dir.path().join(".sentinel/cache")).unwrap().count(), 2);
+        assert_eq!(intentar_leer_cache("explica esto", &a, dir.path()).unwrap(), "respuesta del modelo A");
+        assert_eq!(intentar_leer_cache("explica esto", &b, dir.path()).unwrap(), "respuesta del modelo B");
+        assert!(intentar_leer_cache("explica esto", &model("gpt-4o", "openrouter"), dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_legacy_entries_are_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let m = model("gpt-4o", "openai");
+        let mut s = DefaultHasher::new();
+        "prompt".hash(&mut s);
+        let legacy_dir = dir.path().join(".sentinel/cache");
+        fs::create_dir_all(&legacy_dir).unwrap();
+        fs::write(legacy_dir.join(format!("{:x}.cache", s.finish())), "vieja").unwrap();
+
+        let before = cache_stats();
+        assert!(intentar_leer_cache("prompt", &m, dir.path()).is_none());
+        assert!(cache_stats().misses > before.misses);
+    }
+}
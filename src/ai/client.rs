@@ -6,11 +6,14 @@
 //! Incluye sistema de fallback automático entre modelos.
 
 use crate::ai::cache::{guardar_en_cache, intentar_leer_cache};
-use crate::ai::providers::build_provider;
+use crate::ai::pricing::{self, ModelPrice};
+use crate::ai::providers::{
+    build_provider, describe_missing_model, describe_timeout, embed_many, model_client, ApiStatusError,
+};
 use crate::config::{ModelConfig, SentinelConfig};
 use crate::stats::SentinelStats;
 use colored::*;
-use reqwest::blocking::Client;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
@@ -30,8 +33,10 @@ pub fn consultar_ia_dinamico(
 ) -> anyhow::Result<String> {
     // 1. Intentar Caché
     if config.use_cache {
-        if let Some(res) = intentar_leer_cache(&prompt, project_path) {
-            println!("{}", "   ♻️  Usando respuesta de caché...".dimmed());
+        if let Some(res) = intentar_leer_cache(&prompt, &config.primary_model, project_path) {
+            if !crate::commands::is_quiet() {
+                println!("{}", "   ♻️  Usando respuesta de caché...".dimmed());
+            }
             return Ok(res);
         }
     }
@@ -45,71 +50,149 @@ pub fn consultar_ia_dinamico(
         modelo_principal,
         config.fallback_model.as_ref(),
         Arc::clone(&stats),
+        &config.pricing,
         task,
     );
 
-    // 4. Guardar en Caché si tuvo éxito y parece una respuesta válida
-    if let Ok(ref res) = resultado {
-        if config.use_cache && res.trim().len() > 20 {
-            let _ = guardar_en_cache(&prompt, res, project_path);
-        }
+    // 4. Guardar en Caché si tuvo éxito y parece una respuesta válida, bajo el modelo que
+    // respondió: una respuesta del respaldo no debe servirse luego como del principal
+    let (res, modelo) = resultado?;
+    if config.use_cache && res.trim().len() > 20 {
+        let _ = guardar_en_cache(&prompt, modelo, &res, project_path);
     }
 
-    resultado
+    Ok(res)
 }
 
-fn ejecutar_con_fallback(
+fn ejecutar_con_fallback<'a>(
     prompt: String,
-    principal: &ModelConfig,
-    fallback: Option<&ModelConfig>,
+    principal: &'a ModelConfig,
+    fallback: Option<&'a ModelConfig>,
     stats: Arc<Mutex<SentinelStats>>,
+    pricing: &BTreeMap<String, ModelPrice>,
     task: TaskType,
-) -> anyhow::Result<String> {
-    match consultar_ia(prompt.clone(), principal, Arc::clone(&stats), task) {
-        Ok(res) => Ok(res),
+) -> anyhow::Result<(String, &'a ModelConfig)> {
+    ejecutar_con_fallback_usando(&prompt, principal, fallback, &stats, pricing, |model| {
+        llamar_proveedor(&prompt, model, task)
+    })
+}
+
+/// Intenta con el modelo principal y, si el error es transitorio (red, timeout, 5xx),
+/// reintenta una vez con el de respaldo. Las stats se atribuyen al modelo que respondió,
+/// que se devuelve junto a la respuesta.
+fn ejecutar_con_fallback_usando<'a, F>(
+    prompt: &str,
+    principal: &'a ModelConfig,
+    fallback: Option<&'a ModelConfig>,
+    stats: &Arc<Mutex<SentinelStats>>,
+    pricing: &BTreeMap<String, ModelPrice>,
+    llamar: F,
+) -> anyhow::Result<(String, &'a ModelConfig)>
+where
+    F: Fn(&ModelConfig) -> anyhow::Result<String>,
+{
+    match llamar(principal) {
+        Ok(res) => {
+            registrar_uso(stats, principal, pricing, prompt.len(), &res);
+            Ok((res, principal))
+        }
         Err(e) => {
-            if let Some(fb) = fallback {
+            let Some(fb) = fallback else {
+                return Err(e);
+            };
+            if !es_error_recuperable(&e) {
+                return Err(e);
+            }
+            if !crate::commands::is_quiet() {
                 println!(
                     "{}",
                     format!(
-                        "   ⚠️  Modelo principal falló: {}. Intentando fallback con {}...",
+                        "   ↪️  Modelo principal falló ({}): usando modelo de respaldo {}...",
                         e, fb.name
                     )
-                    .yellow()
+                    .dimmed()
                 );
-                consultar_ia(prompt, fb, stats, task)
-            } else {
-                Err(e)
             }
+            let res = llamar(fb)?;
+            registrar_uso(stats, fb, pricing, prompt.len(), &res);
+            Ok((res, fb))
         }
     }
 }
 
+/// Errores que justifican probar el modelo de respaldo: fallos de red, timeouts y 5xx.
+/// Los 4xx (prompt inválido, auth, etc.) se propagan tal cual.
+pub fn es_error_recuperable(e: &anyhow::Error) -> bool {
+    if let Some(api) = e.downcast_ref::<ApiStatusError>() {
+        return api.status.is_server_error() || api.status == reqwest::StatusCode::REQUEST_TIMEOUT;
+    }
+    if let Some(re) = e.downcast_ref::<reqwest::Error>() {
+        return re.is_timeout()
+            || re.is_connect()
+            || re.is_request()
+            || re.is_body()
+            || re.status().map(|s| s.is_server_error()).unwrap_or(false);
+    }
+    false
+}
+
+/// Las tareas ligeras se cortan a los 30 s en proveedores alojados, salvo que el modelo fije
+/// su propio timeout. Un modelo local puede tardar eso solo en cargarse.
+fn timeout_para(model: &ModelConfig, task: TaskType) -> std::time::Duration {
+    match task {
+        TaskType::Light if model.timeout_secs.is_none() && !model.is_self_hosted() => {
+            model.timeout().min(std::time::Duration::from_secs(30))
+        }
+        _ => model.timeout(),
+    }
+}
+
+fn llamar_proveedor(prompt: &str, model: &ModelConfig, task: TaskType) -> anyhow::Result<String> {
+    let timeout = timeout_para(model, task);
+
+    let client = model_client(model, timeout)?;
+    let provider = build_provider(model)?;
+    let start = std::time::Instant::now();
+    let result = provider
+        .chat(&client, prompt, &model.name)
+        .map_err(|e| describe_missing_model(describe_timeout(e, model, timeout), model));
+    log::debug!(
+        "LLM call {} ({}) {:?}: {} ms, prompt {} chars{}",
+        model.name,
+        model.provider,
+        task,
+        start.elapsed().as_millis(),
+        prompt.len(),
+        if result.is_err() { ", failed" } else { "" }
+    );
+    result
+}
+
+/// Tokens estimados (~4 caracteres por token) y costo según `ai::pricing`.
+fn registrar_uso(
+    stats: &Arc<Mutex<SentinelStats>>,
+    model: &ModelConfig,
+    pricing: &BTreeMap<String, ModelPrice>,
+    prompt_len: usize,
+    respuesta: &str,
+) {
+    let in_tokens = prompt_len as u64 / 4;
+    let out_tokens = respuesta.len() as u64 / 4;
+    let cost = pricing::estimate_cost_with(&model.name, &model.provider, in_tokens, out_tokens, pricing);
+    stats.lock().unwrap().registrar_uso(&model.name, in_tokens + out_tokens, cost);
+}
+
 pub fn consultar_ia(
     prompt: String,
     model: &ModelConfig,
     stats: Arc<Mutex<SentinelStats>>,
+    pricing: &BTreeMap<String, ModelPrice>,
     task: TaskType,
 ) -> anyhow::Result<String> {
-    let timeout = match task {
-        TaskType::Light => std::time::Duration::from_secs(30),
-        TaskType::Deep => std::time::Duration::from_secs(120),
-    };
-
-    let client = Client::builder()
-        .timeout(timeout)
-        .build()
-        .unwrap_or_else(|_| Client::new());
-
-    let prompt_len = prompt.len();
-    let provider = build_provider(model);
-    let resultado = provider.chat(&client, &prompt, &model.name);
+    let resultado = llamar_proveedor(&prompt, model, task);
 
     if let Ok(ref res) = resultado {
-        let tokens = (res.len() as u64 / 4) + (prompt_len as u64 / 4);
-        let mut s = stats.lock().unwrap();
-        s.total_tokens_used += tokens;
-        s.total_cost_usd += (tokens as f64 / 1000.0) * 0.01;
+        registrar_uso(&stats, model, pricing, prompt.len(), res);
     }
 
     resultado
@@ -132,7 +215,125 @@ pub fn obtener_embeddings(
         return model_arc.embed(&textos);
     }
 
-    let client = Client::new();
-    let provider = build_provider(model);
-    provider.embed(&client, textos, &model.name)
+    let timeout = model.timeout();
+    let client = model_client(model, timeout)?;
+    let provider = build_provider(model)?;
+    embed_many(provider.as_ref(), &client, textos, &model.name)
+        .map_err(|e| describe_timeout(e, model, timeout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modelo(name: &str) -> ModelConfig {
+        ModelConfig {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn error_status(code: u16) -> anyhow::Error {
+        ApiStatusError::new(
+            "Test",
+            reqwest::StatusCode::from_u16(code).unwrap(),
+            "boom".to_string(),
+        )
+        .into()
+    }
+
+    #[test]
+    fn test_fallback_used_when_primary_fails_with_5xx() {
+        let stats = Arc::new(Mutex::new(SentinelStats::default()));
+        let principal = modelo("primary");
+        let respaldo = modelo("backup");
+
+        let (res, respondio) = ejecutar_con_fallback_usando("prompt", &principal, Some(&respaldo), &stats, &BTreeMap::new(), |m| {
+            if m.name == "primary" {
+                Err(error_status(503))
+            } else {
+                Ok("respuesta del respaldo".to_string())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(res, "respuesta del respaldo");
+        assert_eq!(respondio.name, "backup", "the cache must be keyed on the model that answered");
+        let s = stats.lock().unwrap();
+        assert!(s.uso_por_modelo.contains_key("backup"));
+        assert!(!s.uso_por_modelo.contains_key("primary"), "failed model must not be billed");
+        assert_eq!(s.total_tokens_used, s.uso_por_modelo["backup"].tokens);
+    }
+
+    #[test]
+    fn test_no_fallback_on_client_error() {
+        let stats = Arc::new(Mutex::new(SentinelStats::default()));
+        let llamadas = std::cell::Cell::new(0);
+        let (principal, respaldo) = (modelo("primary"), modelo("backup"));
+
+        let res = ejecutar_con_fallback_usando("prompt", &principal, Some(&respaldo), &stats, &BTreeMap::new(), |_| {
+            llamadas.set(llamadas.get() + 1);
+            Err(error_status(400))
+        });
+
+        assert!(res.is_err());
+        assert_eq!(llamadas.get(), 1, "4xx must not trigger the fallback model");
+    }
+
+    #[test]
+    fn test_es_error_recuperable_classification() {
+        assert!(es_error_recuperable(&error_status(500)));
+        assert!(es_error_recuperable(&error_status(502)));
+        assert!(!es_error_recuperable(&error_status(401)));
+        assert!(!es_error_recuperable(&anyhow::anyhow!("Estructura inesperada")));
+    }
+
+    #[test]
+    fn test_slow_provider_surfaces_timeout_error() {
+        // Servidor que acepta la conexión pero nunca responde a tiempo
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((_stream, _)) = listener.accept() {
+                std::thread::sleep(std::time::Duration::from_secs(3));
+            }
+        });
+
+        let model = ModelConfig {
+            name: "slow".to_string(),
+            url: format!("http://{}", addr),
+            provider: "openai".to_string(),
+            timeout_secs: Some(1),
+            ..Default::default()
+        };
+        let started = std::time::Instant::now();
+        let err = llamar_proveedor("hola", &model, TaskType::Deep).unwrap_err();
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(3), "must not wait for the server");
+        assert!(err.to_string().contains("Timeout"), "got: {}", err);
+        assert!(es_error_recuperable(&err), "timeouts must still trigger the fallback model");
+    }
+
+    #[test]
+    fn test_ollama_gets_longer_default_timeout() {
+        let mut m = modelo("llama3");
+        assert_eq!(m.timeout(), std::time::Duration::from_secs(crate::config::DEFAULT_TIMEOUT_SECS));
+        m.provider = "ollama".to_string();
+        assert_eq!(m.timeout(), std::time::Duration::from_secs(crate::config::DEFAULT_OLLAMA_TIMEOUT_SECS));
+        m.timeout_secs = Some(45);
+        assert_eq!(m.timeout(), std::time::Duration::from_secs(45));
+    }
+
+    #[test]
+    fn test_light_cap_applies_only_to_hosted_providers() {
+        let secs = std::time::Duration::from_secs;
+        let hosted = modelo("claude");
+        assert_eq!(timeout_para(&hosted, TaskType::Light), secs(30));
+        assert_eq!(timeout_para(&hosted, TaskType::Deep), secs(crate::config::DEFAULT_TIMEOUT_SECS));
+
+        // Ollama detectado por URL, sin `provider`: timeout largo y sin el tope de 30 s
+        let local = ModelConfig { url: "http://localhost:11434".to_string(), provider: String::new(), ..modelo("llama3") };
+        assert_eq!(local.timeout(), secs(crate::config::DEFAULT_OLLAMA_TIMEOUT_SECS));
+        assert_eq!(timeout_para(&local, TaskType::Light), secs(crate::config::DEFAULT_OLLAMA_TIMEOUT_SECS));
+    }
 }
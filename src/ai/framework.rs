@@ -94,6 +94,7 @@ pub fn detectar_framework_con_ia(
         prompt_inicial,
         &config.primary_model,
         Arc::clone(&stats),
+        &config.pricing,
         TaskType::Deep,
     )?;
 
@@ -138,6 +139,7 @@ pub fn detectar_framework_con_ia(
                 prompt_con_contenido,
                 &config.primary_model,
                 Arc::clone(&stats),
+                &config.pricing,
                 TaskType::Deep,
             )?;
 
@@ -202,6 +204,8 @@ pub fn obtener_modelos_disponibles(
         url: api_url.to_string(),
         api_key: api_key.to_string(),
         name: String::new(),
+        ..Default::default()
     };
-    crate::ai::providers::build_provider(&config).list_models()
+    let client = crate::ai::providers::model_client(&config, crate::ai::providers::PING_TIMEOUT)?;
+    crate::ai::providers::build_provider(&config)?.list_models(&client)
 }
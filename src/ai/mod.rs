@@ -11,13 +11,14 @@ pub mod analysis;
 pub mod cache;
 pub mod client;
 pub mod framework;
+pub mod pricing;
 pub mod providers;
 pub mod testing;
 pub mod utils;
 
 // Re-exports públicos
 pub use analysis::analizar_arquitectura;
-pub use cache::limpiar_cache;
+pub use cache::{cache_stats, limpiar_cache};
 pub use client::{TaskType, consultar_ia_dinamico, obtener_embeddings};
 pub use framework::{detectar_framework_con_ia, obtener_modelos_disponibles};
 pub use testing::{TestingFrameworkInfo, TestingStatus, detectar_testing_framework};
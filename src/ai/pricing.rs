@@ -0,0 +1,129 @@
+//! Precios por modelo para estimar el costo de cada llamada.
+//!
+//! Los precios están en USD por millón de tokens (la unidad de las páginas de precios
+//! de los proveedores). Se resuelven por prefijo del nombre del modelo, así que
+//! `claude-3-5-sonnet-20241022` usa la entrada `claude-3-5-sonnet`.
+//!
+//! `[pricing]` en `.sentinelrc.toml` tiene prioridad sobre la tabla built-in:
+//!
+//! ```toml
+//! [pricing."mi-modelo-interno"]
+//! input_per_mtok = 0.5
+//! output_per_mtok = 1.5
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    /// USD por millón de tokens de entrada
+    pub input_per_mtok: f64,
+    /// USD por millón de tokens de salida
+    pub output_per_mtok: f64,
+}
+
+const fn price(input_per_mtok: f64, output_per_mtok: f64) -> ModelPrice {
+    ModelPrice { input_per_mtok, output_per_mtok }
+}
+
+/// Tarifa para modelos desconocidos: la estimación plana histórica de $0.01 por 1K tokens.
+pub const DEFAULT_PRICE: ModelPrice = price(10.0, 10.0);
+
+/// Tabla built-in (prefijo del nombre → precio). Gana el prefijo más largo.
+const PRICING_TABLE: &[(&str, ModelPrice)] = &[
+    // Anthropic
+    ("claude-opus-4", price(15.0, 75.0)),
+    ("claude-sonnet-4", price(3.0, 15.0)),
+    ("claude-3-7-sonnet", price(3.0, 15.0)),
+    ("claude-3-5-sonnet", price(3.0, 15.0)),
+    ("claude-3-5-haiku", price(0.8, 4.0)),
+    ("claude-3-opus", price(15.0, 75.0)),
+    ("claude-3-haiku", price(0.25, 1.25)),
+    // OpenAI
+    ("gpt-4o-mini", price(0.15, 0.6)),
+    ("gpt-4o", price(2.5, 10.0)),
+    ("gpt-4-turbo", price(10.0, 30.0)),
+    ("gpt-4.1-mini", price(0.4, 1.6)),
+    ("gpt-4.1", price(2.0, 8.0)),
+    ("gpt-3.5-turbo", price(0.5, 1.5)),
+    // Google
+    ("gemini-1.5-flash", price(0.075, 0.3)),
+    ("gemini-1.5-pro", price(1.25, 5.0)),
+    ("gemini-2.0-flash", price(0.1, 0.4)),
+    ("gemini-2.5-flash", price(0.3, 2.5)),
+    ("gemini-2.5-pro", price(1.25, 10.0)),
+    // Otros
+    ("deepseek-chat", price(0.27, 1.1)),
+    ("deepseek-reasoner", price(0.55, 2.19)),
+];
+
+/// Proveedores que corren en la máquina del usuario: no tienen costo por token.
+const LOCAL_PROVIDERS: &[&str] = &["ollama", "lm-studio", "local"];
+
+/// Precio de `model` en la tabla built-in (o `DEFAULT_PRICE` si no aparece).
+pub fn price_for(model: &str) -> ModelPrice {
+    let lower = model.to_lowercase();
+    // OpenRouter y similares prefijan el proveedor: "anthropic/claude-3-5-sonnet"
+    let name = lower.rsplit('/').next().unwrap_or(&lower);
+    PRICING_TABLE
+        .iter()
+        .filter(|(prefix, _)| name.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, p)| *p)
+        .unwrap_or(DEFAULT_PRICE)
+}
+
+/// Precio efectivo: `[pricing]` (nombre exacto) y luego la tabla built-in.
+pub fn resolve_price(model: &str, overrides: &BTreeMap<String, ModelPrice>) -> ModelPrice {
+    overrides.get(model).copied().unwrap_or_else(|| price_for(model))
+}
+
+fn cost(p: ModelPrice, in_tokens: u64, out_tokens: u64) -> f64 {
+    (in_tokens as f64 * p.input_per_mtok + out_tokens as f64 * p.output_per_mtok) / 1_000_000.0
+}
+
+/// Costo estimado de una llamada a `model` (con `provider`), aplicando los overrides del config.
+pub fn estimate_cost_with(
+    model: &str,
+    provider: &str,
+    in_tokens: u64,
+    out_tokens: u64,
+    overrides: &BTreeMap<String, ModelPrice>,
+) -> f64 {
+    if !overrides.contains_key(model) && LOCAL_PROVIDERS.contains(&provider) {
+        return 0.0;
+    }
+    cost(resolve_price(model, overrides), in_tokens, out_tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_model_pricing() {
+        // 1M de entrada a $3 + 100K de salida a $15
+        let c = estimate_cost_with("claude-3-5-sonnet-20241022", "anthropic", 1_000_000, 100_000, &BTreeMap::new());
+        assert!((c - 4.5).abs() < 1e-9, "got {}", c);
+        // El prefijo más largo gana: gpt-4o-mini no usa el precio de gpt-4o
+        assert_eq!(price_for("gpt-4o-mini-2024-07-18"), price(0.15, 0.6));
+        assert_eq!(price_for("anthropic/claude-3-haiku"), price(0.25, 1.25));
+        assert_eq!(price_for("modelo-desconocido"), DEFAULT_PRICE);
+    }
+
+    #[test]
+    fn test_config_override_and_local_providers() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("claude-3-5-sonnet-20241022".to_string(), price(1.0, 2.0));
+        overrides.insert("llama3-internal".to_string(), price(0.1, 0.1));
+
+        let negociado = estimate_cost_with("claude-3-5-sonnet-20241022", "anthropic", 1_000_000, 1_000_000, &overrides);
+        assert!((negociado - 3.0).abs() < 1e-9, "got {}", negociado);
+
+        assert_eq!(estimate_cost_with("llama3", "ollama", 5_000, 5_000, &overrides), 0.0);
+        let con_override = estimate_cost_with("llama3-internal", "ollama", 1_000_000, 0, &overrides);
+        assert!((con_override - 0.1).abs() < 1e-9);
+    }
+}
@@ -42,11 +42,7 @@ impl super::AiProvider for AnthropicProvider {
         let body_text = response.text()?;
 
         if !status.is_success() {
-            return Err(anyhow::anyhow!(
-                "Error de API Anthropic (Status {}): {}",
-                status,
-                body_text
-            ));
+            return Err(super::ApiStatusError::new("Anthropic", status, body_text).into());
         }
 
         let body: serde_json::Value = serde_json::from_str(&body_text)?;
@@ -64,16 +60,21 @@ impl super::AiProvider for AnthropicProvider {
         ))
     }
 
-    fn list_models(&self) -> Result<Vec<String>> {
-        let client = Client::new();
+    fn list_models(&self, client: &Client) -> Result<Vec<String>> {
+        self.fetch_models(client)
+    }
+}
+
+impl AnthropicProvider {
+    /// Listado de modelos con `client`; un status no exitoso llega como `ApiStatusError`.
+    fn fetch_models(&self, client: &Client) -> Result<Vec<String>> {
         let url = format!("{}/v1/models", self.url.trim_end_matches('/'));
-        let response = client
+        let request = client
             .get(&url)
             .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .send()?;
+            .header("anthropic-version", "2023-06-01");
 
-        let json: serde_json::Value = response.json()?;
+        let json = super::fetch_json(request, "Anthropic")?;
         let models = json["data"]
             .as_array()
             .ok_or_else(|| anyhow::anyhow!("Respuesta de Claude inválida"))?
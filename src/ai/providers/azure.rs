@@ -0,0 +1,218 @@
+// src/ai/providers/azure.rs
+use anyhow::Result;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::json;
+
+/// Versión de API usada cuando `api_version` no está configurada.
+pub const DEFAULT_API_VERSION: &str = "2024-02-01";
+
+/// Azure OpenAI: el modelo se elige por *deployment* en la URL, no en el body,
+/// y la autenticación usa el header `api-key` en lugar de `Authorization: Bearer`.
+pub struct AzureOpenAiProvider {
+    api_key: String,
+    url: String,
+    deployment: Option<String>,
+    api_version: String,
+}
+
+impl AzureOpenAiProvider {
+    pub fn new(
+        api_key: &str,
+        url: &str,
+        deployment: Option<&str>,
+        api_version: Option<&str>,
+    ) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            url: url.to_string(),
+            deployment: deployment
+                .filter(|d| !d.is_empty())
+                .map(|d| d.to_string()),
+            api_version: api_version
+                .filter(|v| !v.is_empty())
+                .unwrap_or(DEFAULT_API_VERSION)
+                .to_string(),
+        }
+    }
+
+    /// Deployment a usar: el configurado o, si falta, el nombre del modelo.
+    fn deployment_for<'a>(&'a self, model_name: &'a str) -> &'a str {
+        self.deployment.as_deref().unwrap_or(model_name)
+    }
+
+    /// `https://{resource}.openai.azure.com/openai/deployments/{deployment}/{operation}?api-version=...`
+    fn endpoint(&self, model_name: &str, operation: &str) -> String {
+        let base = self.url.trim_end_matches('/');
+        let base = base.strip_suffix("/openai").unwrap_or(base);
+        format!(
+            "{}/openai/deployments/{}/{}?api-version={}",
+            base,
+            self.deployment_for(model_name),
+            operation,
+            self.api_version
+        )
+    }
+
+    /// `GET /openai/models`: no depende de ningún deployment, así que sirve para validar
+    /// la `api-key` aunque `deployment` no esté configurado.
+    fn models_request(&self, client: &Client) -> RequestBuilder {
+        let base = self.url.trim_end_matches('/');
+        let base = base.strip_suffix("/openai").unwrap_or(base);
+        client
+            .get(format!("{}/openai/models?api-version={}", base, self.api_version))
+            .header("api-key", &self.api_key)
+    }
+
+    fn chat_request(&self, client: &Client, prompt: &str, model_name: &str) -> RequestBuilder {
+        client
+            .post(self.endpoint(model_name, "chat/completions"))
+            .header("api-key", &self.api_key)
+            .header("content-type", "application/json")
+            .json(&json!({
+                "messages": [{"role": "user", "content": prompt}]
+            }))
+    }
+}
+
+impl super::AiProvider for AzureOpenAiProvider {
+    fn chat(&self, client: &Client, prompt: &str, model_name: &str) -> Result<String> {
+        let response = self.chat_request(client, prompt, model_name).send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("Azure OpenAI", status, body_text).into());
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        body["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Estructura de Azure OpenAI inesperada. Body: {}", body_text)
+            })
+    }
+
+    fn embed(&self, client: &Client, texts: Vec<String>, model_name: &str) -> Result<Vec<Vec<f32>>> {
+        let response = client
+            .post(self.endpoint(model_name, "embeddings"))
+            .header("api-key", &self.api_key)
+            .json(&json!({ "input": texts }))
+            .send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("Azure OpenAI", status, body_text).into());
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        let embeddings = body["data"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Respuesta de Azure OpenAI Embeddings inesperada"))?
+            .iter()
+            .map(|d| -> anyhow::Result<Vec<f32>> {
+                let values = d["embedding"]
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Azure embedding: 'embedding' faltante o no es array"))?;
+                values
+                    .iter()
+                    .map(|v| {
+                        v.as_f64()
+                            .ok_or_else(|| anyhow::anyhow!("Azure embedding: valor no numérico"))
+                            .map(|f| f as f32)
+                    })
+                    .collect()
+            })
+            .collect::<anyhow::Result<Vec<Vec<f32>>>>()?;
+        Ok(embeddings)
+    }
+
+    // Límite de inputs por petición de los deployments de Azure OpenAI más restrictivos
+    fn embed_batch_size(&self) -> usize {
+        16
+    }
+
+    fn list_models(&self, _client: &Client) -> Result<Vec<String>> {
+        // Azure no expone un endpoint público para listar deployments con la api-key;
+        // devolvemos el deployment configurado.
+        match &self.deployment {
+            Some(d) => Ok(vec![d.clone()]),
+            None => Err(anyhow::anyhow!(
+                "Azure OpenAI: configura `deployment` en el modelo (no hay endpoint para listarlos)"
+            )),
+        }
+    }
+
+    fn ping(&self, client: &Client) -> Result<()> {
+        super::fetch_json(self.models_request(client), "Azure OpenAI").map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::providers::AiProvider;
+
+    fn sample() -> AzureOpenAiProvider {
+        AzureOpenAiProvider::new(
+            "secret-key",
+            "https://my-resource.openai.azure.com/",
+            Some("gpt4o-prod"),
+            Some("2024-06-01"),
+        )
+    }
+
+    #[test]
+    fn test_chat_request_url_and_headers() {
+        let client = Client::new();
+        let request = sample()
+            .chat_request(&client, "hola", "gpt-4o")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://my-resource.openai.azure.com/openai/deployments/gpt4o-prod/chat/completions?api-version=2024-06-01"
+        );
+        assert_eq!(request.headers()["api-key"], "secret-key");
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_deployment_falls_back_to_model_name_and_default_version() {
+        let provider = AzureOpenAiProvider::new(
+            "k",
+            "https://res.openai.azure.com/openai",
+            None,
+            None,
+        );
+        assert_eq!(
+            provider.endpoint("gpt-35-turbo", "embeddings"),
+            format!(
+                "https://res.openai.azure.com/openai/deployments/gpt-35-turbo/embeddings?api-version={}",
+                DEFAULT_API_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn test_ping_request_does_not_need_a_deployment() {
+        let without = AzureOpenAiProvider::new("k", "https://res.openai.azure.com/openai/", None, None);
+        let request = without.models_request(&Client::new()).build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            format!("https://res.openai.azure.com/openai/models?api-version={}", DEFAULT_API_VERSION)
+        );
+        assert_eq!(request.headers()["api-key"], "k");
+    }
+
+    #[test]
+    fn test_list_models_returns_configured_deployment() {
+        assert_eq!(sample().list_models(&Client::new()).unwrap(), vec!["gpt4o-prod".to_string()]);
+        let without = AzureOpenAiProvider::new("k", "https://res.openai.azure.com", None, None);
+        assert!(without.list_models(&Client::new()).is_err());
+    }
+}
@@ -0,0 +1,414 @@
+// src/ai/providers/bedrock.rs
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+/// Versión del formato Anthropic-on-Bedrock (va en el body, no en un header).
+pub const BEDROCK_ANTHROPIC_VERSION: &str = "bedrock-2023-05-31";
+const SERVICE: &str = "bedrock";
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Model IDs de Claude disponibles en Bedrock (no hay endpoint de runtime para listarlos).
+pub const KNOWN_MODELS: &[&str] = &[
+    "anthropic.claude-3-5-sonnet-20241022-v2:0",
+    "anthropic.claude-3-5-haiku-20241022-v1:0",
+    "anthropic.claude-3-7-sonnet-20250219-v1:0",
+    "anthropic.claude-sonnet-4-20250514-v1:0",
+    "anthropic.claude-opus-4-20250514-v1:0",
+];
+
+/// Credenciales AWS de las variables de entorno estándar.
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        match (var("AWS_ACCESS_KEY_ID"), var("AWS_SECRET_ACCESS_KEY")) {
+            (Some(access_key_id), Some(secret_access_key)) => Ok(Self {
+                access_key_id,
+                secret_access_key,
+                session_token: var("AWS_SESSION_TOKEN"),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "Bedrock: define AWS_ACCESS_KEY_ID y AWS_SECRET_ACCESS_KEY (y AWS_SESSION_TOKEN si usas credenciales temporales)"
+            )),
+        }
+    }
+}
+
+/// Amazon Bedrock (`InvokeModel`) con modelos Claude. No usa `api_key`: cada petición
+/// se firma con SigV4 usando las credenciales AWS del entorno.
+pub struct BedrockProvider {
+    url: String,
+    region: String,
+}
+
+impl BedrockProvider {
+    /// Región: la de la URL (`bedrock-runtime.{region}.amazonaws.com`), que es el host al
+    /// que se envía la petición; si no tiene (URL vacía o un proxy), `AWS_REGION`,
+    /// `AWS_DEFAULT_REGION` o `us-east-1`.
+    pub fn new(url: &str) -> Self {
+        let env_region = std::env::var("AWS_REGION")
+            .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+            .ok()
+            .filter(|r| !r.is_empty());
+        Self::with_region(url, &resolve_region(url, env_region))
+    }
+
+    pub fn with_region(url: &str, region: &str) -> Self {
+        let url = if url.trim().is_empty() {
+            format!("https://bedrock-runtime.{}.amazonaws.com", region)
+        } else {
+            url.trim_end_matches('/').to_string()
+        };
+        Self { url, region: region.to_string() }
+    }
+
+    fn host(&self) -> &str {
+        let without_scheme = self.url.split_once("://").map(|(_, rest)| rest).unwrap_or(&self.url);
+        without_scheme.split('/').next().unwrap_or(without_scheme)
+    }
+
+    pub fn request_body(prompt: &str) -> serde_json::Value {
+        json!({
+            "anthropic_version": BEDROCK_ANTHROPIC_VERSION,
+            "max_tokens": 4096,
+            "messages": [{"role": "user", "content": prompt}]
+        })
+    }
+
+    /// Petición `InvokeModel` firmada para `amz_date` (`YYYYMMDDTHHMMSSZ`).
+    fn invoke_request(
+        &self,
+        client: &Client,
+        credentials: &AwsCredentials,
+        prompt: &str,
+        model_id: &str,
+        amz_date: &str,
+    ) -> RequestBuilder {
+        let path = format!("/model/{}/invoke", uri_encode(model_id));
+        let body = Self::request_body(prompt).to_string();
+        let payload_hash = hex(&Sha256::digest(body.as_bytes()));
+
+        let mut headers = vec![
+            ("content-type".to_string(), "application/json".to_string()),
+            ("host".to_string(), self.host().to_string()),
+            ("x-amz-date".to_string(), amz_date.to_string()),
+        ];
+        if let Some(token) = &credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        // Servicios distintos de S3: cada segmento del path se codifica dos veces
+        let canonical_uri = format!("/model/{}/invoke", uri_encode(&uri_encode(model_id)));
+        let authorization = sign_v4(
+            credentials,
+            &self.region,
+            SERVICE,
+            &SigningInput {
+                method: "POST",
+                canonical_uri: &canonical_uri,
+                canonical_query: "",
+                headers: &headers,
+                payload_hash: &payload_hash,
+                amz_date,
+            },
+        );
+
+        let mut request = client
+            .post(format!("{}{}", self.url, path))
+            .header("authorization", authorization)
+            .header("accept", "application/json");
+        for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request.body(body)
+    }
+
+    /// `ListFoundationModels` firmado contra el plano de control
+    /// (`bedrock.{region}.amazonaws.com`): valida las credenciales sin invocar un modelo.
+    fn list_models_request(&self, client: &Client, credentials: &AwsCredentials, amz_date: &str) -> RequestBuilder {
+        let host = format!("bedrock.{}.amazonaws.com", self.region);
+        let query = "byProvider=anthropic";
+        let mut headers = vec![
+            ("host".to_string(), host.clone()),
+            ("x-amz-date".to_string(), amz_date.to_string()),
+        ];
+        if let Some(token) = &credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        let authorization = sign_v4(
+            credentials,
+            &self.region,
+            SERVICE,
+            &SigningInput {
+                method: "GET",
+                canonical_uri: "/foundation-models",
+                canonical_query: query,
+                headers: &headers,
+                payload_hash: &hex(&Sha256::digest(b"")),
+                amz_date,
+            },
+        );
+
+        let mut request = client
+            .get(format!("https://{}/foundation-models?{}", host, query))
+            .header("authorization", authorization)
+            .header("accept", "application/json");
+        for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request
+    }
+}
+
+/// Ver `BedrockProvider::new`: firmar para otra región que la del host la hace rechazar.
+fn resolve_region(url: &str, env_region: Option<String>) -> String {
+    region_from_url(url)
+        .or(env_region)
+        .unwrap_or_else(|| DEFAULT_REGION.to_string())
+}
+
+fn region_from_url(url: &str) -> Option<String> {
+    let host = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let rest = host.strip_prefix("bedrock-runtime.")?;
+    let region = rest.split('.').next()?;
+    (!region.is_empty() && rest.contains("amazonaws.com")).then(|| region.to_string())
+}
+
+/// Texto de la respuesta de Claude en Bedrock (mismo shape que la Messages API).
+pub fn parse_response(body_text: &str) -> Result<String> {
+    let body: serde_json::Value = serde_json::from_str(body_text)?;
+    body["content"]
+        .as_array()
+        .and_then(|blocks| blocks.iter().find(|b| b["type"] == "text"))
+        .and_then(|b| b["text"].as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Estructura de Bedrock inesperada. Body: {}", body_text))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC acepta claves de cualquier tamaño");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Codificación URI de SigV4: todo salvo `A-Z a-z 0-9 - _ . ~`.
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+struct SigningInput<'a> {
+    method: &'a str,
+    canonical_uri: &'a str,
+    canonical_query: &'a str,
+    /// Nombres en minúsculas; se ordenan al firmar
+    headers: &'a [(String, String)],
+    payload_hash: &'a str,
+    amz_date: &'a str,
+}
+
+fn canonical_request(input: &SigningInput) -> (String, String) {
+    let mut headers: Vec<&(String, String)> = input.headers.iter().collect();
+    headers.sort_by(|a, b| a.0.cmp(&b.0));
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(";");
+    let request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        input.method, input.canonical_uri, input.canonical_query, canonical_headers, signed_headers, input.payload_hash
+    );
+    (request, signed_headers)
+}
+
+/// Header `Authorization` de AWS Signature Version 4.
+fn sign_v4(credentials: &AwsCredentials, region: &str, service: &str, input: &SigningInput) -> String {
+    let (request, signed_headers) = canonical_request(input);
+    let date = &input.amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        input.amz_date,
+        scope,
+        hex(&Sha256::digest(request.as_bytes()))
+    );
+
+    let mut key = hmac_sha256(format!("AWS4{}", credentials.secret_access_key).as_bytes(), date);
+    for part in [region, service, "aws4_request"] {
+        key = hmac_sha256(&key, part);
+    }
+    let signature = hex(&hmac_sha256(&key, &string_to_sign));
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+impl super::AiProvider for BedrockProvider {
+    fn chat(&self, client: &Client, prompt: &str, model_name: &str) -> Result<String> {
+        let credentials = AwsCredentials::from_env()?;
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let response = self.invoke_request(client, &credentials, prompt, model_name, &amz_date).send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("Bedrock", status, body_text).into());
+        }
+        parse_response(&body_text)
+    }
+
+    fn embed(&self, _client: &Client, _texts: Vec<String>, _model_name: &str) -> Result<Vec<Vec<f32>>> {
+        Err(anyhow::anyhow!(
+            "Bedrock (Claude) no soporta embeddings. Usa provider 'local'."
+        ))
+    }
+
+    fn list_models(&self, _client: &Client) -> Result<Vec<String>> {
+        Ok(KNOWN_MODELS.iter().map(|m| m.to_string()).collect())
+    }
+
+    // `list_models` es una lista fija: el ping tiene que firmar una petición real
+    fn ping(&self, client: &Client) -> Result<()> {
+        let credentials = AwsCredentials::from_env()?;
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        super::fetch_json(self.list_models_request(client, &credentials, &amz_date), "Bedrock").map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_credentials() -> AwsCredentials {
+        AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        }
+    }
+
+    #[test]
+    fn test_sigv4_matches_aws_documentation_example() {
+        // Ejemplo oficial de AWS (IAM ListUsers, 2015-08-30)
+        let headers = vec![
+            ("content-type".to_string(), "application/x-www-form-urlencoded; charset=utf-8".to_string()),
+            ("host".to_string(), "iam.amazonaws.com".to_string()),
+            ("x-amz-date".to_string(), "20150830T123600Z".to_string()),
+        ];
+        let input = SigningInput {
+            method: "GET",
+            canonical_uri: "/",
+            canonical_query: "Action=ListUsers&Version=2010-05-08",
+            headers: &headers,
+            payload_hash: &hex(&Sha256::digest(b"")),
+            amz_date: "20150830T123600Z",
+        };
+        let (request, signed) = canonical_request(&input);
+        assert_eq!(signed, "content-type;host;x-amz-date");
+        assert_eq!(
+            hex(&Sha256::digest(request.as_bytes())),
+            "f536975d06c0309214f805bb90ccff089219ecd68b2577efef23edd43b7e1a59"
+        );
+        assert_eq!(
+            sign_v4(&example_credentials(), "us-east-1", "iam", &input),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date, \
+             Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+        );
+    }
+
+    #[test]
+    fn test_invoke_request_url_headers_and_body() {
+        let provider = BedrockProvider::with_region("", "eu-west-1");
+        let mut credentials = example_credentials();
+        credentials.session_token = Some("session".to_string());
+        let request = provider
+            .invoke_request(&Client::new(), &credentials, "hola", "anthropic.claude-3-5-haiku-20241022-v1:0", "20260101T000000Z")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://bedrock-runtime.eu-west-1.amazonaws.com/model/anthropic.claude-3-5-haiku-20241022-v1%3A0/invoke"
+        );
+        let auth = request.headers()["authorization"].to_str().unwrap();
+        assert!(auth.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260101/eu-west-1/bedrock/aws4_request"));
+        assert!(auth.contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token"));
+        assert_eq!(request.headers()["x-amz-security-token"], "session");
+
+        let body: serde_json::Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(body["anthropic_version"], BEDROCK_ANTHROPIC_VERSION);
+        assert_eq!(body["messages"][0]["content"], "hola");
+        assert!(body.get("model").is_none(), "the model goes in the path, not the body");
+    }
+
+    #[test]
+    fn test_list_models_request_is_signed_for_the_control_plane() {
+        let provider = BedrockProvider::with_region("", "eu-west-1");
+        let request = provider
+            .list_models_request(&Client::new(), &example_credentials(), "20260101T000000Z")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.method(), "GET");
+        assert_eq!(
+            request.url().as_str(),
+            "https://bedrock.eu-west-1.amazonaws.com/foundation-models?byProvider=anthropic"
+        );
+        let auth = request.headers()["authorization"].to_str().unwrap();
+        assert!(auth.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260101/eu-west-1/bedrock/aws4_request"));
+        assert!(auth.contains("SignedHeaders=host;x-amz-date"));
+    }
+
+    #[test]
+    fn test_parse_response_and_region_from_url() {
+        let body = r#"{"id":"msg_1","type":"message","role":"assistant",
+            "content":[{"type":"text","text":"Todo bien"}],"stop_reason":"end_turn"}"#;
+        assert_eq!(parse_response(body).unwrap(), "Todo bien");
+        assert!(parse_response(r#"{"message":"AccessDenied"}"#).is_err());
+
+        assert_eq!(
+            region_from_url("https://bedrock-runtime.ap-northeast-1.amazonaws.com").as_deref(),
+            Some("ap-northeast-1")
+        );
+        assert_eq!(region_from_url("https://proxy.internal"), None);
+    }
+
+    #[test]
+    fn test_url_region_wins_over_aws_region() {
+        // AWS_REGION=us-east-1 en el runner con la URL de eu-west-1: se firma para el host
+        let url = "https://bedrock-runtime.eu-west-1.amazonaws.com";
+        let provider = BedrockProvider::with_region(url, &resolve_region(url, Some("us-east-1".to_string())));
+        let request = provider
+            .invoke_request(&Client::new(), &example_credentials(), "hola", "anthropic.claude-3-5-haiku-20241022-v1:0", "20260101T000000Z")
+            .build()
+            .unwrap();
+        assert_eq!(request.url().host_str(), Some("bedrock-runtime.eu-west-1.amazonaws.com"));
+        let auth = request.headers()["authorization"].to_str().unwrap();
+        assert!(auth.contains("/20260101/eu-west-1/bedrock/aws4_request"), "{}", auth);
+
+        // Sin región en la URL (proxy o vacía) manda el entorno
+        assert_eq!(resolve_region("https://proxy.internal", Some("us-west-2".to_string())), "us-west-2");
+        assert_eq!(resolve_region("", Some("ap-south-1".to_string())), "ap-south-1");
+        assert_eq!(resolve_region("", None), DEFAULT_REGION);
+    }
+}
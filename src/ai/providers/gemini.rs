@@ -39,11 +39,7 @@ impl super::AiProvider for GeminiProvider {
             let body_text = response.text()?;
 
             if !status.is_success() {
-                return Err(anyhow::anyhow!(
-                    "Error de API Gemini Interactions (Status {}): {}",
-                    status,
-                    body_text
-                ));
+                return Err(super::ApiStatusError::new("Gemini Interactions", status, body_text).into());
             }
 
             let body: serde_json::Value = serde_json::from_str(&body_text)?;
@@ -89,11 +85,7 @@ impl super::AiProvider for GeminiProvider {
             let body_text = response.text()?;
 
             if !status.is_success() {
-                return Err(anyhow::anyhow!(
-                    "Error de API Gemini (Status {}): {}",
-                    status,
-                    body_text
-                ));
+                return Err(super::ApiStatusError::new("Gemini", status, body_text).into());
             }
 
             let body: serde_json::Value = serde_json::from_str(&body_text)?;
@@ -154,17 +146,26 @@ impl super::AiProvider for GeminiProvider {
         Ok(embeddings)
     }
 
-    fn list_models(&self) -> Result<Vec<String>> {
-        let client = Client::new();
-        let response = client
-            .get(format!(
-                "{}/v1beta/models?key={}",
-                self.url.trim_end_matches('/'),
-                self.api_key
-            ))
-            .send()?;
+    // Límite de `batchEmbedContents`
+    fn embed_batch_size(&self) -> usize {
+        100
+    }
+
+    fn list_models(&self, client: &Client) -> Result<Vec<String>> {
+        self.fetch_models(client)
+    }
+}
+
+impl GeminiProvider {
+    /// Listado de modelos con `client`; un status no exitoso llega como `ApiStatusError`.
+    fn fetch_models(&self, client: &Client) -> Result<Vec<String>> {
+        let request = client.get(format!(
+            "{}/v1beta/models?key={}",
+            self.url.trim_end_matches('/'),
+            self.api_key
+        ));
 
-        let json: serde_json::Value = response.json()?;
+        let json = super::fetch_json(request, "Gemini")?;
         let models = json["models"]
             .as_array()
             .ok_or_else(|| anyhow::anyhow!("Respuesta de Gemini inválida"))?
@@ -6,6 +6,10 @@
 //! - `"interactions"` — Google Gemini Interactions API (endpoint distinto)
 //! - `"ollama"` — Ollama local
 //! - `"openai"` / `"lm-studio"` / `"groq"` / `"kimi"` / `"deepseek"` — OpenAI-compatible
+//! - `"azure"` — Azure OpenAI (routing por `deployment` + `api_version`)
+//! - `"openrouter"` — OpenRouter (modelos `proveedor/modelo`, headers de atribución)
+//! - `"bedrock"` — Claude vía Amazon Bedrock (firma SigV4 con credenciales AWS del entorno)
+//! - `"vertex"` — Google Vertex AI (endpoint regional + `project`/`location`, token OAuth de las ADC)
 //!
 //! Para agregar un nuevo proveedor:
 //! 1. Crear `src/ai/providers/mi_proveedor.rs` implementando `AiProvider`
@@ -13,17 +17,50 @@
 //! 3. Agregar un arm al match en `build_provider`
 
 pub mod anthropic;
+pub mod azure;
+pub mod bedrock;
 pub mod gemini;
 pub mod ollama;
 pub mod openai_compat;
+pub mod openrouter;
+pub mod vertex;
 
 pub use anthropic::AnthropicProvider;
+pub use azure::AzureOpenAiProvider;
+pub use bedrock::BedrockProvider;
 pub use gemini::GeminiProvider;
 pub use ollama::OllamaProvider;
 pub use openai_compat::OpenAiCompatProvider;
+pub use openrouter::OpenRouterProvider;
+pub use vertex::VertexProvider;
 
 use crate::config::ModelConfig;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+
+/// Respuesta HTTP no exitosa de un proveedor.
+/// Tipado para que el cliente distinga errores transitorios (5xx) de errores del prompt (4xx).
+#[derive(Debug)]
+pub struct ApiStatusError {
+    pub provider: &'static str,
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl ApiStatusError {
+    pub fn new(provider: &'static str, status: StatusCode, body: String) -> Self {
+        Self { provider, status, body }
+    }
+}
+
+impl std::fmt::Display for ApiStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error de API {} (Status {}): {}", self.provider, self.status, self.body)
+    }
+}
+
+impl std::error::Error for ApiStatusError {}
 
 pub trait AiProvider: Send + Sync {
     fn chat(&self, client: &Client, prompt: &str, model_name: &str) -> anyhow::Result<String>;
@@ -35,19 +72,223 @@ pub trait AiProvider: Send + Sync {
         model_name: &str,
     ) -> anyhow::Result<Vec<Vec<f32>>>;
 
-    fn list_models(&self) -> anyhow::Result<Vec<String>>;
+    /// Máximo de textos por petición de embeddings. `1` = el proveedor no acepta lotes
+    /// y `embed_many` hace una petición por texto.
+    fn embed_batch_size(&self) -> usize {
+        1
+    }
+
+    /// Modelos disponibles. `client` es el de `model_client`: lleva las `headers` del modelo.
+    fn list_models(&self, client: &Client) -> anyhow::Result<Vec<String>>;
+
+    /// Comprobación barata de que el proveedor responde y acepta la api_key. Por defecto
+    /// lista los modelos con `client` (y su timeout).
+    fn ping(&self, client: &Client) -> anyhow::Result<()> {
+        self.list_models(client).map(|_| ())
+    }
 }
 
-/// Único punto de despacho de providers.
-/// El campo `provider` en ModelConfig determina cuál se usa.
-/// Si está vacío, se intenta detectar por URL.
-pub fn build_provider(config: &ModelConfig) -> Box<dyn AiProvider> {
-    let provider = if config.provider.is_empty() {
+/// GET que devuelve JSON; un status no exitoso se devuelve como `ApiStatusError`
+/// para que `PingFailure` distinga credenciales rechazadas.
+pub(crate) fn fetch_json(request: RequestBuilder, provider: &'static str) -> anyhow::Result<serde_json::Value> {
+    let response = request.send()?;
+    let status = response.status();
+    let body = response.text()?;
+    if !status.is_success() {
+        return Err(ApiStatusError::new(provider, status, body).into());
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Por qué falló un `ping`, para dar un mensaje accionable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingFailure {
+    /// 401/403: api_key inválida o sin permisos
+    Auth,
+    /// Timeout, DNS o conexión rechazada
+    Network,
+    /// Otra respuesta de error (5xx, formato inesperado...)
+    Other,
+}
+
+impl PingFailure {
+    pub fn classify(e: &anyhow::Error) -> Self {
+        for cause in e.chain() {
+            if let Some(status) = cause.downcast_ref::<ApiStatusError>() {
+                return match status.status {
+                    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth,
+                    _ => Self::Other,
+                };
+            }
+            if let Some(re) = cause.downcast_ref::<reqwest::Error>()
+                && (re.is_timeout() || re.is_connect())
+            {
+                return Self::Network;
+            }
+        }
+        Self::Other
+    }
+}
+
+/// `true` si `e` es un 429 del proveedor (límite de peticiones).
+pub fn is_rate_limited(e: &anyhow::Error) -> bool {
+    e.chain()
+        .filter_map(|cause| cause.downcast_ref::<ApiStatusError>())
+        .any(|api| api.status == StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// `true` si `e` es la respuesta de un servidor local que no tiene el modelo: Ollama
+/// (`model "x" not found, try pulling it first`) o LM Studio (modelo no cargado).
+pub fn is_model_not_found(e: &anyhow::Error) -> bool {
+    e.chain()
+        .filter_map(|cause| cause.downcast_ref::<ApiStatusError>())
+        .any(|api| {
+            let body = api.body.to_lowercase();
+            matches!(api.status, StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST)
+                && body.contains("model")
+                && (body.contains("not found") || body.contains("not loaded") || body.contains("no models loaded"))
+        })
+}
+
+/// Timeout de `ping_model`: debe ser corto, es solo un health-check.
+pub const PING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Construye el proveedor de `config` y hace `ping`, con un mensaje que distingue
+/// credenciales inválidas de problemas de red.
+pub fn ping_model(config: &ModelConfig, timeout: std::time::Duration) -> anyhow::Result<()> {
+    let provider = build_provider(config)?;
+    provider.ping(&model_client(config, timeout)?).map_err(|e| {
+        let msg = match PingFailure::classify(&e) {
+            PingFailure::Auth => format!(
+                "el proveedor rechazó las credenciales del modelo '{}' (revisa `api_key`)",
+                config.name
+            ),
+            PingFailure::Network => format!(
+                "no se pudo conectar con {} para el modelo '{}' (red, URL o timeout de {} s)",
+                config.url,
+                config.name,
+                timeout.as_secs()
+            ),
+            PingFailure::Other => format!("el proveedor del modelo '{}' respondió con un error", config.name),
+        };
+        e.context(msg)
+    })
+}
+
+/// Embeddings de `texts` en lotes de `embed_batch_size()`, en el mismo orden de entrada.
+pub fn embed_many(
+    provider: &dyn AiProvider,
+    client: &Client,
+    texts: Vec<String>,
+    model_name: &str,
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    let batch_size = provider.embed_batch_size().max(1);
+    let mut results = Vec::with_capacity(texts.len());
+    for chunk in texts.chunks(batch_size) {
+        let embeddings = provider.embed(client, chunk.to_vec(), model_name)?;
+        if embeddings.len() != chunk.len() {
+            anyhow::bail!(
+                "El proveedor devolvió {} embeddings para un lote de {} textos",
+                embeddings.len(),
+                chunk.len()
+            );
+        }
+        results.extend(embeddings);
+    }
+    Ok(results)
+}
+
+/// Cliente HTTP con timeout; `chat`/`embed` lo reciben ya configurado.
+pub fn http_client(timeout: std::time::Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .unwrap_or_else(|_| Client::new())
+}
+
+/// Cabeceras cuyo valor no se muestra en los logs.
+fn is_sensitive_header(name: &str) -> bool {
+    let name = name.to_lowercase();
+    ["authorization", "cookie", "key", "token", "secret", "password"].iter().any(|s| name.contains(s))
+}
+
+/// `headers` del modelo como `HeaderMap`, con los valores `env:`/`file:`/`keyring:` resueltos.
+pub fn custom_headers(config: &ModelConfig) -> anyhow::Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (name, raw) in &config.headers {
+        let value = crate::secrets::resolve_secret(raw)
+            .map_err(|e| anyhow::anyhow!("header '{}' del modelo '{}': {}", name, config.name, e))?;
+        let header_name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| anyhow::anyhow!("nombre de header inválido '{}' en el modelo '{}'", name, config.name))?;
+        let mut header_value = HeaderValue::from_str(&value)
+            .map_err(|_| anyhow::anyhow!("valor inválido para el header '{}' del modelo '{}'", name, config.name))?;
+        let sensitive = is_sensitive_header(name);
+        header_value.set_sensitive(sensitive);
+        log::debug!("header {}: {} ({})", name, if sensitive { "***" } else { value.as_str() }, config.name);
+        headers.insert(header_name, header_value);
+    }
+    Ok(headers)
+}
+
+/// `http_client` con las `headers` del modelo: reqwest las añade a cada petición que haga
+/// el proveedor con este cliente.
+pub fn model_client(config: &ModelConfig, timeout: std::time::Duration) -> anyhow::Result<Client> {
+    let headers = custom_headers(config)?;
+    if headers.is_empty() {
+        return Ok(http_client(timeout));
+    }
+    Ok(Client::builder().timeout(timeout).default_headers(headers).build()?)
+}
+
+/// Añade un mensaje claro a los timeouts de reqwest. El error original se conserva
+/// en la cadena, así que `es_error_recuperable` lo sigue reconociendo.
+pub fn describe_timeout(e: anyhow::Error, config: &ModelConfig, timeout: std::time::Duration) -> anyhow::Error {
+    let is_timeout = e
+        .downcast_ref::<reqwest::Error>()
+        .map(|re| re.is_timeout())
+        .unwrap_or(false);
+    if !is_timeout {
+        return e;
+    }
+    e.context(format!(
+        "Timeout: el modelo '{}' ({}) no respondió en {} s (ajusta `timeout_secs` en la config)",
+        config.name,
+        if config.provider.is_empty() { "auto" } else { &config.provider },
+        timeout.as_secs()
+    ))
+}
+
+/// LM Studio no descarga modelos por API: si el modelo no está cargado se indica cómo
+/// cargarlo en vez de devolver el 404 tal cual.
+pub fn describe_missing_model(e: anyhow::Error, config: &ModelConfig) -> anyhow::Error {
+    if config.provider != "lm-studio" || !is_model_not_found(&e) {
+        return e;
+    }
+    e.context(format!(
+        "LM Studio no tiene cargado el modelo '{}': cárgalo en LM Studio (pestaña Developer o `lms load {}`) y vuelve a intentarlo",
+        config.name, config.name
+    ))
+}
+
+/// Proveedor efectivo del modelo: el campo `provider`, o si está vacío el que se deduce
+/// de la URL (el mismo que elige `build_provider`).
+pub fn provider_name(config: &ModelConfig) -> &str {
+    if config.provider.is_empty() {
         let url = config.url.to_lowercase();
-        if url.contains("interactions") {
+        if url.contains("openai.azure.com") {
+            "azure"
+        } else if url.contains("bedrock-runtime") {
+            "bedrock"
+        } else if url.contains("openrouter.ai") {
+            "openrouter"
+        } else if url.contains("aiplatform.googleapis.com") {
+            "vertex"
+        } else if url.contains("interactions") {
             "interactions"
         } else if url.contains("googleapis") {
             "gemini"
+        } else if url.contains(":11434") || url.contains("ollama") {
+            "ollama"
         } else if url.contains("deepseek")
             || url.contains("groq")
             || url.contains("kimi")
@@ -59,16 +300,196 @@ pub fn build_provider(config: &ModelConfig) -> Box<dyn AiProvider> {
         }
     } else {
         config.provider.as_str()
-    };
+    }
+}
+
+/// Único punto de despacho de providers.
+/// El campo `provider` en ModelConfig determina cuál se usa.
+/// Si está vacío, se intenta detectar por URL.
+/// La `api_key` se resuelve aquí (`env:`, `file:`, `keyring:` o texto plano; ver `crate::secrets`).
+pub fn build_provider(config: &ModelConfig) -> anyhow::Result<Box<dyn AiProvider>> {
+    let api_key = crate::secrets::resolve_secret(&config.api_key)
+        .map_err(|e| anyhow::anyhow!("api_key del modelo '{}': {}", config.name, e))?;
+    let api_key = api_key.as_str();
 
-    match provider {
-        "gemini" => Box::new(GeminiProvider::new(&config.api_key, &config.url, false)),
+    Ok(match provider_name(config) {
+        "gemini" => Box::new(GeminiProvider::new(api_key, &config.url, false)),
         // "interactions" es el alias para la Gemini Interactions API (distinta de Content API)
-        "interactions" => Box::new(GeminiProvider::new(&config.api_key, &config.url, true)),
-        "ollama" => Box::new(OllamaProvider::new(&config.url)),
+        "interactions" => Box::new(GeminiProvider::new(api_key, &config.url, true)),
+        "ollama" => Box::new(
+            OllamaProvider::new(&config.url)
+                .with_auto_pull(config.auto_pull)
+                .with_headers(custom_headers(config)?),
+        ),
         "openai" | "lm-studio" | "groq" | "kimi" | "deepseek" => {
-            Box::new(OpenAiCompatProvider::new(&config.api_key, &config.url))
+            Box::new(OpenAiCompatProvider::new(api_key, &config.url))
         }
-        _ => Box::new(AnthropicProvider::new(&config.api_key, &config.url)),
+        "azure" => Box::new(AzureOpenAiProvider::new(
+            api_key,
+            &config.url,
+            config.deployment.as_deref(),
+            config.api_version.as_deref(),
+        )),
+        "openrouter" => Box::new(OpenRouterProvider::new(api_key, &config.url)),
+        "bedrock" => Box::new(BedrockProvider::new(&config.url)),
+        "vertex" => Box::new(VertexProvider::new(
+            api_key,
+            &config.url,
+            config.project.as_deref(),
+            config.location.as_deref(),
+        )),
+        _ => Box::new(AnthropicProvider::new(api_key, &config.url)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Devuelve `[índice global]` por texto y anota el tamaño de cada petición.
+    struct MockBatchProvider {
+        batch_size: usize,
+        requests: Mutex<Vec<usize>>,
+    }
+
+    impl AiProvider for MockBatchProvider {
+        fn chat(&self, _client: &Client, _prompt: &str, _model_name: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn embed(&self, _client: &Client, texts: Vec<String>, _model_name: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.requests.lock().unwrap().push(texts.len());
+            Ok(texts.iter().map(|t| vec![t.parse::<f32>().unwrap()]).collect())
+        }
+
+        fn embed_batch_size(&self) -> usize {
+            self.batch_size
+        }
+
+        fn list_models(&self, _client: &Client) -> anyhow::Result<Vec<String>> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn test_embed_many_chunks_requests_and_keeps_order() {
+        let texts: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        let client = Client::new();
+
+        let batched = MockBatchProvider { batch_size: 4, requests: Mutex::new(vec![]) };
+        let out = embed_many(&batched, &client, texts.clone(), "m").unwrap();
+        assert_eq!(*batched.requests.lock().unwrap(), vec![4, 4, 2]);
+        let order: Vec<f32> = out.iter().map(|v| v[0]).collect();
+        assert_eq!(order, (0..10).map(|i| i as f32).collect::<Vec<_>>());
+
+        // Sin soporte de lotes: una petición por texto
+        let single = MockBatchProvider { batch_size: 1, requests: Mutex::new(vec![]) };
+        assert_eq!(embed_many(&single, &client, texts, "m").unwrap().len(), 10);
+        assert_eq!(single.requests.lock().unwrap().len(), 10);
+    }
+
+    /// Servidor HTTP de una sola conexión: responde `response` o, con `None`, nunca contesta.
+    fn mock_server(response: Option<&'static str>) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                match response {
+                    Some(r) => {
+                        let _ = stream.write_all(r.as_bytes());
+                    }
+                    None => std::thread::sleep(std::time::Duration::from_secs(3)),
+                }
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_custom_headers_are_sent_by_openai_compat_provider() {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 8192];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let _ = tx.send(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+                let body = "{\"choices\":[{\"message\":{\"content\":\"ok\"}}]}";
+                let _ = stream.write_all(
+                    format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)
+                        .as_bytes(),
+                );
+            }
+        });
+
+        let config = ModelConfig {
+            name: "gpt-4o".to_string(),
+            url,
+            provider: "openai".to_string(),
+            api_key: "sk-test".to_string(),
+            headers: [("X-Org-Id", "acme"), ("X-Cost-Center", "cc-42"), ("X-Gateway-Token", "gw-secret")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+        let client = model_client(&config, std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(build_provider(&config).unwrap().chat(&client, "hola", &config.name).unwrap(), "ok");
+
+        let request = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert!(request.contains("x-org-id: acme"), "{}", request);
+        assert!(request.contains("x-cost-center: cc-42"));
+        assert!(request.contains("x-gateway-token: gw-secret"));
+        assert!(request.contains("authorization: bearer sk-test"), "provider headers are kept");
+
+        assert!(is_sensitive_header("X-Gateway-Token") && is_sensitive_header("Authorization"));
+        assert!(!is_sensitive_header("X-Org-Id"));
+        let bad = ModelConfig { headers: [("X Org".to_string(), "a".to_string())].into_iter().collect(), ..Default::default() };
+        assert!(model_client(&bad, std::time::Duration::from_secs(1)).is_err());
+    }
+
+    const RATE_LIMITED: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nContent-Type: application/json\r\nContent-Length: 24\r\nConnection: close\r\n\r\n{\"error\":\"rate_limited\"}";
+
+    #[test]
+    fn test_azure_embed_error_status_is_an_api_status_error() {
+        let client = http_client(std::time::Duration::from_secs(5));
+        let provider = AzureOpenAiProvider::new("key", &mock_server(Some(RATE_LIMITED)), Some("emb"), Some("2024-06-01"));
+        let err = provider.embed(&client, vec!["hola".to_string()], "emb").unwrap_err();
+        assert!(is_rate_limited(&err), "{:#}", err);
+    }
+
+    #[test]
+    fn test_openrouter_embed_error_status_is_an_api_status_error() {
+        let client = http_client(std::time::Duration::from_secs(5));
+        let provider = OpenRouterProvider::new("sk-or", &mock_server(Some(RATE_LIMITED)));
+        let err = provider.embed(&client, vec!["hola".to_string()], "openai/text-embedding-3-small").unwrap_err();
+        assert!(is_rate_limited(&err), "{:#}", err);
+    }
+
+    #[test]
+    fn test_ping_classifies_auth_vs_network_errors() {
+        let client = http_client(std::time::Duration::from_millis(300));
+
+        let unauthorized = mock_server(Some(
+            "HTTP/1.1 401 Unauthorized\r\nContent-Type: application/json\r\nContent-Length: 27\r\nConnection: close\r\n\r\n{\"error\":\"invalid_api_key\"}",
+        ));
+        let err = OpenAiCompatProvider::new("sk-bad", &unauthorized).ping(&client).unwrap_err();
+        assert_eq!(PingFailure::classify(&err), PingFailure::Auth, "{:#}", err);
+
+        let silent = mock_server(None);
+        let err = AnthropicProvider::new("sk-ant", &silent).ping(&client).unwrap_err();
+        assert_eq!(PingFailure::classify(&err), PingFailure::Network, "{:#}", err);
+
+        let ok = mock_server(Some(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 26\r\nConnection: close\r\n\r\n{\"data\":[{\"id\":\"gpt-4o\"}]}",
+        ));
+        assert!(OpenAiCompatProvider::new("sk-ok", &ok).ping(&client).is_ok());
     }
 }
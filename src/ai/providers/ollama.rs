@@ -1,22 +1,85 @@
 // src/ai/providers/ollama.rs
 use anyhow::Result;
 use reqwest::blocking::Client;
+use reqwest::header::HeaderMap;
 use serde_json::json;
+use std::io::BufRead;
 
 pub struct OllamaProvider {
     url: String,
+    /// Descargar el modelo si Ollama no lo tiene (`auto_pull` en la config)
+    auto_pull: bool,
+    /// `headers` del modelo, para las peticiones que no usan el cliente de `chat` (`pull`)
+    headers: HeaderMap,
 }
 
 impl OllamaProvider {
     pub fn new(url: &str) -> Self {
         Self {
             url: url.to_string(),
+            auto_pull: false,
+            headers: HeaderMap::new(),
         }
     }
+
+    pub fn with_auto_pull(mut self, auto_pull: bool) -> Self {
+        self.auto_pull = auto_pull;
+        self
+    }
+
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
 }
 
 impl super::AiProvider for OllamaProvider {
     fn chat(&self, client: &Client, prompt: &str, model_name: &str) -> Result<String> {
+        match self.generate(client, prompt, model_name) {
+            Err(e) if super::is_model_not_found(&e) => {
+                if !self.auto_pull {
+                    return Err(e.context(format!(
+                        "Ollama no tiene el modelo '{}': descárgalo con `ollama pull {}` o activa `auto_pull = true` en la config",
+                        model_name, model_name
+                    )));
+                }
+                self.pull(model_name)?;
+                self.generate(client, prompt, model_name)
+            }
+            result => result,
+        }
+    }
+
+    fn embed(&self, client: &Client, texts: Vec<String>, model_name: &str) -> Result<Vec<Vec<f32>>> {
+        let url = format!("{}/api/embed", self.url.trim_end_matches('/'));
+        let mut results = Vec::new();
+
+        for texto in texts {
+            let response = client
+                .post(&url)
+                .json(&json!({ "model": model_name, "input": texto }))
+                .send()?;
+
+            let body: serde_json::Value = response.json()?;
+            let embedding = body["embeddings"][0]
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("Respuesta de Ollama Embeddings inesperada"))?
+                .iter()
+                .map(|v| v.as_f64().unwrap() as f32)
+                .collect();
+            results.push(embedding);
+        }
+
+        Ok(results)
+    }
+
+    fn list_models(&self, client: &Client) -> Result<Vec<String>> {
+        self.fetch_models(client)
+    }
+}
+
+impl OllamaProvider {
+    fn generate(&self, client: &Client, prompt: &str, model_name: &str) -> Result<String> {
         let url = format!("{}/api/generate", self.url.trim_end_matches('/'));
 
         let response = client
@@ -32,11 +95,7 @@ impl super::AiProvider for OllamaProvider {
         let body_text = response.text()?;
 
         if !status.is_success() {
-            return Err(anyhow::anyhow!(
-                "Error de API Ollama (Status {}): {}",
-                status,
-                body_text
-            ));
+            return Err(super::ApiStatusError::new("Ollama", status, body_text).into());
         }
 
         let body: serde_json::Value = serde_json::from_str(&body_text)?;
@@ -48,31 +107,60 @@ impl super::AiProvider for OllamaProvider {
             })
     }
 
-    fn embed(&self, client: &Client, texts: Vec<String>, model_name: &str) -> Result<Vec<Vec<f32>>> {
-        let url = format!("{}/api/embed", self.url.trim_end_matches('/'));
-        let mut results = Vec::new();
-
-        for texto in texts {
-            let response = client
-                .post(&url)
-                .json(&json!({ "model": model_name, "input": texto }))
-                .send()?;
+    /// `/api/pull` de `model_name`, mostrando el progreso en stderr. Usa su propio cliente sin
+    /// timeout (con las `headers` del modelo): la descarga puede tardar mucho más que un chat.
+    fn pull(&self, model_name: &str) -> Result<()> {
+        let url = format!("{}/api/pull", self.url.trim_end_matches('/'));
+        let client = Client::builder().timeout(None).default_headers(self.headers.clone()).build()?;
+        let response = client
+            .post(&url)
+            .json(&json!({ "model": model_name, "stream": true }))
+            .send()?;
 
-            let body: serde_json::Value = response.json()?;
-            let embedding = body["embeddings"][0]
-                .as_array()
-                .ok_or_else(|| anyhow::anyhow!("Respuesta de Ollama Embeddings inesperada"))?
-                .iter()
-                .map(|v| v.as_f64().unwrap() as f32)
-                .collect();
-            results.push(embedding);
+        let status = response.status();
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("Ollama", status, response.text()?).into());
         }
 
-        Ok(results)
+        let show = !crate::commands::is_quiet();
+        if show {
+            eprintln!("   ⬇️  Descargando el modelo '{}' en Ollama...", model_name);
+        }
+        // Una línea JSON por evento: {"status":"downloading","total":N,"completed":M} ... {"status":"success"}
+        let mut last_status = String::new();
+        for line in std::io::BufReader::new(response).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: serde_json::Value = serde_json::from_str(&line)?;
+            if let Some(error) = event["error"].as_str() {
+                anyhow::bail!("Ollama no pudo descargar '{}': {}", model_name, error);
+            }
+            let event_status = event["status"].as_str().unwrap_or_default();
+            if event_status == "success" {
+                if show {
+                    eprintln!("\r   ✅ Modelo '{}' descargado{}", model_name, " ".repeat(20));
+                }
+                return Ok(());
+            }
+            if !show {
+                continue;
+            }
+            match (event["completed"].as_u64(), event["total"].as_u64()) {
+                (Some(done), Some(total)) if total > 0 => {
+                    eprint!("\r      {} {:>3}%", event_status, done * 100 / total);
+                }
+                _ if event_status != last_status => eprint!("\r      {}{}", event_status, " ".repeat(20)),
+                _ => {}
+            }
+            last_status = event_status.to_string();
+        }
+        anyhow::bail!("La descarga de '{}' en Ollama terminó sin confirmar", model_name)
     }
 
-    fn list_models(&self) -> Result<Vec<String>> {
-        let client = Client::new();
+    /// Listado de modelos con `client`; un status no exitoso llega como `ApiStatusError`.
+    fn fetch_models(&self, client: &Client) -> Result<Vec<String>> {
         let url_str = self.url.trim_end_matches('/');
         let is_native = !url_str.ends_with("/v1");
 
@@ -82,8 +170,7 @@ impl super::AiProvider for OllamaProvider {
             format!("{}/models", url_str)
         };
 
-        let response = client.get(&target_url).send()?;
-        let json: serde_json::Value = response.json()?;
+        let json = super::fetch_json(client.get(&target_url), "Ollama")?;
 
         if is_native {
             let models = json["models"]
@@ -104,3 +191,84 @@ impl super::AiProvider for OllamaProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::providers::AiProvider;
+    use std::io::{Read, Write};
+
+    /// Ollama falso: `/api/generate` da 404 hasta que llega un `/api/pull`. Devuelve la URL y
+    /// las rutas pedidas, en orden.
+    fn mock_ollama() -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let paths = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = std::sync::Arc::clone(&paths);
+        std::thread::spawn(move || {
+            let mut pulled = false;
+            for stream in listener.incoming().take(3) {
+                let mut stream = stream.unwrap();
+                // Cabeceras + cuerpo completos antes de responder
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text
+                            .lines()
+                            .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                let path = String::from_utf8_lossy(&request).split_whitespace().nth(1).unwrap_or("").to_string();
+                seen.lock().unwrap().push(path.clone());
+                let (status, body) = match path.as_str() {
+                    "/api/pull" => {
+                        pulled = true;
+                        (
+                            "200 OK",
+                            "{\"status\":\"pulling manifest\"}\n{\"status\":\"downloading\",\"total\":100,\"completed\":50}\n{\"status\":\"success\"}\n"
+                                .to_string(),
+                        )
+                    }
+                    _ if pulled => ("200 OK", "{\"response\":\"hola\"}".to_string()),
+                    _ => ("404 Not Found", "{\"error\":\"model \\\"qwen2.5-coder:7b\\\" not found, try pulling it first\"}".to_string()),
+                };
+                let _ = stream.write_all(
+                    format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                );
+            }
+        });
+        (url, paths)
+    }
+
+    #[test]
+    fn test_missing_model_is_pulled_and_chat_retried() {
+        let client = Client::new();
+
+        let (url, paths) = mock_ollama();
+        let err = OllamaProvider::new(&url).chat(&client, "hi", "qwen2.5-coder:7b").unwrap_err();
+        assert!(format!("{:#}", err).contains("ollama pull qwen2.5-coder:7b"), "{:#}", err);
+        assert_eq!(*paths.lock().unwrap(), vec!["/api/generate"]);
+
+        let (url, paths) = mock_ollama();
+        let provider = OllamaProvider::new(&url).with_auto_pull(true);
+        assert_eq!(provider.chat(&client, "hi", "qwen2.5-coder:7b").unwrap(), "hola");
+        assert_eq!(*paths.lock().unwrap(), vec!["/api/generate", "/api/pull", "/api/generate"]);
+    }
+}
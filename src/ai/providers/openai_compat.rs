@@ -39,11 +39,7 @@ impl super::AiProvider for OpenAiCompatProvider {
         let body_text = response.text()?;
 
         if !status.is_success() {
-            return Err(anyhow::anyhow!(
-                "Error de API OpenAI-Compat (Status {}): {}",
-                status,
-                body_text
-            ));
+            return Err(super::ApiStatusError::new("OpenAI-Compat", status, body_text).into());
         }
 
         let body: serde_json::Value = serde_json::from_str(&body_text)?;
@@ -89,8 +85,19 @@ impl super::AiProvider for OpenAiCompatProvider {
         Ok(embeddings)
     }
 
-    fn list_models(&self) -> Result<Vec<String>> {
-        let client = Client::new();
+    // Límite conservador compartido por OpenAI y los compatibles
+    fn embed_batch_size(&self) -> usize {
+        96
+    }
+
+    fn list_models(&self, client: &Client) -> Result<Vec<String>> {
+        self.fetch_models(client)
+    }
+}
+
+impl OpenAiCompatProvider {
+    /// Listado de modelos con `client`; un status no exitoso llega como `ApiStatusError`.
+    fn fetch_models(&self, client: &Client) -> Result<Vec<String>> {
         let url_str = self.url.trim_end_matches('/');
         let target_url = if url_str.ends_with("/v1") {
             format!("{}/models", url_str)
@@ -103,8 +110,7 @@ impl super::AiProvider for OpenAiCompatProvider {
             request = request.header("authorization", format!("Bearer {}", self.api_key));
         }
 
-        let response = request.send()?;
-        let json: serde_json::Value = response.json()?;
+        let json = super::fetch_json(request, "OpenAI-compatible")?;
         let models = json["data"]
             .as_array()
             .ok_or_else(|| anyhow::anyhow!("Respuesta API compatible inválida"))?
@@ -0,0 +1,189 @@
+// src/ai/providers/openrouter.rs
+use anyhow::Result;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::json;
+
+/// Endpoint usado cuando el `url` del modelo está vacío.
+pub const DEFAULT_URL: &str = "https://openrouter.ai/api/v1";
+/// OpenRouter atribuye el tráfico con estos headers (ranking de apps en openrouter.ai).
+const APP_REFERER: &str = "https://github.com/sergiogswv/sentinel-pro";
+const APP_TITLE: &str = "Sentinel";
+
+/// OpenRouter: API compatible con OpenAI que enruta a muchos modelos con nombres
+/// `proveedor/modelo` (ej: `anthropic/claude-3.5-sonnet`).
+pub struct OpenRouterProvider {
+    api_key: String,
+    base: String,
+}
+
+impl OpenRouterProvider {
+    pub fn new(api_key: &str, url: &str) -> Self {
+        let url = url.trim().trim_end_matches('/');
+        let base = if url.is_empty() {
+            DEFAULT_URL.to_string()
+        } else if url.ends_with("/api/v1") {
+            url.to_string()
+        } else {
+            format!("{}/api/v1", url.trim_end_matches("/api"))
+        };
+        Self {
+            api_key: api_key.to_string(),
+            base,
+        }
+    }
+
+    fn with_headers(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = request
+            .header("HTTP-Referer", APP_REFERER)
+            .header("X-Title", APP_TITLE);
+        if self.api_key.is_empty() {
+            request
+        } else {
+            request.header("Authorization", format!("Bearer {}", self.api_key))
+        }
+    }
+
+    fn chat_request(&self, client: &Client, prompt: &str, model_name: &str) -> RequestBuilder {
+        self.with_headers(client.post(format!("{}/chat/completions", self.base)))
+            .json(&json!({
+                "model": model_name,
+                "messages": [{"role": "user", "content": prompt}]
+            }))
+    }
+
+    fn models_request(&self, client: &Client) -> RequestBuilder {
+        self.with_headers(client.get(format!("{}/models", self.base)))
+    }
+}
+
+/// Extrae los ids `proveedor/modelo` de la respuesta de `/models`.
+fn parse_models(body: &serde_json::Value) -> Result<Vec<String>> {
+    let models = body["data"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Respuesta de OpenRouter /models inesperada"))?
+        .iter()
+        .filter_map(|m| m["id"].as_str().map(|s| s.to_string()))
+        .collect();
+    Ok(models)
+}
+
+impl super::AiProvider for OpenRouterProvider {
+    fn chat(&self, client: &Client, prompt: &str, model_name: &str) -> Result<String> {
+        let response = self.chat_request(client, prompt, model_name).send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("OpenRouter", status, body_text).into());
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        body["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Estructura de OpenRouter inesperada. Body: {}", body_text)
+            })
+    }
+
+    fn embed(&self, client: &Client, texts: Vec<String>, model_name: &str) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .with_headers(client.post(format!("{}/embeddings", self.base)))
+            .json(&json!({ "model": model_name, "input": texts }))
+            .send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("OpenRouter", status, body_text).into());
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        let embeddings = body["data"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Respuesta de OpenRouter Embeddings inesperada"))?
+            .iter()
+            .map(|d| -> anyhow::Result<Vec<f32>> {
+                let values = d["embedding"]
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("OpenRouter embedding: 'embedding' faltante o no es array"))?;
+                values
+                    .iter()
+                    .map(|v| {
+                        v.as_f64()
+                            .ok_or_else(|| anyhow::anyhow!("OpenRouter embedding: valor no numérico"))
+                            .map(|f| f as f32)
+                    })
+                    .collect()
+            })
+            .collect::<anyhow::Result<Vec<Vec<f32>>>>()?;
+        Ok(embeddings)
+    }
+
+    fn embed_batch_size(&self) -> usize {
+        96
+    }
+
+    fn list_models(&self, client: &Client) -> Result<Vec<String>> {
+        self.fetch_models(client)
+    }
+}
+
+impl OpenRouterProvider {
+    /// Listado de modelos con `client`; un status no exitoso llega como `ApiStatusError`.
+    fn fetch_models(&self, client: &Client) -> Result<Vec<String>> {
+        let body = super::fetch_json(self.models_request(client), "OpenRouter")?;
+        parse_models(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_request_sets_openrouter_headers() {
+        let client = Client::new();
+        let request = OpenRouterProvider::new("sk-or-123", "https://openrouter.ai/api/v1/")
+            .chat_request(&client, "hola", "anthropic/claude-3.5-sonnet")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.url().as_str(), "https://openrouter.ai/api/v1/chat/completions");
+        assert_eq!(request.headers()["HTTP-Referer"], APP_REFERER);
+        assert_eq!(request.headers()["X-Title"], APP_TITLE);
+        assert_eq!(request.headers()["Authorization"], "Bearer sk-or-123");
+
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(body["model"], "anthropic/claude-3.5-sonnet");
+    }
+
+    #[test]
+    fn test_base_url_normalization() {
+        let client = Client::new();
+        for url in ["", "https://openrouter.ai", "https://openrouter.ai/api"] {
+            let request = OpenRouterProvider::new("k", url).models_request(&client).build().unwrap();
+            assert_eq!(request.url().as_str(), "https://openrouter.ai/api/v1/models", "url: {:?}", url);
+            assert_eq!(request.headers()["X-Title"], APP_TITLE);
+        }
+    }
+
+    #[test]
+    fn test_parse_models_returns_full_identifiers() {
+        let body = json!({
+            "data": [
+                { "id": "openai/gpt-4o", "name": "OpenAI: GPT-4o", "context_length": 128000 },
+                { "id": "meta-llama/llama-3.1-70b-instruct", "name": "Llama 3.1 70B" },
+                { "name": "sin id" }
+            ]
+        });
+        assert_eq!(
+            parse_models(&body).unwrap(),
+            vec!["openai/gpt-4o".to_string(), "meta-llama/llama-3.1-70b-instruct".to_string()]
+        );
+        assert!(parse_models(&json!({ "error": "unauthorized" })).is_err());
+    }
+}
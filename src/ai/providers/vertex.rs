@@ -0,0 +1,312 @@
+// src/ai/providers/vertex.rs
+use anyhow::Result;
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::json;
+use std::sync::Mutex;
+
+/// Región usada cuando no hay `location` en la config, la URL ni `GOOGLE_CLOUD_LOCATION`.
+pub const DEFAULT_LOCATION: &str = "us-central1";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// Modelos Gemini publicados en Vertex AI (el listado de Model Garden requiere otra API).
+pub const KNOWN_MODELS: &[&str] = &[
+    "gemini-2.0-flash",
+    "gemini-2.0-flash-lite",
+    "gemini-1.5-pro",
+    "gemini-1.5-flash",
+    "text-embedding-004",
+];
+
+/// Google Vertex AI: endpoints regionales (`{location}-aiplatform.googleapis.com`) con el
+/// proyecto y la región en el path, autenticados con un access token OAuth en vez de la
+/// API key pública de Gemini.
+///
+/// Token: la `api_key` del modelo si está definida (p. ej. `env:VERTEX_ACCESS_TOKEN`); si
+/// no, las Application Default Credentials (`GOOGLE_APPLICATION_CREDENTIALS` o el archivo
+/// de `gcloud auth application-default login`) y, como último recurso,
+/// `gcloud auth application-default print-access-token`.
+pub struct VertexProvider {
+    url: String,
+    project: String,
+    location: String,
+    token: Mutex<Option<String>>,
+}
+
+impl VertexProvider {
+    /// Proyecto: `project`, el de la URL (`/projects/{p}/`) o `GOOGLE_CLOUD_PROJECT`.
+    /// Región: `location`, la de la URL (`/locations/{l}` o `{l}-aiplatform...`),
+    /// `GOOGLE_CLOUD_LOCATION` o `us-central1`.
+    pub fn new(access_token: &str, url: &str, project: Option<&str>, location: Option<&str>) -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let given = |value: Option<&str>| value.filter(|v| !v.is_empty()).map(|v| v.to_string());
+        let project = given(project)
+            .or_else(|| path_segment_after(url, "projects"))
+            .or_else(|| env("GOOGLE_CLOUD_PROJECT"))
+            .unwrap_or_default();
+        let location = given(location)
+            .or_else(|| path_segment_after(url, "locations"))
+            .or_else(|| location_from_host(url))
+            .or_else(|| env("GOOGLE_CLOUD_LOCATION"))
+            .unwrap_or_else(|| DEFAULT_LOCATION.to_string());
+        let provider = Self::with_location(url, &project, &location);
+        if !access_token.is_empty() {
+            *provider.token.lock().unwrap() = Some(access_token.to_string());
+        }
+        provider
+    }
+
+    pub fn with_location(url: &str, project: &str, location: &str) -> Self {
+        Self {
+            url: url.trim().to_string(),
+            project: project.to_string(),
+            location: location.to_string(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// `https://{host}` de la URL configurada, o el endpoint regional de `location`.
+    fn api_root(&self) -> String {
+        if let Some((scheme, rest)) = self.url.split_once("://") {
+            let host = rest.split('/').next().unwrap_or(rest);
+            return format!("{}://{}", scheme, host);
+        }
+        if self.location == "global" {
+            "https://aiplatform.googleapis.com".to_string()
+        } else {
+            format!("https://{}-aiplatform.googleapis.com", self.location)
+        }
+    }
+
+    /// `{root}/v1/projects/{project}/locations/{location}/publishers/google/models/{model}:{method}`
+    fn endpoint(&self, model_name: &str, method: &str) -> Result<String> {
+        if self.project.is_empty() {
+            anyhow::bail!(
+                "Vertex AI: configura `project` en el modelo (o GOOGLE_CLOUD_PROJECT, o una URL con /projects/<id>/)"
+            );
+        }
+        Ok(format!(
+            "{}/v1/projects/{}/locations/{}/publishers/google/models/{}:{}",
+            self.api_root(),
+            self.project,
+            self.location,
+            model_name,
+            method
+        ))
+    }
+
+    fn access_token(&self, client: &Client) -> Result<String> {
+        let mut cached = self.token.lock().unwrap();
+        if let Some(token) = cached.as_ref() {
+            return Ok(token.clone());
+        }
+        let token = fetch_access_token(client)?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    fn post(&self, client: &Client, model_name: &str, method: &str, body: serde_json::Value) -> Result<RequestBuilder> {
+        Ok(client
+            .post(self.endpoint(model_name, method)?)
+            .bearer_auth(self.access_token(client)?)
+            .header("content-type", "application/json")
+            .json(&body))
+    }
+}
+
+/// Segmento que sigue a `/{key}/` en el path de `url`.
+fn path_segment_after(url: &str, key: &str) -> Option<String> {
+    let mut segments = url.split('/');
+    segments.find(|s| *s == key)?;
+    segments.next().filter(|s| !s.is_empty()).map(|s| s.to_string())
+}
+
+fn location_from_host(url: &str) -> Option<String> {
+    let host = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let location = host.split('/').next()?.strip_suffix("-aiplatform.googleapis.com")?;
+    (!location.is_empty()).then(|| location.to_string())
+}
+
+/// Archivo de Application Default Credentials: `GOOGLE_APPLICATION_CREDENTIALS` o el que
+/// escribe `gcloud auth application-default login`.
+fn adc_path() -> Option<std::path::PathBuf> {
+    if let Some(path) = std::env::var_os("GOOGLE_APPLICATION_CREDENTIALS").filter(|p| !p.is_empty()) {
+        return Some(path.into());
+    }
+    let config_dir = if cfg!(windows) {
+        std::path::PathBuf::from(std::env::var_os("APPDATA")?).join("gcloud")
+    } else {
+        std::path::PathBuf::from(std::env::var_os("HOME")?).join(".config/gcloud")
+    };
+    Some(config_dir.join("application_default_credentials.json")).filter(|p| p.exists())
+}
+
+/// Access token de las ADC. Las credenciales de usuario (`authorized_user`) se canjean
+/// directamente por su refresh token; las de service account (firma JWT RS256) se delegan
+/// en `gcloud`.
+fn fetch_access_token(client: &Client) -> Result<String> {
+    if let Some(path) = adc_path() {
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Vertex AI: no se pudo leer {}: {}", path.display(), e))?;
+        let creds: serde_json::Value = serde_json::from_str(&text)?;
+        if creds["type"] == "authorized_user" {
+            let field = |name: &str| creds[name].as_str().unwrap_or_default().to_string();
+            let request = client.post(TOKEN_URL).form(&[
+                ("grant_type", "refresh_token".to_string()),
+                ("client_id", field("client_id")),
+                ("client_secret", field("client_secret")),
+                ("refresh_token", field("refresh_token")),
+            ]);
+            let response = request.send()?;
+            let status = response.status();
+            let body = response.text()?;
+            if !status.is_success() {
+                return Err(super::ApiStatusError::new("Google OAuth", status, body).into());
+            }
+            let body: serde_json::Value = serde_json::from_str(&body)?;
+            return body["access_token"]
+                .as_str()
+                .map(|t| t.to_string())
+                .ok_or_else(|| anyhow::anyhow!("Respuesta de Google OAuth sin access_token"));
+        }
+    }
+
+    let output = std::process::Command::new("gcloud")
+        .args(["auth", "application-default", "print-access-token"])
+        .output()
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Vertex AI: sin credenciales. Define GOOGLE_APPLICATION_CREDENTIALS, ejecuta \
+                 `gcloud auth application-default login` o pon un access token en `api_key`"
+            )
+        })?;
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || token.is_empty() {
+        anyhow::bail!(
+            "Vertex AI: `gcloud auth application-default print-access-token` falló: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(token)
+}
+
+impl super::AiProvider for VertexProvider {
+    fn chat(&self, client: &Client, prompt: &str, model_name: &str) -> Result<String> {
+        let response = self
+            .post(
+                client,
+                model_name,
+                "generateContent",
+                json!({ "contents": [{"role": "user", "parts": [{"text": prompt}]}] }),
+            )?
+            .send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("Vertex AI", status, body_text).into());
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        body["candidates"][0]["content"]["parts"][0]["text"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| anyhow::anyhow!("Estructura de Vertex AI inesperada. Body: {}", body_text))
+    }
+
+    fn embed(&self, client: &Client, texts: Vec<String>, model_name: &str) -> Result<Vec<Vec<f32>>> {
+        let instances: Vec<serde_json::Value> = texts.into_iter().map(|t| json!({ "content": t })).collect();
+        let response = self
+            .post(client, model_name, "predict", json!({ "instances": instances }))?
+            .send()?;
+
+        let status = response.status();
+        let body_text = response.text()?;
+        if !status.is_success() {
+            return Err(super::ApiStatusError::new("Vertex AI", status, body_text).into());
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&body_text)?;
+        body["predictions"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Respuesta de Vertex AI Embeddings inesperada: {}", body))?
+            .iter()
+            .map(|p| -> anyhow::Result<Vec<f32>> {
+                let values = p["embeddings"]["values"]
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Vertex embedding: 'embeddings.values' faltante o no es array"))?;
+                values
+                    .iter()
+                    .map(|v| {
+                        v.as_f64()
+                            .ok_or_else(|| anyhow::anyhow!("Vertex embedding: valor no numérico"))
+                            .map(|f| f as f32)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // `predict` acepta más instancias, pero con un tope de tokens por petición
+    fn embed_batch_size(&self) -> usize {
+        16
+    }
+
+    fn list_models(&self, _client: &Client) -> Result<Vec<String>> {
+        Ok(KNOWN_MODELS.iter().map(|m| m.to_string()).collect())
+    }
+
+    fn ping(&self, client: &Client) -> Result<()> {
+        let url = self.endpoint("", "")?;
+        let base = url.split("/publishers/").next().unwrap_or(&url);
+        let request = client.get(format!("{}/endpoints", base)).bearer_auth(self.access_token(client)?);
+        super::fetch_json(request, "Vertex AI").map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_project_and_location_config() {
+        let provider = VertexProvider::new("ya29.token", "", Some("acme-prod"), Some("europe-west4"));
+        assert_eq!(
+            provider.endpoint("gemini-2.0-flash", "generateContent").unwrap(),
+            "https://europe-west4-aiplatform.googleapis.com/v1/projects/acme-prod/locations/europe-west4/publishers/google/models/gemini-2.0-flash:generateContent"
+        );
+        assert_eq!(
+            VertexProvider::with_location("", "acme-prod", "global").endpoint("text-embedding-004", "predict").unwrap(),
+            "https://aiplatform.googleapis.com/v1/projects/acme-prod/locations/global/publishers/google/models/text-embedding-004:predict"
+        );
+        assert!(VertexProvider::with_location("", "", "us-central1").endpoint("gemini-1.5-pro", "predict").is_err());
+    }
+
+    #[test]
+    fn test_project_and_location_parsed_from_url() {
+        let provider = VertexProvider::new(
+            "ya29.token",
+            "https://us-east5-aiplatform.googleapis.com/v1/projects/acme-dev/locations/us-east5",
+            None,
+            None,
+        );
+        assert_eq!((provider.project.as_str(), provider.location.as_str()), ("acme-dev", "us-east5"));
+        assert_eq!(
+            provider.endpoint("gemini-1.5-flash", "generateContent").unwrap(),
+            "https://us-east5-aiplatform.googleapis.com/v1/projects/acme-dev/locations/us-east5/publishers/google/models/gemini-1.5-flash:generateContent"
+        );
+        assert_eq!(location_from_host("https://asia-northeast1-aiplatform.googleapis.com").as_deref(), Some("asia-northeast1"));
+        assert_eq!(location_from_host("https://generativelanguage.googleapis.com"), None);
+    }
+
+    #[test]
+    fn test_request_uses_configured_access_token() {
+        let provider = VertexProvider::new("ya29.token", "", Some("acme-prod"), Some("us-central1"));
+        let request = provider
+            .post(&Client::new(), "gemini-2.0-flash", "generateContent", json!({}))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer ya29.token");
+        assert!(request.headers().get("x-goog-api-key").is_none());
+    }
+}
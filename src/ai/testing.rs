@@ -219,6 +219,7 @@ fn consultar_ia_para_testing_dinamico(
         prompt,
         &config.primary_model,
         Arc::new(Mutex::new(SentinelStats::default())),
+        &config.pricing,
         TaskType::Deep,
     )?;
 
@@ -320,6 +321,7 @@ pub fn obtener_sugerencias_complementarias(
         prompt,
         &config.primary_model,
         Arc::new(Mutex::new(SentinelStats::default())),
+        &config.pricing,
         TaskType::Deep,
     )?;
 
@@ -16,6 +16,13 @@ struct IgnoreFile {
     entries: Vec<IgnoreEntry>,
 }
 
+/// `sentinel ignore --export` / `--import`.
+#[derive(Debug, Clone)]
+pub enum IgnoreTransfer {
+    Export(String),
+    Import(String),
+}
+
 fn ignore_path(project_root: &Path) -> std::path::PathBuf {
     project_root.join(".sentinel/ignore.json")
 }
@@ -53,6 +60,7 @@ fn parse_sentinelignore_file(path: &Path) -> Vec<IgnoreEntry> {
             }
 
             // Parse: RULE_NAME file/path.ts optional_symbol
+            // (las líneas `glob[:RULE]` sin espacios son de rules::ignore_file)
             let mut parts = line.split_whitespace();
             let rule = parts.next()?;
             let file = parts.next()?;
@@ -123,16 +131,20 @@ fn collect_sentinelignore_files(dir: &Path, entries: &mut Vec<IgnoreEntry>, dept
     }
 }
 
-pub fn load_ignore_entries(project_root: &Path) -> Vec<IgnoreEntry> {
+/// Solo las entradas de `.sentinel/ignore.json`, sin los `.sentinelignore` por directorio.
+fn load_stored_entries(project_root: &Path) -> Vec<IgnoreEntry> {
     let path = ignore_path(project_root);
-    let mut entries = if !path.exists() {
-        vec![]
-    } else {
-        let content = std::fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str::<IgnoreFile>(&content)
-            .map(|f| f.entries)
-            .unwrap_or_default()
-    };
+    if !path.exists() {
+        return vec![];
+    }
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    serde_json::from_str::<IgnoreFile>(&content)
+        .map(|f| f.entries)
+        .unwrap_or_default()
+}
+
+pub fn load_ignore_entries(project_root: &Path) -> Vec<IgnoreEntry> {
+    let mut entries = load_stored_entries(project_root);
 
     // Merge per-directory .sentinelignore files
     let dir_entries = load_directory_ignores(project_root);
@@ -154,6 +166,65 @@ fn save_ignore_entries(project_root: &Path, entries: Vec<IgnoreEntry>) {
     let _ = std::fs::write(&path, json);
 }
 
+fn is_yaml(path: &Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()), Some("yaml" | "yml"))
+}
+
+fn same_ignore(a: &IgnoreEntry, b: &IgnoreEntry) -> bool {
+    a.rule == b.rule && a.file == b.file && a.symbol == b.symbol
+}
+
+/// Escribe los ignores activos (store + `.sentinelignore`) en `dest`, en YAML si la
+/// extensión es `.yaml`/`.yml` y en JSON en otro caso. Devuelve cuántos se exportaron.
+pub fn export_ignores(project_root: &Path, dest: &Path) -> anyhow::Result<usize> {
+    let mut entries: Vec<IgnoreEntry> = Vec::new();
+    for e in load_ignore_entries(project_root) {
+        if !entries.iter().any(|x| same_ignore(x, &e)) {
+            entries.push(e);
+        }
+    }
+    let count = entries.len();
+    let file = IgnoreFile { version: 1, entries };
+    let content = if is_yaml(dest) {
+        serde_yaml::to_string(&file)?
+    } else {
+        serde_json::to_string_pretty(&file)?
+    };
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(dest, content)?;
+    Ok(count)
+}
+
+/// Fusiona en `.sentinel/ignore.json` las entradas de `src` que no estén ya activas.
+/// Devuelve `(añadidas, ya_presentes)`.
+pub fn import_ignores(project_root: &Path, src: &Path) -> anyhow::Result<(usize, usize)> {
+    let content = std::fs::read_to_string(src)
+        .map_err(|e| anyhow::anyhow!("No se pudo leer '{}': {}", src.display(), e))?;
+    let imported: IgnoreFile = if is_yaml(src) {
+        serde_yaml::from_str(&content)?
+    } else {
+        serde_json::from_str(&content)?
+    };
+
+    let active = load_ignore_entries(project_root);
+    let mut stored = load_stored_entries(project_root);
+    let (mut added, mut present) = (0, 0);
+    for entry in imported.entries {
+        if active.iter().chain(stored.iter()).any(|e| same_ignore(e, &entry)) {
+            present += 1;
+        } else {
+            stored.push(entry);
+            added += 1;
+        }
+    }
+    if added > 0 {
+        save_ignore_entries(project_root, stored);
+    }
+    Ok((added, present))
+}
+
 pub fn handle_ignore_command(
     rule: Option<String>,
     file: Option<String>,
@@ -161,9 +232,39 @@ pub fn handle_ignore_command(
     list: bool,
     clear: Option<String>,
     show_file: bool,
+    transfer: Option<IgnoreTransfer>,
 ) {
     let project_root = std::env::current_dir().unwrap();
 
+    match transfer {
+        Some(IgnoreTransfer::Export(dest)) => {
+            match export_ignores(&project_root, Path::new(&dest)) {
+                Ok(n) => println!("{} {} ignore(s) exportados a {}.", "✅".green(), n, dest.cyan()),
+                Err(e) => {
+                    eprintln!("{} No se pudo exportar: {}", "❌".red(), e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(IgnoreTransfer::Import(src)) => {
+            match import_ignores(&project_root, Path::new(&src)) {
+                Ok((added, present)) => println!(
+                    "{} {} ignore(s) importados, {} ya existían.",
+                    "✅".green(),
+                    added,
+                    present
+                ),
+                Err(e) => {
+                    eprintln!("{} No se pudo importar '{}': {}", "❌".red(), src, e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        None => {}
+    }
+
     if show_file {
         let ignore_file_path = project_root.join(".sentinel/ignores.json");
         println!("{}", ignore_file_path.display());
@@ -203,6 +304,7 @@ pub fn handle_ignore_command(
         println!("Uso: sentinel ignore <REGLA> <ARCHIVO> [--symbol <SÍMBOLO>]");
         println!("     sentinel ignore --list");
         println!("     sentinel ignore --clear <ARCHIVO>");
+        println!("     sentinel ignore --export <ARCHIVO> | --import <ARCHIVO>");
         return;
     };
 
@@ -240,6 +342,7 @@ pub fn handle_ignore_command(
 #[cfg(test)]
 mod tests {
     use super::{normalize_symbol, load_directory_ignores};
+    use super::{export_ignores, import_ignores, load_ignore_entries, save_ignore_entries, IgnoreEntry};
 
     #[test]
     fn test_normalize_strips_suffix_and_lowercases() {
@@ -251,6 +354,35 @@ mod tests {
         assert_eq!(normalize_symbol("SomethingElse"),  "somethingelse");
     }
 
+    #[test]
+    fn test_export_then_import_round_trip() {
+        let source = tempfile::TempDir::new().unwrap();
+        let entry = |rule: &str, file: &str, symbol: Option<&str>| IgnoreEntry {
+            rule: rule.to_string(),
+            file: file.to_string(),
+            symbol: symbol.map(String::from),
+            added: "2026-01-01".to_string(),
+        };
+        save_ignore_entries(source.path(), vec![
+            entry("DEAD_CODE", "src/user.service.ts", Some("processlegacy")),
+            entry("UNUSED_IMPORT", "src/app.ts", None),
+        ]);
+
+        for name in ["shared-ignores.json", "shared-ignores.yaml"] {
+            let shared = source.path().join(name);
+            assert_eq!(export_ignores(source.path(), &shared).unwrap(), 2);
+
+            let fresh = tempfile::TempDir::new().unwrap();
+            assert_eq!(import_ignores(fresh.path(), &shared).unwrap(), (2, 0));
+            assert_eq!(import_ignores(fresh.path(), &shared).unwrap(), (0, 2), "re-import must dedupe");
+
+            let key = |e: &IgnoreEntry| (e.rule.clone(), e.file.clone(), e.symbol.clone());
+            let expected: Vec<_> = load_ignore_entries(source.path()).iter().map(key).collect();
+            let imported: Vec<_> = load_ignore_entries(fresh.path()).iter().map(key).collect();
+            assert_eq!(imported, expected);
+        }
+    }
+
     #[test]
     fn test_load_directory_ignore_file() {
         let tmp = tempfile::TempDir::new().unwrap();
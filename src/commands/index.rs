@@ -1,11 +1,42 @@
 use crate::config::SentinelConfig;
+use crate::index::call_graph::{CallGraph, SymbolLocation};
+use crate::index::quality_history::{QualityHistory, RegressionStatus};
+use crate::index::symbol_table::SymbolTable;
 use crate::index::{IndexDb, ProjectIndexBuilder};
 use colored::Colorize;
 use std::sync::Arc;
 
-pub fn handle_index_command(rebuild: bool, check: bool) {
+/// Argumentos de `sentinel index` tal como llegan del CLI.
+#[derive(Debug, Clone, Default)]
+pub struct IndexOptions {
+    pub rebuild: bool,
+    pub check: bool,
+    pub trend: bool,
+    pub refs: Option<String>,
+    pub cycles: bool,
+    pub stats: bool,
+    /// Formato de --stats: text | json
+    pub format: String,
+    /// Archivo destino del call graph en formato DOT
+    pub export_dot: Option<String>,
+    /// Limita --export-dot a un subárbol de directorios
+    pub module: Option<String>,
+    /// Archivo destino de la tabla de símbolos en JSON
+    pub export_symbols: Option<String>,
+}
+
+pub fn handle_index_command(opts: &IndexOptions) {
+    let IndexOptions { rebuild, check, trend, cycles, stats, .. } = *opts;
+    let refs = opts.refs.as_deref();
+    let format = opts.format.as_str();
     let project_root = std::env::current_dir().unwrap();
-    let config = SentinelConfig::load(&project_root).unwrap_or_default();
+    let config = match SentinelConfig::load(&project_root).unwrap_or_default().with_active_profile(None) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{} {}", "❌".red(), e);
+            return;
+        }
+    };
     let index_path = project_root.join(".sentinel/index.db");
     let index_db = IndexDb::open(&index_path).ok().map(Arc::new);
 
@@ -17,15 +48,15 @@ pub fn handle_index_command(rebuild: bool, check: bool) {
         return;
     };
 
-    if !rebuild && !check {
-        println!("Uso: sentinel index --check | --rebuild");
+    let exports = opts.export_dot.is_some() || opts.export_symbols.is_some();
+    if !rebuild && !check && !trend && refs.is_none() && !cycles && !stats && !exports {
+        println!(
+            "Uso: sentinel index --check | --rebuild | --trend | --refs <símbolo> | --cycles | --stats [--format json] | --export-dot <archivo> [--module <dir>] | --export-symbols <archivo>"
+        );
         return;
     }
 
-    if check {
-        print_index_status(&db, &project_root, &config.file_extensions);
-    }
-
+    // Primero la reconstrucción: los reportes y exportaciones deben leer el índice nuevo
     if rebuild {
         println!("\n{}", "🔄 Reconstruyendo índice desde cero...".bold());
         db.clear_all().expect("Error limpiando el índice");
@@ -40,13 +71,41 @@ pub fn handle_index_command(rebuild: bool, check: bool) {
             count.to_string().cyan()
         );
     }
+
+    if stats {
+        print_stats(&db, format.eq_ignore_ascii_case("json"));
+    }
+
+    if check {
+        print_index_status(&db, &project_root, &config);
+    }
+
+    if trend {
+        print_regressions(&db);
+    }
+
+    if let Some(symbol) = refs {
+        print_references(&db, symbol);
+    }
+
+    if cycles {
+        print_cycles(&db);
+    }
+
+    if let Some(path) = opts.export_dot.as_deref() {
+        write_dot(&db, path, opts.module.as_deref());
+    }
+
+    if let Some(path) = opts.export_symbols.as_deref() {
+        write_symbols(&db, path);
+    }
 }
 
-fn print_index_status(db: &IndexDb, project_root: &std::path::Path, extensions: &[String]) {
-    let disk_count = count_project_files(project_root, extensions);
+fn print_index_status(db: &IndexDb, project_root: &std::path::Path, config: &SentinelConfig) {
+    let disk_count = count_project_files(project_root, &config.file_extensions);
     let index_count = db.indexed_file_count();
     let diff = (disk_count as isize - index_count as isize).unsigned_abs();
-    let stale_threshold = 5.max(disk_count / 10);
+    let stale_threshold = config.index.stale_threshold(disk_count);
     let stale = diff > stale_threshold;
 
     let conn = db.lock();
@@ -88,6 +147,195 @@ fn print_index_status(db: &IndexDb, project_root: &std::path::Path, extensions:
     }
 }
 
+fn format_location(loc: &SymbolLocation) -> String {
+    match loc.line {
+        Some(line) => format!("{}:{}", loc.file_path, line),
+        None => loc.file_path.clone(),
+    }
+}
+
+fn print_references(db: &IndexDb, symbol: &str) {
+    let refs = match CallGraph::new(db).find_references(symbol) {
+        Ok(r) => r,
+        Err(e) => {
+            println!("{} No se pudo consultar el call graph: {}", "❌".red(), e);
+            return;
+        }
+    };
+
+    println!("\n{} {}", "🔗 Referencias de".bold(), symbol.cyan().bold());
+    if refs.definitions.is_empty() {
+        println!("   El símbolo no está en el índice. Corre `sentinel index --rebuild` si es nuevo.");
+        return;
+    }
+    for def in &refs.definitions {
+        println!(
+            "\n   {} {} ({})",
+            "definido en".dimmed(),
+            format_location(&def.definition).cyan(),
+            def.kind
+        );
+        if def.callers.is_empty() {
+            println!("      {}", "sin llamadas registradas".yellow());
+        }
+        for caller in &def.callers {
+            println!("      └─ {}", format_location(caller));
+        }
+    }
+    if !refs.unresolved.is_empty() {
+        println!(
+            "\n   {} ({} definiciones posibles)",
+            "llamadas sin resolver".yellow(),
+            refs.definitions.len()
+        );
+        for caller in &refs.unresolved {
+            println!("      └─ {}", format_location(caller));
+        }
+    }
+}
+
+fn print_stats(db: &IndexDb, json: bool) {
+    let stats = db.stats(10);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats).unwrap_or_default());
+        return;
+    }
+
+    println!("\n{}", "📦 Contenido del índice:".bold());
+    println!("   Archivos indexados:  {}", stats.files.to_string().cyan());
+    println!("   Símbolos:            {}", stats.symbols.to_string().cyan());
+    println!("   Llamadas (aristas):  {}", stats.call_edges.to_string().cyan());
+    println!("   Imports (aristas):   {}", stats.import_edges.to_string().cyan());
+
+    if !stats.symbols_by_language.is_empty() {
+        println!("\n   {:<16} {:>9}", "Lenguaje".bold(), "Símbolos".bold());
+        for (ext, n) in &stats.symbols_by_language {
+            println!("   {:<16} {:>9}", ext, n);
+        }
+    }
+
+    println!("\n   {}", "Símbolos más referenciados:".bold());
+    if stats.top_symbols.is_empty() {
+        println!("   {}", "Sin llamadas a símbolos indexados (el análisis entre archivos será limitado).".yellow());
+    }
+    for (i, (symbol, hits)) in stats.top_symbols.iter().enumerate() {
+        println!("   {:>2}. {:<40} {:>5} llamada(s)", i + 1, symbol.cyan(), hits);
+    }
+
+    println!("\n   {}", "Knowledge Base:".bold());
+    println!("   Fragmentos publicados: {}", stats.kb_chunks.to_string().cyan());
+    match stats.last_kb_sync {
+        Some(s) => println!(
+            "   Última sincronización: {} nuevos, {} actualizados, {} borrados, {} sin cambios",
+            s.added, s.updated, s.deleted, s.unchanged
+        ),
+        None => println!("   {}", "Sin sincronizar (tecla `k` en `sentinel monitor`).".dimmed()),
+    }
+}
+
+fn print_cycles(db: &IndexDb) {
+    let cycles = db.find_import_cycles();
+    if cycles.is_empty() {
+        println!("\n{} Sin imports circulares.", "✅".green());
+        return;
+    }
+    println!("\n{}", format!("🔁 {} import(s) circular(es):", cycles.len()).bold());
+    for cycle in &cycles {
+        println!("   {} → {}", cycle.join(" → "), cycle[0].dimmed());
+    }
+    if cycles.len() >= crate::index::import_index::MAX_CYCLES {
+        println!(
+            "   {}",
+            format!("(mostrando los primeros {})", crate::index::import_index::MAX_CYCLES).dimmed()
+        );
+    }
+}
+
+fn write_dot(db: &IndexDb, path: &str, module: Option<&str>) {
+    let graph = match crate::index::dot::export_dot(db, module) {
+        Ok(g) => g,
+        Err(e) => {
+            println!("{} No se pudo consultar el call graph: {}", "❌".red(), e);
+            return;
+        }
+    };
+    if let Err(e) = std::fs::write(path, &graph.dot) {
+        println!("{} No se pudo escribir {}: {}", "❌".red(), path, e);
+        return;
+    }
+    println!(
+        "{} Call graph exportado a {} ({} nodos, {} aristas)",
+        "✅".green(),
+        path.cyan(),
+        graph.nodes,
+        graph.edges
+    );
+    if graph.edges == 0 {
+        println!("   {}", "Sin llamadas resueltas: corre `sentinel index --rebuild` o amplía --module.".yellow());
+    } else {
+        println!("   {}", format!("dot -Tsvg {} -o callgraph.svg", path).dimmed());
+    }
+}
+
+fn write_symbols(db: &IndexDb, path: &str) {
+    let file = match std::fs::File::create(path) {
+        Ok(f) => f,
+        Err(e) => {
+            println!("{} No se pudo crear {}: {}", "❌".red(), path, e);
+            return;
+        }
+    };
+    match SymbolTable::new(db).export_json(std::io::BufWriter::new(file)) {
+        Ok(summary) => {
+            println!(
+                "{} Símbolos exportados a {} ({} símbolos, {} exportados, {} archivos)",
+                "✅".green(),
+                path.cyan(),
+                summary.symbols,
+                summary.exported,
+                summary.files
+            );
+            if summary.symbols == 0 {
+                println!("   {}", "Índice vacío: agrega --rebuild para indexar antes de exportar.".yellow());
+            }
+        }
+        Err(e) => println!("{} No se pudo exportar la tabla de símbolos: {}", "❌".red(), e),
+    }
+}
+
+fn print_regressions(db: &IndexDb) {
+    let regressions = match QualityHistory::new(db).top_regressions(10) {
+        Ok(r) => r,
+        Err(e) => {
+            println!("{} No se pudo leer el historial de calidad: {}", "❌".red(), e);
+            return;
+        }
+    };
+
+    println!("\n{}", "📈 Archivos que empeoraron (últimas dos ejecuciones de `pro check`):".bold());
+    if regressions.is_empty() {
+        println!("   Sin regresiones (o menos de dos ejecuciones registradas).");
+        return;
+    }
+    for r in &regressions {
+        let tag = match r.status {
+            RegressionStatus::New => " (nuevo)".dimmed().to_string(),
+            RegressionStatus::Deleted => " (eliminado)".dimmed().to_string(),
+            RegressionStatus::Changed => String::new(),
+        };
+        println!("   {}{}", r.file_path.cyan(), tag);
+        println!(
+            "      violaciones: {} → {} ({:+})   complejidad: {:.0} → {:.0} ({:+.0})",
+            r.violations_before,
+            r.violations_after,
+            r.violations_delta(),
+            r.complexity_before,
+            r.complexity_after,
+            r.complexity_delta()
+        );
+    }
+}
+
 pub fn count_project_files(root: &std::path::Path, extensions: &[String]) -> usize {
     ignore::WalkBuilder::new(root)
         .hidden(false)
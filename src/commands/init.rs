@@ -1,11 +1,12 @@
 use std::path::Path;
 use std::collections::HashSet;
 use colored::*;
+use crate::config::SentinelConfig;
 
 /// Scans `root` recursively (up to depth 3) and returns unique file extensions
 /// that Sentinel supports. Ignores node_modules, .git, target, vendor, dist, .sentinel.
 pub fn detect_project_extensions(root: &Path) -> Vec<String> {
-    const SUPPORTED: &[&str] = &["ts", "tsx", "js", "jsx", "go", "py"];
+    const SUPPORTED: &[&str] = &["ts", "tsx", "js", "jsx", "go", "py", "java", "rs", "rb", "cs"];
     const SKIP_DIRS: &[&str] = &["node_modules", ".git", "target", "vendor", "dist", ".sentinel"];
 
     let mut found: HashSet<String> = HashSet::new();
@@ -94,6 +95,117 @@ unused_imports_enabled = true
     Ok(())
 }
 
+/// Flags de `sentinel init --non-interactive`.
+#[derive(Debug, Default)]
+pub struct NonInteractiveInit {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub url: Option<String>,
+}
+
+/// `sentinel init --non-interactive`: escribe un `.sentinelrc.toml` completo sin preguntas
+/// (pensado para CI). El framework se detecta localmente y se acepta tal cual; no se
+/// configura Qdrant ni el framework de testing.
+///
+/// La API key nunca se copia al archivo: si `{PROVEEDOR}_API_KEY` está definida se guarda
+/// la referencia `env:{PROVEEDOR}_API_KEY`, que se resuelve al construir el proveedor.
+pub fn run_init_non_interactive(
+    project_root: &Path,
+    force: bool,
+    opts: &NonInteractiveInit,
+) -> anyhow::Result<SentinelConfig> {
+    let config_path = project_root.join(".sentinelrc.toml");
+    if config_path.exists() && !force {
+        anyhow::bail!(
+            "Ya existe una configuración en {}. Usa --force para sobrescribir.",
+            config_path.display()
+        );
+    }
+
+    let provider = opts.provider.as_deref().unwrap_or("anthropic").trim().to_lowercase();
+    if !crate::config::KNOWN_PROVIDERS.contains(&provider.as_str()) {
+        anyhow::bail!(
+            "proveedor desconocido '{}'. Válidos: {}",
+            provider,
+            crate::config::KNOWN_PROVIDERS.join(", ")
+        );
+    }
+    let env_prefix = provider.to_uppercase().replace('-', "_");
+
+    let url = opts
+        .url
+        .clone()
+        .or_else(|| std::env::var(format!("{}_BASE_URL", env_prefix)).ok())
+        .unwrap_or_else(|| crate::config::default_provider_url(&provider).to_string());
+    if url.trim().is_empty() && !matches!(provider.as_str(), "bedrock" | "vertex") {
+        anyhow::bail!("el proveedor '{}' no tiene URL por defecto: usa --url", provider);
+    }
+
+    let model = opts
+        .model
+        .clone()
+        .unwrap_or_else(|| crate::config::default_provider_model(&provider).to_string());
+    if model.trim().is_empty() {
+        anyhow::bail!("el proveedor '{}' no tiene modelo por defecto: usa --model", provider);
+    }
+
+    let key_var = format!("{}_API_KEY", env_prefix);
+    let api_key = match std::env::var(&key_var) {
+        Ok(value) if !value.trim().is_empty() => format!("env:{}", key_var),
+        _ => {
+            if !crate::config::KEYLESS_PROVIDERS.contains(&provider.as_str()) {
+                println!("   ⚠️  {} no está definida: el proveedor '{}' no tendrá API key.", key_var, provider);
+            }
+            String::new()
+        }
+    };
+
+    let mut extensions = detect_project_extensions(project_root);
+    if extensions.is_empty() {
+        extensions = vec!["ts".to_string(), "js".to_string()];
+    }
+    let framework = SentinelConfig::detectar_framework(project_root);
+    let test_patterns = if framework.contains("nest") {
+        vec!["test/{name}/{name}.spec.ts".to_string()]
+    } else {
+        vec!["{name}.test.{ext}".to_string()]
+    };
+    let name = project_root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project")
+        .to_string();
+
+    let mut config = SentinelConfig::create_default(
+        name,
+        SentinelConfig::detectar_gestor(project_root),
+        framework,
+        vec![
+            "Clean Code".to_string(),
+            "SOLID Principles".to_string(),
+            "Best Practices".to_string(),
+        ],
+        extensions.clone(),
+        crate::config::code_language_for(&extensions),
+        vec![],
+        test_patterns,
+    );
+    config.primary_model.provider = provider;
+    config.primary_model.name = model.trim().to_string();
+    config.primary_model.url = url.trim().to_string();
+    config.primary_model.api_key = api_key;
+
+    std::fs::create_dir_all(project_root.join(".sentinel"))?;
+    config.save(project_root)?;
+    Ok(config)
+}
+
+/// `sentinel init --print-schema`: JSON Schema de la configuración por stdout.
+pub fn print_config_schema() {
+    let schema = crate::config::SentinelConfig::json_schema();
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap_or_default());
+}
+
 pub fn handle_init_command(project_root: &Path, force: bool) {
     println!("\n{}", "🚀 Sentinel Init".bold().green());
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -123,6 +235,28 @@ pub fn handle_init_command(project_root: &Path, force: bool) {
     }
 }
 
+pub fn handle_init_non_interactive(project_root: &Path, force: bool, opts: &NonInteractiveInit) {
+    println!("\n{}", "🚀 Sentinel Init (no interactivo)".bold().green());
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    match run_init_non_interactive(project_root, force, opts) {
+        Ok(config) => {
+            println!("   🔍 Framework: {} ({})", config.framework.cyan(), config.file_extensions.join(", "));
+            println!(
+                "   🤖 Modelo: {} / {}",
+                config.primary_model.provider.cyan(),
+                config.primary_model.name.cyan()
+            );
+            let config_path = project_root.join(".sentinelrc.toml");
+            println!("   ✅ Configuración creada en: {}", config_path.display().to_string().cyan());
+        }
+        Err(e) => {
+            eprintln!("   ❌ {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +346,39 @@ mod tests {
             "file_extensions must be top-level (before any [section])"
         );
     }
+
+    #[test]
+    fn test_non_interactive_init_writes_loadable_config() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("main.go"), "package main").unwrap();
+        std::fs::write(tmp.path().join("manage.py"), "").unwrap();
+
+        let opts = NonInteractiveInit {
+            provider: Some("Ollama".to_string()),
+            model: Some("qwen2.5-coder".to_string()),
+            url: Some("http://ollama.ci:11434".to_string()),
+        };
+        run_init_non_interactive(tmp.path(), false, &opts).unwrap();
+
+        let config = SentinelConfig::load(tmp.path()).expect("config must be loadable");
+        assert_eq!(config.primary_model.provider, "ollama");
+        assert_eq!(config.primary_model.name, "qwen2.5-coder");
+        assert_eq!(config.primary_model.url, "http://ollama.ci:11434");
+        assert_eq!(config.framework, "django");
+        assert_eq!(config.file_extensions, vec!["go".to_string(), "py".to_string()]);
+        assert_eq!(config.code_language, "python");
+        assert!(config.testing_framework.is_none());
+
+        // Sin --force no se sobrescribe; con --force se aplican los defaults del proveedor
+        assert!(run_init_non_interactive(tmp.path(), false, &NonInteractiveInit::default()).is_err());
+        run_init_non_interactive(tmp.path(), true, &NonInteractiveInit::default()).unwrap();
+        let config = SentinelConfig::load(tmp.path()).unwrap();
+        assert_eq!(config.primary_model.provider, "anthropic");
+        assert_eq!(config.primary_model.name, crate::config::default_provider_model("anthropic"));
+        let key = &config.primary_model.api_key;
+        assert!(key.is_empty() || key == "env:ANTHROPIC_API_KEY", "secret must not be written: {}", key);
+
+        let bad = NonInteractiveInit { provider: Some("nope".to_string()), ..Default::default() };
+        assert!(run_init_non_interactive(tmp.path(), true, &bad).is_err());
+    }
 }
@@ -3,10 +3,12 @@ pub mod ignore;
 pub mod init;
 pub mod index;
 pub mod monitor;
+pub mod output;
 pub mod pro;
 pub mod rules;
 
 use clap::{Parser, Subcommand};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Output mode for commands: Normal, Quiet (errors only), or Verbose (debug info)
 #[derive(Debug, Clone, PartialEq)]
@@ -28,6 +30,18 @@ pub fn get_output_mode(quiet: bool, verbose: bool) -> OutputMode {
     }
 }
 
+/// Quiet global para código que no recibe `OutputMode` (cliente de IA, agentes).
+static GLOBAL_QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Registra el modo de salida del comando en curso para el resto del proceso.
+pub fn set_global_output_mode(mode: &OutputMode) {
+    GLOBAL_QUIET.store(*mode == OutputMode::Quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    GLOBAL_QUIET.load(Ordering::Relaxed)
+}
+
 #[derive(Parser)]
 #[command(name = "sentinel")]
 #[command(about = "AI-Powered Code Monitor & Development Suite", long_about = None)]
@@ -57,6 +71,9 @@ pub enum Commands {
         /// Mostrar estado del daemon
         #[arg(long)]
         status: bool,
+        /// Commit interactivo: permite editar el mensaje en $EDITOR y valida `commit_convention`
+        #[arg(long, conflicts_with = "daemon")]
+        interactive: bool,
     },
     /// Gestiona la lista de hallazgos ignorados (falsos positivos)
     Ignore {
@@ -76,6 +93,12 @@ pub enum Commands {
         /// Show the path of the root ignores file
         #[arg(long)]
         show_file: bool,
+        /// Exportar los ignores activos a un archivo compartible (.json o .yaml)
+        #[arg(long, value_name = "ARCHIVO", conflicts_with = "import")]
+        export: Option<String>,
+        /// Fusionar en el store local los ignores de un archivo exportado
+        #[arg(long, value_name = "ARCHIVO")]
+        import: Option<String>,
     },
     /// Gestión del índice de símbolos y call graph
     Index {
@@ -85,33 +108,150 @@ pub enum Commands {
         /// Mostrar estado del índice sin modificar nada
         #[arg(long)]
         check: bool,
+        /// Archivos que más empeoraron entre las dos últimas ejecuciones de `pro check`
+        #[arg(long)]
+        trend: bool,
+        /// Lista las llamadas a un símbolo (archivo:línea) usando el call graph
+        #[arg(long, value_name = "SYMBOL")]
+        refs: Option<String>,
+        /// Lista los imports circulares entre archivos del proyecto
+        #[arg(long)]
+        cycles: bool,
+        /// Contenido del índice: símbolos por lenguaje, aristas y símbolos más referenciados
+        #[arg(long)]
+        stats: bool,
+        /// Formato de --stats: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Exporta el call graph a un archivo Graphviz DOT (un cluster por directorio)
+        #[arg(long, value_name = "FILE")]
+        export_dot: Option<String>,
+        /// Limita --export-dot a un subárbol (ej: src/users)
+        #[arg(long, value_name = "DIR", requires = "export_dot")]
+        module: Option<String>,
+        /// Exporta la tabla de símbolos (nombre, tipo, archivo, línea, exportado) a JSON
+        #[arg(long, value_name = "FILE")]
+        export_symbols: Option<String>,
     },
     /// Inicializa la configuración de Sentinel en el proyecto actual
     Init {
         /// Sobrescribir configuración existente si la hay
         #[arg(long)]
         force: bool,
+        /// Imprime el JSON Schema de .sentinelrc.toml (autocompletado en editores) y sale
+        #[arg(long)]
+        print_schema: bool,
+        /// Sin preguntas (CI): config completa con defaults y la API key desde {PROVEEDOR}_API_KEY
+        #[arg(long)]
+        non_interactive: bool,
+        /// Proveedor de IA para --non-interactive (default: anthropic)
+        #[arg(long, requires = "non_interactive")]
+        provider: Option<String>,
+        /// Modelo para --non-interactive (default: el sugerido para el proveedor)
+        #[arg(long, requires = "non_interactive")]
+        model: Option<String>,
+        /// URL de la API para --non-interactive (default: {PROVEEDOR}_BASE_URL o la del proveedor)
+        #[arg(long, requires = "non_interactive")]
+        url: Option<String>,
     },
     /// Diagnóstico del entorno (config, API key, índice, lenguajes)
-    Doctor,
+    Doctor {
+        /// Formato de salida: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
     /// Lista las reglas activas con umbrales configurables
-    Rules,
+    Rules {
+        /// Activa una regla (built-in o de .sentinel/rules.yaml)
+        #[arg(long, alias = "add", value_name = "RULE")]
+        enable: Vec<String>,
+        /// Desactiva una regla (built-in o de .sentinel/rules.yaml)
+        #[arg(long, alias = "remove", value_name = "RULE")]
+        disable: Vec<String>,
+        /// Cambia el umbral de una regla (ej: HIGH_COMPLEXITY=15)
+        #[arg(long, value_name = "RULE=N")]
+        set_threshold: Vec<String>,
+    },
     /// Comandos avanzados de la versión Pro
     Pro {
+        #[command(flatten)]
+        model_override: ModelOverride,
         #[command(subcommand)]
         subcommand: ProCommands,
     },
 }
 
+/// Modelo y perfil a usar solo en esta ejecución de `sentinel pro` (no se guardan en `.sentinelrc.toml`).
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ModelOverride {
+    /// Perfil de `[profiles.<nombre>]` a aplicar (ej: ci, dev); también vía SENTINEL_PROFILE
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// Nombre del modelo que reemplaza a primary_model (ej: claude-3-5-haiku-20241022)
+    #[arg(long, global = true)]
+    pub model: Option<String>,
+    /// Proveedor del modelo (anthropic, gemini, openai, ollama, ...)
+    #[arg(long, global = true)]
+    pub provider: Option<String>,
+}
+
 #[derive(Subcommand)]
 pub enum ProCommands {
     /// Capa 1: Análisis estático rápido (Dead code, unused imports, complexity)
     Check {
         /// Archivo o carpeta a revisar
-        target: String,
-        /// Formato de salida: text (default) o json (para CI/CD)
+        #[arg(required_unless_present = "stdin")]
+        target: Option<String>,
+        /// Formato de salida: text (default), json, jsonl (una violación por línea y un resumen
+        /// final, en streaming), sarif o github (anotaciones de GitHub Actions)
         #[arg(long, default_value = "text")]
         format: String,
+        /// Muestra cuántas violaciones fueron silenciadas por comentarios inline
+        #[arg(long)]
+        show_suppressed: bool,
+        /// Con el formato por defecto, emite anotaciones si corre en GitHub Actions (GITHUB_ACTIONS=true)
+        #[arg(long)]
+        annotate: bool,
+        /// Calcula un score de riesgo de bugs por archivo con el predictor ML (requiere features.enable_ml)
+        #[arg(long)]
+        risk: bool,
+        /// Solo reporta violaciones que no están en .sentinel/baseline.json
+        #[arg(long, conflicts_with = "write_baseline")]
+        baseline: bool,
+        /// Guarda las violaciones actuales en .sentinel/baseline.json (solo reemplaza las de los archivos revisados)
+        #[arg(long)]
+        write_baseline: bool,
+        /// Vuelve a revisar el destino cada vez que cambia un archivo (solo análisis estático)
+        #[arg(long, conflicts_with = "write_baseline")]
+        watch: bool,
+        /// Severidad mínima que hace salir con código 1: error (default), warning, info o never
+        #[arg(long, default_value = "error", value_parser = ["error", "warning", "info", "never"])]
+        fail_on: String,
+        /// Lee el código desde stdin y emite las violaciones en JSON; la ruta (virtual) solo
+        /// determina el lenguaje y los ignores que aplican. Pensado para plugins de editor.
+        #[arg(long, value_name = "VIRTUAL_PATH", conflicts_with_all = ["target", "watch", "write_baseline", "baseline", "risk"])]
+        stdin: Option<String>,
+        /// Solo muestra las violaciones cuya regla o mensaje coincide con la regex (sin distinguir mayúsculas)
+        #[arg(long, value_name = "REGEX", conflicts_with_all = ["stdin", "write_baseline"])]
+        grep: Option<String>,
+        /// Ejecuta solo los analizadores de esta regla (ej. HIGH_COMPLEXITY); repetible
+        #[arg(long = "only", value_name = "RULE", conflicts_with_all = ["stdin", "write_baseline"])]
+        only: Vec<String>,
+        /// Añade una explicación breve (por qué importa y cómo corregirla) de cada regla reportada
+        #[arg(long)]
+        explain: bool,
+        /// Sin violaciones no imprime nada (ni banners ni JSON vacío); con hallazgos, la salida normal
+        #[arg(long, conflicts_with_all = ["stdin", "watch", "write_baseline"])]
+        quiet_success: bool,
+        /// Omite archivos de más de N bytes (bundles minificados, código generado); pisa `max_file_bytes`
+        #[arg(long, value_name = "BYTES")]
+        max_file_size: Option<u64>,
+        /// Umbral de HIGH_COMPLEXITY solo para esta ejecución; pisa `rule_config.complexity_threshold` sin guardarlo
+        #[arg(long, value_name = "N")]
+        complexity_threshold: Option<std::num::NonZeroUsize>,
+        /// Umbral de FUNCTION_TOO_LONG (líneas) solo para esta ejecución; pisa `rule_config.function_length_threshold`
+        #[arg(long, value_name = "N")]
+        function_length_threshold: Option<std::num::NonZeroUsize>,
     },
     /// Análisis profundo (Capa 1 + Capa 2) e interactivo de un archivo
     Analyze {
@@ -120,22 +260,129 @@ pub enum ProCommands {
     },
     /// Genera un reporte de calidad completo del proyecto
     Report {
-        /// Formato del reporte (json o html)
+        /// Formato del reporte (json, html, markdown o csv)
         #[arg(long, default_value = "json")]
         format: String,
+        /// Incluye la tendencia de calidad de la ventana indicada (ej: 7d, 24h, 30m)
+        #[arg(long)]
+        since: Option<String>,
+        /// Ruta de salida (por defecto sentinel-report.<ext> en la raíz; `-` para stdout)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+        /// Severidad mínima que hace salir con código 1: error (default), warning, info o never
+        #[arg(long, default_value = "error", value_parser = ["error", "warning", "info", "never"])]
+        fail_on: String,
+        /// Omite archivos de más de N bytes (bundles minificados, código generado); pisa `max_file_bytes`
+        #[arg(long, value_name = "BYTES")]
+        max_file_size: Option<u64>,
+    },
+    /// Migra un archivo entre frameworks (ej: express → nestjs)
+    Migrate {
+        /// Framework de origen
+        from: String,
+        /// Framework de destino
+        to: String,
+        /// Archivo a migrar
+        file: String,
     },
     /// Divide un archivo grande en múltiples archivos por dominio
     Split {
         /// Archivo a dividir
         file: String,
+        /// Reemplaza el original por un archivo que re-exporta los módulos nuevos (TS/JS y Python)
+        #[arg(long)]
+        barrel: bool,
+        /// No guarda copia de los archivos sobrescritos (pisa `create_backups`)
+        #[arg(long)]
+        no_backup: bool,
     },
     /// Corrección automática de bugs
     Fix {
         /// Archivo a corregir
         file: String,
+        /// Muestra el diff propuesto sin escribir archivos
+        #[arg(long)]
+        dry_run: bool,
+        /// No guarda copia de los archivos sobrescritos (pisa `create_backups`)
+        #[arg(long)]
+        no_backup: bool,
+    },
+    /// Genera el docstring de un símbolo (JSDoc, Go, Rust) y lo inserta sobre su declaración
+    Doc {
+        /// Archivo que contiene el símbolo
+        file: String,
+        /// Función, clase o método a documentar
+        #[arg(long)]
+        symbol: String,
+        /// Muestra el docstring sin escribir el archivo
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Genera con IA un test para cada archivo sin test y lo ejecuta, reintentando si falla
+    TestAll {
+        /// Archivo o carpeta a cubrir
+        #[arg(default_value = ".")]
+        target: String,
+    },
+    /// Búsqueda semántica de código en la Knowledge Base (fallback: búsqueda por texto)
+    Search {
+        /// Texto a buscar (ej: "validación del token de sesión")
+        query: String,
+        /// Número máximo de resultados
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Formato de salida: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Mapa de archivos fuente con y sin test, con porcentaje por directorio
+    Coverage {
+        /// Archivo o carpeta a analizar
+        #[arg(default_value = ".")]
+        target: String,
+        /// Formato de salida: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Sale con código 1 si la cobertura total queda por debajo de este porcentaje
+        #[arg(long)]
+        min_coverage: Option<f64>,
+    },
+    /// Puntuación de calidad 0–100 (violaciones, complejidad y cobertura) con nota A–F
+    Score {
+        /// Archivo o carpeta a puntuar
+        #[arg(default_value = ".")]
+        target: String,
+        /// Formato de salida: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Precalcula el índice y la caché de embeddings (sin LLM); ideal para un job nocturno de CI
+    Warmup {
+        /// Archivo o carpeta a precalentar
+        #[arg(default_value = ".")]
+        target: String,
+    },
+    /// Exports que ningún archivo del proyecto llama ni importa (requiere el índice)
+    #[command(name = "deadcode", alias = "dead-code")]
+    DeadCode {
+        /// Formato de salida: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Definiciones, llamadas e imports que habría que actualizar al renombrar un símbolo (requiere el índice)
+    #[command(name = "rename-impact")]
+    RenameImpact {
+        /// Nombre del símbolo (función, clase, constante...)
+        symbol: String,
+        /// Formato de salida: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Devuelve un archivo a su última copia de seguridad (`backup_dir`) tras fix/audit/split/review/migrate
+    Restore {
+        /// Archivo a restaurar (relativo a la raíz del proyecto)
+        file: String,
     },
-    /// Ejecución de tests con asistencia de IA
-    TestAll,
     /// Review completo del proyecto (Arquitectura y Coherencia)
     Review {
         /// Listar últimos N reviews guardados
@@ -144,13 +391,35 @@ pub enum ProCommands {
         /// Comparar último review con el anterior
         #[arg(long, default_value_t = false)]
         diff: bool,
+        /// Líneas por archivo en la muestra de código (sustituye el tope del modo; máx. 600)
+        #[arg(long, value_name = "N")]
+        context_lines: Option<usize>,
+        /// Aplicar sin menú las sugerencias de este impacto o superior (high, medium, low)
+        #[arg(long, value_name = "IMPACT", value_parser = ["high", "medium", "low"])]
+        apply: Option<String>,
+        /// No pedir confirmación antes de aplicar (con --apply)
+        #[arg(long, default_value_t = false, requires = "apply")]
+        yes: bool,
+        /// Omite archivos de más de N bytes (bundles minificados, código generado); pisa `max_file_bytes`
+        #[arg(long, value_name = "BYTES")]
+        max_file_size: Option<u64>,
+        /// No guarda copia de los archivos sobrescritos (pisa `create_backups`)
+        #[arg(long)]
+        no_backup: bool,
     },
     /// Ejecutar un workflow definido
     Workflow {
         /// Nombre del workflow (ej: fix-and-verify)
-        name: String,
+        #[arg(required_unless_present = "list")]
+        name: Option<String>,
         /// Archivo objetivo (opcional)
         file: Option<String>,
+        /// Lista los workflows disponibles: predefinidos y los de `.sentinel/workflows/`
+        #[arg(long)]
+        list: bool,
+        /// Formato de salida de --list: text (default) o json
+        #[arg(long, default_value = "text")]
+        format: String,
     },
     /// Auditoría interactiva con correcciones automáticas
     Audit {
@@ -165,9 +434,33 @@ pub enum ProCommands {
         /// Máximo de archivos a auditar (default: 20). Usa un número mayor para proyectos grandes.
         #[arg(long, default_value = "20")]
         max_files: usize,
-        /// Llamadas LLM en paralelo (default: 3, rango 1-10)
+        /// Llamadas LLM en paralelo (default: 3, rango 1-10) o `auto` para ajustarlas según los 429 del proveedor
         #[arg(long, default_value = "3")]
-        concurrency: usize,
+        concurrency: String,
+        /// Muestra el diff de los fixes seleccionados sin escribir archivos
+        #[arg(long)]
+        dry_run: bool,
+        /// Severidad mínima que hace salir con código 1: error (High, default), warning (Medium), info (Low) o never
+        #[arg(long, default_value = "error", value_parser = ["error", "warning", "info", "never"])]
+        fail_on: String,
+        /// Líneas por archivo enviadas al modelo, cortando entre funciones (máx. 600; default: archivo completo)
+        #[arg(long, value_name = "N")]
+        context_lines: Option<usize>,
+        /// Solo muestra los issues cuyo título o descripción coincide con la regex (sin distinguir mayúsculas)
+        #[arg(long, value_name = "REGEX")]
+        grep: Option<String>,
+        /// Omite archivos de más de N bytes (bundles minificados, código generado); pisa `max_file_bytes`
+        #[arg(long, value_name = "BYTES")]
+        max_file_size: Option<u64>,
+        /// Agrupa los issues de la salida no interactiva / JSON por módulo, severidad o archivo
+        #[arg(long, value_name = "KEY", value_parser = ["module", "severity", "file"])]
+        group_by: Option<String>,
+        /// Solo reporta (y cuenta para --fail-on) los issues de esta severidad o mayor
+        #[arg(long, value_name = "LEVEL", default_value = "low", value_parser = ["high", "medium", "low"])]
+        severity_min: String,
+        /// No guarda copia de los archivos sobrescritos (pisa `create_backups`)
+        #[arg(long)]
+        no_backup: bool,
     },
     /// Gestión de modelos de ML Local
     Ml {
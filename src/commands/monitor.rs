@@ -1,16 +1,18 @@
 use std::path::Path;
-use crate::config::SentinelConfig;
+use crate::config::{CommitConvention, SentinelConfig};
 use crate::rules::engine::RuleEngine;
 use crate::stats::SentinelStats;
 use crate::{ai, config, docs, files, git, index, tests as test_runner, ui, business_logic_guard};
 use colored::*;
+use notify::event::{ModifyKind, RenameMode};
 use notify::{Event, EventKind, RecursiveMode, Watcher};
 use std::collections::HashMap;
 use std::io::{self, Write};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, mpsc};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub(crate) fn write_pid_file(pid_path: &Path, pid: u32) -> anyhow::Result<()> {
     if let Some(parent) = pid_path.parent() {
@@ -145,7 +147,12 @@ pub fn handle_status(project_root: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn start_monitor() {
+/// Espera máxima a que los workers terminen el archivo en curso al apagar.
+const ESPERA_APAGADO: Duration = Duration::from_secs(30);
+
+/// `apagado` lo activa el handler de señales de `main`: se deja terminar el archivo en
+/// curso, se guardan las estadísticas y se sale con código 0.
+pub fn start_monitor(apagado: Arc<AtomicBool>, interactive: bool) {
     // Mostrar banner al inicio
     ui::mostrar_banner();
 
@@ -157,7 +164,13 @@ pub fn start_monitor() {
     // Guardar como proyecto activo
     let _ = SentinelConfig::save_active_project(&project_path);
 
-    let config = Arc::new(ui::inicializar_sentinel(&project_path));
+    let config = match ui::inicializar_sentinel(&project_path).with_active_profile(None) {
+        Ok(config) => Arc::new(config),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
     let stats = Arc::new(Mutex::new(SentinelStats::cargar(&project_path)));
 
     // --- Knowledge Base (v5.0.0 Pro) con SQLite ---
@@ -166,7 +179,11 @@ pub fn start_monitor() {
     let index_builder = Arc::new(index::ProjectIndexBuilder::new(Arc::clone(&index_db)));
 
     // Motor de Reglas Pro
-    let mut rule_engine = RuleEngine::new();
+    let mut rule_engine = RuleEngine::new()
+        .with_sentinelignore(&project_path)
+        .with_entry_patterns(&project_path, &config)
+        .with_secret_allowlist(&project_path, &config.rule_config.secret_allowlist)
+        .with_thresholds(&config.rule_config);
     let rules_path = project_path.join(".sentinel/rules.yaml");
     if rules_path.exists() {
         if let Err(e) = rule_engine.load_from_yaml(&rules_path) {
@@ -188,7 +205,7 @@ pub fn start_monitor() {
     let (tx, rx) = mpsc::channel::<PathBuf>();
     let (stdin_tx, stdin_rx) = mpsc::channel::<String>();
     let stdin_rx = Arc::new(Mutex::new(stdin_rx));
-    let esperando_input = Arc::new(Mutex::new(false));
+    let esperando_input = Arc::new(EsperaTeclado::default());
 
     // Hilo teclado
     let project_path_hilo = project_path.clone();
@@ -197,14 +214,18 @@ pub fn start_monitor() {
     let pausa_hilo = Arc::clone(&esta_pausado);
     let esperando_input_hilo = Arc::clone(&esperando_input);
     let index_builder_hilo = Arc::clone(&index_builder);
+    let index_db_hilo = Arc::clone(&index_db);
 
     thread::spawn(move || {
         loop {
             let mut input = String::new();
             if io::stdin().read_line(&mut input).is_ok() {
                 let cmd = input.trim().to_lowercase();
-                if *esperando_input_hilo.lock().unwrap() {
+                if esperando_input_hilo.activa() {
                     let _ = stdin_tx.send(cmd);
+                    // No volver a leer hasta que la pregunta termine: lo que siga (el
+                    // editor de `--interactive`) necesita el terminal para él solo
+                    esperando_input_hilo.esperar_fin();
                 } else if cmd == "p" {
                     let mut p = pausa_hilo.lock().unwrap();
                     *p = !*p;
@@ -234,11 +255,26 @@ pub fn start_monitor() {
                         s.bugs_criticos_evitados.to_string().red()
                     );
                     println!("💰 Costo Acumulado: ${:.4}", s.total_cost_usd);
+                    for (modelo, uso) in &s.uso_por_modelo {
+                        println!(
+                            "   └─ {}: ${:.4} ({} tokens)",
+                            modelo.cyan(),
+                            uso.cost_usd,
+                            uso.tokens
+                        );
+                    }
                     println!("🎟️ Tokens Usados:   {}", s.total_tokens_used);
                     println!(
                         "⏳ Tiempo Ahorrado: {}h",
                         (s.tiempo_estimado_ahorrado_mins as f32 / 60.0)
                     );
+                    let cache = ai::cache_stats();
+                    println!(
+                        "♻️  Caché:          {} hits / {} misses ({:.0}%)",
+                        cache.hits,
+                        cache.misses,
+                        cache.hit_rate() * 100.0
+                    );
                     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                 } else if cmd == "l" {
                     print!(
@@ -269,10 +305,19 @@ pub fn start_monitor() {
                                 no_fix: false,
                                 format: "text".to_string(),
                                 max_files: 20,
-                                concurrency: 3,
+                                concurrency: "3".to_string(),
+                                dry_run: false,
+                                fail_on: "error".to_string(),
+                                context_lines: None,
+                                grep: None,
+                                max_file_size: None,
+                                group_by: None,
+                                severity_min: "low".to_string(),
+                                no_backup: false,
                             },
                             false,
                             false,
+                            &crate::commands::ModelOverride::default(),
                         );
                         println!("✅ Auditoría terminada. Volviendo a monitorear...\n");
                     }
@@ -280,6 +325,17 @@ pub fn start_monitor() {
                     println!("   🧠 Re-indexando proyecto...");
                     let _ = index_builder_hilo.index_project(&project_path_hilo, &config_hilo.file_extensions);
                     println!("   ✅ Re-indexación completada.");
+                    println!("   🔌 Sincronizando Knowledge Base...");
+                    match crate::kb::sync_project(&config_hilo, &project_path_hilo, &index_db_hilo) {
+                        Ok(s) => println!(
+                            "   ✅ KB actualizada: {} nuevos, {} actualizados, {} borrados, {} sin cambios.",
+                            s.added.to_string().green(),
+                            s.updated.to_string().yellow(),
+                            s.deleted.to_string().red(),
+                            s.unchanged
+                        ),
+                        Err(e) => println!("   ⚠️  KB no sincronizada: {}", e),
+                    }
                 } else if cmd == "h" || cmd == "help" {
                     ui::mostrar_ayuda(Some(&config_hilo));
                 } else if cmd == "x" {
@@ -299,33 +355,37 @@ pub fn start_monitor() {
 
     // Watcher
     let config_watcher = Arc::clone(&config);
+    let ignore_matcher = config.ignore_matcher(&project_path);
     let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
         if let Ok(event) = res {
-            if let EventKind::Modify(_) = event.kind {
-                for path in event.paths {
-                    if !config_watcher.debe_ignorar(&path) {
-                        let _ = tx.send(path);
-                    }
+            for path in rutas_de_evento(&event.kind, event.paths, &config_watcher, &ignore_matcher) {
+                // El archivo temporal de un guardado atómico ya no existe tras el rename
+                if path.is_file() {
+                    let _ = tx.send(path);
                 }
             }
         }
     })
     .unwrap();
-    let project_path_watcher = project_path.clone();
-    watcher
-        .watch(&project_path_watcher.join("src"), RecursiveMode::Recursive)
-        .unwrap();
-
-    let leer_respuesta = move || -> Option<String> {
-        *esperando_input.lock().unwrap() = true;
-        let res = stdin_rx
-            .lock()
-            .unwrap()
-            .recv_timeout(std::time::Duration::from_secs(30))
-            .ok();
-        *esperando_input.lock().unwrap() = false;
-        res
-    };
+    let (raices, faltantes) = resolver_raices_watch(&project_path, &config.watch_paths);
+    for faltante in &faltantes {
+        println!("{} watch_paths: '{}' no existe, se omite.", "⚠️".yellow(), faltante);
+    }
+    for raiz in &raices {
+        if let Err(e) = watcher.watch(raiz, RecursiveMode::Recursive) {
+            println!("{} No se pudo vigilar '{}': {}", "⚠️".yellow(), raiz.display(), e);
+        }
+    }
+    if raices.is_empty() {
+        println!("{} Ninguna carpeta de watch_paths existe; no hay nada que vigilar.", "❌".red());
+        return;
+    }
+
+    let prompt = Arc::new(PromptGate {
+        turno: Mutex::new(()),
+        esperando_input,
+        stdin_rx,
+    });
 
     println!(
         "\n{} {}",
@@ -338,22 +398,381 @@ pub fn start_monitor() {
     // Mostrar ayuda de comandos al inicio
     ui::mostrar_ayuda(Some(&config));
 
-    let mut ultimo_cambio: HashMap<PathBuf, Instant> = HashMap::new();
-    while let Ok(changed_path) = rx.recv() {
-        thread::sleep(std::time::Duration::from_millis(500));
-        while rx.try_recv().is_ok() {}
+    // Pool acotado de workers: cada cambio se analiza en paralelo (hasta monitor_concurrency)
+    let worker = Arc::new(MonitorWorker {
+        config: Arc::clone(&config),
+        stats: Arc::clone(&stats),
+        project_path: project_path.clone(),
+        index_builder: Arc::clone(&index_builder),
+        rule_engine: Arc::clone(&rule_engine),
+        prompt,
+        interactive,
+    });
+    let (job_tx, job_rx) = mpsc::channel::<PathBuf>();
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let (fin_tx, fin_rx) = mpsc::channel::<()>();
+    let n_workers = config.monitor_concurrency.max(1);
+    for _ in 0..n_workers {
+        let job_rx = Arc::clone(&job_rx);
+        let worker = Arc::clone(&worker);
+        let apagado = Arc::clone(&apagado);
+        let fin_tx = fin_tx.clone();
+        thread::spawn(move || {
+            loop {
+                let siguiente = job_rx.lock().unwrap().recv();
+                match siguiente {
+                    // Al apagar se descartan los cambios encolados que no empezaron
+                    Ok(_) if apagado.load(Ordering::SeqCst) => break,
+                    Ok(path) => worker.procesar_cambio(&path),
+                    Err(_) => break,
+                }
+            }
+            let _ = fin_tx.send(());
+        });
+    }
+    drop(fin_tx);
 
+    let mut ultimo_cambio: HashMap<PathBuf, Instant> = HashMap::new();
+    let ventana = config.coalesce_window();
+    let cooldown = config.analysis_cooldown();
+    while let Some(pendientes) = recibir_lote_hasta_apagado(&rx, ventana, &apagado) {
         if *pausa_loop.lock().unwrap() {
             continue;
         }
 
-        let ahora = Instant::now();
-        if let Some(ultimo) = ultimo_cambio.get(&changed_path) {
-            if ahora.duration_since(*ultimo) < std::time::Duration::from_secs(10) {
-                continue;
+        for path in planificar_despachos(pendientes, &mut ultimo_cambio, Instant::now(), cooldown) {
+            let _ = job_tx.send(path);
+        }
+    }
+
+    // Apagado ordenado: cerrar la cola y esperar a que cada worker termine su archivo
+    drop(job_tx);
+    let limite = Instant::now() + ESPERA_APAGADO;
+    for _ in 0..n_workers {
+        let restante = limite.saturating_duration_since(Instant::now());
+        if fin_rx.recv_timeout(restante).is_err() {
+            eprintln!("   ⚠️  Un análisis no terminó a tiempo; se guardan las estadísticas igualmente.");
+            break;
+        }
+    }
+    let s = stats.lock().unwrap();
+    s.guardar(&project_path);
+    println!("\n{}", "📊 Resumen de la sesión".bright_green().bold());
+    println!(
+        "   Análisis: {} · Bugs evitados: {} · Costo acumulado: ${:.4}",
+        s.total_analisis, s.bugs_criticos_evitados, s.total_cost_usd
+    );
+    println!("👋 Sentinel detenido.");
+    std::process::exit(0);
+}
+
+/// Carpetas a vigilar según `watch_paths`: normalizadas, sin las que no existen
+/// (devueltas aparte para avisar) y sin las contenidas en otra raíz ya vigilada.
+pub(crate) fn resolver_raices_watch(project_path: &Path, watch_paths: &[String]) -> (Vec<PathBuf>, Vec<String>) {
+    let por_defecto = ["src".to_string()];
+    let watch_paths = if watch_paths.is_empty() { &por_defecto[..] } else { watch_paths };
+
+    let mut existentes: Vec<PathBuf> = Vec::new();
+    let mut faltantes = Vec::new();
+    for rel in watch_paths {
+        let raiz = normalizar_ruta(&project_path.join(rel));
+        if !raiz.is_dir() {
+            if !faltantes.contains(rel) {
+                faltantes.push(rel.clone());
+            }
+            continue;
+        }
+        if !existentes.contains(&raiz) {
+            existentes.push(raiz);
+        }
+    }
+
+    // Una raíz anidada en otra generaría eventos duplicados
+    let raices = existentes
+        .iter()
+        .filter(|r| !existentes.iter().any(|otra| otra != *r && r.starts_with(otra)))
+        .cloned()
+        .collect();
+    (raices, faltantes)
+}
+
+/// Rutas de un evento del watcher que deben analizarse, normalizadas y filtradas
+/// con `debe_ignorar`. Además de `Modify`, acepta `Create` y renames: los editores
+/// que guardan de forma atómica (escriben un temporal y lo renombran) solo emiten
+/// estos. De un rename interesa el destino, nunca el origen.
+pub(crate) fn rutas_de_evento(
+    kind: &EventKind,
+    paths: Vec<PathBuf>,
+    config: &SentinelConfig,
+    matcher: &ignore::gitignore::Gitignore,
+) -> Vec<PathBuf> {
+    let candidatas: Vec<PathBuf> = match kind {
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => Vec::new(),
+        // paths = [origen, destino]
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => paths.into_iter().skip(1).collect(),
+        EventKind::Modify(_) | EventKind::Create(_) => paths,
+        _ => Vec::new(),
+    };
+
+    let mut rutas: Vec<PathBuf> = Vec::new();
+    for path in candidatas {
+        let path = normalizar_ruta(&path);
+        if !config.debe_ignorar(matcher, &path) && !rutas.contains(&path) {
+            rutas.push(path);
+        }
+    }
+    rutas
+}
+
+/// Normalización léxica (`a/./b/../c` → `a/c`) para que el mismo archivo llegue
+/// con una única clave al mapa de debounce.
+fn normalizar_ruta(path: &Path) -> PathBuf {
+    use std::path::Component;
+    let mut normalizada = PathBuf::new();
+    for componente in path.components() {
+        match componente {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalizada.pop() {
+                    normalizada.push("..");
+                }
             }
+            otro => normalizada.push(otro.as_os_str()),
         }
-        ultimo_cambio.insert(changed_path.clone(), ahora);
+    }
+    normalizada
+}
+
+/// Bloquea hasta el próximo cambio y agrupa en un lote sin duplicados los que lleguen
+/// durante `ventana` (`coalesce_ms` del config: ráfagas de guardados, formatters). `None` cuando el watcher se cerró.
+pub(crate) fn recibir_lote(rx: &mpsc::Receiver<PathBuf>, ventana: Duration) -> Option<Vec<PathBuf>> {
+    let primero = rx.recv().ok()?;
+    Some(completar_lote(primero, rx, ventana))
+}
+
+/// Cada cuánto se revisa el flag de apagado mientras no llegan cambios.
+const SONDEO_APAGADO: Duration = Duration::from_millis(200);
+
+/// Como `recibir_lote`, pero devuelve `None` en cuanto se activa `apagado`.
+pub(crate) fn recibir_lote_hasta_apagado(
+    rx: &mpsc::Receiver<PathBuf>,
+    ventana: Duration,
+    apagado: &AtomicBool,
+) -> Option<Vec<PathBuf>> {
+    loop {
+        if apagado.load(Ordering::SeqCst) {
+            return None;
+        }
+        match rx.recv_timeout(SONDEO_APAGADO) {
+            Ok(primero) => {
+                let lote = completar_lote(primero, rx, ventana);
+                return (!apagado.load(Ordering::SeqCst)).then_some(lote);
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+fn completar_lote(primero: PathBuf, rx: &mpsc::Receiver<PathBuf>, ventana: Duration) -> Vec<PathBuf> {
+    thread::sleep(ventana);
+    let mut lote = vec![primero];
+    for path in rx.try_iter() {
+        if !lote.contains(&path) {
+            lote.push(path);
+        }
+    }
+    lote
+}
+
+/// Un archivo analizado en `ultimo` vuelve a ser elegible cuando pasó el cooldown.
+pub(crate) fn cooldown_cumplido(ahora: Instant, ultimo: Instant, cooldown: Duration) -> bool {
+    ahora.saturating_duration_since(ultimo) >= cooldown
+}
+
+/// Decide qué archivos de un lote de eventos se despachan al pool de análisis.
+/// Deduplica el lote y aplica el cooldown por archivo (`analysis_cooldown_secs`) sobre
+/// `ultimo_cambio`, registrando el instante de cada archivo despachado.
+pub(crate) fn planificar_despachos(
+    pendientes: Vec<PathBuf>,
+    ultimo_cambio: &mut HashMap<PathBuf, Instant>,
+    ahora: Instant,
+    cooldown: Duration,
+) -> Vec<PathBuf> {
+    let mut despachar: Vec<PathBuf> = Vec::new();
+    for path in pendientes {
+        if despachar.contains(&path) {
+            continue;
+        }
+        if let Some(ultimo) = ultimo_cambio.get(&path)
+            && !cooldown_cumplido(ahora, *ultimo, cooldown)
+        {
+            continue;
+        }
+        ultimo_cambio.insert(path.clone(), ahora);
+        despachar.push(path);
+    }
+    despachar
+}
+
+/// Si hay una pregunta esperando respuesta. Mientras dura, el hilo de teclado reenvía lo
+/// que lee al `PromptGate` en vez de interpretarlo como comando (p/x/...), y tras reenviar
+/// una respuesta no vuelve a leer de stdin hasta que la pregunta termina.
+#[derive(Default)]
+struct EsperaTeclado {
+    activa: Mutex<bool>,
+    fin: Condvar,
+}
+
+impl EsperaTeclado {
+    fn activa(&self) -> bool {
+        *self.activa.lock().unwrap()
+    }
+
+    fn set(&self, activa: bool) {
+        *self.activa.lock().unwrap() = activa;
+        if !activa {
+            self.fin.notify_all();
+        }
+    }
+
+    fn esperar_fin(&self) {
+        let mut activa = self.activa.lock().unwrap();
+        while *activa {
+            activa = self.fin.wait(activa).unwrap();
+        }
+    }
+}
+
+/// Serializa las preguntas interactivas: con varios workers analizando en paralelo,
+/// solo uno puede estar esperando respuesta del usuario a la vez.
+struct PromptGate {
+    turno: Mutex<()>,
+    esperando_input: Arc<EsperaTeclado>,
+    stdin_rx: Arc<Mutex<mpsc::Receiver<String>>>,
+}
+
+impl PromptGate {
+    fn preguntar(&self, mensaje: &str) -> Option<String> {
+        self.preguntar_con(mensaje, str::to_string)
+    }
+
+    /// Como `preguntar`, pero `al_responder` corre con la respuesta antes de devolver stdin
+    /// al hilo de teclado. Ahí va lo que necesita el terminal (`$EDITOR`): si el hilo ya
+    /// estuviera leyendo otra vez, se quedaría con lo que se escribe en el editor.
+    fn preguntar_con<R>(&self, mensaje: &str, al_responder: impl FnOnce(&str) -> R) -> Option<R> {
+        let _turno = self.turno.lock().unwrap();
+        // Respuestas que llegaron tarde a una pregunta anterior
+        while self.stdin_rx.lock().unwrap().try_recv().is_ok() {}
+        print!("{}", mensaje);
+        io::stdout().flush().unwrap();
+        self.esperando_input.set(true);
+        let res = self
+            .stdin_rx
+            .lock()
+            .unwrap()
+            .recv_timeout(Duration::from_secs(30))
+            .ok()
+            .map(|respuesta| al_responder(&respuesta));
+        self.esperando_input.set(false);
+        res
+    }
+}
+
+/// Veces que se puede volver a pedir el mensaje a la IA si no cumple la convención.
+const MAX_REINTENTOS_COMMIT: usize = 3;
+
+/// Estado compartido por los workers que procesan cambios del monitor.
+struct MonitorWorker {
+    config: Arc<SentinelConfig>,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: PathBuf,
+    index_builder: Arc<index::ProjectIndexBuilder>,
+    rule_engine: Arc<RuleEngine>,
+    prompt: Arc<PromptGate>,
+    /// `monitor --interactive`: editar/validar el mensaje antes del commit
+    interactive: bool,
+}
+
+impl MonitorWorker {
+    /// Sin `--interactive`: con `commit_convention = "conventional"` se pide otro mensaje a
+    /// la IA mientras no cumpla la convención (hasta `MAX_REINTENTOS_COMMIT` veces).
+    fn mensaje_conforme(&self, codigo: &str, file_path: &Path, mut msg: String) -> String {
+        if self.config.commit_convention != CommitConvention::Conventional {
+            return msg;
+        }
+        for _ in 0..MAX_REINTENTOS_COMMIT {
+            let Err(error) = git::validar_conventional_commit(&msg) else {
+                return msg;
+            };
+            msg = git::regenerar_mensaje_commit(
+                codigo,
+                file_path,
+                &msg,
+                &error,
+                &self.config,
+                Arc::clone(&self.stats),
+                &self.project_path,
+            );
+        }
+        if let Err(error) = git::validar_conventional_commit(&msg) {
+            println!("{}", format!("⚠️  El mensaje no sigue Conventional Commits: {}", error).yellow());
+        }
+        msg
+    }
+
+    /// Flujo de `--interactive`: valida el mensaje según `commit_convention` (ofreciendo
+    /// regenerarlo con la IA) y permite editarlo en `$EDITOR` antes de confirmar.
+    fn commit_interactivo(&self, codigo: &str, file_path: &Path, mut msg: String) {
+        let conventional = self.config.commit_convention == CommitConvention::Conventional;
+        let mut reintentos = 0;
+        loop {
+            if conventional && let Err(error) = git::validar_conventional_commit(&msg) {
+                println!(
+                    "{}",
+                    format!("⚠️  El mensaje no sigue Conventional Commits: {}", error).yellow()
+                );
+                if reintentos < MAX_REINTENTOS_COMMIT
+                    && self.prompt.preguntar("🔁 ¿Pedir otro mensaje a la IA? (s/n): ").as_deref() == Some("s")
+                {
+                    reintentos += 1;
+                    msg = git::regenerar_mensaje_commit(
+                        codigo,
+                        file_path,
+                        &msg,
+                        &error,
+                        &self.config,
+                        Arc::clone(&self.stats),
+                        &self.project_path,
+                    );
+                    continue;
+                }
+            }
+
+            let pregunta = format!(
+                "\n🚀 Mensaje: {}\n📝 ¿Commit? (s = sí, n = no, e = editar): ",
+                msg.bright_cyan().bold()
+            );
+            let respuesta = self.prompt.preguntar_con(&pregunta, |r| {
+                let editado = (r == "e").then(|| git::editar_mensaje_commit(&msg));
+                (r.to_string(), editado)
+            });
+            match respuesta {
+                Some((_, Some(Ok(editado)))) if !editado.is_empty() => msg = editado,
+                Some((_, Some(Ok(_)))) => println!("{}", "⚠️  Mensaje vacío: se mantiene el anterior.".yellow()),
+                Some((_, Some(Err(e)))) => println!("{}", format!("❌ No se pudo abrir el editor: {}", e).red()),
+                Some((r, None)) => {
+                    git::preguntar_commit(&self.project_path, &msg, &r);
+                    return;
+                }
+                None => return,
+            }
+        }
+    }
+
+    fn procesar_cambio(&self, changed_path: &Path) {
+        let config = &self.config;
+        let project_path = &self.project_path;
 
         let file_name = changed_path
             .file_name()
@@ -363,13 +782,13 @@ pub fn start_monitor() {
             .to_string();
 
         // --- Actualizar Índice de Símbolos (SQLite) ---
-        let _ = index_builder.index_file(&changed_path, &project_path);
+        let _ = self.index_builder.index_file(changed_path, project_path);
 
         // --- BusinessLogicGuard: detectar regresiones vs último commit ---
         let regression_context = {
-            let prev = business_logic_guard::get_git_previous_content(&changed_path, &project_path);
+            let prev = business_logic_guard::get_git_previous_content(changed_path, project_path);
             if let Some(prev_content) = prev {
-                if let Ok(new_content) = std::fs::read_to_string(&changed_path) {
+                if let Ok(new_content) = std::fs::read_to_string(changed_path) {
                     business_logic_guard::build_regression_context(&prev_content, &new_content)
                 } else {
                     None
@@ -382,10 +801,7 @@ pub fn start_monitor() {
         if let Some(ref diff_ctx) = regression_context {
             println!("\n🔍 {} Analizando regresiones vs último commit...", "BusinessLogicGuard:".bold().yellow());
             let regression_prompt = business_logic_guard::build_regression_prompt(diff_ctx, &file_name);
-            let config_bg = Arc::clone(&config);
-            let stats_bg = Arc::clone(&stats);
-            let project_bg = project_path.clone();
-            if let Ok(result) = ai::client::consultar_ia_dinamico(regression_prompt, ai::client::TaskType::Light, &config_bg, stats_bg, &project_bg) {
+            if let Ok(result) = ai::client::consultar_ia_dinamico(regression_prompt, ai::client::TaskType::Light, config, Arc::clone(&self.stats), project_path) {
                 if result.contains("REGRESION_DETECTADA") {
                     println!("   {} {}", "⚠️  REGRESIÓN:".red().bold(), result.lines().find(|l| l.contains("REGRESION_DETECTADA")).unwrap_or(""));
                 } else if result.contains("REVISAR") {
@@ -397,9 +813,10 @@ pub fn start_monitor() {
         }
 
         let base_name = match files::detectar_archivo_padre(
-            &changed_path,
-            &project_path,
+            changed_path,
+            project_path,
             &config.parent_patterns,
+            &config.package_root_markers,
         ) {
             Some(padre) => {
                 println!(
@@ -412,23 +829,21 @@ pub fn start_monitor() {
         };
 
         let test_rel_path =
-            files::buscar_archivo_test(&base_name, &project_path, &config.test_patterns);
+            files::buscar_archivo_test(&base_name, project_path, &config.test_patterns);
 
         if test_rel_path.is_none() {
-            println!("\n🔔 CAMBIO EN: {}", file_name.cyan().bold());
-            println!(
-                "{}",
+            let pregunta = format!(
+                "\n🔔 CAMBIO EN: {}\n{}\n🔍 ¿Deseas que revise el código de todas formas? (s/n) [30s timeout]: ",
+                file_name.cyan().bold(),
                 "⚠️  No se encontraron tests para este archivo.".yellow()
             );
-            print!("🔍 ¿Deseas que revise el código de todas formas? (s/n) [30s timeout]: ");
-            io::stdout().flush().unwrap();
 
-            match leer_respuesta() {
+            match self.prompt.preguntar(&pregunta) {
                 Some(respuesta) if respuesta == "s" => {
-                    if let Ok(codigo) = std::fs::read_to_string(&changed_path) {
+                    if let Ok(codigo) = std::fs::read_to_string(changed_path) {
                         // Validar Reglas Pro (Estáticas)
                         let spinner = ui::crear_progreso("   🔍 Validando reglas estáticas...");
-                        let violaciones = rule_engine.validate_file(&changed_path, &codigo);
+                        let violaciones = self.rule_engine.validate_file(changed_path, &codigo);
                         spinner.finish_and_clear();
 
                         if !violaciones.is_empty() {
@@ -446,10 +861,10 @@ pub fn start_monitor() {
                         let resultado_analisis = ai::analizar_arquitectura(
                             &codigo,
                             &file_name,
-                            Arc::clone(&stats),
-                            &config,
-                            &project_path,
-                            &changed_path,
+                            Arc::clone(&self.stats),
+                            config,
+                            project_path,
+                            changed_path,
                         );
                         spinner_ai.finish_and_clear();
 
@@ -474,16 +889,16 @@ pub fn start_monitor() {
                     println!("   ⏭️  Revisión omitida. Continuando monitoreo...");
                 }
             }
-            continue;
+            return;
         }
 
         if let Some(test_path) = test_rel_path {
             println!("\n🔔 CAMBIO EN: {}", file_name.cyan().bold());
 
-            if let Ok(codigo) = std::fs::read_to_string(&changed_path) {
+            if let Ok(codigo) = std::fs::read_to_string(changed_path) {
                 // Validar Reglas Pro (Estáticas)
                 let spinner = ui::crear_progreso("   🔍 Validando reglas estáticas...");
-                let violaciones = rule_engine.validate_file(&changed_path, &codigo);
+                let violaciones = self.rule_engine.validate_file(changed_path, &codigo);
                 spinner.finish_and_clear();
 
                 if !violaciones.is_empty() {
@@ -505,48 +920,50 @@ pub fn start_monitor() {
                 let resultado_analisis = ai::analizar_arquitectura(
                     &codigo,
                     &file_name,
-                    Arc::clone(&stats),
-                    &config,
-                    &project_path,
-                    &changed_path,
+                    Arc::clone(&self.stats),
+                    config,
+                    project_path,
+                    changed_path,
                 );
                 spinner_ai.finish_and_clear();
 
                 match resultado_analisis {
                     Ok(true) => {
-                        if test_runner::ejecutar_tests(&test_path, &project_path).is_ok() {
+                        if test_runner::ejecutar_tests(&test_path, project_path).is_ok() {
                             let _ = docs::actualizar_documentacion(
                                 &codigo,
-                                &changed_path,
-                                &config,
-                                Arc::clone(&stats),
-                                &project_path,
+                                changed_path,
+                                config,
+                                Arc::clone(&self.stats),
+                                project_path,
                             );
                             let msg = git::generar_mensaje_commit(
                                 &codigo,
-                                &file_name,
-                                &config,
-                                Arc::clone(&stats),
-                                &project_path,
+                                changed_path,
+                                config,
+                                Arc::clone(&self.stats),
+                                project_path,
                             );
-                            println!("\n🚀 Mensaje: {}", msg.bright_cyan().bold());
-                            print!("📝 ¿Commit? (s/n): ");
-                            io::stdout().flush().unwrap();
-                            if let Some(r) = leer_respuesta() {
-                                git::preguntar_commit(&project_path, &msg, &r);
-                            }
-                        } else {
-                            print!("\n🔍 ¿Ayuda con test? (s/n): ");
-                            io::stdout().flush().unwrap();
-                            if leer_respuesta().as_deref() == Some("s") {
-                                let _ = test_runner::pedir_ayuda_test(
-                                    &codigo,
-                                    &test_path,
-                                    &config,
-                                    Arc::clone(&stats),
-                                    &project_path,
+                            if self.interactive {
+                                self.commit_interactivo(&codigo, changed_path, msg);
+                            } else {
+                                let msg = self.mensaje_conforme(&codigo, changed_path, msg);
+                                let pregunta = format!(
+                                    "\n🚀 Mensaje: {}\n📝 ¿Commit? (s/n): ",
+                                    msg.bright_cyan().bold()
                                 );
+                                if let Some(r) = self.prompt.preguntar(&pregunta) {
+                                    git::preguntar_commit(project_path, &msg, &r);
+                                }
                             }
+                        } else if self.prompt.preguntar("\n🔍 ¿Ayuda con test? (s/n): ").as_deref() == Some("s") {
+                            let _ = test_runner::pedir_ayuda_test(
+                                &codigo,
+                                &test_path,
+                                config,
+                                Arc::clone(&self.stats),
+                                project_path,
+                            );
                         }
                     }
                     _ => {}
@@ -561,6 +978,153 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_resolver_raices_watch_skips_missing_and_nested() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        for dir in ["src", "lib", "packages/api/src", "packages/web"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let watch_paths: Vec<String> = ["src", "lib", "./src", "packages", "packages/api/src", "apps", "apps"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let (raices, faltantes) = resolver_raices_watch(root, &watch_paths);
+        assert_eq!(raices, vec![root.join("src"), root.join("lib"), root.join("packages")]);
+        assert_eq!(faltantes, vec!["apps".to_string()]);
+
+        // Config antigua sin watch_paths: se vigila src/
+        let (raices, _) = resolver_raices_watch(root, &[]);
+        assert_eq!(raices, vec![root.join("src")]);
+    }
+
+    #[test]
+    fn test_recibir_lote_coalesces_burst() {
+        let (tx, rx) = mpsc::channel();
+        let a = PathBuf::from("src/a.ts");
+        let b = PathBuf::from("src/b.ts");
+        for p in [&a, &b, &a] {
+            tx.send(p.clone()).unwrap();
+        }
+        assert_eq!(recibir_lote(&rx, Duration::from_millis(10)), Some(vec![a.clone(), b.clone()]));
+
+        // Ráfaga que llega después de empezar a esperar: un solo lote
+        let tx2 = tx.clone();
+        let (c, d) = (PathBuf::from("src/c.ts"), PathBuf::from("src/d.ts"));
+        let (c2, d2) = (c.clone(), d.clone());
+        let emisor = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx2.send(c2).unwrap();
+            thread::sleep(Duration::from_millis(20));
+            tx2.send(d2).unwrap();
+        });
+        assert_eq!(recibir_lote(&rx, Duration::from_millis(300)), Some(vec![c, d]));
+        emisor.join().unwrap();
+
+        drop(tx);
+        assert_eq!(recibir_lote(&rx, Duration::from_millis(10)), None);
+    }
+
+    #[test]
+    fn test_recibir_lote_hasta_apagado_exits_on_flag() {
+        let (tx, rx) = mpsc::channel::<PathBuf>();
+        let apagado = Arc::new(AtomicBool::new(false));
+
+        tx.send(PathBuf::from("src/a.ts")).unwrap();
+        assert_eq!(
+            recibir_lote_hasta_apagado(&rx, Duration::from_millis(10), &apagado),
+            Some(vec![PathBuf::from("src/a.ts")])
+        );
+
+        // Sin eventos y con el watcher vivo, el flag debe desbloquear la espera
+        let flag = Arc::clone(&apagado);
+        let senal = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            flag.store(true, Ordering::SeqCst);
+        });
+        let inicio = Instant::now();
+        assert_eq!(recibir_lote_hasta_apagado(&rx, Duration::from_millis(10), &apagado), None);
+        assert!(inicio.elapsed() < Duration::from_secs(2));
+        senal.join().unwrap();
+        drop(tx);
+    }
+
+    #[test]
+    fn test_planificar_despachos_dedup_and_debounce() {
+        let a = PathBuf::from("src/a.ts");
+        let b = PathBuf::from("src/b.ts");
+        let mut ultimo_cambio = HashMap::new();
+        let t0 = Instant::now();
+        let ventana = Duration::from_secs(10);
+
+        // Lote con duplicados: cada archivo se despacha una sola vez
+        let lote = vec![a.clone(), b.clone(), a.clone()];
+        assert_eq!(planificar_despachos(lote, &mut ultimo_cambio, t0, ventana), vec![a.clone(), b.clone()]);
+
+        // Dentro de la ventana de debounce: nada se despacha
+        let t1 = t0 + Duration::from_secs(5);
+        assert!(planificar_despachos(vec![a.clone(), b.clone()], &mut ultimo_cambio, t1, ventana).is_empty());
+
+        // Pasada la ventana: se vuelve a despachar y se actualiza el instante
+        let t2 = t0 + Duration::from_secs(11);
+        assert_eq!(planificar_despachos(vec![a.clone()], &mut ultimo_cambio, t2, ventana), vec![a.clone()]);
+        assert_eq!(ultimo_cambio[&a], t2);
+        assert_eq!(ultimo_cambio[&b], t0);
+    }
+
+    #[test]
+    fn test_cooldown_cumplido_and_config_minimums() {
+        let t0 = Instant::now();
+        let cooldown = Duration::from_secs(3);
+        assert!(!cooldown_cumplido(t0 + Duration::from_secs(2), t0, cooldown));
+        assert!(cooldown_cumplido(t0 + Duration::from_secs(3), t0, cooldown), "the boundary is inclusive");
+        assert!(cooldown_cumplido(t0 + Duration::from_secs(9), t0, cooldown));
+        // Reloj que retrocede (ultimo > ahora): no elegible, sin panic
+        assert!(!cooldown_cumplido(t0, t0 + Duration::from_secs(1), cooldown));
+
+        let config = SentinelConfig { analysis_cooldown_secs: 0, coalesce_ms: 5, ..Default::default() };
+        assert_eq!(config.analysis_cooldown(), Duration::from_secs(1));
+        assert_eq!(config.coalesce_window(), Duration::from_millis(50));
+        let config = SentinelConfig { analysis_cooldown_secs: 30, coalesce_ms: 800, ..Default::default() };
+        assert_eq!(config.analysis_cooldown(), Duration::from_secs(30));
+        assert_eq!(config.coalesce_window(), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn test_rutas_de_evento_rename_create_and_ignore() {
+        use notify::event::{CreateKind, DataChange};
+        let config = SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ignore_patterns: vec!["node_modules".to_string()],
+            ..Default::default()
+        };
+        let matcher = config.ignore_matcher(Path::new("/p"));
+
+        // Guardado atómico: temporal → archivo real; solo interesa el destino
+        let rename = EventKind::Modify(ModifyKind::Name(RenameMode::Both));
+        let paths = vec![PathBuf::from("/p/src/.app.ts.tmp123"), PathBuf::from("/p/src/./app.ts")];
+        assert_eq!(rutas_de_evento(&rename, paths, &config, &matcher), vec![PathBuf::from("/p/src/app.ts")]);
+
+        // Origen de un rename: el archivo ya no existe en esa ruta
+        let from = EventKind::Modify(ModifyKind::Name(RenameMode::From));
+        assert!(rutas_de_evento(&from, vec![PathBuf::from("/p/src/app.ts")], &config, &matcher).is_empty());
+
+        let create = EventKind::Create(CreateKind::File);
+        let paths = vec![
+            PathBuf::from("/p/src/nested/../b.ts"),
+            PathBuf::from("/p/src/b.ts"),
+            PathBuf::from("/p/node_modules/x.ts"),
+            PathBuf::from("/p/src/b.ts~"),
+            PathBuf::from("/p/src/b.spec.ts"),
+        ];
+        assert_eq!(rutas_de_evento(&create, paths, &config, &matcher), vec![PathBuf::from("/p/src/b.ts")]);
+
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+        assert_eq!(rutas_de_evento(&modify, vec![PathBuf::from("src/c.ts")], &config, &matcher), vec![PathBuf::from("src/c.ts")]);
+        assert!(rutas_de_evento(&EventKind::Remove(notify::event::RemoveKind::File), vec![PathBuf::from("src/c.ts")], &config, &matcher).is_empty());
+    }
+
     #[test]
     fn test_pid_file_write_and_read() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,141 @@
+//! Destino de la salida de los comandos.
+//!
+//! Los handlers escriben con `outln!(out, ...)` en un `OutputSink` en vez de `println!`:
+//! en producción es stdout, en tests un buffer en memoria (`OutputSink::buffer`), y más
+//! adelante podrá ser un archivo (`--log-file`). Los avisos que no forman parte del
+//! resultado (errores, notas en formatos máquina) siguen yendo a stderr.
+
+use std::io::Write;
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+pub struct OutputSink {
+    writer: Box<dyn Write + Send>,
+    /// Salida retenida desde `hold` (aún no escrita en `writer`)
+    held: Option<Vec<u8>>,
+}
+
+impl OutputSink {
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self { writer, held: None }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(Box::new(std::io::stdout()))
+    }
+
+    /// Sink en memoria y un handle para leer lo escrito (tests).
+    #[cfg(test)]
+    pub fn buffer() -> (Self, CapturedOutput) {
+        let captured = CapturedOutput::default();
+        (Self::new(Box::new(captured.clone())), captured)
+    }
+
+    /// Escribe `text` y un salto de línea. Un error de escritura (p. ej. tubería cerrada)
+    /// no debe abortar el comando: se ignora, igual que haría `println!` con `| head`.
+    pub fn line(&mut self, text: impl std::fmt::Display) {
+        let _ = writeln!(self, "{}", text);
+    }
+
+    /// Escribe `text` sin salto de línea.
+    pub fn raw(&mut self, text: impl std::fmt::Display) {
+        let _ = write!(self, "{}", text);
+        let _ = self.flush();
+    }
+
+    /// Retiene lo que se escriba a partir de ahora hasta `release` (se escribe) o `discard`
+    /// (se descarta). Sirve para decidir al final si la salida se muestra (`--quiet-success`).
+    pub fn hold(&mut self) {
+        self.held = Some(Vec::new());
+    }
+
+    pub fn release(&mut self) {
+        if let Some(held) = self.held.take() {
+            let _ = self.writer.write_all(&held);
+            let _ = self.writer.flush();
+        }
+    }
+
+    pub fn discard(&mut self) {
+        self.held = None;
+    }
+}
+
+impl Write for OutputSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.held.as_mut() {
+            Some(held) => {
+                held.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            None => self.writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Contenido escrito en un `OutputSink::buffer`.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct CapturedOutput(Arc<Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl CapturedOutput {
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+#[cfg(test)]
+impl Write for CapturedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `println!` sobre un `OutputSink`: `outln!(out)` o `outln!(out, "{} archivos", n)`.
+macro_rules! outln {
+    ($out:expr) => {
+        $out.line("")
+    };
+    ($out:expr, $($arg:tt)*) => {
+        $out.line(format_args!($($arg)*))
+    };
+}
+pub(crate) use outln;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_sink_captures_lines() {
+        let (mut out, captured) = OutputSink::buffer();
+        outln!(out, "{} archivo(s)", 3);
+        outln!(out);
+        out.raw("sin salto");
+        assert_eq!(captured.contents(), "3 archivo(s)\n\nsin salto");
+    }
+
+    #[test]
+    fn test_held_output_is_released_or_discarded() {
+        let (mut out, captured) = OutputSink::buffer();
+        out.hold();
+        outln!(out, "banner");
+        assert_eq!(captured.contents(), "");
+        out.discard();
+        outln!(out, "visible");
+        out.hold();
+        outln!(out, "hallazgo");
+        out.release();
+        assert_eq!(captured.contents(), "visible\nhallazgo\n");
+    }
+}
@@ -0,0 +1,235 @@
+use colored::*;
+use std::path::Path;
+
+/// Lista los archivos que se generarán/modificarán a partir de los bloques de un agente.
+pub fn listar_bloques(bloques: &[(Option<String>, String)]) {
+    println!("\n📂 {} archivo(s) a generar/modificar:", bloques.len().to_string().cyan());
+    for (path_opt, _) in bloques {
+        match path_opt {
+            Some(p) => println!("   • {}", p.cyan()),
+            None => println!("   • (sin ruta — se mostrará en consola)"),
+        }
+    }
+}
+
+/// Diff unificado (3 líneas de contexto) entre el contenido actual y el propuesto.
+/// Devuelve una cadena vacía si no hay cambios.
+pub fn diff_unificado(rel_path: &str, original: &str, propuesto: &str) -> String {
+    if original == propuesto {
+        return String::new();
+    }
+    similar::TextDiff::from_lines(original, propuesto)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", rel_path), &format!("b/{}", rel_path))
+        .to_string()
+}
+
+fn imprimir_diff(diff: &str) {
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            println!("{}", line.bold());
+        } else if line.starts_with('+') {
+            println!("{}", line.green());
+        } else if line.starts_with('-') {
+            println!("{}", line.red());
+        } else if line.starts_with("@@") {
+            println!("{}", line.cyan());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// `--dry-run`: muestra el diff de cada bloque contra el archivo en disco sin escribir nada.
+///
+/// Devuelve los diffs generados (uno por archivo con cambios).
+pub fn previsualizar_bloques(project_root: &Path, bloques: &[(Option<String>, String)]) -> Vec<String> {
+    let mut diffs = Vec::new();
+    for (path_opt, code) in bloques {
+        let Some(rel_path) = path_opt else {
+            println!("\n{}", "[Código sin ruta — no se puede comparar:]".yellow());
+            println!("{}", code);
+            continue;
+        };
+        let target = match crate::files::secure_join(project_root, Path::new(rel_path)) {
+            Ok(t) => t,
+            Err(e) => {
+                println!("   ⚠️  '{}' omitido: {}", rel_path.yellow(), e);
+                continue;
+            }
+        };
+        let original = std::fs::read_to_string(&target).unwrap_or_default();
+        let diff = diff_unificado(rel_path, &original, code);
+        if diff.is_empty() {
+            println!("   = {} (sin cambios)", rel_path.dimmed());
+            continue;
+        }
+        imprimir_diff(&diff);
+        diffs.push(diff);
+    }
+    println!(
+        "\n🔎 {} (dry-run): {} archivo(s) con cambios, nada se escribió en disco.",
+        "Vista previa".bold(),
+        diffs.len().to_string().cyan()
+    );
+    diffs
+}
+
+/// Escribe en disco los bloques `(ruta, código)` extraídos con `extraer_todos_bloques`.
+///
+/// - Los bloques sin ruta se imprimen para copiarlos manualmente.
+/// - Se descartan parches que no parsean con la gramática tree-sitter del lenguaje.
+/// - Si el destino existe, se descartan respuestas truncadas (< 1/3 del original)
+///   y se guarda una copia en `backups` antes de sobrescribir.
+///
+/// Devuelve cuántos archivos se guardaron.
+pub fn aplicar_bloques(
+    project_root: &Path,
+    bloques: &[(Option<String>, String)],
+    backups: &super::backup::Backups,
+) -> usize {
+    let mut saved = 0;
+    for (path_opt, code) in bloques {
+        match path_opt {
+            Some(rel_path) => {
+                let target = match crate::files::secure_join(project_root, Path::new(rel_path)) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        println!("   ⚠️  '{}' omitido: {}", rel_path.yellow(), e);
+                        continue;
+                    }
+                };
+
+                if target.is_dir() {
+                    println!("   ⚠️  '{}' es un directorio, omitido.", rel_path.yellow());
+                    continue;
+                }
+
+                let ext = target.extension().and_then(|e| e.to_str()).unwrap_or("");
+                if !crate::rules::is_syntactically_valid(code, ext) {
+                    println!("   ⚠️  '{}': parche descartado: sintaxis inválida.", rel_path.yellow());
+                    continue;
+                }
+
+                if let Some(parent) = target.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+
+                if target.exists() {
+                    let original_len = std::fs::metadata(&target)
+                        .map(|m| m.len() as usize)
+                        .unwrap_or(0);
+
+                    if original_len > 0 && code.len() < original_len / 3 {
+                        println!(
+                            "   ⚠️  '{}': respuesta truncada ({} chars vs {} original), saltando.",
+                            rel_path, code.len(), original_len
+                        );
+                        continue;
+                    }
+
+                    if let Err(e) = backups.backup_before_write(&target) {
+                        println!("   ⚠️  No se pudo crear backup de '{}': {}", rel_path, e);
+                        continue;
+                    }
+                }
+
+                match std::fs::write(&target, code) {
+                    Ok(_) => {
+                        println!("   ✅ {}", rel_path.green());
+                        saved += 1;
+                    }
+                    Err(e) => println!("   ❌ '{}': {}", rel_path, e),
+                }
+            }
+            None => {
+                println!("\n{}", "[Código sin ruta — cópialo manualmente:]".yellow());
+                println!("{}", code);
+            }
+        }
+    }
+    saved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::pro::backup::Backups;
+    use tempfile::TempDir;
+
+    fn backups(root: &Path) -> Backups {
+        Backups::new(root, Path::new(".sentinel/backups"))
+    }
+
+    #[test]
+    fn test_aplicar_bloques_writes_and_backs_up() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "old").unwrap();
+
+        let bloques = vec![
+            (Some("src/a.ts".to_string()), "new content".to_string()),
+            (Some("src/nested/b.ts".to_string()), "created".to_string()),
+            (None, "orphan".to_string()),
+        ];
+        assert_eq!(aplicar_bloques(root, &bloques, &backups(root)), 2);
+        assert_eq!(std::fs::read_to_string(root.join("src/a.ts")).unwrap(), "new content");
+        let backup = backups(root).latest(&root.join("src/a.ts")).unwrap();
+        assert!(backup.starts_with(root.join(".sentinel/backups/src")));
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), "old");
+        assert!(!root.join("src/a.ts.bak").exists());
+        assert!(root.join("src/nested/b.ts").exists());
+    }
+
+    #[test]
+    fn test_aplicar_bloques_rejects_traversal_and_truncation() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join("big.ts"), "x".repeat(300)).unwrap();
+
+        let bloques = vec![
+            (Some("../escape.ts".to_string()), "evil".to_string()),
+            (Some("big.ts".to_string()), "tiny".to_string()),
+        ];
+        assert_eq!(aplicar_bloques(root, &bloques, &backups(root)), 0);
+        assert_eq!(std::fs::read_to_string(root.join("big.ts")).unwrap().len(), 300);
+    }
+
+    #[test]
+    fn test_aplicar_bloques_rejects_broken_syntax() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let original = "export function total(a: number, b: number) {\n  return a + b;\n}\n";
+        std::fs::write(root.join("sum.ts"), original).unwrap();
+
+        let roto = "export function total(a: number, b: number) {\n  return a + b * (;\n}\n";
+        let bloques = vec![(Some("sum.ts".to_string()), roto.to_string())];
+        assert_eq!(aplicar_bloques(root, &bloques, &backups(root)), 0);
+        assert_eq!(std::fs::read_to_string(root.join("sum.ts")).unwrap(), original);
+        assert!(!root.join(".sentinel/backups").exists());
+    }
+
+    #[test]
+    fn test_previsualizar_bloques_leaves_file_untouched() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let original = "const a = 1;\nconst b = 2;\n";
+        std::fs::write(root.join("a.ts"), original).unwrap();
+
+        let bloques = vec![(Some("a.ts".to_string()), "const a = 1;\nconst b = 3;\n".to_string())];
+        let diffs = previsualizar_bloques(root, &bloques);
+
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].contains("-const b = 2;"));
+        assert!(diffs[0].contains("+const b = 3;"));
+        assert_eq!(std::fs::read(root.join("a.ts")).unwrap(), original.as_bytes());
+        assert!(!root.join(".sentinel").exists(), "dry-run must not create backups");
+    }
+
+    #[test]
+    fn test_diff_unificado_empty_when_identical() {
+        assert!(diff_unificado("a.ts", "x\n", "x\n").is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::agents::base::{Agent, AgentContext, Task, TaskType};
 use crate::agents::reviewer::ReviewerAgent;
 use crate::ui;
+use super::audit_progress::{self, AuditProgress};
 use colored::*;
+use dialoguer::Confirm;
 use serde::{Deserialize, Serialize};
 use std::io::{BufRead, Write};
 
@@ -9,16 +11,28 @@ use std::io::{BufRead, Write};
 pub struct AuditIssue {
     pub title: String,
     pub description: String,
-    pub severity: String,
+    pub severity: super::Severity,
     pub suggested_fix: String,
     #[serde(default)]
     pub file_path: String,
 }
 
+/// `(parent_dir, module_prefix)` of a file. `module_prefix` is the filename stem before the
+/// first dot: `users/user.service.ts` → (`users`, `user`).
+fn module_key(path: &std::path::Path) -> (std::path::PathBuf, String) {
+    let parent = path.parent().unwrap_or(path).to_path_buf();
+    let prefix = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.split('.').next())
+        .unwrap_or("")
+        .to_string();
+    (parent, prefix)
+}
+
 /// Groups files into batches for audit LLM calls.
 ///
-/// Groups by `(parent_dir, module_prefix)` to keep semantically related files together.
-/// `module_prefix` is the filename stem before the first dot: `user.service.ts` → `user`.
+/// Groups by `module_key` to keep semantically related files together.
 /// Splits groups exceeding `max_files_per_batch` or `max_lines_per_batch`.
 pub fn build_audit_batches(
     files: &[std::path::PathBuf],
@@ -27,21 +41,11 @@ pub fn build_audit_batches(
 ) -> Vec<Vec<std::path::PathBuf>> {
     use std::collections::HashMap;
 
-    fn module_prefix(path: &std::path::Path) -> String {
-        path.file_name()
-            .and_then(|n| n.to_str())
-            .and_then(|n| n.split('.').next())
-            .unwrap_or("")
-            .to_string()
-    }
-
     // Group by (parent_dir, module_prefix) — keeps user.service.ts + user.controller.ts together
     let mut groups: HashMap<(std::path::PathBuf, String), Vec<std::path::PathBuf>> =
         HashMap::new();
     for f in files {
-        let parent = f.parent().unwrap_or(f.as_path()).to_path_buf();
-        let prefix = module_prefix(f);
-        groups.entry((parent, prefix)).or_default().push(f.clone());
+        groups.entry(module_key(f)).or_default().push(f.clone());
     }
 
     // Split each group by file count and line count caps (sorted for deterministic output)
@@ -74,12 +78,284 @@ pub fn build_audit_batches(
     final_batches
 }
 
-pub fn handle_audit(
-    target: String,
-    no_fix: bool,
-    format: String,
-    max_files: usize,
+/// `--group-by`: cómo se agrupan los issues en la salida no interactiva.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditGroupBy {
+    /// Carpeta + prefijo de módulo, los mismos grupos que `build_audit_batches`
+    Module,
+    Severity,
+    File,
+}
+
+impl AuditGroupBy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "module" => Some(Self::Module),
+            "severity" => Some(Self::Severity),
+            "file" => Some(Self::File),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Module => "module",
+            Self::Severity => "severity",
+            Self::File => "file",
+        }
+    }
+}
+
+/// Issues agrupados según `by`, con claves relativas a `project_root`. Severidad de mayor a
+/// menor; módulos y archivos en orden alfabético. Dentro de cada grupo se mantiene el orden.
+pub fn group_issues<'a>(
+    issues: &'a [AuditIssue],
+    by: AuditGroupBy,
+    project_root: &std::path::Path,
+) -> Vec<(String, Vec<&'a AuditIssue>)> {
+    let rel = |p: &std::path::Path| {
+        p.strip_prefix(project_root).unwrap_or(p).to_string_lossy().replace('\\', "/")
+    };
+    let mut groups: std::collections::BTreeMap<(std::cmp::Reverse<Option<super::Severity>>, String), Vec<&AuditIssue>> =
+        std::collections::BTreeMap::new();
+    for issue in issues {
+        let path = std::path::Path::new(&issue.file_path);
+        let key = match by {
+            AuditGroupBy::Severity => (std::cmp::Reverse(Some(issue.severity)), issue.severity.to_string()),
+            AuditGroupBy::File => (std::cmp::Reverse(None), rel(path)),
+            AuditGroupBy::Module => {
+                let (parent, prefix) = module_key(path);
+                let dir = rel(&parent);
+                let name = if dir.is_empty() { prefix } else { format!("{}/{}", dir, prefix) };
+                (std::cmp::Reverse(None), name)
+            }
+        };
+        groups.entry(key).or_default().push(issue);
+    }
+    groups.into_iter().map(|((_, name), issues)| (name, issues)).collect()
+}
+
+/// Conserva los issues de severidad `min` o mayor; devuelve cuántos se descartaron.
+pub fn retain_min_severity(issues: &mut Vec<AuditIssue>, min: super::Severity) -> usize {
+    let before = issues.len();
+    issues.retain(|issue| issue.severity >= min);
+    before - issues.len()
+}
+
+/// Gasto de la auditoría en curso para la barra de progreso: lo acumulado en `stats` desde
+/// `start` (`total_cost_usd`, `total_tokens_used` al empezar), como `$0.0123 / 4512 tokens`.
+pub fn running_cost(stats: &crate::stats::SentinelStats, start: (f64, u64)) -> String {
+    let cost = (stats.total_cost_usd - start.0).max(0.0);
+    let tokens = stats.total_tokens_used.saturating_sub(start.1);
+    format!("${:.4} / {} tokens", cost, tokens)
+}
+
+/// Parsea la respuesta JSON de un batch y asocia cada issue a uno de sus archivos.
+/// Devuelve `None` si la respuesta no es un array de issues válido.
+pub fn parse_batch_issues(output: &str, batch_files: &[std::path::PathBuf]) -> Option<Vec<AuditIssue>> {
+    let json_str = crate::ai::utils::extraer_json(output);
+    let mut issues = serde_json::from_str::<Vec<AuditIssue>>(&json_str).ok()?;
+    for issue in &mut issues {
+        let matched_path = batch_files
+            .iter()
+            .find(|f| {
+                f.to_string_lossy().contains(&issue.file_path)
+                    || issue.file_path.contains(
+                        &f.file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                    )
+            })
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| {
+                batch_files
+                    .first()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            });
+        issue.file_path = matched_path;
+    }
+    Some(issues)
+}
+
+/// Ejecuta `run` sobre cada elemento con a lo sumo `concurrency` tareas simultáneas
+/// y devuelve los resultados en el orden de entrada, no en el de finalización.
+/// `on_complete` se llama al terminar cada tarea (progreso); si una tarea hace panic
+/// su posición se rellena con `on_lost()`.
+pub(crate) async fn run_bounded<T, R, F, Fut>(
+    items: Vec<T>,
     concurrency: usize,
+    run: F,
+    on_complete: impl Fn(),
+    on_lost: impl Fn() -> R,
+) -> Vec<R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> Fut,
+    Fut: std::future::Future<Output = R> + Send + 'static,
+{
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let mut set = tokio::task::JoinSet::new();
+    let total = items.len();
+
+    for (idx, item) in items.into_iter().enumerate() {
+        let semaphore = std::sync::Arc::clone(&semaphore);
+        let fut = run(item);
+        set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (idx, fut.await)
+        });
+    }
+
+    let mut slots: Vec<Option<R>> = (0..total).map(|_| None).collect();
+    while let Some(joined) = set.join_next().await {
+        if let Ok((idx, result)) = joined {
+            slots[idx] = Some(result);
+        }
+        on_complete();
+    }
+    slots.into_iter().map(|r| r.unwrap_or_else(&on_lost)).collect()
+}
+
+/// Tope de `--concurrency`.
+const MAX_CONCURRENCY: usize = 10;
+
+/// `--concurrency`: llamadas LLM simultáneas fijas o `auto` (ver `AdaptiveConcurrency`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Concurrency {
+    Fixed(usize),
+    Auto,
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self::Fixed(3)
+    }
+}
+
+impl Concurrency {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        if value.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Auto);
+        }
+        value
+            .parse::<usize>()
+            .map(|n| Self::Fixed(n.clamp(1, MAX_CONCURRENCY)))
+            .map_err(|_| format!("--concurrency debe ser un número (1-{}) o 'auto', no '{}'", MAX_CONCURRENCY, value))
+    }
+}
+
+/// Controlador de `--concurrency auto`: empieza en 2, sube 1 por cada ronda sin errores y
+/// se divide a la mitad ante un 429. El nivel que provocó el 429 menos uno pasa a ser el
+/// techo, así que converge al ritmo sostenible del proveedor en vez de oscilar.
+#[derive(Debug, Clone)]
+pub struct AdaptiveConcurrency {
+    current: usize,
+    ceiling: usize,
+}
+
+impl Default for AdaptiveConcurrency {
+    fn default() -> Self {
+        Self { current: 2, ceiling: MAX_CONCURRENCY }
+    }
+}
+
+impl AdaptiveConcurrency {
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn record_round(&mut self, rate_limited: bool) {
+        if rate_limited {
+            self.ceiling = self.current.saturating_sub(1).max(1);
+            self.current = (self.current / 2).max(1);
+        } else {
+            self.current = (self.current + 1).min(self.ceiling);
+        }
+    }
+}
+
+/// Veces que un batch vuelve a la cola tras un 429 antes de darlo por fallido.
+const MAX_RATE_LIMIT_REQUEUES: usize = 5;
+/// Pausa tras una ronda con 429, antes de seguir con menos concurrencia.
+const RATE_LIMIT_PAUSE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Como `run_bounded`, pero en rondas de `controller.current()` tareas. `run` devuelve el
+/// resultado y si fue un 429: esos elementos vuelven a la cola y la ronda cuenta como
+/// limitada para el controlador.
+pub(crate) async fn run_adaptive<T, R, F, Fut>(
+    items: Vec<T>,
+    controller: &mut AdaptiveConcurrency,
+    run: F,
+    on_complete: impl Fn(),
+    on_lost: impl Fn() -> (R, bool),
+) -> Vec<R>
+where
+    T: Clone + Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> Fut,
+    Fut: std::future::Future<Output = (R, bool)> + Send + 'static,
+{
+    let total = items.len();
+    let mut pending: std::collections::VecDeque<(usize, T)> = items.into_iter().enumerate().collect();
+    let mut requeues = vec![0usize; total];
+    let mut slots: Vec<Option<R>> = (0..total).map(|_| None).collect();
+
+    while !pending.is_empty() {
+        let n = controller.current().min(pending.len());
+        let round: Vec<(usize, T)> = pending.drain(..n).collect();
+        let inputs = round.iter().map(|(_, item)| item.clone()).collect();
+        let outcomes = run_bounded(inputs, n, &run, || {}, &on_lost).await;
+
+        let mut rate_limited = false;
+        for ((idx, item), (result, limited)) in round.into_iter().zip(outcomes) {
+            rate_limited |= limited;
+            if limited && requeues[idx] < MAX_RATE_LIMIT_REQUEUES {
+                requeues[idx] += 1;
+                pending.push_back((idx, item));
+            } else {
+                slots[idx] = Some(result);
+                on_complete();
+            }
+        }
+        controller.record_round(rate_limited);
+        if rate_limited && !pending.is_empty() {
+            tokio::time::sleep(RATE_LIMIT_PAUSE).await;
+        }
+    }
+    slots.into_iter().map(|r| r.unwrap_or_else(|| on_lost().0)).collect()
+}
+
+/// Argumentos de `pro audit` tal como llegan del CLI.
+#[derive(Debug, Clone, Default)]
+pub struct AuditOptions {
+    /// Archivo o carpeta a auditar (relativo a la raíz del proyecto)
+    pub target: String,
+    /// Solo reporta, sin el flujo interactivo de fixes
+    pub no_fix: bool,
+    /// text | json
+    pub format: String,
+    pub max_files: usize,
+    /// Llamadas LLM en paralelo
+    pub concurrency: Concurrency,
+    /// Muestra el diff de los fixes sin escribir archivos
+    pub dry_run: bool,
+    /// Severidad mínima que hace salir con código 1
+    pub fail_on: super::FailOn,
+    /// Líneas por archivo enviadas al modelo (`None`: archivo completo)
+    pub context_lines: Option<usize>,
+    /// Regex sobre título o descripción; los conteos y el código de salida solo cuentan lo que coincide
+    pub grep: Option<String>,
+    /// Agrupa la salida no interactiva (texto y JSON)
+    pub group_by: Option<AuditGroupBy>,
+    /// `--severity-min`: descarta los issues de severidad menor (`None` = todos); cuenta
+    /// también para `--fail-on`
+    pub severity_min: Option<super::Severity>,
+}
+
+pub fn handle_audit(
+    options: &AuditOptions,
     _quiet: bool,
     _verbose: bool,
     agent_context: &AgentContext,
@@ -87,13 +363,22 @@ pub fn handle_audit(
     index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
     rt: &tokio::runtime::Runtime,
 ) {
+    let AuditOptions { target, no_fix, format, max_files, concurrency, dry_run, fail_on, context_lines, .. } =
+        options.clone();
     let json_mode = format.to_lowercase() == "json";
     let is_tty = std::io::IsTerminal::is_terminal(&std::io::stdout());
     let non_interactive = no_fix || json_mode || !is_tty;
 
-    if output_mode == crate::commands::OutputMode::Verbose {
-        eprintln!("[DEBUG] Auditing {} with concurrency={}", target, concurrency);
-    }
+    log::debug!("Auditing {} with concurrency={:?}", target, concurrency);
+
+    let grep = match super::parse_grep(options.grep.as_deref()) {
+        Ok(re) => re,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            if let Some(h) = index_handle { let _ = h.join(); }
+            std::process::exit(2);
+        }
+    };
 
     let path = agent_context.project_root.join(&target);
     if !path.exists() {
@@ -124,6 +409,7 @@ pub fn handle_audit(
                 }
             }
         }
+        files_to_audit = super::skip_large_files(files_to_audit, &agent_context.config, &agent_context.project_root, &output_mode);
     }
 
     if files_to_audit.is_empty() {
@@ -169,18 +455,19 @@ pub fn handle_audit(
 
     let _total_batches = final_batches.len();
 
-    let concurrency = concurrency.clamp(1, 10);
-    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency));
-
     // Pre-build all batch data before entering the async context
+    #[derive(Clone)]
     struct BatchData {
         batch_idx: usize,
         batch_context: String,
         batch_rel_paths: Vec<String>,
         batch_files: Vec<std::path::PathBuf>,
         module_name: String,
+        hash: String,
     }
 
+    // Sin --context-lines se envía el archivo completo
+    let context_budget = context_lines.map(|n| n.clamp(1, super::sampling::MAX_CONTEXT_LINES));
     let mut batch_data_list: Vec<BatchData> = Vec::new();
     for (batch_idx, batch_files) in final_batches.iter().enumerate() {
         let mut batch_context = String::new();
@@ -189,7 +476,11 @@ pub fn handle_audit(
             let rel_path = file_path
                 .strip_prefix(&agent_context.project_root)
                 .unwrap_or(file_path);
-            let content = std::fs::read_to_string(file_path).unwrap_or_default();
+            let mut content = std::fs::read_to_string(file_path).unwrap_or_default();
+            if let Some(budget) = context_budget {
+                let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                content = super::sampling::sample_source(&content, ext, budget).0;
+            }
             batch_context.push_str(&format!(
                 "\n\n=== {} ===\n{}",
                 rel_path.display(),
@@ -205,6 +496,7 @@ pub fn handle_audit(
             .unwrap_or_else(|| "módulo".to_string());
         batch_data_list.push(BatchData {
             batch_idx,
+            hash: audit_progress::batch_hash(&batch_context),
             batch_context,
             batch_rel_paths,
             batch_files: batch_files.clone(),
@@ -212,87 +504,166 @@ pub fn handle_audit(
         });
     }
 
+    // Reanudar una auditoría interrumpida del mismo target (batches ya completados se saltan)
+    let hashes: Vec<String> = batch_data_list.iter().map(|bd| bd.hash.clone()).collect();
+    let mut progress = AuditProgress::new(&target);
+    if let Some(previous) = AuditProgress::load(&agent_context.project_root, &target) {
+        let done = previous.completed_count(&hashes);
+        if done > 0 {
+            let resume = if is_tty && !json_mode {
+                Confirm::new()
+                    .with_prompt(format!(
+                        "Se encontró una auditoría interrumpida de '{}' ({}/{} batches completados). ¿Reanudar?",
+                        target,
+                        done,
+                        hashes.len()
+                    ))
+                    .default(true)
+                    .interact()
+                    .unwrap_or(true)
+            } else {
+                eprintln!("   ♻️  Reanudando auditoría interrumpida ({}/{} batches completados).", done, hashes.len());
+                true
+            };
+            if resume {
+                progress = previous;
+            }
+        }
+    }
+    let resumed: std::collections::HashMap<usize, Vec<AuditIssue>> = hashes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, h)| progress.batches.get(h).map(|issues| (i, issues.clone())))
+        .collect();
+    let pending = progress.pending(&hashes);
+    batch_data_list.retain(|bd| pending.contains(&bd.batch_idx));
+    if let Err(e) = progress.save(&agent_context.project_root) {
+        eprintln!("   ⚠️  No se pudo guardar el progreso de la auditoría: {}", e);
+    }
+    let progress = std::sync::Arc::new(std::sync::Mutex::new(progress));
+
     if !json_mode && output_mode != crate::commands::OutputMode::Quiet {
-        println!(
-            "   Procesando {} batches ({} en paralelo)...",
-            batch_data_list.len(),
-            concurrency
-        );
+        let parallel = match concurrency {
+            Concurrency::Fixed(n) => format!("{} en paralelo", n),
+            Concurrency::Auto => "concurrencia automática".to_string(),
+        };
+        println!("   Procesando {} batches ({})...", batch_data_list.len(), parallel);
     }
 
-    // Parallel execution with JoinSet
-    let batch_results: Vec<Result<(usize, String, Vec<std::path::PathBuf>), String>> =
-        rt.block_on(async {
-            let mut set = tokio::task::JoinSet::new();
-
-            for bd in batch_data_list {
-                let permit = semaphore.clone().acquire_owned().await.unwrap();
-                let config = std::sync::Arc::clone(&agent_context.config);
-                let stats = std::sync::Arc::clone(&agent_context.stats);
-                let project_root = agent_context.project_root.clone();
-                let index_db = agent_context.index_db.clone();
-
-                set.spawn(async move {
-                    let _permit = permit;
-                    let ctx = AgentContext {
-                        config,
-                        stats,
-                        project_root,
-                        index_db,
-                    };
-                    let reviewer = ReviewerAgent::new();
-                    let task = Task {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        description: format!(
-                            "Realiza una auditoría técnica de MÚLTIPLES archivos del módulo '{}'.\n\
-                            ARCHIVOS INCLUIDOS: {}\n\
-                            OBJETIVO: Identificar problemas de calidad, seguridad o bugs CORREGIBLES.\n\
-                            REGLAS:\n\
-                            1. Analiza TODOS los archivos y genera un array JSON con los problemas.\n\
-                            2. Cada objeto DEBE tener: title, description, severity (High/Medium/Low), suggested_fix, file_path (nombre del archivo al que pertenece el issue).\n\
-                            3. Responde ÚNICAMENTE con el bloque ```json — sin texto introductorio.\n\
-                            FORMATO JSON REQUERIDO:\n\
-                            ```json\n\
-                            [\n\
-                              {{\"title\": \"...\", \"description\": \"...\", \"severity\": \"High|Medium|Low\", \"suggested_fix\": \"...\", \"file_path\": \"nombre-del-archivo.ts\"}}\n\
-                            ]\n\
-                            ```",
-                            bd.module_name,
-                            bd.batch_rel_paths.join(", ")
-                        ),
-                        task_type: TaskType::Analyze,
-                        file_path: bd.batch_files.first().cloned(),
-                        context: Some(bd.batch_context),
-                    };
-
-                    // Up to 3 attempts with 2s delay on failure
-                    let mut last_err = String::new();
-                    for attempt in 0..3usize {
-                        match reviewer.execute(&task, &ctx).await {
-                            Ok(res) => {
-                                return Ok((bd.batch_idx, res.output, bd.batch_files));
-                            }
-                            Err(e) => {
-                                last_err = e.to_string();
-                                if attempt < 2 {
-                                    tokio::time::sleep(
-                                        tokio::time::Duration::from_secs(2),
-                                    )
-                                    .await;
-                                }
-                            }
+    let pb_batches = if json_mode || output_mode == crate::commands::OutputMode::Quiet {
+        indicatif::ProgressBar::hidden()
+    } else {
+        let pb = indicatif::ProgressBar::new(batch_data_list.len() as u64);
+        pb.set_style(
+            indicatif::ProgressStyle::default_bar()
+                .template("   {bar:30.cyan/blue} {pos}/{len} batches {msg}")
+                .unwrap(),
+        );
+        pb
+    };
+    // Las stats ya traen lo de ejecuciones anteriores: la barra muestra solo lo de esta
+    let cost_start = {
+        let s = agent_context.stats.lock().unwrap();
+        (s.total_cost_usd, s.total_tokens_used)
+    };
+    let on_batch_done = || {
+        pb_batches.inc(1);
+        if !pb_batches.is_hidden() {
+            pb_batches.set_message(running_cost(&agent_context.stats.lock().unwrap(), cost_start));
+        }
+    };
+
+    // Hasta `concurrency` llamadas LLM a la vez; los resultados vuelven en orden de batch.
+    // Las stats se comparten vía Arc<Mutex<SentinelStats>>, así que las escrituras concurrentes son seguras.
+    // Cada batch devuelve también si terminó en 429 (solo se corta el reintento en modo auto).
+    type BatchResult = Result<(usize, String, Vec<std::path::PathBuf>), String>;
+    let adaptive = concurrency == Concurrency::Auto;
+    let mut auto_concurrency: Option<usize> = None;
+    let run_batch = |bd: BatchData| {
+        let ctx = AgentContext {
+            config: std::sync::Arc::clone(&agent_context.config),
+            stats: std::sync::Arc::clone(&agent_context.stats),
+            project_root: agent_context.project_root.clone(),
+            index_db: agent_context.index_db.clone(),
+        };
+        let progress = std::sync::Arc::clone(&progress);
+        async move {
+            let reviewer = ReviewerAgent::new();
+            let task = Task {
+                id: uuid::Uuid::new_v4().to_string(),
+                description: format!(
+                    "Realiza una auditoría técnica de MÚLTIPLES archivos del módulo '{}'.\n\
+                    ARCHIVOS INCLUIDOS: {}\n\
+                    OBJETIVO: Identificar problemas de calidad, seguridad o bugs CORREGIBLES.\n\
+                    REGLAS:\n\
+                    1. Analiza TODOS los archivos y genera un array JSON con los problemas.\n\
+                    2. Cada objeto DEBE tener: title, description, severity (High/Medium/Low), suggested_fix, file_path (nombre del archivo al que pertenece el issue).\n\
+                    3. Responde ÚNICAMENTE con el bloque ```json — sin texto introductorio.\n\
+                    FORMATO JSON REQUERIDO:\n\
+                    ```json\n\
+                    [\n\
+                      {{\"title\": \"...\", \"description\": \"...\", \"severity\": \"High|Medium|Low\", \"suggested_fix\": \"...\", \"file_path\": \"nombre-del-archivo.ts\"}}\n\
+                    ]\n\
+                    ```",
+                    bd.module_name,
+                    bd.batch_rel_paths.join(", ")
+                ),
+                task_type: TaskType::Analyze,
+                file_path: bd.batch_files.first().cloned(),
+                context: Some(bd.batch_context),
+            };
+
+            // Up to 3 attempts with 2s delay on failure
+            let mut last_err = String::new();
+            for attempt in 0..3usize {
+                match reviewer.execute(&task, &ctx).await {
+                    Ok(res) => {
+                        // Checkpoint: solo batches con respuesta válida cuentan como completados
+                        if let Some(issues) = parse_batch_issues(&res.output, &bd.batch_files) {
+                            let mut progress = progress.lock().unwrap();
+                            progress.record(&bd.hash, issues);
+                            let _ = progress.save(&ctx.project_root);
                         }
+                        return (Ok((bd.batch_idx, res.output, bd.batch_files)), false);
                     }
-                    Err(last_err)
-                });
-            }
-
-            let mut results = Vec::new();
-            while let Some(join_result) = set.join_next().await {
-                results.push(join_result.unwrap_or_else(|e| Err(e.to_string())));
+                    Err(e) => {
+                        if adaptive && crate::ai::providers::is_rate_limited(&e) {
+                            return (Err(e.to_string()), true);
+                        }
+                        last_err = e.to_string();
+                        if attempt < 2 {
+                            tokio::time::sleep(
+                                tokio::time::Duration::from_secs(2),
+                            )
+                            .await;
+                        }
+                    }
+                }
             }
+            (Err(last_err), false)
+        }
+    };
+    let lost = || -> (BatchResult, bool) { (Err("la tarea del batch terminó inesperadamente".to_string()), false) };
+    let batch_results: Vec<BatchResult> = match concurrency {
+        Concurrency::Fixed(n) => rt
+            .block_on(run_bounded(batch_data_list, n, run_batch, on_batch_done, lost))
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect(),
+        Concurrency::Auto => {
+            let mut controller = AdaptiveConcurrency::default();
+            let results = rt.block_on(run_adaptive(batch_data_list, &mut controller, run_batch, on_batch_done, lost));
+            auto_concurrency = Some(controller.current());
             results
-        });
+        }
+    };
+    pb_batches.finish_and_clear();
+    if let Some(n) = auto_concurrency
+        && !json_mode
+        && output_mode != crate::commands::OutputMode::Quiet
+    {
+        println!("   ⚙️  Concurrencia final (auto): {}", n.to_string().cyan());
+    }
 
     // Process results — same normalization logic as before
     let pb_final = if !json_mode {
@@ -301,46 +672,34 @@ pub fn handle_audit(
         indicatif::ProgressBar::hidden()
     };
 
-    for result in batch_results {
-        match result {
-            Ok((_batch_idx, output, batch_files)) => {
-                let json_str = crate::ai::utils::extraer_json(&output);
-                match serde_json::from_str::<Vec<AuditIssue>>(&json_str) {
-                    Ok(mut issues) => {
-                        for issue in &mut issues {
-                            let matched_path = batch_files
-                                .iter()
-                                .find(|f| {
-                                    f.to_string_lossy().contains(&issue.file_path)
-                                        || issue.file_path.contains(
-                                            &f.file_name()
-                                                .map(|n| n.to_string_lossy().to_string())
-                                                .unwrap_or_default(),
-                                        )
-                                })
-                                .map(|f| f.to_string_lossy().to_string())
-                                .unwrap_or_else(|| {
-                                    batch_files
-                                        .first()
-                                        .map(|f| f.to_string_lossy().to_string())
-                                        .unwrap_or_default()
-                                });
-                            issue.file_path = matched_path;
-                        }
-                        all_issues.extend(issues);
-                    }
-                    Err(_) => {
-                        parse_failures += 1;
-                    }
+    // Resultados en orden de batch: primero lo recuperado del checkpoint, luego lo nuevo
+    let mut pending_results = batch_results.into_iter();
+    for batch_idx in 0..hashes.len() {
+        if let Some(issues) = resumed.get(&batch_idx) {
+            all_issues.extend(issues.iter().cloned());
+            continue;
+        }
+        match pending_results.next() {
+            Some(Ok((_batch_idx, output, batch_files))) => {
+                match parse_batch_issues(&output, &batch_files) {
+                    Some(issues) => all_issues.extend(issues),
+                    None => parse_failures += 1,
                 }
             }
-            Err(_) => {
+            _ => {
                 parse_failures += 1;
             }
         }
     }
 
     pb_final.finish_and_clear();
+    // Todos los batches terminaron bien: el checkpoint ya no hace falta. Si alguno falló se
+    // conserva, para que la próxima ejecución reintente solo esos
+    if parse_failures == 0 {
+        AuditProgress::clear(&agent_context.project_root);
+    } else if output_mode != crate::commands::OutputMode::Quiet {
+        eprintln!("   ♻️  Progreso guardado: la próxima auditoría de '{}' reintentará solo los batches fallidos.", target);
+    }
 
     // Deduplicar: misma combinación (título normalizado, archivo) → conservar solo primero
     {
@@ -351,6 +710,27 @@ pub fn handle_audit(
         });
     }
 
+    let hidden_by_grep = grep.as_ref().map(|re| {
+        let before = all_issues.len();
+        all_issues.retain(|issue| super::grep_matches(re, &issue.title, &issue.description));
+        before - all_issues.len()
+    });
+    if let (Some(hidden), Some(pattern)) = (hidden_by_grep, options.grep.as_deref())
+        && hidden > 0
+        && output_mode != crate::commands::OutputMode::Quiet
+    {
+        let msg = format!("🔎 {} issue(s) ocultos por --grep '{}'.", hidden, pattern);
+        if json_mode { eprintln!("{}", msg) } else { println!("{}", msg) }
+    }
+    let hidden_by_severity = options.severity_min.map(|min| retain_min_severity(&mut all_issues, min));
+    if let (Some(hidden), Some(min)) = (hidden_by_severity, options.severity_min)
+        && hidden > 0
+        && output_mode != crate::commands::OutputMode::Quiet
+    {
+        let msg = format!("🔽 {} issue(s) por debajo de --severity-min {}.", hidden, min.to_string().to_lowercase());
+        if json_mode { eprintln!("{}", msg) } else { println!("{}", msg) }
+    }
+
     if all_issues.is_empty() {
         if parse_failures > 0 && parse_failures == files_to_audit.len() {
             if output_mode != crate::commands::OutputMode::Quiet {
@@ -385,9 +765,9 @@ pub fn handle_audit(
 
     // Modo no-interactivo: --no-fix o --format json
     if non_interactive {
-        let n_high = all_issues.iter().filter(|i| i.severity.to_lowercase() == "high").count();
-        let n_medium = all_issues.iter().filter(|i| i.severity.to_lowercase() == "medium").count();
-        let n_low = all_issues.iter().filter(|i| i.severity.to_lowercase() == "low").count();
+        let count = |severity: super::Severity| all_issues.iter().filter(|i| i.severity == severity).count();
+        let (n_high, n_medium, n_low) =
+            (count(super::Severity::High), count(super::Severity::Medium), count(super::Severity::Low));
 
         if json_mode {
             #[derive(serde::Serialize)]
@@ -397,15 +777,38 @@ pub fn handle_audit(
                 high: usize,
                 medium: usize,
                 low: usize,
-                issues: Vec<AuditIssue>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                hidden_by_grep: Option<usize>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                hidden_by_severity: Option<usize>,
+                /// Concurrencia en la que convergió `--concurrency auto`
+                #[serde(skip_serializing_if = "Option::is_none")]
+                auto_concurrency: Option<usize>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                group_by: Option<&'static str>,
+                /// Lista, o con `--group-by` un objeto `{grupo: [issues]}`
+                issues: serde_json::Value,
             }
+            let issues = match options.group_by {
+                Some(by) => serde_json::Value::Object(
+                    group_issues(&all_issues, by, &agent_context.project_root)
+                        .into_iter()
+                        .map(|(name, issues)| (name, serde_json::to_value(issues).unwrap_or_default()))
+                        .collect(),
+                ),
+                None => serde_json::to_value(&all_issues).unwrap_or_default(),
+            };
             let out = AuditJsonOutput {
                 files_audited: files_to_audit.len(),
                 total_issues: all_issues.len(),
                 high: n_high,
                 medium: n_medium,
                 low: n_low,
-                issues: all_issues.clone(),
+                hidden_by_grep,
+                hidden_by_severity,
+                auto_concurrency,
+                group_by: options.group_by.map(AuditGroupBy::as_str),
+                issues,
             };
             println!("{}", serde_json::to_string_pretty(&out).unwrap_or_default());
         } else {
@@ -414,22 +817,32 @@ pub fn handle_audit(
                     "\n📑 Auditoría: {} issues — 🔴 {} High  🟡 {} Medium  🟢 {} Low",
                     all_issues.len(), n_high, n_medium, n_low
                 );
-                for issue in &all_issues {
-                    let rel_file = std::path::Path::new(&issue.file_path)
-                        .strip_prefix(&agent_context.project_root)
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_else(|_| issue.file_path.clone());
-                    println!(
-                        "   [{}] {} — {} ({})",
-                        issue.severity.to_uppercase(),
-                        issue.title.bold(),
-                        issue.description,
-                        rel_file.cyan()
-                    );
+                let groups = match options.group_by {
+                    Some(by) => group_issues(&all_issues, by, &agent_context.project_root),
+                    None => vec![(String::new(), all_issues.iter().collect())],
+                };
+                for (name, issues) in &groups {
+                    if !name.is_empty() {
+                        println!("\n   {} {}", name.cyan().bold(), format!("({})", issues.len()).dimmed());
+                    }
+                    for issue in issues {
+                        let rel_file = std::path::Path::new(&issue.file_path)
+                            .strip_prefix(&agent_context.project_root)
+                            .map(|p| p.display().to_string())
+                            .unwrap_or_else(|_| issue.file_path.clone());
+                        println!(
+                            "   [{}] {} — {} ({})",
+                            issue.severity.to_string().to_uppercase(),
+                            issue.title.bold(),
+                            issue.description,
+                            rel_file.cyan()
+                        );
+                    }
                 }
             }
         }
-        if n_high > 0 {
+        // High → error, Medium → warning, Low → info
+        if fail_on.should_fail(n_high, n_medium, n_low) {
             if let Some(h) = index_handle { let _ = h.join(); }
             std::process::exit(1);
         }
@@ -477,7 +890,7 @@ pub fn handle_audit(
                 "Issue {}/{} · {} · {}",
                 idx + 1,
                 display_issues.len(),
-                issue.severity.to_uppercase().bold(),
+                issue.severity.to_string().to_uppercase().bold(),
                 rel_file.cyan()
             );
             println!("{}", issue.title.bold());
@@ -519,6 +932,22 @@ pub fn handle_audit(
         return;
     }
 
+    if dry_run {
+        // Vista previa: solo los fixes que traen bloques de código con ruta pueden compararse
+        for &idx in &selected_indices {
+            let issue = &all_issues[idx];
+            println!("\n🔎 {}", issue.title.bold());
+            let bloques = crate::ai::utils::extraer_todos_bloques(&issue.suggested_fix);
+            if bloques.iter().all(|(path, _)| path.is_none()) {
+                println!("   {}", "El fix sugerido no incluye un archivo completo; no hay diff que mostrar.".dimmed());
+                continue;
+            }
+            super::apply::previsualizar_bloques(&agent_context.project_root, &bloques);
+        }
+        if let Some(h) = index_handle { let _ = h.join(); }
+        return;
+    }
+
     if output_mode != crate::commands::OutputMode::Quiet {
         println!("\n🚀 Aplicando {} correcciones...", selected_indices.len());
     }
@@ -538,14 +967,20 @@ pub fn handle_audit(
             );
         }
 
-        // Aplicar el fix sugerido (pseudo-implementación)
-        if let Ok(content) = std::fs::read_to_string(file_path) {
-            // En una implementación real, aquí iría lógica para parsear y aplicar fixes.
-            // Por ahora simplemente log.
-            let _ = content.len(); // suppres unused warning
+        // Solo los fixes con archivo completo se escriben (validados por aplicar_bloques)
+        let bloques = crate::ai::utils::extraer_todos_bloques(&issue.suggested_fix);
+        if bloques.iter().all(|(path, _)| path.is_none()) {
             if output_mode != crate::commands::OutputMode::Quiet {
-                println!("   ✅ Fix aplicado");
+                println!("   {}", "El fix sugerido no incluye un archivo completo; aplícalo manualmente.".dimmed());
             }
+            continue;
+        }
+        let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+        let saved = super::apply::aplicar_bloques(&agent_context.project_root, &bloques, &backups);
+        if saved > 0 {
+            let mut s = agent_context.stats.lock().unwrap();
+            s.sugerencias_aplicadas += 1;
+            s.guardar(&agent_context.project_root);
         }
     }
 
@@ -559,6 +994,89 @@ pub fn handle_audit(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_run_bounded_keeps_batch_order_and_cap() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let completed = AtomicUsize::new(0);
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let results = rt.block_on(run_bounded(
+            (0..6usize).collect(),
+            3,
+            |batch| {
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    // Los primeros batches tardan más: terminan fuera de orden
+                    tokio::time::sleep(std::time::Duration::from_millis(60 - batch as u64 * 10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    format!("batch-{}", batch)
+                }
+            },
+            || {
+                completed.fetch_add(1, Ordering::SeqCst);
+            },
+            || "lost".to_string(),
+        ));
+
+        let expected: Vec<String> = (0..6).map(|i| format!("batch-{}", i)).collect();
+        assert_eq!(results, expected);
+        assert_eq!(completed.load(Ordering::SeqCst), 6);
+        assert!(peak.load(Ordering::SeqCst) <= 3, "peak concurrency {}", peak.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_adaptive_concurrency_settles_below_provider_limit() {
+        // Proveedor simulado: responde 429 en cuanto hay más de 4 peticiones simultáneas
+        let mut controller = AdaptiveConcurrency::default();
+        assert_eq!(controller.current(), 2);
+        let mut history = Vec::new();
+        for _ in 0..30 {
+            let level = controller.current();
+            controller.record_round(level > 4);
+            history.push(controller.current());
+        }
+        assert!(history.contains(&5), "must probe above the limit at least once: {:?}", history);
+        assert!(history[10..].iter().all(|&n| n == 4), "must settle at 4: {:?}", history);
+
+        assert_eq!(Concurrency::parse("auto"), Ok(Concurrency::Auto));
+        assert_eq!(Concurrency::parse("50"), Ok(Concurrency::Fixed(MAX_CONCURRENCY)));
+        assert!(Concurrency::parse("many").is_err());
+    }
+
+    #[test]
+    fn test_run_adaptive_requeues_rate_limited_items() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let mut controller = AdaptiveConcurrency::default();
+        // El primer intento del elemento 1 recibe un 429; al reintentarlo responde
+        let results = rt.block_on(run_adaptive(
+            (0..4usize).collect(),
+            &mut controller,
+            |item| {
+                let attempts = Arc::clone(&attempts);
+                async move {
+                    let n = attempts.fetch_add(1, Ordering::SeqCst);
+                    let limited = item == 1 && n < 2;
+                    (format!("item-{}", item), limited)
+                }
+            },
+            || {},
+            || ("lost".to_string(), false),
+        ));
+        assert_eq!(results, vec!["item-0", "item-1", "item-2", "item-3"]);
+        assert_eq!(attempts.load(Ordering::SeqCst), 5);
+        assert_eq!(controller.current(), 1, "the 429 round halves and caps the level");
+    }
+
     fn write_file(dir: &tempfile::TempDir, name: &str) -> std::path::PathBuf {
         let path = dir.path().join(name);
         std::fs::write(&path, "x\n").unwrap();
@@ -626,7 +1144,7 @@ mod tests {
             AuditIssue {
                 title: title.to_string(),
                 description: String::new(),
-                severity: "high".to_string(),
+                severity: super::super::Severity::High,
                 suggested_fix: String::new(),
                 file_path: file_path.to_string(),
             }
@@ -649,6 +1167,77 @@ mod tests {
         assert_eq!(issues[2].title, "Import no usado");
     }
 
+    #[test]
+    fn test_severity_min_keeps_medium_and_high() {
+        use crate::commands::pro::Severity::{High, Low, Medium};
+        let issue = |title: &str, severity| AuditIssue {
+            title: title.to_string(),
+            description: String::new(),
+            severity,
+            suggested_fix: String::new(),
+            file_path: "/repo/src/a.ts".to_string(),
+        };
+        let mut issues = vec![
+            issue("Import no usado", Low),
+            issue("SQL sin parametrizar", High),
+            issue("Nombre poco claro", Low),
+            issue("Falta validación", Medium),
+        ];
+
+        assert_eq!(retain_min_severity(&mut issues.clone(), Low), 0);
+        assert_eq!(retain_min_severity(&mut issues, Medium), 2);
+        let titles: Vec<&str> = issues.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, vec!["SQL sin parametrizar", "Falta validación"]);
+        assert_eq!(retain_min_severity(&mut issues, High), 1);
+        assert_eq!(issues[0].severity, High);
+    }
+
+    #[test]
+    fn test_group_issues_by_module_severity_and_file() {
+        let root = std::path::Path::new("/repo");
+        let issue = |title: &str, severity: crate::commands::pro::Severity, file: &str| AuditIssue {
+            title: title.to_string(),
+            description: String::new(),
+            severity,
+            suggested_fix: String::new(),
+            file_path: root.join(file).to_string_lossy().into_owned(),
+        };
+        use crate::commands::pro::Severity::{High, Low};
+        let issues = vec![
+            issue("SQL sin parametrizar", High, "src/users/user.repository.ts"),
+            issue("Token sin expirar", Low, "src/auth/auth.service.ts"),
+            issue("Validación ausente", Low, "src/users/user.controller.ts"),
+            issue("Clave hardcodeada", High, "src/auth/auth.service.ts"),
+        ];
+
+        let by_module = group_issues(&issues, AuditGroupBy::Module, root);
+        let titles = |group: &[&AuditIssue]| group.iter().map(|i| i.title.clone()).collect::<Vec<_>>();
+        assert_eq!(by_module.len(), 2);
+        assert_eq!(by_module[0].0, "src/auth/auth");
+        assert_eq!(titles(&by_module[0].1), vec!["Token sin expirar", "Clave hardcodeada"]);
+        assert_eq!(by_module[1].0, "src/users/user");
+        assert_eq!(titles(&by_module[1].1), vec!["SQL sin parametrizar", "Validación ausente"]);
+
+        let by_severity: Vec<String> = group_issues(&issues, AuditGroupBy::Severity, root).into_iter().map(|g| g.0).collect();
+        assert_eq!(by_severity, vec!["High", "Low"]);
+        let by_file = group_issues(&issues, AuditGroupBy::File, root);
+        assert_eq!(by_file.len(), 3);
+        assert_eq!(by_file[0].0, "src/auth/auth.service.ts");
+        assert_eq!(AuditGroupBy::parse("Module"), Some(AuditGroupBy::Module));
+    }
+
+    #[test]
+    fn test_running_cost_counts_only_this_run() {
+        let mut stats = crate::stats::SentinelStats::default();
+        stats.registrar_uso("claude", 10_000, 1.5);
+        let start = (stats.total_cost_usd, stats.total_tokens_used);
+        assert_eq!(running_cost(&stats, start), "$0.0000 / 0 tokens");
+
+        stats.registrar_uso("claude", 4512, 0.01234);
+        stats.registrar_uso("gpt-4o", 300, 0.0009);
+        assert_eq!(running_cost(&stats, start), "$0.0132 / 4812 tokens");
+    }
+
     #[test]
     fn test_non_interactive_logic() {
         let no_fix = false;
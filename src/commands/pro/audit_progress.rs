@@ -0,0 +1,115 @@
+//! Checkpoint de `pro audit`: guarda los issues de cada batch terminado en
+//! `.sentinel/audit_progress.json` para poder reanudar tras un Ctrl-C o un crash.
+//!
+//! Los batches se identifican por el hash de su contexto (rutas + contenido), así
+//! que un archivo modificado entre ejecuciones invalida solo su batch.
+
+use super::audit::AuditIssue;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const PROGRESS_FILE: &str = ".sentinel/audit_progress.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditProgress {
+    pub target: String,
+    /// Hash del batch → issues ya normalizados
+    pub batches: BTreeMap<String, Vec<AuditIssue>>,
+}
+
+pub fn progress_path(project_root: &Path) -> PathBuf {
+    project_root.join(PROGRESS_FILE)
+}
+
+pub fn batch_hash(batch_context: &str) -> String {
+    format!("{:x}", Sha256::digest(batch_context.as_bytes()))
+}
+
+impl AuditProgress {
+    pub fn new(target: &str) -> Self {
+        Self { target: target.to_string(), batches: BTreeMap::new() }
+    }
+
+    /// Progreso previo para el mismo `target`, si existe y es legible.
+    pub fn load(project_root: &Path, target: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(progress_path(project_root)).ok()?;
+        let progress: Self = serde_json::from_str(&content).ok()?;
+        (progress.target == target && !progress.batches.is_empty()).then_some(progress)
+    }
+
+    pub fn save(&self, project_root: &Path) -> anyhow::Result<()> {
+        let path = progress_path(project_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Escritura atómica: un crash a mitad no deja un JSON truncado
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(self)?)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    pub fn clear(project_root: &Path) {
+        let _ = std::fs::remove_file(progress_path(project_root));
+    }
+
+    pub fn record(&mut self, hash: &str, issues: Vec<AuditIssue>) {
+        self.batches.insert(hash.to_string(), issues);
+    }
+
+    /// Cuántos de los batches actuales ya están completos.
+    pub fn completed_count(&self, hashes: &[String]) -> usize {
+        hashes.iter().filter(|h| self.batches.contains_key(*h)).count()
+    }
+
+    /// Índices de los batches que faltan por procesar.
+    pub fn pending(&self, hashes: &[String]) -> Vec<usize> {
+        hashes
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| !self.batches.contains_key(*h))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(title: &str) -> AuditIssue {
+        AuditIssue {
+            title: title.to_string(),
+            description: String::new(),
+            severity: super::super::Severity::High,
+            suggested_fix: String::new(),
+            file_path: "src/a.ts".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resume_only_processes_remaining_batches() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hashes: Vec<String> = ["b0", "b1", "b2", "b3"].iter().map(|c| batch_hash(c)).collect();
+
+        // Primera ejecución: se interrumpe tras completar los batches 0 y 2
+        let mut progress = AuditProgress::new("src");
+        progress.record(&hashes[0], vec![issue("SQL injection")]);
+        progress.save(tmp.path()).unwrap();
+        progress.record(&hashes[2], vec![]);
+        progress.save(tmp.path()).unwrap();
+
+        // Reanudación
+        let resumed = AuditProgress::load(tmp.path(), "src").expect("progress must be found");
+        assert_eq!(resumed.completed_count(&hashes), 2);
+        assert_eq!(resumed.pending(&hashes), vec![1, 3]);
+        assert_eq!(resumed.batches[&hashes[0]][0].title, "SQL injection");
+
+        // Otro target no reanuda; al terminar se borra
+        assert!(AuditProgress::load(tmp.path(), "lib").is_none());
+        AuditProgress::clear(tmp.path());
+        assert!(AuditProgress::load(tmp.path(), "src").is_none());
+    }
+}
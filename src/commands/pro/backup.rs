@@ -0,0 +1,194 @@
+//! Copias de seguridad de los archivos que sobrescriben fix/audit/split/review/migrate/doc y
+//! `pro restore` para volver a la última.
+//!
+//! Las copias van a `backup_dir` (por defecto `.sentinel/backups`) con la misma ruta
+//! relativa que el original y un sello de tiempo, en vez de dejar un `.bak` al lado:
+//! `src/a.ts` → `.sentinel/backups/src/a.ts.20261016-101500.123456.bak`.
+//!
+//! Con `create_backups = false` (o `--no-backup`) no se copia nada: el historial queda a
+//! cargo de git.
+
+use colored::*;
+use std::path::{Path, PathBuf};
+
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S%.6f";
+
+pub struct Backups {
+    project_root: PathBuf,
+    dir: PathBuf,
+    /// `create_backups`: si es `false`, `make_backup` no escribe nada
+    enabled: bool,
+}
+
+impl Backups {
+    /// `dir` relativo se resuelve desde `project_root`.
+    pub fn new(project_root: &Path, dir: &Path) -> Self {
+        Self { project_root: project_root.to_path_buf(), dir: project_root.join(dir), enabled: true }
+    }
+
+    /// Carpeta de `backup_dir` en la config (o la de por defecto si está vacía); sin copias
+    /// si `create_backups` es `false`.
+    pub fn for_config(project_root: &Path, config: &crate::config::SentinelConfig) -> Self {
+        let dir = if config.backup_dir.trim().is_empty() {
+            crate::config::DEFAULT_BACKUP_DIR
+        } else {
+            config.backup_dir.trim()
+        };
+        Self { enabled: config.create_backups, ..Self::new(project_root, Path::new(dir)) }
+    }
+
+    /// Carpeta de las copias de `path` y prefijo de sus nombres (`a.ts.`).
+    fn slot(&self, path: &Path) -> (PathBuf, String) {
+        let rel = path.strip_prefix(&self.project_root).unwrap_or(path);
+        let rel = if rel.is_absolute() { Path::new(rel.file_name().unwrap_or_default()) } else { rel };
+        let parent = rel.parent().map(|p| self.dir.join(p)).unwrap_or_else(|| self.dir.clone());
+        let name = rel.file_name().unwrap_or_default().to_string_lossy();
+        (parent, format!("{}.", name))
+    }
+
+    /// Copia `path` antes de sobrescribirlo, salvo que `create_backups` esté desactivado
+    /// (entonces devuelve `None` sin tocar el disco).
+    pub fn backup_before_write(&self, path: &Path) -> std::io::Result<Option<PathBuf>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        self.make_backup(path).map(Some)
+    }
+
+    /// Copia `path` a una entrada nueva de `backup_dir` y devuelve su ruta.
+    pub fn make_backup(&self, path: &Path) -> std::io::Result<PathBuf> {
+        let (dir, prefix) = self.slot(path);
+        std::fs::create_dir_all(&dir)?;
+        let backup = loop {
+            let stamp = chrono::Local::now().format(STAMP_FORMAT);
+            let candidate = dir.join(format!("{}{}.bak", prefix, stamp));
+            if !candidate.exists() {
+                break candidate;
+            }
+        };
+        std::fs::copy(path, &backup)?;
+        Ok(backup)
+    }
+
+    /// Copia más reciente de `path`, si hay alguna.
+    pub fn latest(&self, path: &Path) -> Option<PathBuf> {
+        let (dir, prefix) = self.slot(path);
+        std::fs::read_dir(&dir)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let stamp = name.strip_prefix(&prefix)?.strip_suffix(".bak")?;
+                // `a.ts.` también es prefijo de las copias de `a.ts.orig`: solo vale un sello exacto
+                chrono::NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).ok()?;
+                Some(name)
+            })
+            .max()
+            .map(|name| dir.join(name))
+    }
+
+    /// Devuelve `path` al contenido de su última copia y la retira, de modo que otra
+    /// restauración vuelve a la anterior. Devuelve la copia usada.
+    pub fn restore(&self, path: &Path) -> std::io::Result<PathBuf> {
+        let backup = self.latest(path).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, format!("no hay copias en {}", self.dir.display()))
+        })?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&backup, path)?;
+        std::fs::remove_file(&backup)?;
+        Ok(backup)
+    }
+}
+
+pub fn handle_restore(file: &str, agent_context: &crate::agents::base::AgentContext, output_mode: crate::commands::OutputMode) {
+    let root = &agent_context.project_root;
+    let target = match crate::files::secure_join(root, Path::new(file)) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    let backups = Backups::for_config(root, &agent_context.config);
+    match backups.restore(&target) {
+        Ok(backup) => {
+            if output_mode != crate::commands::OutputMode::Quiet {
+                let rel = backup.strip_prefix(root).unwrap_or(&backup);
+                println!("{} {} restaurado desde {}", "✅".green(), file.cyan(), rel.display().to_string().dimmed());
+            }
+        }
+        Err(e) => {
+            eprintln!("{} No se pudo restaurar '{}': {}", "❌".red(), file, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_backup_writes_under_configured_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src/users")).unwrap();
+        std::fs::write(root.join("src/users/user.service.ts"), "v1").unwrap();
+        let config = crate::config::SentinelConfig { backup_dir: "tmp/bk".to_string(), ..Default::default() };
+        let backups = Backups::for_config(root, &config);
+
+        let backup = backups.make_backup(&root.join("src/users/user.service.ts")).unwrap();
+        assert!(backup.starts_with(root.join("tmp/bk/src/users")), "{}", backup.display());
+        let name = backup.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("user.service.ts.") && name.ends_with(".bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "v1");
+        assert!(!root.join("src/users/user.service.ts.bak").exists(), "nothing next to the original");
+
+        // Sin backup_dir se usa el de por defecto
+        let default = Backups::for_config(root, &crate::config::SentinelConfig::default());
+        let backup = default.make_backup(&root.join("src/users/user.service.ts")).unwrap();
+        assert!(backup.starts_with(root.join(crate::config::DEFAULT_BACKUP_DIR)));
+    }
+
+    #[test]
+    fn test_default_config_keeps_backups() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join("a.ts"), "v1").unwrap();
+
+        // Sin .sentinelrc.toml los comandos usan SentinelConfig::default()
+        let backups = Backups::for_config(root, &crate::config::SentinelConfig::default());
+        let backup = backups.backup_before_write(&root.join("a.ts")).unwrap().expect("backup");
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), "v1");
+
+        let config = crate::config::SentinelConfig { create_backups: false, ..Default::default() };
+        let disabled = Backups::for_config(root, &config);
+        assert!(disabled.backup_before_write(&root.join("a.ts")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_restore_round_trip_walks_back_through_backups() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file = root.join("a.ts");
+        let backups = Backups::new(root, Path::new(".sentinel/backups"));
+
+        std::fs::write(&file, "v1").unwrap();
+        backups.make_backup(&file).unwrap();
+        std::fs::write(&file, "v2").unwrap();
+        backups.make_backup(&file).unwrap();
+        std::fs::write(&file, "v3").unwrap();
+        // Copias de otro archivo con el mismo prefijo no cuentan
+        std::fs::write(root.join("a.ts.orig"), "other").unwrap();
+        backups.make_backup(&root.join("a.ts.orig")).unwrap();
+
+        backups.restore(&file).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2");
+        backups.restore(&file).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1");
+        assert!(backups.restore(&file).is_err());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1");
+    }
+}
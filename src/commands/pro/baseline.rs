@@ -0,0 +1,165 @@
+//! Baseline de violaciones para `pro check` (patrón "ratchet"): `--write-baseline`
+//! congela los hallazgos actuales y `--baseline` solo reporta los nuevos.
+//!
+//! Las entradas se comparan por `(archivo, regla, hash del mensaje)` sin la línea,
+//! para tolerar desplazamientos al editar el archivo. Si una clave aparece N veces
+//! en la baseline, solo la ocurrencia N+1 en adelante se considera nueva.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub const BASELINE_FILE: &str = ".sentinel/baseline.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub file: String,
+    pub rule: String,
+    /// Solo informativa: no participa en la comparación
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message_hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+pub fn message_hash(message: &str) -> String {
+    let digest = Sha256::digest(message.trim().as_bytes());
+    digest.iter().take(8).map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn baseline_path(project_root: &Path) -> PathBuf {
+    project_root.join(BASELINE_FILE)
+}
+
+impl Baseline {
+    /// `(file, rule, line, message)` de cada violación actual.
+    pub fn from_violations<'a>(
+        violations: impl IntoIterator<Item = (&'a str, &'a str, Option<usize>, &'a str)>,
+    ) -> Self {
+        let entries = violations
+            .into_iter()
+            .map(|(file, rule, line, message)| BaselineEntry {
+                file: file.to_string(),
+                rule: rule.to_string(),
+                line,
+                message_hash: message_hash(message),
+            })
+            .collect();
+        Self { version: 1, entries }
+    }
+
+    /// Baseline tras revisar solo `checked` (rutas relativas): las entradas de esos archivos
+    /// se reemplazan por las de `fresh` y las del resto del proyecto se conservan.
+    pub fn replace_files(mut self, checked: &HashSet<String>, fresh: Baseline) -> Self {
+        self.entries.retain(|e| !checked.contains(&e.file));
+        self.entries.extend(fresh.entries);
+        self.version = fresh.version;
+        self
+    }
+
+    pub fn load(project_root: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(baseline_path(project_root))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, project_root: &Path) -> anyhow::Result<()> {
+        let path = baseline_path(project_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Conserva solo los elementos que no estaban en la baseline.
+    /// `key` devuelve `(file, rule, message)` de cada elemento.
+    pub fn retain_new<T>(&self, items: &mut Vec<T>, key: impl Fn(&T) -> (&str, &str, &str)) {
+        let mut remaining: HashMap<(String, String, String), usize> = HashMap::new();
+        for e in &self.entries {
+            *remaining
+                .entry((e.file.clone(), e.rule.clone(), e.message_hash.clone()))
+                .or_default() += 1;
+        }
+        items.retain(|item| {
+            let (file, rule, message) = key(item);
+            let lookup = (file.to_string(), rule.to_string(), message_hash(message));
+            match remaining.get_mut(&lookup) {
+                Some(n) if *n > 0 => {
+                    *n -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Finding = (&'static str, &'static str, Option<usize>, &'static str);
+
+    fn retain(baseline: &Baseline, findings: &[Finding]) -> Vec<Finding> {
+        let mut items = findings.to_vec();
+        baseline.retain_new(&mut items, |f| (f.0, f.1, f.3));
+        items
+    }
+
+    #[test]
+    fn test_recheck_after_writing_baseline_has_no_new_findings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let current: Vec<Finding> = vec![
+            ("src/a.ts", "DEAD_CODE", Some(10), "'foo' se declara pero no parece usarse"),
+            ("src/a.ts", "UNUSED_IMPORT", Some(1), "El import 'x' no parece usarse"),
+            ("src/b.ts", "DEAD_CODE", Some(3), "'bar' se declara pero no parece usarse"),
+        ];
+        Baseline::from_violations(current.iter().copied()).save(tmp.path()).unwrap();
+
+        let loaded = Baseline::load(tmp.path()).unwrap();
+        // Mismas violaciones con líneas desplazadas: siguen cubiertas
+        let shifted: Vec<Finding> = current.iter().map(|f| (f.0, f.1, f.2.map(|l| l + 5), f.3)).collect();
+        assert!(retain(&loaded, &shifted).is_empty());
+    }
+
+    #[test]
+    fn test_new_violation_surfaces_exactly_once() {
+        let baseline = Baseline::from_violations([
+            ("src/a.ts", "DEAD_CODE", Some(10), "'foo' se declara pero no parece usarse"),
+        ]);
+        let now: Vec<Finding> = vec![
+            ("src/a.ts", "DEAD_CODE", Some(12), "'foo' se declara pero no parece usarse"),
+            ("src/a.ts", "DEAD_CODE", Some(30), "'nuevo' se declara pero no parece usarse"),
+        ];
+        let new = retain(&baseline, &now);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].3, "'nuevo' se declara pero no parece usarse");
+
+        // Una segunda ocurrencia idéntica excede el conteo de la baseline
+        let dup: Vec<Finding> = vec![now[0], now[0]];
+        assert_eq!(retain(&baseline, &dup).len(), 1);
+    }
+
+    #[test]
+    fn test_partial_write_replaces_only_checked_files() {
+        let existing = Baseline::from_violations([
+            ("src/a.ts", "DEAD_CODE", Some(10), "'foo' se declara pero no parece usarse"),
+            ("src/b.ts", "DEAD_CODE", Some(3), "'bar' se declara pero no parece usarse"),
+        ]);
+        // `pro check src/a.ts --write-baseline`: 'foo' se arregló y apareció 'baz'
+        let fresh = Baseline::from_violations([
+            ("src/a.ts", "UNUSED_IMPORT", Some(1), "El import 'baz' no parece usarse"),
+        ]);
+        let checked: HashSet<String> = ["src/a.ts".to_string()].into_iter().collect();
+        let merged = existing.replace_files(&checked, fresh);
+
+        let keys: Vec<(&str, &str)> = merged.entries.iter().map(|e| (e.file.as_str(), e.rule.as_str())).collect();
+        assert_eq!(keys, vec![("src/b.ts", "DEAD_CODE"), ("src/a.ts", "UNUSED_IMPORT")]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::commands::ignore::load_ignore_entries;
+use crate::commands::output::{outln, OutputSink};
 use crate::rules::RuleLevel;
 use colored::*;
 use serde::Serialize;
@@ -14,6 +15,48 @@ struct JsonIssue {
     line: Option<usize>,
 }
 
+fn json_issue(v: &FileViolation) -> JsonIssue {
+    JsonIssue {
+        file: v.file_path.clone(),
+        rule: v.rule_name.clone(),
+        severity: match v.level {
+            RuleLevel::Error => "error",
+            RuleLevel::Warning => "warning",
+            RuleLevel::Info => "info",
+        }
+        .to_string(),
+        message: v.message.clone(),
+        line: v.line,
+    }
+}
+
+/// Entrada de `explanations` en la salida JSON de `--explain`.
+#[derive(Serialize)]
+struct RuleExplanation {
+    rule: String,
+    explanation: String,
+    help_uri: String,
+}
+
+/// Explicaciones de las reglas distintas de `rules` (en orden de aparición); las reglas
+/// fuera del catálogo se omiten.
+fn rule_explanations<'a>(rules: impl Iterator<Item = &'a str>) -> Vec<RuleExplanation> {
+    let mut out: Vec<RuleExplanation> = Vec::new();
+    for rule in rules {
+        if out.iter().any(|e| e.rule == rule) {
+            continue;
+        }
+        if let Some(explanation) = crate::rules::catalog::explanation(rule) {
+            out.push(RuleExplanation {
+                rule: rule.to_string(),
+                explanation,
+                help_uri: crate::rules::catalog::help_uri(rule),
+            });
+        }
+    }
+    out
+}
+
 struct FileViolation {
     file_path: String,
     rule_name: String,
@@ -24,32 +67,225 @@ struct FileViolation {
     value: Option<usize>,
 }
 
+/// Argumentos de `pro check` tal como llegan del CLI.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Archivo o carpeta a revisar (relativo a la raíz del proyecto)
+    pub target: String,
+    /// text | json | jsonl | sarif | github
+    pub format: String,
+    /// Informa cuántas violaciones fueron silenciadas por comentarios inline
+    pub show_suppressed: bool,
+    /// Emite anotaciones de GitHub Actions si se detecta `GITHUB_ACTIONS=true`
+    pub annotate: bool,
+    /// Añade un score de riesgo de bugs por archivo (predictor ML)
+    pub risk: bool,
+    /// Filtra las violaciones presentes en `.sentinel/baseline.json`
+    pub baseline: bool,
+    /// Congela las violaciones actuales en `.sentinel/baseline.json`
+    pub write_baseline: bool,
+    /// Re-ejecuta el check ante cada cambio; los errores no terminan el proceso
+    pub watch: bool,
+    /// Severidad mínima que hace salir con código 1
+    pub fail_on: super::FailOn,
+    /// Regex sobre regla o mensaje; los conteos y el código de salida solo cuentan lo que coincide
+    pub grep: Option<String>,
+    /// `--only`: reglas cuyos analizadores se ejecutan (vacío = todas)
+    pub only: Vec<String>,
+    /// `--explain`: explicación del catálogo para cada regla distinta del resultado
+    pub explain: bool,
+    /// `--quiet-success`: la salida solo se muestra si hay alguna violación
+    pub quiet_success: bool,
+}
+
+/// `--format github` explícito, o `--annotate` con el formato por defecto dentro de GitHub Actions.
+pub fn is_github_mode(format: &str, annotate: bool) -> bool {
+    let lower = format.to_lowercase();
+    lower == "github"
+        || (annotate
+            && lower == "text"
+            && std::env::var("GITHUB_ACTIONS").map(|v| v == "true").unwrap_or(false))
+}
+
+/// La violación coincide con una entrada de `.sentinel/ignore.json` (regla, archivo y símbolo).
+pub(super) fn is_ignored(
+    entries: &[crate::commands::ignore::IgnoreEntry],
+    rule_name: &str,
+    file_path: &str,
+    symbol: Option<&str>,
+) -> bool {
+    entries.iter().any(|e| {
+        e.rule == rule_name
+            && (file_path.contains(&e.file) || e.file.contains(file_path))
+            && e.symbol
+                .as_ref()
+                .map(|s| {
+                    let norm_entry = crate::commands::ignore::normalize_symbol(s);
+                    let norm_violation = symbol
+                        .map(crate::commands::ignore::normalize_symbol)
+                        .unwrap_or_default();
+                    norm_entry == norm_violation
+                })
+                .unwrap_or(true)
+    })
+}
+
+/// Filtro por umbrales de `[rule_config]`: solo se conservan las violaciones que superan
+/// los umbrales configurados o pertenecen a categorías habilitadas.
+pub(super) fn passes_rule_config(rule_cfg: &crate::config::RuleConfig, rule_name: &str, value: Option<usize>) -> bool {
+    match rule_name {
+        "HIGH_COMPLEXITY" => value.map(|n| n > rule_cfg.complexity_threshold).unwrap_or(true),
+        "FUNCTION_TOO_LONG" => value.map(|n| n > rule_cfg.function_length_threshold).unwrap_or(true),
+        "DEAD_CODE" | "DEAD_CODE_GLOBAL" => rule_cfg.dead_code_enabled,
+        "UNUSED_IMPORT" => rule_cfg.unused_imports_enabled,
+        _ => true,
+    }
+}
+
+/// Conserva solo las violaciones que pasan `--grep`; devuelve cuántas se ocultaron.
+fn apply_grep(violations: &mut Vec<FileViolation>, re: &regex::Regex) -> usize {
+    let before = violations.len();
+    violations.retain(|v| super::grep_matches(re, &v.rule_name, &v.message));
+    before - violations.len()
+}
+
+/// (errores, warnings, infos) de las violaciones a reportar.
+fn count_by_level(violations: &[FileViolation]) -> (usize, usize, usize) {
+    violations.iter().fold((0, 0, 0), |(e, w, i), v| match v.level {
+        RuleLevel::Error => (e + 1, w, i),
+        RuleLevel::Warning => (e, w + 1, i),
+        RuleLevel::Info => (e, w, i + 1),
+    })
+}
+
+/// Rutas de `files` relativas a `project_root`, como las de `FileViolation::file_path`.
+fn relative_paths(files: &[std::path::PathBuf], project_root: &std::path::Path) -> std::collections::HashSet<String> {
+    files
+        .iter()
+        .map(|f| f.strip_prefix(project_root).unwrap_or(f).display().to_string())
+        .collect()
+}
+
+/// Un `CIRCULAR_IMPORT` (info) por ciclo que toca algún archivo revisado, anclado en el
+/// primer archivo revisado del ciclo.
+fn circular_import_violations(
+    cycles: &[Vec<String>],
+    files_to_check: &[std::path::PathBuf],
+    agent_context: &crate::agents::base::AgentContext,
+) -> Vec<FileViolation> {
+    let checked = relative_paths(files_to_check, &agent_context.project_root);
+    cycles
+        .iter()
+        .filter_map(|cycle| {
+            let anchor = cycle.iter().find(|f| checked.contains(f.as_str()))?;
+            Some(FileViolation {
+                file_path: anchor.clone(),
+                rule_name: "CIRCULAR_IMPORT".to_string(),
+                symbol: None,
+                message: format!("Import circular: {} → {}", cycle.join(" → "), cycle[0]),
+                level: RuleLevel::Info,
+                line: None,
+                value: Some(cycle.len()),
+            })
+        })
+        .collect()
+}
+
+/// `--format jsonl`: las violaciones de cada archivo se filtran y se emiten (un objeto JSON
+/// por línea) en cuanto se revisa el archivo, sin acumularlas. Aplica los mismos filtros y en
+/// el mismo orden que los demás formatos: ignore.json, `[rule_config]`, baseline y `--grep`.
+struct JsonlStream<'a> {
+    ignore_entries: Vec<crate::commands::ignore::IgnoreEntry>,
+    rule_cfg: &'a crate::config::RuleConfig,
+    baseline: Option<super::baseline::Baseline>,
+    grep: Option<&'a regex::Regex>,
+    /// Métricas por archivo para `quality_history` (antes de baseline y `--grep`)
+    metrics: Vec<crate::index::quality_history::FileMetrics>,
+    /// (errores, warnings, infos) emitidos
+    counts: (usize, usize, usize),
+    hidden_by_baseline: usize,
+    hidden_by_grep: usize,
+}
+
+impl JsonlStream<'_> {
+    /// `file`: `(ruta, complejidad)` si `batch` son las violaciones de un archivo revisado.
+    fn emit(&mut self, mut batch: Vec<FileViolation>, file: Option<(&str, f64)>, out: &mut OutputSink) {
+        batch.retain(|v| {
+            !is_ignored(&self.ignore_entries, &v.rule_name, &v.file_path, v.symbol.as_deref())
+                && passes_rule_config(self.rule_cfg, &v.rule_name, v.value)
+        });
+        if let Some((path, complexity)) = file {
+            self.metrics.push(file_metrics(path, complexity, &batch));
+        }
+        if let Some(ref baseline) = self.baseline {
+            let before = batch.len();
+            baseline.retain_new(&mut batch, |v| (v.file_path.as_str(), v.rule_name.as_str(), v.message.as_str()));
+            self.hidden_by_baseline += before - batch.len();
+        }
+        if let Some(re) = self.grep {
+            self.hidden_by_grep += apply_grep(&mut batch, re);
+        }
+        if !batch.is_empty() {
+            out.release();
+        }
+        let (e, w, i) = count_by_level(&batch);
+        self.counts = (self.counts.0 + e, self.counts.1 + w, self.counts.2 + i);
+        for v in &batch {
+            outln!(out, "{}", serde_json::to_string(&json_issue(v)).unwrap_or_default());
+        }
+    }
+}
+
 pub fn handle_check(
-    target: String,
-    format: String,
+    options: &CheckOptions,
     _quiet: bool,
     _verbose: bool,
     agent_context: &crate::agents::base::AgentContext,
     output_mode: crate::commands::OutputMode,
     index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+    out: &mut OutputSink,
 ) {
-    let (json_mode, sarif_mode) = super::format_to_mode(&format);
+    let target = &options.target;
+    let (json_mode, sarif_mode) = super::format_to_mode(&options.format);
+    let github_mode = is_github_mode(&options.format, options.annotate);
+    let jsonl_mode = options.format.eq_ignore_ascii_case("jsonl");
+    let text_mode = !json_mode && !sarif_mode && !github_mode && !jsonl_mode;
+
+    let grep = match super::parse_grep(options.grep.as_deref()) {
+        Ok(re) => re,
+        Err(e) => {
+            if json_mode || jsonl_mode {
+                outln!(out, "{}", serde_json::json!({ "error": e.to_string() }));
+            } else {
+                eprintln!("{} {}", "❌".red(), e);
+            }
+            if let Some(h) = index_handle { let _ = h.join(); }
+            std::process::exit(2);
+        }
+    };
 
-    let path = agent_context.project_root.join(&target);
+    let path = agent_context.project_root.join(target);
 
     if !path.exists() {
-        if json_mode {
-            println!("{{\"error\":\"El destino '{}' no existe\"}}",  target);
+        if json_mode || jsonl_mode {
+            outln!(out, "{{\"error\":\"El destino '{}' no existe\"}}",  target);
         } else if sarif_mode {
             let empty = super::render_sarif(&[]);
-            println!("{}", empty);
+            outln!(out, "{}", empty);
+        } else if github_mode {
+            outln!(out, "::error title=sentinel::El destino '{}' no existe", target);
         } else {
-            println!("{} El destino '{}' no existe en el proyecto.", "❌".red(), target);
+            outln!(out, "{} El destino '{}' no existe en el proyecto.", "❌".red(), target);
         }
         if let Some(h) = index_handle { let _ = h.join(); }
         std::process::exit(2);
     }
 
+    // --quiet-success: todo lo que sigue se retiene hasta saber si hay violaciones
+    if options.quiet_success {
+        out.hold();
+    }
+
     let mut files_to_check = Vec::new();
     if path.is_file() {
         files_to_check.push(path.clone());
@@ -69,6 +305,7 @@ pub fn handle_check(
                 }
             }
         }
+        files_to_check = super::skip_large_files(files_to_check, &agent_context.config, &agent_context.project_root, &output_mode);
     }
 
     if files_to_check.is_empty() {
@@ -78,19 +315,24 @@ pub fn handle_check(
                 .as_ref()
                 .map(|db| db.is_populated())
                 .unwrap_or(false);
-            println!(
+            outln!(out,
                 "{{\"checked\":0,\"errors\":0,\"warnings\":0,\"infos\":0,\"index_populated\":{},\"issues\":[]}}",
                 index_populated
             );
+        } else if jsonl_mode {
+            outln!(out, "{}", serde_json::json!({ "summary": true, "checked": 0, "errors": 0, "warnings": 0, "infos": 0 }));
         } else if sarif_mode {
-            println!("{}", super::render_sarif(&[]));
+            outln!(out, "{}", super::render_sarif(&[]));
+        } else if github_mode {
+            // Sin archivos no hay anotaciones que emitir
         } else {
-            println!("{} No se encontraron archivos para revisar en '{}'.", "⚠️".yellow(), target);
+            outln!(out, "{} No se encontraron archivos para revisar en '{}'.", "⚠️".yellow(), target);
         }
+        out.discard();
         return;
     }
 
-    if !json_mode && !sarif_mode && output_mode != crate::commands::OutputMode::Quiet {
+    if text_mode && output_mode != crate::commands::OutputMode::Quiet {
         // TS-first note: shown when no TS/JS files in target
         let has_ts_js = files_to_check.iter().any(|f| {
             matches!(
@@ -99,28 +341,33 @@ pub fn handle_check(
             )
         });
         if !has_ts_js {
-            println!(
+            outln!(out,
                 "ℹ️  Análisis estático optimizado para TypeScript/JavaScript."
             );
-            println!(
+            outln!(out,
                 "   Soporte para Go, Python, Rust, Java y otros lenguajes: próxima versión.\n"
             );
         }
-        println!("\n{} Capa 1 — Análisis Estático en {} archivo(s)...",
+        outln!(out, "\n{} Capa 1 — Análisis Estático en {} archivo(s)...",
             "⚡".cyan(), files_to_check.len());
     }
 
-    if output_mode == crate::commands::OutputMode::Verbose && !json_mode && !sarif_mode {
-        println!("\n📂 Archivos procesados:");
+    if output_mode == crate::commands::OutputMode::Verbose && text_mode {
+        outln!(out, "\n📂 Archivos procesados:");
         for file_path in &files_to_check {
             let rel = file_path
                 .strip_prefix(&agent_context.project_root)
                 .unwrap_or(file_path);
-            println!("   {}", rel.display());
+            outln!(out, "   {}", rel.display());
         }
     }
 
-    let mut rule_engine = crate::rules::engine::RuleEngine::new();
+    let mut rule_engine = crate::rules::engine::RuleEngine::new()
+        .with_sentinelignore(&agent_context.project_root)
+        .with_entry_patterns(&agent_context.project_root, &agent_context.config)
+        .with_secret_allowlist(&agent_context.project_root, &agent_context.config.rule_config.secret_allowlist)
+        .with_thresholds(&agent_context.config.rule_config)
+        .with_only(&options.only);
     if let Some(ref db) = agent_context.index_db {
         rule_engine = rule_engine.with_index_db(std::sync::Arc::clone(db));
     }
@@ -128,109 +375,267 @@ pub fn handle_check(
     if rules_path.exists() {
         let _ = rule_engine.load_from_yaml(&rules_path);
     }
+    if let Err(e) = rule_engine.check_only() {
+        out.release();
+        if json_mode || jsonl_mode {
+            outln!(out, "{}", serde_json::json!({ "error": e.to_string() }));
+        } else {
+            eprintln!("{} {}", "❌".red(), e);
+        }
+        if let Some(h) = index_handle { let _ = h.join(); }
+        std::process::exit(2);
+    }
+
+    // `--risk` y los ciclos de imports se calculan aparte: solo si `--only` los incluye
+    let risk = options.risk && rule_engine.selected("HIGH_RISK");
+    let detect_cycles = rule_engine.selected("CIRCULAR_IMPORT");
+
+    // Con --write-baseline no se reporta nada: se recorre el camino normal
+    let mut stream = (jsonl_mode && !options.write_baseline).then(|| {
+        if risk {
+            eprintln!("{}", "ℹ️  --risk no se calcula con --format jsonl.".dimmed());
+        }
+        let baseline = options.baseline.then(|| super::baseline::Baseline::load(&agent_context.project_root));
+        if let Some(Err(ref e)) = baseline {
+            eprintln!(
+                "⚠️  No se pudo leer {} ({}); se reportan todas las violaciones.",
+                super::baseline::BASELINE_FILE,
+                e
+            );
+        }
+        JsonlStream {
+            ignore_entries: load_ignore_entries(&agent_context.project_root),
+            rule_cfg: &agent_context.config.rule_config,
+            baseline: baseline.and_then(Result::ok),
+            grep: grep.as_ref(),
+            metrics: Vec::new(),
+            counts: (0, 0, 0),
+            hidden_by_baseline: 0,
+            hidden_by_grep: 0,
+        }
+    });
 
     let mut violations: Vec<FileViolation> = Vec::new();
+    let mut n_suppressed = 0usize;
+    // Archivo → suma de complejidad de sus funciones marcadas (antes de umbrales)
+    let mut complexity_by_file: Vec<(String, f64)> = Vec::new();
+    // (ruta relativa, contenido) para `--risk`
+    let mut risk_inputs: Vec<(String, String)> = Vec::new();
 
     for file_path in &files_to_check {
+        log::debug!("Checking {}", file_path.display());
         let content = std::fs::read_to_string(file_path).unwrap_or_default();
-        let file_violations = rule_engine.validate_file(file_path, &content);
+        let (file_violations, suppressed) = rule_engine.validate_file_with_suppressed(file_path, &content);
+        n_suppressed += suppressed;
 
         let rel = file_path
             .strip_prefix(&agent_context.project_root)
             .unwrap_or(file_path);
         let rel_str = rel.display().to_string();
 
-        for v in file_violations {
-            violations.push(FileViolation {
-                file_path: rel_str.clone(),
-                rule_name: v.rule_name,
-                symbol: v.symbol,
-                message: v.message,
-                level: v.level,
-                line: v.line,
-                value: v.value,
-            });
+        let complexity: usize = file_violations
+            .iter()
+            .filter(|v| v.rule_name == "HIGH_COMPLEXITY")
+            .filter_map(|v| v.value)
+            .sum();
+        let batch = file_violations.into_iter().map(|v| FileViolation {
+            file_path: rel_str.clone(),
+            rule_name: v.rule_name,
+            symbol: v.symbol,
+            message: v.message,
+            level: v.level,
+            line: v.line,
+            value: v.value,
+        });
+        if let Some(ref mut stream) = stream {
+            stream.emit(batch.collect(), Some((&rel_str, complexity as f64)), out);
+            continue;
+        }
+        complexity_by_file.push((rel_str.clone(), complexity as f64));
+        if risk {
+            risk_inputs.push((rel_str.clone(), content.clone()));
+        }
+        violations.extend(batch);
+    }
+
+    let cycles = agent_context
+        .index_db
+        .as_ref()
+        .filter(|db| detect_cycles && db.is_populated())
+        .map(|db| circular_import_violations(&db.find_import_cycles(), &files_to_check, agent_context))
+        .unwrap_or_default();
+
+    if let Some(mut stream) = stream {
+        stream.emit(cycles, None, out);
+        // En --watch cada guardado dispararía una ejecución: el historial solo registra revisiones puntuales
+        if let Some(ref db) = agent_context.index_db
+            && !options.watch
+        {
+            let scope = crate::index::quality_history::run_scope(&options.target, &agent_context.config.rule_config);
+            let _ = crate::index::quality_history::QualityHistory::new(db).record_run(&stream.metrics, &scope);
+        }
+        let (n_errors, n_warnings, n_infos) = stream.counts;
+        if options.baseline && output_mode != crate::commands::OutputMode::Quiet {
+            eprintln!("📌 {} violación(es) preexistente(s) ocultas por la baseline.", stream.hidden_by_baseline);
         }
+        let mut summary = serde_json::json!({
+            "summary": true,
+            "checked": files_to_check.len(),
+            "errors": n_errors,
+            "warnings": n_warnings,
+            "infos": n_infos,
+        });
+        if options.show_suppressed {
+            summary["suppressed"] = n_suppressed.into();
+        }
+        if stream.grep.is_some() {
+            summary["hidden_by_grep"] = stream.hidden_by_grep.into();
+        }
+        outln!(out, "{}", summary);
+        out.discard();
+        if options.fail_on.should_fail(n_errors, n_warnings, n_infos) && !options.watch {
+            if let Some(h) = index_handle { let _ = h.join(); }
+            std::process::exit(1);
+        }
+        return;
     }
+    violations.extend(cycles);
 
     // Apply ignore list: remove suppressed findings
     let ignore_entries = load_ignore_entries(&agent_context.project_root);
     if !ignore_entries.is_empty() {
-        violations.retain(|v| {
-            !ignore_entries.iter().any(|e| {
-                e.rule == v.rule_name
-                    && (v.file_path.contains(&e.file) || e.file.contains(&v.file_path))
-                    && e.symbol
-                        .as_ref()
-                        .map(|s| {
-                            let norm_entry = crate::commands::ignore::normalize_symbol(s);
-                            let norm_violation = v.symbol.as_deref()
-                                .map(|vs| crate::commands::ignore::normalize_symbol(vs))
-                                .unwrap_or_default();
-                            norm_entry == norm_violation
-                        })
-                        .unwrap_or(true)
-                })
-            });
+        violations.retain(|v| !is_ignored(&ignore_entries, &v.rule_name, &v.file_path, v.symbol.as_deref()));
     }
 
-    // Filter by rule config thresholds — mirrors filter semantics: only keep violations
-    // that exceed configured thresholds or belong to enabled rule categories.
     let rule_cfg = &agent_context.config.rule_config;
-    violations.retain(|v| match v.rule_name.as_str() {
-        "HIGH_COMPLEXITY" => v.value.map(|n| n > rule_cfg.complexity_threshold).unwrap_or(true),
-        "FUNCTION_TOO_LONG" => v.value.map(|n| n > rule_cfg.function_length_threshold).unwrap_or(true),
-        "DEAD_CODE" | "DEAD_CODE_GLOBAL" => rule_cfg.dead_code_enabled,
-        "UNUSED_IMPORT" => rule_cfg.unused_imports_enabled,
-        _ => true,
-    });
+    violations.retain(|v| passes_rule_config(rule_cfg, &v.rule_name, v.value));
+
+    let mut risk_scores: Vec<(String, f32)> = Vec::new();
+    if risk {
+        let threshold = rule_cfg.risk_threshold;
+        if let Some((predictor, embed)) = load_risk_model(agent_context, text_mode) {
+            let (scores, high_risk) = score_file_risks(&risk_inputs, &violations, &predictor, &embed, threshold);
+            risk_scores = scores;
+            violations.extend(high_risk);
+            // Mantener las violaciones agrupadas por archivo en el orden de revisión
+            let order: std::collections::HashMap<&str, usize> = risk_inputs
+                .iter()
+                .enumerate()
+                .map(|(i, (f, _))| (f.as_str(), i))
+                .collect();
+            violations.sort_by_key(|v| order.get(v.file_path.as_str()).copied().unwrap_or(usize::MAX));
+        }
+    }
+
+    if let Some(ref db) = agent_context.index_db
+        && !options.watch
+    {
+        let scope = crate::index::quality_history::run_scope(&options.target, &agent_context.config.rule_config);
+        record_quality_run(db, &complexity_by_file, &violations, &scope);
+    }
+
+    let notice = |out: &mut OutputSink, msg: String| {
+        if text_mode {
+            outln!(out, "{}", msg);
+        } else {
+            eprintln!("{}", msg);
+        }
+    };
+    if options.write_baseline {
+        let fresh = super::baseline::Baseline::from_violations(
+            violations
+                .iter()
+                .map(|v| (v.file_path.as_str(), v.rule_name.as_str(), v.line, v.message.as_str())),
+        );
+        // Con un destino parcial solo se reemplazan las entradas de los archivos revisados
+        let baseline = match super::baseline::Baseline::load(&agent_context.project_root) {
+            Ok(existing) => {
+                existing.replace_files(&relative_paths(&files_to_check, &agent_context.project_root), fresh)
+            }
+            Err(_) => fresh,
+        };
+        if let Err(e) = baseline.save(&agent_context.project_root) {
+            eprintln!("{} No se pudo guardar la baseline: {}", "❌".red(), e);
+            if let Some(h) = index_handle { let _ = h.join(); }
+            std::process::exit(2);
+        }
+        notice(out, format!(
+            "📌 Baseline guardada con {} violación(es) en {}.",
+            baseline.entries.len(),
+            super::baseline::BASELINE_FILE
+        ));
+        if let Some(h) = index_handle { let _ = h.join(); }
+        return;
+    }
+    if options.baseline {
+        match super::baseline::Baseline::load(&agent_context.project_root) {
+            Ok(baseline) => {
+                let before = violations.len();
+                baseline.retain_new(&mut violations, |v| {
+                    (v.file_path.as_str(), v.rule_name.as_str(), v.message.as_str())
+                });
+                if output_mode != crate::commands::OutputMode::Quiet {
+                    notice(out, format!(
+                        "📌 {} violación(es) preexistente(s) ocultas por la baseline.",
+                        before - violations.len()
+                    ));
+                }
+            }
+            Err(e) => notice(out, format!(
+                "⚠️  No se pudo leer {} ({}); se reportan todas las violaciones.",
+                super::baseline::BASELINE_FILE,
+                e
+            )),
+        }
+    }
+
+    let hidden_by_grep = grep.as_ref().map(|re| apply_grep(&mut violations, re));
 
     let mut json_issues: Vec<JsonIssue> = Vec::new();
     let mut sarif_issues: Vec<SarifIssue> = Vec::new();
-    let mut n_errors = 0usize;
-    let mut n_warnings = 0usize;
-    let mut n_infos = 0usize;
+    // Contenido por archivo para los fingerprints de SARIF
+    let mut sources: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let (n_errors, n_warnings, n_infos) = count_by_level(&violations);
+    if n_errors + n_warnings + n_infos > 0 {
+        out.release();
+    }
 
     // Group by file for display
     let mut current_file = String::new();
     for v in &violations {
-        if !json_mode && !sarif_mode && v.file_path != current_file {
+        if text_mode && v.file_path != current_file {
             current_file = v.file_path.clone();
-            println!("\n📄 {}", current_file.bold().cyan());
+            outln!(out, "\n📄 {}", current_file.bold().cyan());
         }
 
-        let (sev_str, icon) = match v.level {
-            RuleLevel::Error   => { n_errors   += 1; ("error",   "❌ ERROR") }
-            RuleLevel::Warning => { n_warnings += 1; ("warning", "⚠️  WARN ") }
-            RuleLevel::Info    => { n_infos    += 1; ("info",    "ℹ️  INFO ") }
+        let icon = match v.level {
+            RuleLevel::Error   => "❌ ERROR",
+            RuleLevel::Warning => "⚠️  WARN ",
+            RuleLevel::Info    => "ℹ️  INFO ",
         };
 
         if json_mode {
-            json_issues.push(JsonIssue {
-                file: v.file_path.clone(),
-                rule: v.rule_name.clone(),
-                severity: sev_str.to_string(),
-                message: v.message.clone(),
-                line: v.line,
-            });
+            json_issues.push(json_issue(v));
         }
-        if sarif_mode {
-            let sev = match v.level {
-                RuleLevel::Error   => "error",
-                RuleLevel::Warning => "warning",
-                RuleLevel::Info    => "note",
-            };
+        if sarif_mode || github_mode {
+            let snippet = v.line.filter(|_| sarif_mode).and_then(|line| {
+                let content = sources.entry(v.file_path.clone()).or_insert_with(|| {
+                    std::fs::read_to_string(agent_context.project_root.join(&v.file_path)).unwrap_or_default()
+                });
+                super::render::code_snippet(content, line)
+            });
             sarif_issues.push(SarifIssue {
                 file: v.file_path.clone(),
                 rule: v.rule_name.clone(),
-                severity: sev.to_string(),
+                severity: v.level.sarif_level().to_string(),
                 message: v.message.clone(),
                 line: v.line,
+                snippet,
             });
         }
-        if !json_mode && !sarif_mode {
+        if text_mode {
             let line_info = v.line.map(|l| format!(":{}", l)).unwrap_or_default();
-            println!("   {} [{}{}]: {}", icon.color(match v.level {
+            outln!(out, "   {} [{}{}]: {}", icon.color(match v.level {
                 RuleLevel::Error   => "red",
                 RuleLevel::Warning => "yellow",
                 RuleLevel::Info    => "blue",
@@ -243,7 +648,7 @@ pub fn handle_check(
                 .to_string();
             let hint_file = if rel_file.is_empty() { v.file_path.as_str() } else { rel_file.as_str() };
             if let Some(ref sym) = v.symbol {
-                println!(
+                outln!(out,
                     "      {} sentinel ignore {} {} {}",
                     "👉".dimmed(),
                     v.rule_name.dimmed(),
@@ -251,7 +656,7 @@ pub fn handle_check(
                     sym.dimmed()
                 );
             } else {
-                println!(
+                outln!(out,
                     "      {} sentinel ignore {} {}",
                     "👉".dimmed(),
                     v.rule_name.dimmed(),
@@ -261,8 +666,40 @@ pub fn handle_check(
         }
     }
 
+    if text_mode && !risk_scores.is_empty() {
+        outln!(out, "\n🎯 Riesgo de bugs por archivo:");
+        let mut sorted: Vec<&(String, f32)> = risk_scores.iter().collect();
+        sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        for (file, score) in sorted {
+            let indicator = if *score >= rule_cfg.risk_threshold {
+                "●".red()
+            } else if *score >= rule_cfg.risk_threshold / 2.0 {
+                "●".yellow()
+            } else {
+                "●".green()
+            };
+            outln!(out, "   {} {:.2}  {}", indicator, score, file);
+        }
+    }
+
+    if text_mode && options.explain {
+        let explanations = rule_explanations(violations.iter().map(|v| v.rule_name.as_str()));
+        if !explanations.is_empty() {
+            outln!(out, "\n📘 {}", "Por qué importan estas reglas:".bold());
+        }
+        for e in &explanations {
+            outln!(out, "\n   {}", e.rule.yellow().bold());
+            outln!(out, "   {}", e.explanation);
+            outln!(out, "   {}", e.help_uri.dimmed());
+        }
+    }
+
     if sarif_mode {
-        println!("{}", super::render_sarif(&sarif_issues));
+        outln!(out, "{}", super::render_sarif(&sarif_issues));
+    } else if github_mode {
+        for issue in &sarif_issues {
+            outln!(out, "{}", super::render_github_annotation(issue));
+        }
     } else if json_mode {
         #[derive(serde::Serialize)]
         struct JsonOutput {
@@ -271,44 +708,409 @@ pub fn handle_check(
             warnings: usize,
             infos: usize,
             index_populated: bool,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            suppressed: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            hidden_by_grep: Option<usize>,
             issues: Vec<JsonIssue>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            explanations: Option<Vec<RuleExplanation>>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            files: Option<Vec<JsonFileRisk>>,
+        }
+        #[derive(serde::Serialize)]
+        struct JsonFileRisk {
+            file: String,
+            risk_score: f32,
         }
         let index_populated = agent_context
             .index_db
             .as_ref()
             .map(|db| db.is_populated())
             .unwrap_or(false);
-        let out = JsonOutput {
+        let doc = JsonOutput {
             checked: files_to_check.len(),
             errors: n_errors,
             warnings: n_warnings,
             infos: n_infos,
             index_populated,
+            suppressed: options.show_suppressed.then_some(n_suppressed),
+            hidden_by_grep,
+            explanations: options
+                .explain
+                .then(|| rule_explanations(json_issues.iter().map(|i| i.rule.as_str()))),
             issues: json_issues,
+            files: (!risk_scores.is_empty()).then(|| {
+                risk_scores
+                    .iter()
+                    .map(|(file, score)| JsonFileRisk { file: file.clone(), risk_score: *score })
+                    .collect()
+            }),
         };
-        println!("{}", serde_json::to_string_pretty(&out).unwrap_or_default());
+        outln!(out, "{}", serde_json::to_string_pretty(&doc).unwrap_or_default());
     } else if output_mode != crate::commands::OutputMode::Quiet {
         if n_errors == 0 && n_warnings == 0 && n_infos == 0 {
-            println!("\n✅ Sin problemas detectados en {} archivo(s).", files_to_check.len());
+            outln!(out, "\n✅ Sin problemas detectados en {} archivo(s).", files_to_check.len());
         } else {
-            println!("\n🚩 {} error(s)  ⚠️  {} warning(s)  ℹ️  {} info(s)",
+            outln!(out, "\n🚩 {} error(s)  ⚠️  {} warning(s)  ℹ️  {} info(s)",
                 n_errors.to_string().red().bold(),
                 n_warnings.to_string().yellow(),
                 n_infos.to_string().blue());
         }
+        if options.show_suppressed {
+            outln!(out, "🔇 {} violación(es) silenciada(s) por comentarios inline.", n_suppressed);
+        }
     }
+    if let (Some(hidden), Some(pattern)) = (hidden_by_grep, options.grep.as_deref())
+        && hidden > 0
+        && output_mode != crate::commands::OutputMode::Quiet
+    {
+        notice(out, format!("🔎 {} violación(es) ocultas por --grep '{}'.", hidden, pattern));
+    }
+
+    // Sin violaciones: lo retenido por --quiet-success no se muestra
+    out.discard();
 
-    // Exit 1 si hay errores → CI falla el build
-    if n_errors > 0 {
+    // Exit 1 según --fail-on → CI falla el build (también con --format json)
+    if options.fail_on.should_fail(n_errors, n_warnings, n_infos) && !options.watch {
         if let Some(h) = index_handle { let _ = h.join(); }
         std::process::exit(1);
     }
 }
 
+/// Violaciones de `content` como si estuviera en `virtual_path` (relativa a la raíz): aplica
+/// comentarios inline, `.sentinelignore`, `.sentinel/ignore.json` y `[rule_config]`, sin
+/// leer ni escribir el archivo. Devuelve el mismo JSON que `--format json` (incluidos
+/// `suppressed` y `explanations` según `options`).
+pub fn check_source(
+    virtual_path: &str,
+    content: &str,
+    agent_context: &crate::agents::base::AgentContext,
+    options: &CheckOptions,
+) -> serde_json::Value {
+    let mut rule_engine = crate::rules::engine::RuleEngine::new()
+        .with_sentinelignore(&agent_context.project_root)
+        .with_entry_patterns(&agent_context.project_root, &agent_context.config)
+        .with_secret_allowlist(&agent_context.project_root, &agent_context.config.rule_config.secret_allowlist)
+        .with_thresholds(&agent_context.config.rule_config);
+    if let Some(ref db) = agent_context.index_db {
+        rule_engine = rule_engine.with_index_db(std::sync::Arc::clone(db));
+    }
+    let rules_path = agent_context.project_root.join(".sentinel/rules.yaml");
+    if rules_path.exists() {
+        let _ = rule_engine.load_from_yaml(&rules_path);
+    }
+
+    let rel = virtual_path.trim_start_matches("./");
+    let (violations, suppressed) =
+        rule_engine.validate_file_with_suppressed(&agent_context.project_root.join(rel), content);
+
+    let ignore_entries = load_ignore_entries(&agent_context.project_root);
+    let rule_cfg = &agent_context.config.rule_config;
+    let issues: Vec<JsonIssue> = violations
+        .into_iter()
+        .filter(|v| !is_ignored(&ignore_entries, &v.rule_name, rel, v.symbol.as_deref()))
+        .filter(|v| passes_rule_config(rule_cfg, &v.rule_name, v.value))
+        .map(|v| JsonIssue {
+            file: rel.to_string(),
+            rule: v.rule_name,
+            severity: match v.level {
+                RuleLevel::Error => "error",
+                RuleLevel::Warning => "warning",
+                RuleLevel::Info => "info",
+            }
+            .to_string(),
+            message: v.message,
+            line: v.line,
+        })
+        .collect();
+
+    let count = |sev: &str| issues.iter().filter(|i| i.severity == sev).count();
+    let mut out = serde_json::json!({
+        "checked": 1,
+        "errors": count("error"),
+        "warnings": count("warning"),
+        "infos": count("info"),
+        "index_populated": agent_context.index_db.as_ref().map(|db| db.is_populated()).unwrap_or(false),
+        "issues": issues,
+    });
+    if options.show_suppressed {
+        out["suppressed"] = serde_json::json!(suppressed);
+    }
+    if options.explain {
+        let explanations = rule_explanations(issues.iter().map(|i| i.rule.as_str()));
+        out["explanations"] = serde_json::json!(explanations);
+    }
+    out
+}
+
+/// `pro check --stdin <ruta-virtual>`: lint de un buffer sin guardar (plugins de editor / LSP).
+/// Siempre responde en JSON por stdout; `--fail-on` decide el código de salida.
+pub fn handle_check_stdin(
+    virtual_path: &str,
+    options: &CheckOptions,
+    agent_context: &crate::agents::base::AgentContext,
+    out: &mut OutputSink,
+) {
+    let code = check_reader(std::io::stdin(), virtual_path, options, agent_context, out);
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// Cuerpo de `handle_check_stdin` sobre cualquier `input`: escribe el JSON en `out` y
+/// devuelve el código de salida (1 si `--fail-on` se cumple, 2 si no se pudo leer).
+fn check_reader(
+    mut input: impl std::io::Read,
+    virtual_path: &str,
+    options: &CheckOptions,
+    agent_context: &crate::agents::base::AgentContext,
+    out: &mut OutputSink,
+) -> i32 {
+    let mut content = String::new();
+    if let Err(e) = input.read_to_string(&mut content) {
+        outln!(out, "{}", serde_json::json!({ "error": format!("No se pudo leer stdin: {}", e) }));
+        return 2;
+    }
+    let result = check_source(virtual_path, &content, agent_context, options);
+    outln!(out, "{}", serde_json::to_string_pretty(&result).unwrap_or_default());
+
+    let n = |key: &str| result[key].as_u64().unwrap_or(0) as usize;
+    i32::from(options.fail_on.should_fail(n("errors"), n("warnings"), n("infos")))
+}
+
+/// `pro check --watch`: revisa el destino, y vuelve a hacerlo (limpiando la terminal)
+/// cada vez que cambia un archivo bajo él. Usa el mismo watcher y agrupado que el monitor.
+pub fn handle_check_watch(
+    options: &CheckOptions,
+    quiet: bool,
+    verbose: bool,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+    index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+    out: &mut OutputSink,
+) {
+    use crate::commands::monitor::{recibir_lote, rutas_de_evento};
+    use notify::{RecursiveMode, Watcher};
+
+    // Valida el destino y muestra el primer resultado (sale con 2 si no existe)
+    handle_check(options, quiet, verbose, agent_context, output_mode.clone(), index_handle, out);
+
+    let path = agent_context.project_root.join(&options.target);
+    let config = std::sync::Arc::clone(&agent_context.config);
+    let matcher = config.ignore_matcher(&agent_context.project_root);
+    let (tx, rx) = std::sync::mpsc::channel();
+    let watcher = notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+        if let Ok(event) = res {
+            for p in rutas_de_evento(&event.kind, event.paths, &config, &matcher) {
+                let _ = tx.send(p);
+            }
+        }
+    });
+    let mode = if path.is_dir() { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    let mut watcher = match watcher {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("{} No se pudo iniciar el watcher: {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = watcher.watch(&path, mode) {
+        eprintln!("{} No se pudo vigilar '{}': {}", "❌".red(), options.target, e);
+        std::process::exit(2);
+    }
+
+    // Ctrl-C: salida limpia en vez de cortar el proceso a mitad de una salida
+    std::thread::spawn(|| {
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build();
+        if let Ok(rt) = rt
+            && rt.block_on(tokio::signal::ctrl_c()).is_ok()
+        {
+            println!("\n👋 Modo watch finalizado.");
+            std::process::exit(0);
+        }
+    });
+
+    outln!(out, "\n👀 Vigilando {} (Ctrl-C para salir)...", options.target.cyan());
+    while let Some(lote) = recibir_lote(&rx, agent_context.config.coalesce_window()) {
+        out.raw("\x1B[2J\x1B[1;1H");
+        outln!(out,
+            "🔁 {} — {} archivo(s) modificado(s)",
+            chrono::Local::now().format("%H:%M:%S").to_string().dimmed(),
+            lote.len()
+        );
+        handle_check(options, quiet, verbose, agent_context, output_mode.clone(), None, out);
+        outln!(out, "\n👀 Vigilando {} (Ctrl-C para salir)...", options.target.cyan());
+    }
+}
+
+type EmbedFn = Box<dyn Fn(&str) -> anyhow::Result<Vec<f32>>>;
+
+/// Carga el predictor y el modelo de embeddings para `--risk`. Si ML está desactivado
+/// o el modelo no carga, avisa y devuelve `None`: el check continúa sin scores.
+fn load_risk_model(
+    agent_context: &crate::agents::base::AgentContext,
+    text_mode: bool,
+) -> Option<(crate::ml::predictor::OnnxPredictor, EmbedFn)> {
+    let warn = |msg: String| {
+        // En formatos máquina el aviso va a stderr para no romper el documento
+        if text_mode {
+            println!("{} {}", "⚠️".yellow(), msg.yellow());
+        } else {
+            eprintln!("⚠️ {}", msg);
+        }
+    };
+    let config = &agent_context.config;
+    if !config.features.as_ref().map(|f| f.enable_ml).unwrap_or(false) {
+        warn("--risk requiere features.enable_ml = true en .sentinelrc.toml; se omite el score.".to_string());
+        return None;
+    }
+    let model_path = config
+        .ml
+        .as_ref()
+        .map(|ml| agent_context.project_root.join(&ml.models_path).join(&ml.bug_predictor_model))
+        .unwrap_or_default();
+    let loaded = crate::ml::predictor::OnnxPredictor::new(&model_path.to_string_lossy())
+        .and_then(|p| crate::ml::embeddings::EmbeddingModel::get_or_init().map(|m| (p, m)));
+    match loaded {
+        Ok((predictor, model)) => {
+            let embed: EmbedFn = Box::new(move |content: &str| {
+                // El modelo trunca a 512 tokens; basta con el inicio del archivo
+                let snippet: String = content.chars().take(4000).collect();
+                model
+                    .embed(&[snippet])?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("Embedding vacío"))
+            });
+            Some((predictor, embed))
+        }
+        Err(e) => {
+            warn(format!("No se pudo cargar el modelo ML ({}); se omite el score de riesgo.", e));
+            None
+        }
+    }
+}
+
+/// Score de riesgo por archivo y violaciones `HIGH_RISK` para los que superan `threshold`.
+/// Los archivos cuyo embedding falla se omiten.
+fn score_file_risks(
+    files: &[(String, String)],
+    violations: &[FileViolation],
+    predictor: &dyn crate::ml::predictor::BugRiskPredictor,
+    embed: &dyn Fn(&str) -> anyhow::Result<Vec<f32>>,
+    threshold: f32,
+) -> (Vec<(String, f32)>, Vec<FileViolation>) {
+    let mut scores = Vec::new();
+    let mut high_risk = Vec::new();
+    for (file, content) in files {
+        let Ok(probability) = embed(content).and_then(|e| predictor.predict_bug_probability(&e)) else {
+            continue;
+        };
+        let count = |level: RuleLevel| {
+            violations.iter().filter(|v| &v.file_path == file && v.level == level).count()
+        };
+        let score = crate::ml::predictor::combine_risk(
+            probability,
+            count(RuleLevel::Error),
+            count(RuleLevel::Warning),
+        );
+        if score >= threshold {
+            high_risk.push(FileViolation {
+                file_path: file.clone(),
+                rule_name: "HIGH_RISK".to_string(),
+                symbol: None,
+                message: format!("Riesgo de bugs {:.2} (umbral {:.2}).", score, threshold),
+                level: RuleLevel::Info,
+                line: None,
+                value: None,
+            });
+        }
+        scores.push((file.clone(), score));
+    }
+    (scores, high_risk)
+}
+
+/// Guarda las métricas por archivo de esta ejecución en `quality_history`
+/// (base de `sentinel index --trend`).
+fn file_metrics(file: &str, complexity: f64, violations: &[FileViolation]) -> crate::index::quality_history::FileMetrics {
+    let count = |rule: &str| {
+        violations
+            .iter()
+            .filter(|v| v.file_path == file && (rule.is_empty() || v.rule_name == rule))
+            .count() as i32
+    };
+    crate::index::quality_history::FileMetrics {
+        file_path: file.to_string(),
+        dead_functions: count("DEAD_CODE"),
+        unused_imports: count("UNUSED_IMPORT"),
+        complexity_score: complexity,
+        violations_count: count(""),
+        tests_passing: true,
+    }
+}
+
+fn record_quality_run(
+    db: &crate::index::IndexDb,
+    complexity_by_file: &[(String, f64)],
+    violations: &[FileViolation],
+    scope: &str,
+) {
+    let metrics: Vec<crate::index::quality_history::FileMetrics> = complexity_by_file
+        .iter()
+        .map(|(file, complexity)| file_metrics(file, *complexity, violations))
+        .collect();
+    let _ = crate::index::quality_history::QualityHistory::new(db).record_run(&metrics, scope);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::commands::ignore::IgnoreEntry;
 
+    #[test]
+    fn test_risk_score_attached_and_threshold_gate() {
+        use super::{score_file_risks, FileViolation};
+        use crate::rules::RuleLevel;
+
+        struct StubPredictor;
+        impl crate::ml::predictor::BugRiskPredictor for StubPredictor {
+            fn predict_bug_probability(&self, embedding: &[f32]) -> anyhow::Result<f32> {
+                Ok(embedding[0])
+            }
+        }
+        // El "embedding" codifica la probabilidad deseada según el contenido
+        let embed = |content: &str| -> anyhow::Result<Vec<f32>> {
+            Ok(vec![if content.contains("risky") { 0.9 } else { 0.1 }])
+        };
+        let error = |file: &str| FileViolation {
+            file_path: file.to_string(),
+            rule_name: "HIGH_COMPLEXITY".to_string(),
+            symbol: None,
+            message: String::new(),
+            level: RuleLevel::Error,
+            line: Some(1),
+            value: Some(20),
+        };
+
+        let files = vec![
+            ("src/safe.ts".to_string(), "const a = 1;".to_string()),
+            ("src/risky.ts".to_string(), "// risky".to_string()),
+        ];
+        let violations: Vec<FileViolation> = (0..4).map(|_| error("src/risky.ts")).collect();
+        let (scores, high_risk) = score_file_risks(&files, &violations, &StubPredictor, &embed, 0.7);
+
+        assert_eq!(scores.len(), 2, "every file gets a score");
+        let safe = scores.iter().find(|(f, _)| f == "src/safe.ts").unwrap().1;
+        let risky = scores.iter().find(|(f, _)| f == "src/risky.ts").unwrap().1;
+        assert!((0.0..=1.0).contains(&safe) && (0.0..=1.0).contains(&risky));
+        assert!(risky > 0.7 && safe < 0.7, "safe={} risky={}", safe, risky);
+
+        assert_eq!(high_risk.len(), 1);
+        assert_eq!(high_risk[0].file_path, "src/risky.ts");
+        assert_eq!(high_risk[0].rule_name, "HIGH_RISK");
+        assert_eq!(high_risk[0].level, RuleLevel::Info);
+    }
+
     #[test]
     fn test_ignore_filter_removes_matching_entry() {
         // Simulate the filter logic used in the check handler
@@ -359,4 +1161,232 @@ mod tests {
         assert_eq!(violations[0].symbol.as_deref(), Some("getUser"));
         assert_eq!(violations[1].rule_name, "UNUSED_IMPORT");
     }
+
+    #[test]
+    fn test_grep_keeps_matching_rules_and_updates_counts() {
+        use super::{apply_grep, count_by_level, FileViolation};
+        use crate::rules::RuleLevel;
+
+        let violation = |rule: &str, message: &str, level: RuleLevel| FileViolation {
+            file_path: "src/app.ts".to_string(),
+            rule_name: rule.to_string(),
+            symbol: None,
+            message: message.to_string(),
+            level,
+            line: Some(1),
+            value: None,
+        };
+        let mut violations = vec![
+            violation("UNUSED_IMPORT", "Import 'a' no usado", RuleLevel::Warning),
+            violation("HIGH_COMPLEXITY", "Complejidad 14", RuleLevel::Error),
+            violation("UNUSED_IMPORT", "Import 'b' no usado", RuleLevel::Warning),
+            violation("DEAD_CODE", "Función nunca llamada", RuleLevel::Error),
+        ];
+        assert_eq!(count_by_level(&violations), (2, 2, 0));
+
+        let re = crate::commands::pro::parse_grep(Some("unused")).unwrap().unwrap();
+        assert_eq!(apply_grep(&mut violations, &re), 2);
+        assert!(violations.iter().all(|v| v.rule_name == "UNUSED_IMPORT"));
+        assert_eq!(count_by_level(&violations), (0, 2, 0), "errors no longer count");
+
+        // También filtra por mensaje, y una regex inválida se rechaza
+        let mut by_message = vec![violation("HIGH_COMPLEXITY", "Complejidad 14", RuleLevel::Error)];
+        let re = crate::commands::pro::parse_grep(Some("complejidad \\d+")).unwrap().unwrap();
+        assert_eq!(apply_grep(&mut by_message, &re), 0);
+        assert!(crate::commands::pro::parse_grep(Some("(")).is_err());
+    }
+
+    #[test]
+    fn test_check_source_reports_unused_import_from_stdin() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = crate::agents::base::AgentContext {
+            config: std::sync::Arc::new(crate::config::SentinelConfig::default()),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: tmp.path().to_path_buf(),
+            index_db: None,
+        };
+        // Lo que un plugin de editor enviaría por stdin para un buffer sin guardar
+        let mut stdin = std::io::Cursor::new("import { helper } from './utils';\n\nexport const answer = 42;\n");
+        let mut buffer = String::new();
+        std::io::Read::read_to_string(&mut stdin, &mut buffer).unwrap();
+
+        let out = super::check_source("src/draft.ts", &buffer, &ctx, &super::CheckOptions::default());
+        let issue = out["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|i| i["rule"] == "UNUSED_IMPORT")
+            .expect("UNUSED_IMPORT reported");
+        assert_eq!(issue["file"], "src/draft.ts");
+        assert_eq!(issue["line"], 1);
+        assert!(!tmp.path().join("src/draft.ts").exists(), "nothing is written to disk");
+        assert!(out.get("explanations").is_none(), "only with --explain");
+
+        // --explain: una explicación del catálogo por regla distinta
+        let explain = super::CheckOptions { explain: true, ..Default::default() };
+        let out = super::check_source("src/draft.ts", &buffer, &ctx, &explain);
+        let explanations = out["explanations"].as_array().unwrap();
+        let unused = explanations.iter().find(|e| e["rule"] == "UNUSED_IMPORT").expect("UNUSED_IMPORT explained");
+        assert_eq!(unused["explanation"], crate::rules::catalog::explanation("UNUSED_IMPORT").unwrap());
+        assert!(unused["help_uri"].as_str().unwrap().ends_with("#unused_import"));
+        assert_eq!(explanations.iter().filter(|e| e["rule"] == "UNUSED_IMPORT").count(), 1);
+
+        // Con la categoría desactivada en [rule_config] desaparece
+        let mut config = crate::config::SentinelConfig::default();
+        config.rule_config.unused_imports_enabled = false;
+        let ctx = crate::agents::base::AgentContext { config: std::sync::Arc::new(config), ..ctx };
+        let out = super::check_source("src/draft.ts", &buffer, &ctx, &super::CheckOptions::default());
+        assert!(out["issues"].as_array().unwrap().iter().all(|i| i["rule"] != "UNUSED_IMPORT"));
+    }
+
+    #[test]
+    fn test_stdin_check_prints_json_and_decides_exit_code() {
+        use crate::commands::output::OutputSink;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = crate::agents::base::AgentContext {
+            config: std::sync::Arc::new(crate::config::SentinelConfig::default()),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: tmp.path().to_path_buf(),
+            index_db: None,
+        };
+        let run = |input: &[u8], fail_on: crate::commands::pro::FailOn| {
+            let options = super::CheckOptions { fail_on, ..Default::default() };
+            let (mut out, captured) = OutputSink::buffer();
+            let code = super::check_reader(input, "./src/a.ts", &options, &ctx, &mut out);
+            (code, captured.contents())
+        };
+        let source = b"import { helper } from './b';\nexport const a = 1;\n";
+
+        let (code, output) = run(source, crate::commands::pro::FailOn::Warning);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(json["issues"].as_array().unwrap().iter().any(|i| i["rule"] == "UNUSED_IMPORT" && i["file"] == "src/a.ts"));
+        assert_eq!(code, 1, "an UNUSED_IMPORT warning trips --fail-on warning");
+        assert_eq!(run(source, crate::commands::pro::FailOn::Never).0, 0);
+
+        // Entrada ilegible: error en JSON y código 2
+        let (code, output) = run(&[0xff, 0xfe], crate::commands::pro::FailOn::Never);
+        assert_eq!(code, 2);
+        assert!(serde_json::from_str::<serde_json::Value>(&output).unwrap()["error"].is_string());
+    }
+
+    #[test]
+    fn test_handle_check_writes_report_to_sink() {
+        use crate::commands::output::OutputSink;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src/a.ts"), "import { helper } from './b';\nexport const a = 1;\n").unwrap();
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        let ctx = crate::agents::base::AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: tmp.path().to_path_buf(),
+            index_db: None,
+        };
+        let run = |format: &str| {
+            let options = super::CheckOptions {
+                target: "src".to_string(),
+                format: format.to_string(),
+                fail_on: crate::commands::pro::FailOn::Never,
+                ..Default::default()
+            };
+            let (mut out, captured) = OutputSink::buffer();
+            super::handle_check(&options, false, false, &ctx, crate::commands::OutputMode::Normal, None, &mut out);
+            captured.contents()
+        };
+
+        let text = run("text");
+        assert!(text.contains("a.ts"));
+        assert!(text.contains("UNUSED_IMPORT"));
+        assert!(text.contains("error(s)"), "summary line: {}", text);
+
+        let json: serde_json::Value = serde_json::from_str(&run("json")).unwrap();
+        assert_eq!(json["checked"], 1);
+        assert!(json["issues"].as_array().unwrap().iter().any(|i| i["rule"] == "UNUSED_IMPORT" && i["file"] == "src/a.ts"));
+    }
+
+    #[test]
+    fn test_jsonl_emits_one_object_per_line_and_a_final_summary() {
+        use crate::commands::output::OutputSink;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src/a.ts"), "import { helper } from './b';\nexport const a = 1;\n").unwrap();
+        std::fs::write(tmp.path().join("src/b.ts"), "import { x } from './c';\nimport { y } from './d';\nexport const b = 2;\n").unwrap();
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        let ctx = crate::agents::base::AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: tmp.path().to_path_buf(),
+            index_db: None,
+        };
+        let options = super::CheckOptions {
+            target: "src".to_string(),
+            format: "jsonl".to_string(),
+            fail_on: crate::commands::pro::FailOn::Never,
+            ..Default::default()
+        };
+        let (mut out, captured) = OutputSink::buffer();
+        super::handle_check(&options, false, false, &ctx, crate::commands::OutputMode::Normal, None, &mut out);
+
+        let contents = captured.contents();
+        let lines: Vec<serde_json::Value> =
+            contents.lines().map(|l| serde_json::from_str(l).expect("each line is a JSON document")).collect();
+        let (summary, issues) = lines.split_last().unwrap();
+        assert_eq!(summary["summary"], true);
+        assert_eq!(summary["checked"], 2);
+        assert!(issues.iter().all(|i| i.get("summary").is_none() && i["rule"].is_string()));
+        assert_eq!(issues.iter().filter(|i| i["rule"] == "UNUSED_IMPORT").count(), 3);
+        let total = ["errors", "warnings", "infos"].iter().map(|k| summary[k].as_u64().unwrap()).sum::<u64>();
+        assert_eq!(total as usize, issues.len());
+    }
+
+    #[test]
+    fn test_quiet_success_prints_nothing_on_clean_tree() {
+        use crate::commands::output::OutputSink;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src/clean.ts"), "console.log(\"hola\");\n").unwrap();
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        let ctx = crate::agents::base::AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: tmp.path().to_path_buf(),
+            index_db: None,
+        };
+        let run = |format: &str, quiet_success: bool| {
+            let options = super::CheckOptions {
+                target: "src".to_string(),
+                format: format.to_string(),
+                fail_on: crate::commands::pro::FailOn::Never,
+                quiet_success,
+                ..Default::default()
+            };
+            let (mut out, captured) = OutputSink::buffer();
+            super::handle_check(&options, false, false, &ctx, crate::commands::OutputMode::Normal, None, &mut out);
+            captured.contents()
+        };
+
+        assert!(run("text", false).contains("Sin problemas detectados"));
+        assert_eq!(run("text", true), "");
+        assert!(run("json", false).contains("\"issues\": []"));
+        assert_eq!(run("json", true), "");
+
+        // Con hallazgos la salida es la de siempre, banner incluido
+        std::fs::write(tmp.path().join("src/dirty.ts"), "import { helper } from './b';\nexport const b = 2;\n").unwrap();
+        let text = run("text", true);
+        assert!(text.contains("Capa 1"));
+        assert!(text.contains("UNUSED_IMPORT"));
+    }
 }
@@ -0,0 +1,360 @@
+//! `pro coverage`: mapa de qué archivos fuente tienen un test asociado.
+//!
+//! No mide cobertura de líneas: un archivo cuenta como cubierto si existe un test
+//! para él según los `test_patterns` del config, los directorios de tests habituales
+//! (`test/`, `tests/`, `__tests__/`, `spec/`) o un test hermano en la misma carpeta.
+//! Los sufijos que el framework no suele testear (`.module.ts`, `.dto.ts`, ...) se excluyen.
+//!
+//! Idiomas por lenguaje: en Go el test es del paquete (cualquier `_test.go` de la carpeta
+//! cubre todos sus archivos) y en Rust cuenta un módulo `#[cfg(test)]` inline.
+
+use crate::files;
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize)]
+pub struct DirCoverage {
+    pub dir: String,
+    pub testable: usize,
+    pub tested: usize,
+    pub percent: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverageReport {
+    pub testable: usize,
+    pub tested: usize,
+    pub percent: f64,
+    pub directories: Vec<DirCoverage>,
+    pub untested: Vec<String>,
+}
+
+fn percent(tested: usize, testable: usize) -> f64 {
+    if testable == 0 {
+        return 100.0;
+    }
+    (tested as f64 * 1000.0 / testable as f64).round() / 10.0
+}
+
+/// Archivos que ya son tests (no se cuentan como código fuente).
+fn es_archivo_test(rel: &Path) -> bool {
+    let in_test_dir = rel.components().any(|c| {
+        matches!(c.as_os_str().to_str(), Some("test" | "tests" | "__tests__" | "spec"))
+    });
+    let name = rel.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let stem = rel.file_stem().and_then(|n| n.to_str()).unwrap_or("");
+    in_test_dir
+        || name.contains(".spec.")
+        || name.contains(".test.")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || stem.starts_with("test_")
+        || (stem.ends_with("Test") && stem.len() > 4)
+}
+
+/// Test en la misma carpeta: `a.spec.ts`, `a.test.ts`, `a_test.go`, `test_a.py`, `a_spec.rb`.
+fn tiene_test_hermano(abs: &Path, base_name: &str) -> bool {
+    let Some(dir) = abs.parent() else { return false };
+    let ext = abs.extension().and_then(|e| e.to_str()).unwrap_or("");
+    [
+        format!("{}.spec.{}", base_name, ext),
+        format!("{}.test.{}", base_name, ext),
+        format!("{}_test.{}", base_name, ext),
+        format!("{}_spec.{}", base_name, ext),
+        format!("test_{}.{}", base_name, ext),
+    ]
+    .iter()
+    .any(|candidate| dir.join(candidate).is_file())
+}
+
+/// Go: los tests se agrupan por paquete (= carpeta), no por archivo.
+fn paquete_go_tiene_tests(abs: &Path) -> bool {
+    let Some(dir) = abs.parent() else { return false };
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten().any(|e| {
+                e.file_name().to_str().is_some_and(|n| n.ends_with("_test.go")) && e.path().is_file()
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Rust: `#[cfg(test)] mod tests { ... }` (o cualquier módulo con ese atributo) en el archivo.
+pub fn tiene_modulo_test_inline(source: &str) -> bool {
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&tree_sitter_rust::LANGUAGE.into()).is_err() {
+        return false;
+    }
+    let Some(tree) = parser.parse(source, None) else { return false };
+
+    fn buscar(node: tree_sitter::Node, source: &[u8]) -> bool {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() == "attribute_item"
+                && child
+                    .utf8_text(source)
+                    .is_ok_and(|t| t.replace(' ', "").contains("cfg(test)"))
+                && child.next_named_sibling().is_some_and(|n| n.kind() == "mod_item")
+            {
+                return true;
+            }
+            if child.kind() == "mod_item"
+                && let Some(body) = child.child_by_field_name("body")
+                && buscar(body, source)
+            {
+                return true;
+            }
+        }
+        false
+    }
+    buscar(tree.root_node(), source.as_bytes())
+}
+
+fn tiene_test(project_root: &Path, abs: &Path, base_name: &str, test_patterns: &[String]) -> bool {
+    match abs.extension().and_then(|e| e.to_str()) {
+        Some("go") if paquete_go_tiene_tests(abs) => return true,
+        Some("rs") if std::fs::read_to_string(abs).is_ok_and(|s| tiene_modulo_test_inline(&s)) => return true,
+        _ => {}
+    }
+    files::buscar_archivo_test(base_name, project_root, test_patterns).is_some()
+        || files::buscar_test_en_directorios(base_name, project_root)
+        || tiene_test_hermano(abs, base_name)
+}
+
+/// Calcula la cobertura por directorio de `source_files` (rutas absolutas bajo `project_root`).
+pub fn compute_coverage(
+    project_root: &Path,
+    source_files: &[PathBuf],
+    framework: &str,
+    test_patterns: &[String],
+) -> CoverageReport {
+    let excluded = files::sufijos_sin_test_por_framework(framework);
+    let mut dirs: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut untested = Vec::new();
+
+    for abs in source_files {
+        let rel = abs.strip_prefix(project_root).unwrap_or(abs);
+        let file_name = rel.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if es_archivo_test(rel) || excluded.iter().any(|s| file_name.ends_with(s)) {
+            continue;
+        }
+        let base_name = file_name.split('.').next().unwrap_or(file_name);
+        let dir = rel
+            .parent()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+
+        let entry = dirs.entry(dir).or_insert((0, 0));
+        entry.0 += 1;
+        if tiene_test(project_root, abs, base_name, test_patterns) {
+            entry.1 += 1;
+        } else {
+            untested.push(rel.to_string_lossy().replace('\\', "/"));
+        }
+    }
+
+    let testable: usize = dirs.values().map(|(t, _)| t).sum();
+    let tested: usize = dirs.values().map(|(_, c)| c).sum();
+    untested.sort();
+    CoverageReport {
+        testable,
+        tested,
+        percent: percent(tested, testable),
+        directories: dirs
+            .into_iter()
+            .map(|(dir, (testable, tested))| DirCoverage {
+                dir,
+                testable,
+                tested,
+                percent: percent(tested, testable),
+            })
+            .collect(),
+        untested,
+    }
+}
+
+fn colorear(percent: f64) -> ColoredString {
+    let text = format!("{:>5.1}%", percent);
+    if percent >= 80.0 {
+        text.green()
+    } else if percent >= 50.0 {
+        text.yellow()
+    } else {
+        text.red()
+    }
+}
+
+fn print_tree(report: &CoverageReport) {
+    println!("\n🧪 {}", "COBERTURA DE TESTS POR DIRECTORIO".bold());
+    for d in &report.directories {
+        let depth = if d.dir == "." { 0 } else { d.dir.matches('/').count() };
+        let name = d.dir.rsplit('/').next().unwrap_or(&d.dir);
+        println!(
+            "   {}{}/  {} {}",
+            "  ".repeat(depth),
+            name.cyan(),
+            colorear(d.percent),
+            format!("({}/{})", d.tested, d.testable).dimmed()
+        );
+    }
+    println!(
+        "\n   Total: {} {}",
+        colorear(report.percent).bold(),
+        format!("({}/{} archivos con test)", report.tested, report.testable).dimmed()
+    );
+    if !report.untested.is_empty() {
+        println!("\n   {} Sin test:", "⚠️".yellow());
+        for f in report.untested.iter().take(20) {
+            println!("      - {}", f);
+        }
+        if report.untested.len() > 20 {
+            println!("      ... y {} más", report.untested.len() - 20);
+        }
+    }
+}
+
+pub fn handle_coverage(
+    target: &str,
+    format: &str,
+    min_coverage: Option<f64>,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+) {
+    let (json_mode, _) = super::format_to_mode(format);
+    let root = &agent_context.project_root;
+    let path = root.join(target);
+    if !path.exists() {
+        if json_mode {
+            println!("{{\"error\":\"El destino '{}' no existe\"}}", target);
+        } else {
+            println!("{} El destino '{}' no existe en el proyecto.", "❌".red(), target);
+        }
+        std::process::exit(2);
+    }
+
+    let extensions = &agent_context.config.file_extensions;
+    let mut source_files = Vec::new();
+    let walker = ignore::WalkBuilder::new(&path).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if p.is_file() && extensions.iter().any(|e| e == ext) {
+            source_files.push(p.to_path_buf());
+        }
+    }
+
+    let config = &agent_context.config;
+    let report = compute_coverage(root, &source_files, &config.framework, &config.test_patterns);
+
+    if json_mode {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else if output_mode != crate::commands::OutputMode::Quiet {
+        print_tree(&report);
+    }
+
+    if let Some(min) = min_coverage
+        && report.percent < min
+    {
+        if !json_mode {
+            eprintln!(
+                "\n{} Cobertura {:.1}% por debajo del mínimo {:.1}%.",
+                "❌".red(),
+                report.percent,
+                min
+            );
+        }
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(root: &Path, rel: &str) -> PathBuf {
+        let p = root.join(rel);
+        std::fs::create_dir_all(p.parent().unwrap()).unwrap();
+        std::fs::write(&p, "").unwrap();
+        p
+    }
+
+    #[test]
+    fn test_coverage_mixed_tested_and_untested() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let files = vec![
+            touch(root, "src/users/users.service.ts"),
+            touch(root, "src/users/users.controller.ts"),
+            touch(root, "src/users/users.module.ts"), // excluido en NestJS
+            touch(root, "src/users/users.service.spec.ts"), // test hermano
+            touch(root, "src/orders/orders.service.ts"),
+            touch(root, "src/orders/orders.repository.ts"),
+            touch(root, "test/orders.spec.ts"),
+            touch(root, "src/main.ts"),
+        ];
+        let patterns = vec!["src/{name}/{name}.controller.spec.ts".to_string()];
+        touch(root, "src/users/users.controller.spec.ts");
+
+        let report = compute_coverage(root, &files, "NestJS", &patterns);
+        // Testeables: users.service, users.controller, orders.service, orders.repository, main
+        assert_eq!(report.testable, 5);
+        // users.service (hermano), users.controller (patrón), orders.* (test/ contiene "orders")
+        assert_eq!(report.tested, 4);
+        assert_eq!(report.percent, 80.0);
+        assert_eq!(report.untested, vec!["src/main.ts"]);
+
+        let users = report.directories.iter().find(|d| d.dir == "src/users").unwrap();
+        assert_eq!((users.tested, users.testable, users.percent), (2, 2, 100.0));
+        let src = report.directories.iter().find(|d| d.dir == "src").unwrap();
+        assert_eq!(src.percent, 0.0);
+    }
+
+    #[test]
+    fn test_rust_inline_test_module_counts_as_covered() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let with_tests = root.join("src/parser.rs");
+        let without_tests = root.join("src/lexer.rs");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            &with_tests,
+            "pub fn parse() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn it_parses() { super::parse(); }\n}\n",
+        )
+        .unwrap();
+        // Un comentario o un string con el atributo no cuentan
+        std::fs::write(&without_tests, "// #[cfg(test)] mod tests\npub fn lex() -> &'static str { \"#[cfg(test)]\" }\n").unwrap();
+
+        assert!(tiene_modulo_test_inline(&std::fs::read_to_string(&with_tests).unwrap()));
+        assert!(!tiene_modulo_test_inline(&std::fs::read_to_string(&without_tests).unwrap()));
+
+        let report = compute_coverage(root, &[with_tests, without_tests], "", &[]);
+        assert_eq!((report.tested, report.testable), (1, 2));
+        assert_eq!(report.untested, vec!["src/lexer.rs"]);
+    }
+
+    #[test]
+    fn test_go_files_covered_by_package_tests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let files = vec![
+            touch(root, "internal/billing/invoice.go"),
+            touch(root, "internal/billing/tax.go"),
+            touch(root, "internal/billing/billing_test.go"),
+            touch(root, "internal/auth/token.go"),
+        ];
+        let report = compute_coverage(root, &files, "", &[]);
+        assert_eq!((report.tested, report.testable), (2, 3));
+        assert_eq!(report.untested, vec!["internal/auth/token.go"]);
+    }
+
+    #[test]
+    fn test_coverage_empty_is_full() {
+        let tmp = tempfile::tempdir().unwrap();
+        let report = compute_coverage(tmp.path(), &[], "", &[]);
+        assert_eq!(report.percent, 100.0);
+        assert!(report.directories.is_empty());
+    }
+}
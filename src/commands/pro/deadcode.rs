@@ -0,0 +1,176 @@
+//! `pro deadcode`: barrido de todo el proyecto en busca de exports sin referencias.
+//!
+//! A diferencia del aviso por archivo de `pro check`, usa el índice completo (tabla de
+//! símbolos, call graph e imports): un símbolo exportado se reporta solo si ningún archivo
+//! lo llama ni lo importa y no es un punto de entrada (`index::entry_points`, incluidos
+//! los `entrypoint_patterns` de la config).
+
+use crate::index::IndexDb;
+use crate::index::call_graph::{CallGraph, UnreferencedExport};
+use crate::index::entry_points::{EntryPatterns, is_entry_point};
+use colored::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Exports sin referencias que no son raíces del proyecto. Los decoradores de `entry`
+/// se buscan en el código de `project_root`.
+pub fn find_dead_exports(
+    db: &IndexDb,
+    project_root: &Path,
+    entry: &EntryPatterns,
+) -> anyhow::Result<Vec<UnreferencedExport>> {
+    let mut dead = CallGraph::new(db).unreferenced_exports()?;
+    dead.retain(|s| !is_entry_point(&s.file_path, &s.name) && !entry.matches_file(Path::new(&s.file_path)));
+
+    // Un parseo por archivo para resolver los símbolos decorados
+    let mut roots: HashMap<String, HashSet<String>> = HashMap::new();
+    dead.retain(|s| {
+        let file_roots = roots.entry(s.file_path.clone()).or_insert_with(|| {
+            let ext = Path::new(&s.file_path).extension().and_then(|e| e.to_str()).unwrap_or("");
+            std::fs::read_to_string(project_root.join(&s.file_path))
+                .map(|source| entry.decorated_roots(ext, &source))
+                .unwrap_or_default()
+        });
+        !file_roots.contains(&s.name)
+    });
+    Ok(dead)
+}
+
+pub fn handle_deadcode(
+    format: &str,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+    index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+) {
+    let (json_mode, _) = super::format_to_mode(format);
+    // El barrido necesita el índice completo: esperar la auto-indexación si está en curso
+    if let Some(h) = index_handle {
+        let _ = h.join();
+    }
+    let db = match agent_context.index_db {
+        Some(ref db) if db.is_populated() => db,
+        _ => {
+            if json_mode {
+                println!("{{\"error\":\"Índice no disponible\"}}");
+            } else {
+                println!(
+                    "{} Índice no disponible. Corre {} primero.",
+                    "❌".red(),
+                    "`sentinel index --rebuild`".cyan()
+                );
+            }
+            std::process::exit(2);
+        }
+    };
+
+    let entry = EntryPatterns::for_config(&agent_context.project_root, &agent_context.config);
+    let dead = match find_dead_exports(db, &agent_context.project_root, &entry) {
+        Ok(dead) => dead,
+        Err(e) => {
+            if json_mode {
+                println!("{}", serde_json::json!({ "error": e.to_string() }));
+            } else {
+                println!("{} Error consultando el índice: {}", "❌".red(), e);
+            }
+            std::process::exit(2);
+        }
+    };
+
+    if json_mode {
+        let out = serde_json::json!({ "dead": dead.len(), "symbols": dead });
+        println!("{}", serde_json::to_string_pretty(&out).unwrap_or_default());
+        return;
+    }
+    if output_mode == crate::commands::OutputMode::Quiet && dead.is_empty() {
+        return;
+    }
+    if dead.is_empty() {
+        println!("{} No hay exports sin referencias en el proyecto.", "✅".green());
+        return;
+    }
+
+    println!("\n{}", "💀 EXPORTS SIN REFERENCIAS".bold().yellow());
+    let mut by_file: BTreeMap<&str, Vec<&UnreferencedExport>> = BTreeMap::new();
+    for s in &dead {
+        by_file.entry(s.file_path.as_str()).or_default().push(s);
+    }
+    for (file, symbols) in &by_file {
+        println!("\n   {}", file.cyan());
+        for s in symbols {
+            let line = s.line.map(|l| format!(":{}", l)).unwrap_or_default();
+            println!("      • {} {}", s.name.bold(), format!("({}{})", s.kind, line).dimmed());
+        }
+    }
+    println!(
+        "\n   {} {} símbolo(s) en {} archivo(s). Revisa si son API pública antes de borrarlos.",
+        "ℹ️".cyan(),
+        dead.len(),
+        by_file.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::ProjectIndexBuilder;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_reports_only_exports_never_referenced() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src/lib");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("math.ts"),
+            "export function used(a: number) {\n  return a + 1;\n}\n\nexport function unused(a: number) {\n  return a - 1;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            src.join("calc.ts"),
+            "import { used } from './math';\n\nexport function main() {\n  return used(1);\n}\n",
+        )
+        .unwrap();
+        // Los helpers de tests son raíces aunque nadie los llame
+        std::fs::write(src.join("math.test.ts"), "export function fixture() {\n  return 1;\n}\n").unwrap();
+
+        let db_file = tempfile::NamedTempFile::new().unwrap();
+        let db = Arc::new(IndexDb::open(db_file.path()).unwrap());
+        ProjectIndexBuilder::new(Arc::clone(&db))
+            .index_project(dir.path(), &["ts".to_string()])
+            .unwrap();
+
+        let dead = find_dead_exports(&db, dir.path(), &EntryPatterns::default()).unwrap();
+        let names: Vec<&str> = dead.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["unused"]);
+        assert_eq!(dead[0].file_path, "src/lib/math.ts");
+        assert_eq!(dead[0].line, Some(5));
+    }
+
+    #[test]
+    fn test_decorated_handlers_are_roots_but_private_helpers_are_not() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src/users");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("users.api.ts"),
+            "import { Controller, Get } from '@nestjs/common';\n\n@Controller('users')\nexport class UsersApi {\n  @Get()\n  findAll() {\n    return [];\n  }\n\n  private formatName(name: string) {\n    return name.trim();\n  }\n}\n",
+        )
+        .unwrap();
+
+        let db_file = tempfile::NamedTempFile::new().unwrap();
+        let db = Arc::new(IndexDb::open(db_file.path()).unwrap());
+        ProjectIndexBuilder::new(Arc::clone(&db))
+            .index_project(dir.path(), &["ts".to_string()])
+            .unwrap();
+
+        let names = |entry: &EntryPatterns| -> Vec<String> {
+            find_dead_exports(&db, dir.path(), entry).unwrap().into_iter().map(|s| s.name).collect()
+        };
+        // Sin patrones el archivo no sigue la convención `.controller.ts`: la clase parece
+        // muerta. Sus métodos (también el privado) no son exports y nunca se reportan.
+        assert_eq!(names(&EntryPatterns::default()), vec!["UsersApi"]);
+
+        let nest = EntryPatterns::new(dir.path(), &crate::index::entry_points::default_patterns("nestjs"));
+        assert!(names(&nest).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::agents::base::AgentContext;
+use crate::agents::documenter::document_task;
+use crate::agents::orchestrator::AgentOrchestrator;
+use crate::ui;
+use colored::*;
+
+pub fn handle_doc(
+    file: &str,
+    symbol: &str,
+    dry_run: bool,
+    agent_context: &AgentContext,
+    orchestrator: &AgentOrchestrator,
+    output_mode: crate::commands::OutputMode,
+    rt: &tokio::runtime::Runtime,
+) {
+    let path = match crate::files::secure_join(&agent_context.project_root, std::path::Path::new(file)) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    if !path.is_file() {
+        eprintln!("{} El archivo '{}' no existe.", "❌".red(), file);
+        std::process::exit(2);
+    }
+
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!("\n📝 Documentando {} en {}...", symbol.cyan(), file.cyan());
+    }
+    let task = document_task(file, symbol);
+    let pb = ui::crear_progreso("Generando docstring (DocAgent)...");
+    let result = rt.block_on(orchestrator.execute_task("DocAgent", &task, agent_context));
+    pb.finish_and_clear();
+
+    let res = match result {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{} Error al generar la documentación: {}", "❌".red(), e);
+            std::process::exit(1);
+        }
+    };
+    let Some(updated) = res.artifacts.first() else {
+        println!("   ℹ️  {}: no se modifica el archivo.", res.output);
+        return;
+    };
+
+    println!("\n{}", res.output.dimmed());
+    if dry_run {
+        println!("\n   {} --dry-run: no se escribió '{}'.", "ℹ️".cyan(), file);
+        return;
+    }
+    // Misma ruta de escritura que fix: validación de sintaxis, guarda de truncado y backup
+    let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+    let bloques = [(Some(file.to_string()), updated.clone())];
+    if super::apply::aplicar_bloques(&agent_context.project_root, &bloques, &backups) == 0 {
+        eprintln!("{} No se escribió '{}'.", "❌".red(), file);
+        std::process::exit(1);
+    }
+    println!("\n✅ Docstring de {} añadido en {}.", symbol.green(), file);
+}
@@ -0,0 +1,133 @@
+use crate::agents::base::{AgentContext, Task, TaskType};
+use crate::agents::orchestrator::AgentOrchestrator;
+use crate::rules::RuleViolation;
+use crate::ui;
+use colored::*;
+use dialoguer::Confirm;
+
+fn build_fix_task(file: &str, content: &str, violations: &[RuleViolation]) -> Task {
+    let mut context = format!("ARCHIVO ({}):\n```\n{}\n```\n", file, content);
+    if !violations.is_empty() {
+        context.push_str("\nPROBLEMAS DETECTADOS POR EL ANÁLISIS ESTÁTICO:\n");
+        for v in violations {
+            let line = v.line.map(|l| format!("L{}", l)).unwrap_or_else(|| "-".to_string());
+            context.push_str(&format!(" - [{}] {}: {}\n", v.rule_name, line, v.message));
+        }
+    }
+
+    Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: format!(
+            "CORRECCIÓN DE BUGS: revisa '{}' y corrige los bugs y problemas detectados.\n\
+            - Devuelve el archivo COMPLETO corregido, no solo fragmentos.\n\
+            - No cambies comportamiento que no esté relacionado con los problemas.",
+            file
+        ),
+        task_type: TaskType::Fix,
+        file_path: Some(std::path::PathBuf::from(file)),
+        context: Some(context),
+    }
+}
+
+pub fn handle_fix(
+    file: &str,
+    dry_run: bool,
+    agent_context: &AgentContext,
+    orchestrator: &AgentOrchestrator,
+    output_mode: crate::commands::OutputMode,
+    rt: &tokio::runtime::Runtime,
+) {
+    let path = match crate::files::secure_join(&agent_context.project_root, std::path::Path::new(file)) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} No se pudo leer '{}': {}", "❌".red(), file, e);
+            std::process::exit(1);
+        }
+    };
+
+    let engine = crate::rules::engine::RuleEngine::new()
+        .with_sentinelignore(&agent_context.project_root)
+        .with_entry_patterns(&agent_context.project_root, &agent_context.config)
+        .with_secret_allowlist(&agent_context.project_root, &agent_context.config.rule_config.secret_allowlist)
+        .with_thresholds(&agent_context.config.rule_config);
+    let violations = engine.validate_file(&path, &content);
+
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!(
+            "\n🛠️  Corrigiendo {} ({} problema(s) estático(s) detectado(s))...",
+            file.cyan(),
+            violations.len()
+        );
+    }
+
+    let task = build_fix_task(file, &content, &violations);
+    let pb = ui::crear_progreso("Generando corrección (FixSuggesterAgent)...");
+    let result = rt.block_on(orchestrator.execute_task("FixSuggesterAgent", &task, agent_context));
+    pb.finish_and_clear();
+
+    let res = match result {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{} Error al generar la corrección: {}", "❌".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let bloques = crate::ai::utils::extraer_todos_bloques(&res.output);
+    if bloques.is_empty() {
+        println!("{}", res.output);
+        println!("\n{} La IA no devolvió bloques de código aplicables.", "⚠️".yellow());
+        return;
+    }
+
+    if dry_run {
+        super::apply::previsualizar_bloques(&agent_context.project_root, &bloques);
+        return;
+    }
+
+    super::apply::listar_bloques(&bloques);
+    let apply = Confirm::new()
+        .with_prompt("¿Deseas aplicar la corrección?")
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+
+    if apply {
+        let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+        let saved = super::apply::aplicar_bloques(&agent_context.project_root, &bloques, &backups);
+        if saved > 0 {
+            let mut s = agent_context.stats.lock().unwrap();
+            s.sugerencias_aplicadas += 1;
+            s.guardar(&agent_context.project_root);
+        }
+        println!("\n✅ {} archivo(s) guardados.", saved.to_string().green());
+    } else {
+        println!("   ⏭️  Corrección descartada.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{Cli, Commands, ProCommands};
+    use clap::Parser;
+
+    #[test]
+    fn test_fix_dry_run_flag_parses() {
+        let cli = Cli::try_parse_from(["sentinel", "pro", "fix", "src/a.ts", "--dry-run"]).unwrap();
+        match cli.command {
+            Some(Commands::Pro { subcommand: ProCommands::Fix { file, dry_run, no_backup }, .. }) => {
+                assert_eq!(file, "src/a.ts");
+                assert!(dry_run);
+                assert!(!no_backup);
+            }
+            _ => panic!("expected pro fix"),
+        }
+    }
+}
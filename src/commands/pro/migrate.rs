@@ -0,0 +1,185 @@
+use crate::agents::base::{AgentContext, Task, TaskType};
+use crate::agents::orchestrator::AgentOrchestrator;
+use crate::rules::registry::{normalize_framework_name, FrameworkRegistry};
+use crate::rules::FrameworkDefinition;
+use crate::ui;
+use colored::*;
+use dialoguer::Confirm;
+
+/// Valida origen y destino contra el registro de frameworks.
+pub fn validate_frameworks<'a>(
+    registry: &'a FrameworkRegistry,
+    from: &str,
+    to: &str,
+) -> anyhow::Result<(&'a FrameworkDefinition, &'a FrameworkDefinition)> {
+    let unknown = |name: &str| {
+        anyhow::anyhow!(
+            "Framework desconocido: '{}'. Frameworks conocidos: {}",
+            name,
+            registry.names().join(", ")
+        )
+    };
+    let source = registry.find(from).ok_or_else(|| unknown(from))?;
+    let target = registry.find(to).ok_or_else(|| unknown(to))?;
+    if normalize_framework_name(&source.framework) == normalize_framework_name(&target.framework) {
+        return Err(anyhow::anyhow!(
+            "Origen y destino son el mismo framework ({})",
+            source.framework
+        ));
+    }
+    Ok((source, target))
+}
+
+fn build_migration_task(
+    source: &FrameworkDefinition,
+    target: &FrameworkDefinition,
+    file: &str,
+    content: &str,
+) -> Task {
+    let mut context = format!("ARCHIVO ORIGINAL ({}):\n```\n{}\n```\n", file, content);
+
+    if !target.rules.is_empty() {
+        context.push_str(&format!("\nREGLAS DEL FRAMEWORK DESTINO ({}):\n", target.framework));
+        for rule in &target.rules {
+            context.push_str(&format!(" - {}: {}\n", rule.name, rule.description));
+        }
+    }
+    if !target.architecture_patterns.is_empty() {
+        context.push_str("\nCAPAS ESPERADAS EN EL DESTINO:\n");
+        for p in &target.architecture_patterns {
+            context.push_str(&format!(" - {} ({}) → {}\n", p.name, p.selector, p.expected_layer));
+        }
+    }
+
+    Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: format!(
+            "MIGRACIÓN DE FRAMEWORK: {src} → {dst}\n\n\
+            Traduce el archivo '{file}' de {src} ({src_lang}) a {dst} ({dst_lang}).\n\
+            - Conserva la lógica de negocio, rutas, validaciones y manejo de errores.\n\
+            - Adapta estructura, decoradores, inyección de dependencias e imports a las convenciones de {dst}.\n\
+            - Si {dst} requiere varios archivos (ej: controller + service + module), genera un bloque por archivo.",
+            src = source.framework,
+            dst = target.framework,
+            src_lang = source.language,
+            dst_lang = target.language,
+            file = file,
+        ),
+        task_type: TaskType::Refactor,
+        file_path: Some(std::path::PathBuf::from(file)),
+        context: Some(context),
+    }
+}
+
+pub fn handle_migrate(
+    from: &str,
+    to: &str,
+    file: &str,
+    agent_context: &AgentContext,
+    orchestrator: &AgentOrchestrator,
+    output_mode: crate::commands::OutputMode,
+    rt: &tokio::runtime::Runtime,
+) {
+    let registry = FrameworkRegistry::load(&agent_context.project_root);
+    let (source, target) = match validate_frameworks(&registry, from, to) {
+        Ok(pair) => pair,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+
+    let path = match crate::files::secure_join(&agent_context.project_root, std::path::Path::new(file)) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} No se pudo leer '{}': {}", "❌".red(), file, e);
+            std::process::exit(1);
+        }
+    };
+
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!(
+            "\n🔀 Migrando {} de {} a {}...",
+            file.cyan(),
+            source.framework.yellow(),
+            target.framework.green()
+        );
+    }
+
+    let task = build_migration_task(source, target, file, &content);
+    let pb = ui::crear_progreso("Generando migración (FixSuggesterAgent)...");
+    let result = rt.block_on(orchestrator.execute_task("FixSuggesterAgent", &task, agent_context));
+    pb.finish_and_clear();
+
+    let res = match result {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{} Error en la migración: {}", "❌".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let bloques = crate::ai::utils::extraer_todos_bloques(&res.output);
+    if bloques.is_empty() {
+        println!("{}", res.output);
+        println!("\n{} La IA no devolvió bloques de código aplicables.", "⚠️".yellow());
+        return;
+    }
+
+    super::apply::listar_bloques(&bloques);
+    let apply = Confirm::new()
+        .with_prompt("¿Deseas escribir los archivos migrados?")
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+
+    if apply {
+        let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+        let saved = super::apply::aplicar_bloques(&agent_context.project_root, &bloques, &backups);
+        println!("\n✅ {} archivo(s) guardados.", saved.to_string().green());
+    } else {
+        println!("   ⏭️  Migración descartada.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Cli, Commands, ProCommands};
+    use clap::Parser;
+
+    #[test]
+    fn test_migrate_argument_parsing() {
+        let cli = Cli::try_parse_from(["sentinel", "pro", "migrate", "express", "nestjs", "src/app.js"]).unwrap();
+        match cli.command {
+            Some(Commands::Pro { subcommand: ProCommands::Migrate { from, to, file }, .. }) => {
+                assert_eq!(from, "express");
+                assert_eq!(to, "nestjs");
+                assert_eq!(file, "src/app.js");
+            }
+            _ => panic!("expected pro migrate"),
+        }
+        assert!(Cli::try_parse_from(["sentinel", "pro", "migrate", "express"]).is_err());
+    }
+
+    #[test]
+    fn test_validate_frameworks() {
+        let registry = FrameworkRegistry::builtin();
+        let (src, dst) = validate_frameworks(&registry, "express", "NestJS").unwrap();
+        assert_eq!(src.framework, "Express");
+        assert_eq!(dst.framework, "NestJS");
+
+        let err = validate_frameworks(&registry, "express", "rails-on-rust").unwrap_err().to_string();
+        assert!(err.contains("rails-on-rust"));
+        assert!(err.contains("NestJS"), "error must list known frameworks");
+
+        assert!(validate_frameworks(&registry, "nestjs", "NestJS").is_err());
+    }
+}
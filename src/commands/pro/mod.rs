@@ -1,14 +1,31 @@
+pub mod apply;
 pub mod audit;
+pub mod audit_progress;
+pub mod backup;
+pub mod baseline;
 pub mod check;
+pub mod coverage;
+pub mod deadcode;
+pub mod doc;
+pub mod fix;
+pub mod migrate;
+pub mod rename_impact;
 pub mod render;
+pub mod report;
 pub mod review;
+pub mod sampling;
+pub mod score;
+pub mod search;
+pub mod split;
+pub mod test_all;
+pub mod warmup;
 
-pub use render::{render_sarif, get_changed_files, SarifIssue};
+pub use render::{render_sarif, render_github_annotation, get_changed_files, SarifIssue};
 pub use review::{ReviewRecord, save_review_record, load_review_records, diff_reviews};
 pub use audit::AuditIssue;
 
 use crate::agents::base::AgentContext;
-use crate::commands::ProCommands;
+use crate::commands::{ModelOverride, ProCommands};
 use crate::config::SentinelConfig;
 use crate::index::IndexDb;
 use crate::index::ProjectIndexBuilder;
@@ -26,8 +43,235 @@ pub fn format_to_mode(format: &str) -> (bool, bool) {
     (json_mode, sarif_mode)
 }
 
-pub fn handle_pro_command(subcommand: ProCommands, quiet: bool, verbose: bool) {
+/// `--max-file-size` de los comandos que recorren el proyecto; pisa `max_file_bytes`.
+fn max_file_size_override(subcommand: &ProCommands) -> Option<u64> {
+    match subcommand {
+        ProCommands::Check { max_file_size, .. }
+        | ProCommands::Audit { max_file_size, .. }
+        | ProCommands::Report { max_file_size, .. }
+        | ProCommands::Review { max_file_size, .. } => *max_file_size,
+        _ => None,
+    }
+}
+
+/// `--no-backup` de los comandos que sobrescriben archivos; pisa `create_backups`.
+fn no_backup_override(subcommand: &ProCommands) -> bool {
+    match subcommand {
+        ProCommands::Fix { no_backup, .. }
+        | ProCommands::Audit { no_backup, .. }
+        | ProCommands::Split { no_backup, .. }
+        | ProCommands::Review { no_backup, .. } => *no_backup,
+        _ => false,
+    }
+}
+
+/// `--complexity-threshold` / `--function-length-threshold` de `check`: pisan `[rule_config]`
+/// solo para esta ejecución (no se escriben en `.sentinelrc.toml`).
+fn threshold_overrides(subcommand: &ProCommands, mut rule_config: crate::config::RuleConfig) -> crate::config::RuleConfig {
+    if let ProCommands::Check { complexity_threshold, function_length_threshold, .. } = subcommand {
+        if let Some(n) = complexity_threshold {
+            rule_config.complexity_threshold = n.get();
+        }
+        if let Some(n) = function_length_threshold {
+            rule_config.function_length_threshold = n.get();
+        }
+    }
+    rule_config
+}
+
+/// `(indexados, en disco)` si el número de archivos del índice difiere del de disco más
+/// que `index.stale_threshold_pct`; `None` si está al día o vacío.
+pub fn stale_index(agent_context: &AgentContext) -> Option<(usize, usize)> {
+    let db = agent_context.index_db.as_ref().filter(|db| db.is_populated())?;
+    let disk_count = count_project_files(&agent_context.project_root, &agent_context.config.file_extensions);
+    let index_count = db.indexed_file_count();
+    let diff = (disk_count as isize - index_count as isize).unsigned_abs();
+    (diff > agent_context.config.index.stale_threshold(disk_count)).then_some((index_count, disk_count))
+}
+
+/// Quita de `files` los que superan `config.max_file_bytes` (0 = sin límite) y deja una nota
+/// atenuada por cada uno en stderr (stdout queda limpio para json/sarif).
+pub fn skip_large_files(
+    files: Vec<std::path::PathBuf>,
+    config: &SentinelConfig,
+    root: &std::path::Path,
+    output_mode: &crate::commands::OutputMode,
+) -> Vec<std::path::PathBuf> {
+    let (kept, skipped) = crate::files::filtrar_por_tamano(files, config.max_file_bytes);
+    if *output_mode != crate::commands::OutputMode::Quiet {
+        for (path, size) in &skipped {
+            let rel = path.strip_prefix(root).unwrap_or(path).display().to_string();
+            eprintln!(
+                "{}",
+                format!(
+                    "   ⏭️  {} omitido: {} KB supera el límite de {} KB (--max-file-size)",
+                    rel,
+                    size / 1024,
+                    config.max_file_bytes / 1024
+                )
+                .dimmed()
+            );
+        }
+    }
+    kept
+}
+
+/// Aplica `--profile` (o `SENTINEL_PROFILE`) y luego `--model` / `--provider` sobre una
+/// copia de la configuración.
+///
+/// Al cambiar de proveedor, la URL y la API key se toman de `{PROVEEDOR}_BASE_URL` y
+/// `{PROVEEDOR}_API_KEY` (o la URL por defecto del proveedor). Nada se guarda en disco.
+pub fn apply_model_override(config: &SentinelConfig, model_override: &ModelOverride) -> anyhow::Result<SentinelConfig> {
+    let mut config = config.clone().with_active_profile(model_override.profile.as_deref())?;
+    if let Some(provider) = model_override.provider.as_deref() {
+        let provider = provider.to_lowercase();
+        if !crate::config::KNOWN_PROVIDERS.contains(&provider.as_str()) {
+            anyhow::bail!(
+                "proveedor desconocido '{}'. Válidos: {}",
+                provider,
+                crate::config::KNOWN_PROVIDERS.join(", ")
+            );
+        }
+        if provider != config.primary_model.provider {
+            let env_prefix = provider.to_uppercase().replace('-', "_");
+            let pm = &mut config.primary_model;
+            pm.url = env::var(format!("{}_BASE_URL", env_prefix))
+                .unwrap_or_else(|_| crate::config::default_provider_url(&provider).to_string());
+            pm.api_key = env::var(format!("{}_API_KEY", env_prefix)).unwrap_or_default();
+            pm.deployment = None;
+            pm.api_version = None;
+            pm.provider = provider;
+        }
+    }
+    if let Some(model) = model_override.model.as_deref() {
+        if model.trim().is_empty() {
+            anyhow::bail!("--model no puede estar vacío");
+        }
+        config.primary_model.name = model.trim().to_string();
+    }
+    Ok(config)
+}
+
+/// Umbral de `--fail-on`: severidad mínima de hallazgo que hace salir con código 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailOn {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Never,
+}
+
+impl FailOn {
+    /// Valores desconocidos caen en `error` (clap ya restringe los aceptados).
+    pub fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "warning" => Self::Warning,
+            "info" => Self::Info,
+            "never" => Self::Never,
+            _ => Self::Error,
+        }
+    }
+
+    /// Decide el código de salida por los hallazgos, independientemente del formato de salida.
+    pub fn should_fail(self, errors: usize, warnings: usize, infos: usize) -> bool {
+        match self {
+            Self::Error => errors > 0,
+            Self::Warning => errors + warnings > 0,
+            Self::Info => errors + warnings + infos > 0,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Severidad de un issue de `audit` o impacto de una sugerencia de `review`. Los modelos la
+/// devuelven con cualquier capitalización y con sinónimos (`Critical`, `minor`, `Alta`...):
+/// se normaliza al parsear para que ningún hallazgo se pierda en los conteos por nivel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl std::str::FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "high" | "critical" | "blocker" | "severe" | "major" | "alta" | "alto" | "crítica" | "critica"
+            | "crítico" | "critico" => Ok(Self::High),
+            "medium" | "moderate" | "normal" | "media" | "medio" | "moderada" | "moderado" => Ok(Self::Medium),
+            "low" | "minor" | "trivial" | "info" | "baja" | "bajo" | "menor" => Ok(Self::Low),
+            other => anyhow::bail!("severidad desconocida '{}' (usa high, medium o low)", other),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Severity {
+    /// Una etiqueta no reconocida cuenta como `Medium`: el issue se conserva en vez de
+    /// invalidar toda la respuesta del modelo.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(raw.parse().unwrap_or(Self::Medium))
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::High => "High",
+            Self::Medium => "Medium",
+            Self::Low => "Low",
+        })
+    }
+}
+
+/// `review`/`audit` hacen muchas llamadas al modelo: antes de empezar (solo si las van a
+/// hacer) se comprueba que el primario (o, si falla, el de fallback) responde, y si
+/// ninguno lo hace se sale con 2.
+fn ensure_ai_reachable(config: &SentinelConfig, output_mode: &crate::commands::OutputMode) {
+    use crate::ai::providers::{ping_model, PING_TIMEOUT};
+    let Err(primary_err) = ping_model(&config.primary_model, PING_TIMEOUT) else {
+        return;
+    };
+    if let Some(fallback) = config.fallback_model.as_ref()
+        && ping_model(fallback, PING_TIMEOUT).is_ok()
+    {
+        if *output_mode != crate::commands::OutputMode::Quiet {
+            eprintln!(
+                "{} Modelo primario no disponible ({:#}); se usará '{}'.",
+                "⚠️".yellow(),
+                primary_err,
+                fallback.name
+            );
+        }
+        return;
+    }
+    eprintln!("{} {:#}", "❌".red(), primary_err);
+    std::process::exit(2);
+}
+
+/// Compila el patrón de `--grep` (sin distinguir mayúsculas).
+pub fn parse_grep(pattern: Option<&str>) -> anyhow::Result<Option<regex::Regex>> {
+    pattern
+        .map(|p| {
+            regex::RegexBuilder::new(p)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| anyhow::anyhow!("--grep inválido '{}': {}", p, e))
+        })
+        .transpose()
+}
+
+/// Un hallazgo pasa `--grep` si la regex coincide con su regla/título o con su mensaje.
+pub fn grep_matches(re: &regex::Regex, rule: &str, message: &str) -> bool {
+    re.is_match(rule) || re.is_match(message)
+}
+
+pub fn handle_pro_command(subcommand: ProCommands, quiet: bool, verbose: bool, model_override: &ModelOverride) {
     let output_mode = crate::commands::get_output_mode(quiet, verbose);
+    crate::commands::set_global_output_mode(&output_mode);
 
     // Buscar la raíz del proyecto inteligentemente
     let project_root = SentinelConfig::find_project_root()
@@ -55,6 +299,31 @@ pub fn handle_pro_command(subcommand: ProCommands, quiet: bool, verbose: bool) {
         }
         SentinelConfig::default()
     });
+    let config = match apply_model_override(&config, model_override) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    let config = match max_file_size_override(&subcommand) {
+        Some(max_file_bytes) => SentinelConfig { max_file_bytes, ..config },
+        None => config,
+    };
+    let config = if no_backup_override(&subcommand) {
+        SentinelConfig { create_backups: false, ..config }
+    } else {
+        config
+    };
+    let rule_config = threshold_overrides(&subcommand, config.rule_config.clone());
+    let config = SentinelConfig { rule_config, ..config };
+    if model_override.model.is_some() || model_override.provider.is_some() {
+        log::debug!(
+            "modelo para esta ejecución: {} ({})",
+            config.primary_model.name,
+            config.primary_model.provider
+        );
+    }
 
     let db_path = project_root.join(".sentinel/index.db");
     let index_db = match IndexDb::open(&db_path) {
@@ -81,24 +350,52 @@ pub fn handle_pro_command(subcommand: ProCommands, quiet: bool, verbose: bool) {
     orchestrator.register(Arc::new(crate::agents::reviewer::ReviewerAgent::new()));
     orchestrator.register(Arc::new(crate::agents::tester::TesterAgent::new()));
     orchestrator.register(Arc::new(crate::agents::splitter::SplitterAgent::new()));
+    orchestrator.register(Arc::new(crate::agents::documenter::DocAgent::new()));
 
     // Ejecutar en Runtime de Tokio
     let rt = tokio::runtime::Runtime::new().unwrap();
 
     // Detect JSON/SARIF mode before dispatching (to suppress indexing messages in machine-readable output)
     let json_mode_global = match &subcommand {
-        ProCommands::Check { format, .. } => {
+        // --quiet-success: stdout vacío si no hay hallazgos, sin avisos previos
+        ProCommands::Check { format, annotate, stdin, quiet_success, .. } => {
             let fmt = format.to_lowercase();
-            fmt == "json" || fmt == "sarif"
+            stdin.is_some()
+                || *quiet_success
+                || fmt == "json"
+                || fmt == "jsonl"
+                || fmt == "sarif"
+                || check::is_github_mode(format, *annotate)
         }
         ProCommands::Audit { format, .. } => format.to_lowercase() == "json",
+        ProCommands::Coverage { format, .. } => format.to_lowercase() == "json",
+        ProCommands::Score { format, .. } => format.to_lowercase() == "json",
+        ProCommands::Search { format, .. } => format.to_lowercase() == "json",
+        ProCommands::DeadCode { format } => format.to_lowercase() == "json",
+        ProCommands::RenameImpact { format, .. } => format.to_lowercase() == "json",
+        ProCommands::Workflow { list, format, .. } => *list && format.to_lowercase() == "json",
+        // El reporte siempre se emite como documento (json/html/markdown) por stdout
+        ProCommands::Report { .. } => true,
         _ => false,
     };
 
+    // `check --stdin` se invoca en cada pulsación: nunca dispara una indexación completa
+    let stdin_mode = matches!(&subcommand, ProCommands::Check { stdin: Some(_), .. });
+
     // Auto-indexación: si el índice está vacío, indexar en background mientras corre el comando
     let mut index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>> = None;
-    if let Some(ref db) = agent_context.index_db {
-        if !db.is_populated() {
+    if let Some(ref db) = agent_context.index_db
+        && !stdin_mode
+    {
+        if !db.is_populated() && !agent_context.config.index.auto_index {
+            if output_mode != crate::commands::OutputMode::Quiet {
+                eprintln!(
+                    "\n{} Índice vacío y auto_index desactivado. Corre {} para indexar.",
+                    "ℹ️".cyan(),
+                    "`sentinel index --rebuild`".cyan()
+                );
+            }
+        } else if !db.is_populated() {
             if !json_mode_global && output_mode != crate::commands::OutputMode::Quiet {
                 println!(
                     "\n{} {}",
@@ -117,57 +414,100 @@ pub fn handle_pro_command(subcommand: ProCommands, quiet: bool, verbose: bool) {
     }
 
     // Stale-index warning: warn once if disk file count diverges significantly from index
-    if !json_mode_global && output_mode != crate::commands::OutputMode::Quiet {
-        if let Some(ref db) = agent_context.index_db {
-            if db.is_populated() {
-                let disk_count = count_project_files(
-                    &agent_context.project_root,
-                    &agent_context.config.file_extensions,
-                );
-                let index_count = db.indexed_file_count();
-                let diff = (disk_count as isize - index_count as isize).unsigned_abs();
-                let stale_threshold = 5.max(disk_count / 10);
-                if diff > stale_threshold {
-                    println!(
-                        "\n{} {} ({} indexados, {} en disco).",
-                        "⚠️".yellow(),
-                        "Índice posiblemente desactualizado".yellow(),
-                        index_count,
-                        disk_count
-                    );
-                    println!(
-                        "   Corre {} para actualizar.\n",
-                        "`sentinel index --rebuild`".cyan()
-                    );
-                }
-            }
-        }
+    if !json_mode_global && output_mode != crate::commands::OutputMode::Quiet
+        && let Some((index_count, disk_count)) = stale_index(&agent_context)
+    {
+        println!(
+            "\n{} {} ({} indexados, {} en disco).",
+            "⚠️".yellow(),
+            "Índice posiblemente desactualizado".yellow(),
+            index_count,
+            disk_count
+        );
+        println!(
+            "   Corre {} para actualizar.\n",
+            "`sentinel index --rebuild`".cyan()
+        );
     }
 
+    let mut out = crate::commands::output::OutputSink::stdout();
     match subcommand {
-        ProCommands::Check { target, format } => {
-            check::handle_check(target, format, quiet, verbose, &agent_context, output_mode, index_handle);
+        ProCommands::Check { target, format, show_suppressed, annotate, risk, baseline, write_baseline, watch, fail_on, stdin, grep, only, explain, quiet_success, .. } => {
+            let fail_on = FailOn::parse(&fail_on);
+            let target = target.unwrap_or_default();
+            let options = check::CheckOptions { target, format, show_suppressed, annotate, risk, baseline, write_baseline, watch, fail_on, grep, only, explain, quiet_success };
+            if let Some(virtual_path) = stdin {
+                check::handle_check_stdin(&virtual_path, &options, &agent_context, &mut out);
+            } else if watch {
+                check::handle_check_watch(&options, quiet, verbose, &agent_context, output_mode, index_handle, &mut out);
+            } else {
+                check::handle_check(&options, quiet, verbose, &agent_context, output_mode, index_handle, &mut out);
+            }
         }
-        ProCommands::Review { history, diff } => {
-            review::handle_review(history, diff, quiet, verbose, &agent_context, output_mode, &rt);
+        ProCommands::Review { history, diff, context_lines, apply, yes, .. } => {
+            // --history y --diff solo leen reviews guardados: no llaman al modelo
+            if !history && !diff {
+                ensure_ai_reachable(&agent_context.config, &output_mode);
+            }
+            // clap ya restringe --apply a high/medium/low
+            let apply = apply.and_then(|a| a.parse::<Severity>().ok());
+            let options = review::ReviewOptions { history, diff, context_lines, apply, yes };
+            review::handle_review(&options, quiet, verbose, &agent_context, output_mode, &rt);
         }
-        ProCommands::Audit { target, no_fix, format, max_files, concurrency } => {
-            audit::handle_audit(target, no_fix, format, max_files, concurrency, quiet, verbose, &agent_context, output_mode, index_handle, &rt);
+        ProCommands::Audit { target, no_fix, format, max_files, concurrency, dry_run, fail_on, context_lines, grep, group_by, severity_min, .. } => {
+            let fail_on = FailOn::parse(&fail_on);
+            let concurrency = audit::Concurrency::parse(&concurrency).unwrap_or_else(|e| {
+                eprintln!("{} {}", "❌".red(), e);
+                std::process::exit(2);
+            });
+            ensure_ai_reachable(&agent_context.config, &output_mode);
+            // clap ya restringe --group-by a module/severity/file
+            let group_by = group_by.as_deref().and_then(audit::AuditGroupBy::parse);
+            // `low` (default) no descarta nada
+            let severity_min = severity_min.parse::<Severity>().ok().filter(|s| *s > Severity::Low);
+            let options = audit::AuditOptions { target, no_fix, format, max_files, concurrency, dry_run, fail_on, context_lines, grep, group_by, severity_min };
+            audit::handle_audit(&options, quiet, verbose, &agent_context, output_mode, index_handle, &rt);
         }
         ProCommands::Analyze { file } => {
             handle_analyze(&file, &agent_context, &orchestrator, output_mode, &rt);
         }
-        ProCommands::Report { format } => {
-            handle_report(&format, &agent_context, output_mode, &rt);
+        ProCommands::Report { format, since, output, fail_on, .. } => {
+            let fail_on = FailOn::parse(&fail_on);
+            let options = report::ReportOptions { format, since, output, fail_on };
+            report::handle_report(&options, &agent_context, output_mode, &mut out, &rt);
+        }
+        ProCommands::Split { file, barrel, .. } => {
+            split::handle_split(&file, barrel, &agent_context, &orchestrator, output_mode, &rt);
+        }
+        ProCommands::Fix { file, dry_run, .. } => {
+            fix::handle_fix(&file, dry_run, &agent_context, &orchestrator, output_mode, &rt);
+        }
+        ProCommands::Doc { file, symbol, dry_run } => {
+            doc::handle_doc(&file, &symbol, dry_run, &agent_context, &orchestrator, output_mode, &rt);
+        }
+        ProCommands::TestAll { target } => {
+            test_all::handle_test_all(&target, &agent_context, &orchestrator, output_mode, &rt);
         }
-        ProCommands::Split { file } => {
-            handle_split(&file, &agent_context, &orchestrator, output_mode, &rt);
+        ProCommands::Search { query, limit, format } => {
+            search::handle_search(&query, limit, &format, &agent_context, output_mode);
         }
-        ProCommands::Fix { file } => {
-            handle_fix(&file, &agent_context, &orchestrator, output_mode, &rt);
+        ProCommands::Coverage { target, format, min_coverage } => {
+            coverage::handle_coverage(&target, &format, min_coverage, &agent_context, output_mode);
         }
-        ProCommands::TestAll => {
-            handle_test_all(&agent_context, &orchestrator, output_mode, &rt);
+        ProCommands::Score { target, format } => {
+            score::handle_score(&target, &format, &agent_context, output_mode);
+        }
+        ProCommands::Warmup { target } => {
+            warmup::handle_warmup(&target, &agent_context, output_mode, index_handle);
+        }
+        ProCommands::DeadCode { format } => {
+            deadcode::handle_deadcode(&format, &agent_context, output_mode, index_handle);
+        }
+        ProCommands::RenameImpact { symbol, format } => {
+            rename_impact::handle_rename_impact(&symbol, &format, &agent_context, output_mode, index_handle);
+        }
+        ProCommands::Restore { file } => {
+            backup::handle_restore(&file, &agent_context, output_mode);
         }
         ProCommands::Ml { subcommand } => {
             handle_ml(subcommand, &agent_context, output_mode, &rt);
@@ -175,8 +515,14 @@ pub fn handle_pro_command(subcommand: ProCommands, quiet: bool, verbose: bool) {
         ProCommands::CleanCache { target } => {
             handle_clean_cache(target.as_deref(), &agent_context, output_mode);
         }
-        ProCommands::Workflow { name, file } => {
-            handle_workflow(&name, file.as_deref(), &agent_context, &orchestrator, output_mode, &rt);
+        ProCommands::Migrate { from, to, file } => {
+            migrate::handle_migrate(&from, &to, &file, &agent_context, &orchestrator, output_mode, &rt);
+        }
+        ProCommands::Workflow { list: true, format, .. } => {
+            handle_workflow_list(&format, &agent_context, output_mode);
+        }
+        ProCommands::Workflow { name, file, .. } => {
+            handle_workflow(name.as_deref().unwrap_or_default(), file.as_deref(), &agent_context, &orchestrator, &rt);
         }
     }
 }
@@ -195,56 +541,6 @@ fn handle_analyze(
     }
 }
 
-fn handle_report(
-    format: &str,
-    _agent_context: &AgentContext,
-    output_mode: crate::commands::OutputMode,
-    _rt: &tokio::runtime::Runtime,
-) {
-    // Placeholder
-    if output_mode != crate::commands::OutputMode::Quiet {
-        println!("Report handler stub: {}", format);
-    }
-}
-
-fn handle_split(
-    _file: &str,
-    _agent_context: &AgentContext,
-    _orchestrator: &crate::agents::orchestrator::AgentOrchestrator,
-    output_mode: crate::commands::OutputMode,
-    _rt: &tokio::runtime::Runtime,
-) {
-    // Placeholder
-    if output_mode != crate::commands::OutputMode::Quiet {
-        println!("Split handler stub");
-    }
-}
-
-fn handle_fix(
-    _file: &str,
-    _agent_context: &AgentContext,
-    _orchestrator: &crate::agents::orchestrator::AgentOrchestrator,
-    output_mode: crate::commands::OutputMode,
-    _rt: &tokio::runtime::Runtime,
-) {
-    // Placeholder
-    if output_mode != crate::commands::OutputMode::Quiet {
-        println!("Fix handler stub");
-    }
-}
-
-fn handle_test_all(
-    _agent_context: &AgentContext,
-    _orchestrator: &crate::agents::orchestrator::AgentOrchestrator,
-    output_mode: crate::commands::OutputMode,
-    _rt: &tokio::runtime::Runtime,
-) {
-    // Placeholder
-    if output_mode != crate::commands::OutputMode::Quiet {
-        println!("TestAll handler stub");
-    }
-}
-
 fn handle_ml(
     _subcommand: crate::commands::MlCommands,
     _agent_context: &AgentContext,
@@ -272,16 +568,79 @@ fn handle_clean_cache(
 }
 
 fn handle_workflow(
-    _name: &str,
-    _file: Option<&str>,
-    _agent_context: &AgentContext,
-    _orchestrator: &crate::agents::orchestrator::AgentOrchestrator,
-    output_mode: crate::commands::OutputMode,
-    _rt: &tokio::runtime::Runtime,
+    name: &str,
+    file: Option<&str>,
+    agent_context: &AgentContext,
+    orchestrator: &crate::agents::orchestrator::AgentOrchestrator,
+    rt: &tokio::runtime::Runtime,
 ) {
-    // Placeholder
+    let available = crate::agents::workflow::available_workflows(&agent_context.project_root);
+    let Some(workflow) = available.into_iter().map(|w| w.workflow).find(|w| w.name == name) else {
+        eprintln!(
+            "{} Workflow '{}' no encontrado. Consulta los disponibles con {}",
+            "❌".red(),
+            name,
+            "`sentinel pro workflow --list`".cyan()
+        );
+        std::process::exit(2);
+    };
+    // Un YAML con un agente mal escrito fallaría a mitad de camino, tras aplicar pasos previos
+    let missing = workflow.missing_agents(orchestrator);
+    if !missing.is_empty() {
+        eprintln!(
+            "{} El workflow '{}' usa agentes desconocidos: {}. Disponibles: {}",
+            "❌".red(),
+            workflow.name,
+            missing.join(", "),
+            orchestrator.agent_names().join(", ")
+        );
+        std::process::exit(2);
+    }
+
+    let engine = crate::agents::workflow::WorkflowEngine::new(orchestrator);
+    if let Err(e) = rt.block_on(engine.execute_workflow(&workflow, agent_context, file.map(str::to_string))) {
+        eprintln!("{} {}", "❌".red(), e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_workflow_list(format: &str, agent_context: &AgentContext, output_mode: crate::commands::OutputMode) {
+    let (json_mode, _) = format_to_mode(format);
+    let available = crate::agents::workflow::available_workflows(&agent_context.project_root);
+    let source = |path: &Option<std::path::PathBuf>| match path {
+        Some(p) => p.strip_prefix(&agent_context.project_root).unwrap_or(p).display().to_string(),
+        None => "built-in".to_string(),
+    };
+
+    if json_mode {
+        let workflows: Vec<serde_json::Value> = available
+            .iter()
+            .map(|w| {
+                serde_json::json!({
+                    "name": w.workflow.name,
+                    "description": w.workflow.description,
+                    "steps": w.workflow.steps.len(),
+                    "source": source(&w.path),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "workflows": workflows })).unwrap_or_default());
+        return;
+    }
+
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!("\n{}", "🔄 Workflows disponibles".bold());
+    }
+    for w in &available {
+        println!(
+            "\n   {} {}",
+            w.workflow.name.cyan().bold(),
+            format!("({} paso(s), {})", w.workflow.steps.len(), source(&w.path)).dimmed()
+        );
+        println!("      {}", w.workflow.description);
+    }
     if output_mode != crate::commands::OutputMode::Quiet {
-        println!("Workflow handler stub");
+        println!("\n   Ejecuta uno con {}", "`sentinel pro workflow <nombre> [archivo]`".cyan());
     }
 }
 
@@ -310,6 +669,150 @@ mod tests {
         assert!(!sarif);
     }
 
+    #[test]
+    fn test_model_override_replaces_primary_model_only_in_copy() {
+        let config = SentinelConfig::default();
+        let ov = ModelOverride { model: Some("claude-3-5-haiku-20241022".to_string()), provider: None, profile: None };
+        let overridden = apply_model_override(&config, &ov).unwrap();
+        assert_eq!(overridden.primary_model.name, "claude-3-5-haiku-20241022");
+        assert_eq!(overridden.primary_model.provider, config.primary_model.provider);
+        assert_ne!(config.primary_model.name, overridden.primary_model.name);
+
+        let ov = ModelOverride { model: Some("llama3.1".to_string()), provider: Some("ollama".to_string()), profile: None };
+        let overridden = apply_model_override(&config, &ov).unwrap();
+        assert_eq!(overridden.primary_model.name, "llama3.1");
+        assert_eq!(overridden.primary_model.provider, "ollama");
+
+        let ov = ModelOverride { model: None, provider: Some("acme-ai".to_string()), profile: None };
+        let err = apply_model_override(&config, &ov).unwrap_err().to_string();
+        assert!(err.contains("acme-ai") && err.contains("anthropic"));
+    }
+
+    #[test]
+    fn test_threshold_flags_override_rule_config_for_check() {
+        use clap::Parser;
+        let parse = |args: &[&str]| match crate::commands::Cli::try_parse_from(args) {
+            Ok(cli) => match cli.command {
+                Some(crate::commands::Commands::Pro { subcommand, .. }) => Ok(subcommand),
+                _ => panic!("expected pro check"),
+            },
+            Err(e) => Err(e),
+        };
+        // Complejidad 8 y 14 líneas: por debajo de los umbrales por defecto (10 y 50)
+        let source = "export function route(a: number): string {\n  if (a === 1) return 'a';\n  if (a === 2) return 'b';\n  if (a === 3) return 'c';\n  if (a === 4) return 'd';\n  if (a === 5) return 'e';\n  if (a === 6) return 'f';\n  if (a === 7) return 'g';\n  const rest = String(a);\n  const trimmed = rest.trim();\n  const upper = trimmed.toUpperCase();\n  const lower = upper.toLowerCase();\n  return lower;\n}\n";
+        // Complejidad 4 y 6 líneas: por debajo del piso de los analizadores (5 y 10)
+        let small = "export function pick(a: number): string {\n  if (a === 1) return 'a';\n  if (a === 2) return 'b';\n  if (a === 3) return 'c';\n  const s = String(a);\n  return s;\n}\n";
+        let tmp = tempfile::tempdir().unwrap();
+        let issues = |subcommand: &ProCommands, source: &str| {
+            let config = SentinelConfig {
+                rule_config: threshold_overrides(subcommand, crate::config::RuleConfig::default()),
+                ..Default::default()
+            };
+            let ctx = AgentContext {
+                config: Arc::new(config),
+                stats: Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+                project_root: tmp.path().to_path_buf(),
+                index_db: None,
+            };
+            let out = check::check_source("src/route.ts", source, &ctx, &check::CheckOptions::default());
+            let mut issues: Vec<(String, String)> = out["issues"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| (i["rule"].as_str().unwrap().to_string(), i["message"].as_str().unwrap().to_string()))
+                .collect();
+            issues.retain(|(r, _)| r == "HIGH_COMPLEXITY" || r == "FUNCTION_TOO_LONG");
+            issues
+        };
+        let flagged = |subcommand: &ProCommands| -> Vec<String> {
+            issues(subcommand, source).into_iter().map(|(rule, _)| rule).collect()
+        };
+
+        let default = parse(&["sentinel", "pro", "check", "src"]).unwrap();
+        assert!(flagged(&default).is_empty());
+        let overridden =
+            parse(&["sentinel", "pro", "check", "src", "--complexity-threshold", "6", "--function-length-threshold", "12"]).unwrap();
+        assert_eq!(flagged(&overridden), vec!["HIGH_COMPLEXITY", "FUNCTION_TOO_LONG"]);
+        assert!(issues(&overridden, source)[0].1.contains("máximo recomendado: 6"), "{:?}", issues(&overridden, source));
+
+        // Un umbral por debajo del piso también se respeta
+        assert!(issues(&default, small).is_empty());
+        let below_floor =
+            parse(&["sentinel", "pro", "check", "src", "--complexity-threshold", "3", "--function-length-threshold", "5"]).unwrap();
+        let below = issues(&below_floor, small);
+        assert_eq!(below.iter().map(|(r, _)| r.as_str()).collect::<Vec<_>>(), vec!["HIGH_COMPLEXITY", "FUNCTION_TOO_LONG"]);
+        assert!(below[1].1.contains("máximo recomendado: 5"), "{:?}", below);
+        assert!(parse(&["sentinel", "pro", "check", "src", "--complexity-threshold", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_fail_on_exit_decision_with_one_warning() {
+        // Fixture: una violación warning, ningún error ni info
+        let (errors, warnings, infos) = (0, 1, 0);
+        assert!(!FailOn::parse("error").should_fail(errors, warnings, infos));
+        assert!(FailOn::parse("warning").should_fail(errors, warnings, infos));
+        assert!(FailOn::parse("info").should_fail(errors, warnings, infos));
+        assert!(!FailOn::parse("never").should_fail(errors, warnings, infos));
+        assert!(!FailOn::Never.should_fail(3, 0, 0), "never ignora incluso errores");
+        assert_eq!(FailOn::parse("WARNING"), FailOn::Warning);
+    }
+
+    #[test]
+    fn test_severity_normalizes_case_and_synonyms() {
+        for (raw, expected) in [
+            ("HIGH", Severity::High),
+            ("high", Severity::High),
+            ("Critical", Severity::High),
+            (" Alta ", Severity::High),
+            ("Medium", Severity::Medium),
+            ("moderate", Severity::Medium),
+            ("minor", Severity::Low),
+            ("LOW", Severity::Low),
+        ] {
+            assert_eq!(raw.parse::<Severity>().unwrap(), expected, "{}", raw);
+        }
+        assert!("urgentísimo".parse::<Severity>().is_err());
+        assert!(Severity::High > Severity::Medium && Severity::Medium > Severity::Low);
+
+        // En la respuesta del modelo, lo no reconocido cuenta como Medium y se serializa normalizado
+        let issues: Vec<AuditIssue> = serde_json::from_str(
+            r#"[{"title":"a","description":"","severity":"Critical","suggested_fix":""},
+                {"title":"b","description":"","severity":"whatever","suggested_fix":""}]"#,
+        )
+        .unwrap();
+        assert_eq!((issues[0].severity, issues[1].severity), (Severity::High, Severity::Medium));
+        assert_eq!(serde_json::to_value(&issues[0]).unwrap()["severity"], "High");
+    }
+
+    #[test]
+    fn test_no_backup_flag_applies_fix_without_backup() {
+        use clap::Parser;
+        let subcommand = match crate::commands::Cli::try_parse_from(["sentinel", "pro", "fix", "src/a.ts", "--no-backup"]) {
+            Ok(crate::commands::Cli { command: Some(crate::commands::Commands::Pro { subcommand, .. }), .. }) => subcommand,
+            _ => panic!("expected pro fix"),
+        };
+        assert!(no_backup_override(&subcommand));
+        let config = SentinelConfig { create_backups: !no_backup_override(&subcommand), ..Default::default() };
+
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(root.join("src/big.ts"), "x".repeat(300)).unwrap();
+        let backups = backup::Backups::for_config(root, &config);
+
+        let bloques = vec![
+            (Some("src/a.ts".to_string()), "export const a = 2;\n".to_string()),
+            (Some("src/big.ts".to_string()), "tiny".to_string()),
+        ];
+        assert_eq!(apply::aplicar_bloques(root, &bloques, &backups), 1);
+        assert_eq!(std::fs::read_to_string(root.join("src/a.ts")).unwrap(), "export const a = 2;\n");
+        // El guard de truncado sigue activo sin copias
+        assert_eq!(std::fs::read_to_string(root.join("src/big.ts")).unwrap().len(), 300);
+        assert!(!root.join(crate::config::DEFAULT_BACKUP_DIR).exists());
+        assert!(!root.join("src/a.ts.bak").exists());
+    }
+
     #[test]
     fn test_format_to_mode_case_insensitive() {
         let (json, _) = format_to_mode("JSON");
@@ -0,0 +1,251 @@
+//! `pro rename-impact <símbolo>`: todo lo que habría que actualizar al renombrar un símbolo.
+//!
+//! Solo análisis, no edita nada. Junta las definiciones y las llamadas del call graph con
+//! los archivos que importan el símbolo (`import_usage`) y las agrupa por archivo. El
+//! índice no guarda la línea de los imports: se busca en el archivo la línea de import
+//! que nombra al símbolo.
+
+use crate::index::call_graph::CallGraph;
+use crate::index::IndexDb;
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKind {
+    Definition,
+    Import,
+    Call,
+}
+
+impl ReferenceKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Definition => "definición",
+            Self::Import => "import",
+            Self::Call => "llamada",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImpactLocation {
+    pub file: String,
+    pub line: Option<usize>,
+    pub kind: ReferenceKind,
+}
+
+/// Resultado de `rename_impact`, ordenado por archivo y línea.
+#[derive(Debug, Default)]
+pub struct RenameImpact {
+    pub locations: Vec<ImpactLocation>,
+}
+
+impl RenameImpact {
+    /// Llamadas e imports (lo que rompe al renombrar, sin contar las definiciones).
+    pub fn references(&self) -> usize {
+        self.locations.iter().filter(|l| l.kind != ReferenceKind::Definition).count()
+    }
+
+    pub fn by_file(&self) -> BTreeMap<&str, Vec<&ImpactLocation>> {
+        let mut groups: BTreeMap<&str, Vec<&ImpactLocation>> = BTreeMap::new();
+        for location in &self.locations {
+            groups.entry(location.file.as_str()).or_default().push(location);
+        }
+        groups
+    }
+}
+
+/// Primera línea (1-based) de `file` que importa `symbol`.
+fn import_line(file: &Path, symbol: &str) -> Option<usize> {
+    let content = std::fs::read_to_string(file).ok()?;
+    let word = regex::Regex::new(&format!(r"\b{}\b", regex::escape(symbol))).ok()?;
+    let mut in_import = false;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        // `import {` de varias líneas: el símbolo puede estar en una línea intermedia
+        let starts_import = trimmed.starts_with("import ")
+            || trimmed.starts_with("from ")
+            || trimmed.starts_with("use ")
+            || trimmed.contains("require(");
+        if (starts_import || in_import) && word.is_match(line) {
+            return Some(i + 1);
+        }
+        if starts_import && trimmed.ends_with('{') {
+            in_import = true;
+        } else if in_import && trimmed.contains('}') {
+            in_import = false;
+        }
+    }
+    None
+}
+
+pub fn rename_impact(db: &IndexDb, project_root: &Path, symbol: &str) -> anyhow::Result<RenameImpact> {
+    let graph = CallGraph::new(db);
+    let refs = graph.find_references(symbol)?;
+
+    let mut locations = Vec::new();
+    for def in &refs.definitions {
+        locations.push(ImpactLocation {
+            file: def.definition.file_path.clone(),
+            line: def.definition.line,
+            kind: ReferenceKind::Definition,
+        });
+        for caller in &def.callers {
+            locations.push(ImpactLocation { file: caller.file_path.clone(), line: caller.line, kind: ReferenceKind::Call });
+        }
+    }
+    for caller in &refs.unresolved {
+        locations.push(ImpactLocation { file: caller.file_path.clone(), line: caller.line, kind: ReferenceKind::Call });
+    }
+    for file in graph.importers(symbol)? {
+        let line = import_line(&project_root.join(&file), symbol);
+        locations.push(ImpactLocation { file, line, kind: ReferenceKind::Import });
+    }
+
+    locations.sort_by(|a, b| (&a.file, a.line, a.kind).cmp(&(&b.file, b.line, b.kind)));
+    locations.dedup();
+    Ok(RenameImpact { locations })
+}
+
+pub fn handle_rename_impact(
+    symbol: &str,
+    format: &str,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+    index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+) {
+    let (json_mode, _) = super::format_to_mode(format);
+    // Las referencias salen del índice completo: esperar la auto-indexación si está en curso
+    if let Some(h) = index_handle {
+        let _ = h.join();
+    }
+    let db = match agent_context.index_db {
+        Some(ref db) if db.is_populated() => db,
+        _ => {
+            if json_mode {
+                println!("{{\"error\":\"Índice no disponible\"}}");
+            } else {
+                println!(
+                    "{} Índice no disponible. Corre {} primero.",
+                    "❌".red(),
+                    "`sentinel index --rebuild`".cyan()
+                );
+            }
+            std::process::exit(2);
+        }
+    };
+
+    let impact = match rename_impact(db, &agent_context.project_root, symbol) {
+        Ok(impact) => impact,
+        Err(e) => {
+            if json_mode {
+                println!("{}", serde_json::json!({ "error": e.to_string() }));
+            } else {
+                println!("{} Error consultando el índice: {}", "❌".red(), e);
+            }
+            std::process::exit(2);
+        }
+    };
+    let groups = impact.by_file();
+
+    if json_mode {
+        let out = serde_json::json!({
+            "symbol": symbol,
+            "references": impact.references(),
+            "files": groups.len(),
+            "stale_index": super::stale_index(agent_context).is_some(),
+            "locations": impact.locations,
+        });
+        println!("{}", serde_json::to_string_pretty(&out).unwrap_or_default());
+        return;
+    }
+
+    if impact.locations.is_empty() {
+        println!(
+            "{} '{}' no aparece en el índice (ni definido, ni llamado, ni importado).",
+            "ℹ️".cyan(),
+            symbol
+        );
+        return;
+    }
+
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!("\n{} {}", "✏️  Impacto de renombrar".bold(), symbol.cyan().bold());
+    }
+    for (file, locations) in &groups {
+        println!("\n   {}", file.cyan());
+        for l in locations {
+            let position = match l.line {
+                Some(line) => format!("{}:{}", file, line),
+                None => file.to_string(),
+            };
+            println!("      └─ {} {}", position, format!("({})", l.kind.label()).dimmed());
+        }
+    }
+    println!(
+        "\n   {} {} referencia(s) en {} archivo(s) a actualizar. No se ha modificado nada.",
+        "ℹ️".cyan(),
+        impact.references(),
+        groups.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    #[test]
+    fn test_lists_call_sites_and_imports_across_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src/auth")).unwrap();
+        std::fs::write(
+            root.join("src/auth/login.ts"),
+            "import {\n  Logger,\n  hashPassword,\n} from '../crypto';\n\nexport const login = (p: string) => hashPassword(p);\n",
+        )
+        .unwrap();
+        let db = IndexDb::open(root.join(".sentinel/index.db")).unwrap();
+        {
+            let conn = db.lock();
+            conn.execute(
+                "INSERT INTO symbols (name, kind, file_path, line_start) VALUES ('hashPassword', 'function', 'src/crypto.ts', 2)",
+                [],
+            )
+            .unwrap();
+            for (file, line) in [("src/auth/login.ts", 5), ("src/users/user.service.ts", 11), ("src/users/user.service.ts", 30)] {
+                conn.execute(
+                    "INSERT INTO call_graph (caller_file, caller_symbol, callee_symbol, line_number) VALUES (?, 'login', 'hashPassword', ?)",
+                    params![file, line],
+                )
+                .unwrap();
+            }
+            conn.execute(
+                "INSERT INTO import_usage (file_path, import_name, import_src) VALUES ('src/auth/login.ts', 'hashPassword', '../crypto')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let impact = rename_impact(&db, root, "hashPassword").unwrap();
+        let found: Vec<(&str, Option<usize>, ReferenceKind)> =
+            impact.locations.iter().map(|l| (l.file.as_str(), l.line, l.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("src/auth/login.ts", Some(3), ReferenceKind::Import),
+                ("src/auth/login.ts", Some(6), ReferenceKind::Call),
+                ("src/crypto.ts", Some(3), ReferenceKind::Definition),
+                ("src/users/user.service.ts", Some(12), ReferenceKind::Call),
+                ("src/users/user.service.ts", Some(31), ReferenceKind::Call),
+            ]
+        );
+        assert_eq!(impact.references(), 4);
+        assert_eq!(impact.by_file().len(), 3);
+
+        assert!(rename_impact(&db, root, "missing").unwrap().locations.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
 
 /// Input type for SARIF rendering.
@@ -8,37 +9,81 @@ pub struct SarifIssue {
     pub severity: String,  // "error", "warning", "note"
     pub message: String,
     pub line: Option<usize>,
+    /// Source lines around `line` (see `code_snippet`), used for the partial fingerprint
+    pub snippet: Option<String>,
+}
+
+/// Lines of context on each side of the violation that go into the fingerprint.
+const SNIPPET_CONTEXT: usize = 1;
+
+/// The violation line of `content` (1-based) plus `SNIPPET_CONTEXT` lines around it.
+pub fn code_snippet(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let idx = line.checked_sub(1).filter(|&i| i < lines.len())?;
+    let start = idx.saturating_sub(SNIPPET_CONTEXT);
+    let end = (idx + SNIPPET_CONTEXT + 1).min(lines.len());
+    Some(lines[start..end].join("\n"))
+}
+
+/// Stable hash of (rule, file, snippet without indentation or blank lines). The line number
+/// is left out on purpose so moving code up or down keeps the same alert open.
+fn partial_fingerprint(issue: &SarifIssue) -> String {
+    let normalized: Vec<&str> = issue
+        .snippet
+        .as_deref()
+        .unwrap_or(&issue.message)
+        .lines()
+        .map(str::trim_start)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let mut hasher = Sha256::new();
+    for part in [issue.rule.as_str(), issue.file.as_str(), &normalized.join("\n")] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    format!("{:x}", hasher.finalize())
 }
 
 /// Renders a SARIF 2.1.0 JSON string from a list of issues.
 /// Returns a pretty-printed JSON string compatible with GitHub Security tab.
 pub fn render_sarif(issues: &[SarifIssue]) -> String {
-    // Collect unique rule IDs for the driver.rules array
-    let mut seen_rules: Vec<&str> = Vec::new();
+    // Collect unique rule IDs (first-seen order) for the driver.rules array
+    let mut seen_rules: Vec<&SarifIssue> = Vec::new();
     for issue in issues {
-        if !seen_rules.contains(&issue.rule.as_str()) {
-            seen_rules.push(&issue.rule);
+        if !seen_rules.iter().any(|r| r.rule == issue.rule) {
+            seen_rules.push(issue);
         }
     }
 
-    let rules_json: Vec<serde_json::Value> = seen_rules.iter().map(|r| {
+    let rules_json: Vec<serde_json::Value> = seen_rules.iter().map(|first| {
+        let info = crate::rules::catalog::find_rule(&first.rule);
+        // Custom rules (.sentinel/rules.yaml) are not in the catalog: fall back to the id/message
+        let short = info.map(|i| i.short_description).unwrap_or(first.rule.as_str());
+        let full = info.map(|i| i.full_description).unwrap_or(first.message.as_str());
         serde_json::json!({
-            "id": r,
-            "shortDescription": { "text": r }
+            "id": first.rule,
+            "name": first.rule,
+            "shortDescription": { "text": short },
+            "fullDescription": { "text": full },
+            "helpUri": crate::rules::catalog::help_uri(&first.rule),
+            "defaultConfiguration": { "level": sarif_level(&first.severity) }
         })
     }).collect();
 
+    // Identical snippets in the same file get an occurrence suffix (`:1`, `:2`, ...)
+    let mut occurrences: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
     let results_json: Vec<serde_json::Value> = issues.iter().map(|i| {
-        let level = match i.severity.as_str() {
-            "error"          => "error",
-            "note" | "info"  => "note",
-            _                => "warning",
-        };
+        let rule_index = seen_rules.iter().position(|r| r.rule == i.rule).unwrap_or(0);
         let start_line = i.line.unwrap_or(1);
+        let hash = partial_fingerprint(i);
+        let occurrence = occurrences.entry(hash.clone()).or_insert(0);
+        *occurrence += 1;
         serde_json::json!({
             "ruleId": i.rule,
-            "level": level,
+            "ruleIndex": rule_index,
+            "level": sarif_level(&i.severity),
             "message": { "text": i.message },
+            "partialFingerprints": { "sentinelFingerprint/v1": format!("{}:{}", hash, occurrence) },
             "locations": [{
                 "physicalLocation": {
                     "artifactLocation": {
@@ -70,6 +115,39 @@ pub fn render_sarif(issues: &[SarifIssue]) -> String {
     serde_json::to_string_pretty(&sarif).unwrap_or_default()
 }
 
+/// Normalizes a severity string to a SARIF `level` (`error`, `warning` or `note`).
+fn sarif_level(severity: &str) -> &'static str {
+    match severity {
+        "error"         => "error",
+        "note" | "info" => "note",
+        _               => "warning",
+    }
+}
+
+/// Renders a GitHub Actions workflow command (`::error file=...,line=...,title=RULE::message`)
+/// so the issue shows up as an inline annotation on the PR diff.
+pub fn render_github_annotation(issue: &SarifIssue) -> String {
+    let command = match issue.severity.as_str() {
+        "error"         => "error",
+        "note" | "info" => "notice",
+        _               => "warning",
+    };
+    let mut props = format!("file={}", escape_github_property(&issue.file));
+    if let Some(line) = issue.line {
+        props.push_str(&format!(",line={}", line));
+    }
+    props.push_str(&format!(",title={}", escape_github_property(&issue.rule)));
+    format!("::{} {}::{}", command, props, escape_github_data(&issue.message))
+}
+
+fn escape_github_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_github_property(s: &str) -> String {
+    escape_github_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
 /// Returns absolute paths of files changed in the current working tree (via `git diff --name-only HEAD`).
 /// Silently returns empty Vec if not a git repo or git is unavailable.
 pub fn get_changed_files(project_root: &Path) -> Vec<PathBuf> {
@@ -109,6 +187,7 @@ mod tests {
                 severity: "warning".to_string(),
                 message: "userId no se usa".to_string(),
                 line: Some(23),
+                snippet: None,
             },
         ];
         let sarif = render_sarif(&issues);
@@ -122,6 +201,104 @@ mod tests {
         assert!(parsed["runs"][0]["results"][0]["ruleId"] == "DEAD_CODE");
     }
 
+    #[test]
+    fn test_render_sarif_rules_are_deduplicated_and_indexed() {
+        let issue = |rule: &str, severity: &str| SarifIssue {
+            file: "src/a.ts".to_string(),
+            rule: rule.to_string(),
+            severity: severity.to_string(),
+            message: format!("{} found", rule),
+            line: Some(1),
+            snippet: None,
+        };
+        let issues = vec![
+            issue("DEAD_CODE", "warning"),
+            issue("HIGH_COMPLEXITY", "error"),
+            issue("DEAD_CODE", "warning"),
+            issue("CUSTOM_RULE", "note"),
+        ];
+        let parsed: serde_json::Value = serde_json::from_str(&render_sarif(&issues)).unwrap();
+        let rules = parsed["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        let results = parsed["runs"][0]["results"].as_array().unwrap();
+
+        let ids: Vec<&str> = rules.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["DEAD_CODE", "HIGH_COMPLEXITY", "CUSTOM_RULE"]);
+        for r in rules {
+            assert!(r["shortDescription"]["text"].is_string());
+            assert!(r["fullDescription"]["text"].is_string());
+            assert!(r["helpUri"].as_str().unwrap().contains('#'));
+        }
+
+        assert_eq!(results.len(), 4);
+        for res in results {
+            let idx = res["ruleIndex"].as_u64().unwrap() as usize;
+            assert!(idx < rules.len(), "ruleIndex out of range");
+            assert_eq!(rules[idx]["id"], res["ruleId"]);
+        }
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(results[3]["level"], "note");
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_shifts_and_indentation() {
+        let before = "import { db } from './db';\n\nfunction load() {\n  const unused = 1;\n  return db;\n}\n";
+        // Same function moved down two lines and re-indented
+        let after = "import { db } from './db';\n// header\n// more\n\nfunction load() {\n    const unused = 1;\n    return db;\n}\n";
+        let issue = |content: &str, line: usize| SarifIssue {
+            file: "src/load.ts".to_string(),
+            rule: "DEAD_CODE".to_string(),
+            severity: "warning".to_string(),
+            message: "'unused' no se usa".to_string(),
+            line: Some(line),
+            snippet: code_snippet(content, line),
+        };
+        let fingerprint = |issue: SarifIssue| {
+            let parsed: serde_json::Value = serde_json::from_str(&render_sarif(&[issue])).unwrap();
+            parsed["runs"][0]["results"][0]["partialFingerprints"]["sentinelFingerprint/v1"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+
+        let first = fingerprint(issue(before, 4));
+        assert_eq!(first, fingerprint(issue(after, 6)));
+        assert!(first.ends_with(":1"));
+        // A different rule on the same code is a different alert
+        assert_ne!(first, fingerprint(SarifIssue { rule: "UNUSED_IMPORT".to_string(), ..issue(before, 4) }));
+    }
+
+    #[test]
+    fn test_render_github_annotation_error_with_line() {
+        let issue = SarifIssue {
+            file: "src/user.service.ts".to_string(),
+            rule: "HIGH_COMPLEXITY".to_string(),
+            severity: "error".to_string(),
+            message: "Complejidad 14 en 'save'".to_string(),
+            line: Some(42),
+            snippet: None,
+        };
+        assert_eq!(
+            render_github_annotation(&issue),
+            "::error file=src/user.service.ts,line=42,title=HIGH_COMPLEXITY::Complejidad 14 en 'save'"
+        );
+    }
+
+    #[test]
+    fn test_render_github_annotation_levels_and_escaping() {
+        let issue = SarifIssue {
+            file: "a,b.ts".to_string(),
+            rule: "DEAD_CODE".to_string(),
+            severity: "note".to_string(),
+            message: "100% muerto\nlinea 2".to_string(),
+            line: None,
+            snippet: None,
+        };
+        assert_eq!(
+            render_github_annotation(&issue),
+            "::notice file=a%2Cb.ts,title=DEAD_CODE::100%25 muerto%0Alinea 2"
+        );
+    }
+
     #[test]
     fn test_get_changed_files_returns_vec() {
         // Verify it doesn't panic in any directory (git or non-git)
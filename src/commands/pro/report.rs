@@ -0,0 +1,741 @@
+use crate::agents::base::AgentContext;
+use crate::commands::output::{outln, OutputSink};
+use crate::index::quality_history::{FileMetrics, QualityHistory, QualityTrend, run_scope};
+use crate::rules::RuleLevel;
+use colored::*;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Parsea duraciones del tipo `7d`, `24h` o `30m`.
+pub fn parse_since(input: &str) -> anyhow::Result<chrono::Duration> {
+    let trimmed = input.trim();
+    let split_at = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow::anyhow!("Duración '{}' sin unidad (usa d, h o m, ej: 7d)", input))?;
+    let (amount, unit) = trimmed.split_at(split_at);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Duración '{}' inválida (ej: 7d, 24h, 30m)", input))?;
+
+    let duration = match unit {
+        "d" => chrono::Duration::try_days(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        _ => return Err(anyhow::anyhow!("Unidad '{}' no soportada en '{}' (usa d, h o m)", unit, input)),
+    };
+    duration
+        .filter(|d| *d > chrono::Duration::zero())
+        .ok_or_else(|| anyhow::anyhow!("Duración '{}' fuera de rango", input))
+}
+
+fn signed(n: i64) -> String {
+    if n > 0 { format!("+{}", n) } else { n.to_string() }
+}
+
+fn no_data_message(since_label: &str) -> String {
+    format!("Sin datos históricos en los últimos {}.", since_label)
+}
+
+pub fn trend_json(since_label: &str, trend: Option<&QualityTrend>) -> serde_json::Value {
+    match trend {
+        Some(t) => serde_json::json!({
+            "since": since_label,
+            "available": true,
+            "from": t.oldest_timestamp,
+            "to": t.newest_timestamp,
+            "records": t.records,
+            "files": t.files,
+            "violations": { "before": t.violations_before, "after": t.violations_after, "delta": t.violations_delta() },
+            "dead_functions": { "before": t.dead_functions_before, "after": t.dead_functions_after, "delta": t.dead_functions_delta() },
+            "unused_imports": { "before": t.unused_imports_before, "after": t.unused_imports_after, "delta": t.unused_imports_delta() },
+        }),
+        None => serde_json::json!({
+            "since": since_label,
+            "available": false,
+            "message": no_data_message(since_label),
+        }),
+    }
+}
+
+pub fn trend_markdown(since_label: &str, trend: Option<&QualityTrend>) -> String {
+    let mut out = format!("## Tendencia (últimos {})\n\n", since_label);
+    match trend {
+        Some(t) => {
+            out.push_str(&format!(
+                "{} registro(s) de {} archivo(s), {} → {}\n\n",
+                t.records, t.files, t.oldest_timestamp, t.newest_timestamp
+            ));
+            out.push_str("| Métrica | Antes | Ahora | Δ |\n|---|---|---|---|\n");
+            out.push_str(&format!("| Violaciones | {} | {} | {} |\n", t.violations_before, t.violations_after, signed(t.violations_delta())));
+            out.push_str(&format!("| Funciones muertas | {} | {} | {} |\n", t.dead_functions_before, t.dead_functions_after, signed(t.dead_functions_delta())));
+            out.push_str(&format!("| Imports sin usar | {} | {} | {} |\n", t.unused_imports_before, t.unused_imports_after, signed(t.unused_imports_delta())));
+        }
+        None => {
+            out.push_str(&no_data_message(since_label));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+pub fn trend_html(since_label: &str, trend: Option<&QualityTrend>) -> String {
+    let mut out = format!("<section class=\"trend\">\n<h2>Tendencia (últimos {})</h2>\n", since_label);
+    match trend {
+        Some(t) => {
+            out.push_str(&format!(
+                "<p>{} registro(s) de {} archivo(s), {} → {}</p>\n",
+                t.records, t.files, t.oldest_timestamp, t.newest_timestamp
+            ));
+            out.push_str("<table>\n<tr><th>Métrica</th><th>Antes</th><th>Ahora</th><th>Δ</th></tr>\n");
+            for (label, before, after, delta) in [
+                ("Violaciones", t.violations_before, t.violations_after, t.violations_delta()),
+                ("Funciones muertas", t.dead_functions_before, t.dead_functions_after, t.dead_functions_delta()),
+                ("Imports sin usar", t.unused_imports_before, t.unused_imports_after, t.unused_imports_delta()),
+            ] {
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    label, before, after, signed(delta)
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        None => out.push_str(&format!("<p>{}</p>\n", no_data_message(since_label))),
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportIssue {
+    pub rule: String,
+    pub level: String,
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub path: String,
+    pub complexity: f64,
+    pub issues: Vec<ReportIssue>,
+}
+
+/// Resultado del análisis estático que alimenta el reporte.
+#[derive(Debug, Default, Serialize)]
+pub struct ReportData {
+    pub files_analyzed: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    /// Solo archivos con al menos una violación
+    pub files: Vec<FileReport>,
+}
+
+fn level_label(level: &RuleLevel) -> &'static str {
+    match level {
+        RuleLevel::Error => "error",
+        RuleLevel::Warning => "warning",
+        RuleLevel::Info => "info",
+    }
+}
+
+/// Recorre el proyecto, valida cada archivo con el `RuleEngine` (mismos filtros que
+/// `pro check`) y guarda una fila de `quality_history` por archivo analizado.
+pub fn collect_report_data(agent_context: &AgentContext, output_mode: &crate::commands::OutputMode) -> ReportData {
+    let root = &agent_context.project_root;
+    let config = &agent_context.config;
+
+    let mut files = Vec::new();
+    let walker = ignore::WalkBuilder::new(root).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if p.is_file() && config.file_extensions.iter().any(|e| e == ext) {
+            files.push(p.to_path_buf());
+        }
+    }
+    let mut files = super::skip_large_files(files, config, root, output_mode);
+    files.sort();
+
+    let mut rule_engine = crate::rules::engine::RuleEngine::new()
+        .with_sentinelignore(root)
+        .with_entry_patterns(root, &agent_context.config)
+        .with_secret_allowlist(root, &agent_context.config.rule_config.secret_allowlist)
+        .with_thresholds(&agent_context.config.rule_config);
+    if let Some(ref db) = agent_context.index_db {
+        rule_engine = rule_engine.with_index_db(std::sync::Arc::clone(db));
+    }
+    let rules_path = root.join(".sentinel/rules.yaml");
+    if rules_path.exists() {
+        let _ = rule_engine.load_from_yaml(&rules_path);
+    }
+    let ignore_entries = crate::commands::ignore::load_ignore_entries(root);
+
+    let mut data = ReportData { files_analyzed: files.len(), ..Default::default() };
+    let mut metrics = Vec::with_capacity(files.len());
+    for file in &files {
+        let content = std::fs::read_to_string(file).unwrap_or_default();
+        let rel = file.strip_prefix(root).unwrap_or(file).display().to_string();
+        let (violations, _) = rule_engine.validate_file_with_suppressed(file, &content);
+
+        let complexity: usize = violations
+            .iter()
+            .filter(|v| v.rule_name == "HIGH_COMPLEXITY")
+            .filter_map(|v| v.value)
+            .sum();
+        let issues: Vec<ReportIssue> = violations
+            .into_iter()
+            .filter(|v| {
+                !super::check::is_ignored(&ignore_entries, &v.rule_name, &rel, v.symbol.as_deref())
+                    && super::check::passes_rule_config(&config.rule_config, &v.rule_name, v.value)
+            })
+            .map(|v| ReportIssue {
+                rule: v.rule_name,
+                level: level_label(&v.level).to_string(),
+                line: v.line,
+                symbol: v.symbol,
+                message: v.message,
+            })
+            .collect();
+
+        let count = |rule: &str| issues.iter().filter(|i| i.rule == rule).count() as i32;
+        metrics.push(FileMetrics {
+            file_path: rel.clone(),
+            dead_functions: count("DEAD_CODE"),
+            unused_imports: count("UNUSED_IMPORT"),
+            complexity_score: complexity as f64,
+            violations_count: issues.len() as i32,
+            tests_passing: true,
+        });
+        for issue in &issues {
+            match issue.level.as_str() {
+                "error" => data.errors += 1,
+                "warning" => data.warnings += 1,
+                _ => data.infos += 1,
+            }
+        }
+        if !issues.is_empty() {
+            data.files.push(FileReport { path: rel, complexity: complexity as f64, issues });
+        }
+    }
+
+    if let Some(ref db) = agent_context.index_db
+        && let Err(e) = QualityHistory::new(db).record_run(&metrics, &run_scope(".", &config.rule_config))
+    {
+        log::debug!("No se pudo guardar el historial de calidad: {}", e);
+    }
+    data
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn issues_markdown(data: &ReportData) -> String {
+    let mut out = format!(
+        "## Resumen\n\n{} archivo(s) analizados · {} errores · {} warnings · {} infos\n\n",
+        data.files_analyzed, data.errors, data.warnings, data.infos
+    );
+    if data.files.is_empty() {
+        out.push_str("Sin violaciones.\n\n");
+        return out;
+    }
+    out.push_str("## Violaciones\n\n| Archivo | Línea | Nivel | Regla | Mensaje |\n|---|---|---|---|---|\n");
+    for file in &data.files {
+        for issue in &file.issues {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                file.path,
+                issue.line.map(|l| l.to_string()).unwrap_or_default(),
+                issue.level,
+                issue.rule,
+                issue.message.replace('|', "\\|")
+            ));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+pub fn issues_html(data: &ReportData) -> String {
+    let mut out = format!(
+        "<section class=\"summary\">\n<h2>Resumen</h2>\n<p>{} archivo(s) analizados · {} errores · {} warnings · {} infos</p>\n</section>\n",
+        data.files_analyzed, data.errors, data.warnings, data.infos
+    );
+    out.push_str("<section class=\"issues\">\n<h2>Violaciones</h2>\n");
+    if data.files.is_empty() {
+        out.push_str("<p>Sin violaciones.</p>\n");
+    } else {
+        out.push_str("<table>\n<tr><th>Archivo</th><th>Línea</th><th>Nivel</th><th>Regla</th><th>Mensaje</th></tr>\n");
+        for file in &data.files {
+            for issue in &file.issues {
+                out.push_str(&format!(
+                    "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    issue.level,
+                    escape_html(&file.path),
+                    issue.line.map(|l| l.to_string()).unwrap_or_default(),
+                    issue.level,
+                    issue.rule,
+                    escape_html(&issue.message)
+                ));
+            }
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+/// Campo CSV según RFC 4180: entre comillas (duplicándolas) si lleva comas, comillas o saltos de línea.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Una fila por violación (`file,level,rule,line,message`), con cabecera y fin de línea CRLF.
+pub fn issues_csv(data: &ReportData) -> String {
+    let mut out = String::from("file,level,rule,line,message\r\n");
+    for file in &data.files {
+        for issue in &file.issues {
+            let line = issue.line.map(|l| l.to_string()).unwrap_or_default();
+            let row = [file.path.as_str(), issue.level.as_str(), issue.rule.as_str(), line.as_str(), issue.message.as_str()];
+            out.push_str(&row.map(csv_field).join(","));
+            out.push_str("\r\n");
+        }
+    }
+    out
+}
+
+/// Ejecuciones de `quality_history` que muestra el gráfico del reporte HTML.
+const TREND_CHART_RUNS: usize = 20;
+
+/// Gráfico de líneas (SVG inline) con las violaciones totales por ejecución. Los datos van
+/// como array JSON en un `<script type="application/json">` que el script dibuja; sin
+/// dependencias externas. Vacío si hay menos de 2 puntos.
+pub fn violations_chart_html(runs: &[(String, i64)]) -> String {
+    if runs.len() < 2 {
+        return String::new();
+    }
+    let points: Vec<serde_json::Value> = runs
+        .iter()
+        .map(|(timestamp, violations)| serde_json::json!({ "t": timestamp, "v": violations }))
+        .collect();
+    let data = serde_json::to_string(&points).unwrap_or_default().replace("</", "<\\/");
+    format!(
+        r##"<section class="chart">
+<h2>Violaciones por ejecución (últimas {n})</h2>
+<svg id="violations-chart" viewBox="0 0 600 200" width="600" height="200" role="img" aria-label="Violaciones por ejecución">
+<polyline fill="none" stroke="#d9534f" stroke-width="2" points=""/>
+</svg>
+<script type="application/json" id="violations-data">{data}</script>
+<script>
+(function () {{
+  var runs = JSON.parse(document.getElementById("violations-data").textContent);
+  var svg = document.getElementById("violations-chart");
+  var max = Math.max.apply(null, runs.map(function (r) {{ return r.v; }}).concat([1]));
+  var pts = runs.map(function (r, i) {{
+    var x = 20 + i * 560 / (runs.length - 1);
+    var y = 180 - r.v * 160 / max;
+    var dot = document.createElementNS("http://www.w3.org/2000/svg", "circle");
+    dot.setAttribute("cx", x); dot.setAttribute("cy", y); dot.setAttribute("r", 3);
+    var tip = document.createElementNS("http://www.w3.org/2000/svg", "title");
+    tip.textContent = r.t + ": " + r.v;
+    dot.appendChild(tip);
+    svg.appendChild(dot);
+    return x + "," + y;
+  }});
+  svg.querySelector("polyline").setAttribute("points", pts.join(" "));
+}})();
+</script>
+</section>
+"##,
+        n = runs.len(),
+        data = data
+    )
+}
+
+/// Destino del reporte: un archivo o stdout (`--output -`).
+#[derive(Debug, PartialEq)]
+pub enum ReportDestination {
+    Stdout,
+    File(PathBuf),
+}
+
+impl ReportDestination {
+    /// Sin `--output` se usa `sentinel-report.<ext>` en la raíz del proyecto.
+    pub fn resolve(output: Option<&str>, project_root: &Path, format: &str) -> Self {
+        match output {
+            Some("-") => Self::Stdout,
+            Some(path) => Self::File(PathBuf::from(path)),
+            None => {
+                let ext = match format {
+                    "html" => "html",
+                    "markdown" | "md" => "md",
+                    "csv" => "csv",
+                    _ => "json",
+                };
+                Self::File(project_root.join(format!("sentinel-report.{}", ext)))
+            }
+        }
+    }
+
+    /// Escribe el reporte creando los directorios padre si hace falta.
+    pub fn write(&self, report: &str, stdout: &mut impl Write) -> anyhow::Result<()> {
+        match self {
+            Self::Stdout => {
+                stdout.write_all(report.as_bytes())?;
+                if !report.ends_with('\n') {
+                    stdout.write_all(b"\n")?;
+                }
+                stdout.flush()?;
+            }
+            Self::File(path) => {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(path, report)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Argumentos de `pro report` tal como llegan del CLI.
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// json, html, markdown o csv
+    pub format: String,
+    /// Ventana de la tendencia (`7d`, `24h`, `30m`)
+    pub since: Option<String>,
+    /// Ruta de salida; `-` para el sink de salida
+    pub output: Option<String>,
+    pub fail_on: super::FailOn,
+}
+
+pub fn handle_report(
+    options: &ReportOptions,
+    agent_context: &AgentContext,
+    output_mode: crate::commands::OutputMode,
+    out: &mut OutputSink,
+    _rt: &tokio::runtime::Runtime,
+) {
+    let since = options.since.as_deref();
+    let output = options.output.as_deref();
+    let fail_on = options.fail_on;
+    let format = options.format.to_lowercase();
+    if !matches!(format.as_str(), "json" | "html" | "markdown" | "md" | "csv") {
+        eprintln!("{} Formato '{}' no soportado (usa json, html, markdown o csv).", "❌".red(), format);
+        std::process::exit(2);
+    }
+
+    let trend: Option<(String, Option<QualityTrend>)> = match since {
+        Some(label) => {
+            let duration = match parse_since(label) {
+                Ok(d) => d,
+                Err(e) => {
+                    eprintln!("{} {}", "❌".red(), e);
+                    std::process::exit(2);
+                }
+            };
+            let data = agent_context
+                .index_db
+                .as_ref()
+                .and_then(|db| QualityHistory::new(db).get_trend(duration).ok())
+                .flatten();
+            Some((label.to_string(), data))
+        }
+        None => None,
+    };
+
+    log::debug!("Generating {} report (since: {:?})", format, since);
+
+    let data = collect_report_data(agent_context, &output_mode);
+    let project = &agent_context.config.project_name;
+    let generated_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let report = match format.as_str() {
+        "json" => {
+            let mut doc = serde_json::json!({
+                "project": project,
+                "generated_at": generated_at,
+                "summary": {
+                    "files_analyzed": data.files_analyzed,
+                    "errors": data.errors,
+                    "warnings": data.warnings,
+                    "infos": data.infos,
+                },
+                "files": data.files,
+            });
+            if let Some((label, data)) = &trend {
+                doc["trend"] = trend_json(label, data.as_ref());
+            }
+            serde_json::to_string_pretty(&doc).unwrap_or_default()
+        }
+        "html" => {
+            let mut html = format!(
+                "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Sentinel Report — {0}</title></head>\n<body>\n<h1>Sentinel Report — {0}</h1>\n<p>Generado: {1}</p>\n",
+                project, generated_at
+            );
+            html.push_str(&issues_html(&data));
+            let runs = agent_context
+                .index_db
+                .as_ref()
+                .and_then(|db| QualityHistory::new(db).violations_per_run(TREND_CHART_RUNS, &run_scope(".", &agent_context.config.rule_config)).ok())
+                .unwrap_or_default();
+            html.push_str(&violations_chart_html(&runs));
+            if let Some((label, data)) = &trend {
+                html.push_str(&trend_html(label, data.as_ref()));
+            }
+            html.push_str("</body>\n</html>\n");
+            html
+        }
+        // Solo las violaciones: la tendencia no encaja en una tabla plana
+        "csv" => issues_csv(&data),
+        _ => {
+            let mut md = format!("# Sentinel Report — {}\n\nGenerado: {}\n\n", project, generated_at);
+            md.push_str(&issues_markdown(&data));
+            if let Some((label, data)) = &trend {
+                md.push_str(&trend_markdown(label, data.as_ref()));
+            }
+            md
+        }
+    };
+
+    let destination = ReportDestination::resolve(output, &agent_context.project_root, &format);
+    if let Err(e) = destination.write(&report, out) {
+        eprintln!("{} No se pudo escribir el reporte: {}", "❌".red(), e);
+        std::process::exit(1);
+    }
+    if let ReportDestination::File(path) = &destination
+        && output_mode != crate::commands::OutputMode::Quiet
+    {
+        outln!(out, "{} Reporte generado en {}", "✅".green(), path.display().to_string().cyan());
+    }
+
+    if fail_on.should_fail(data.errors, data.warnings, data.infos) {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since_units() {
+        assert_eq!(parse_since("7d").unwrap(), chrono::Duration::days(7));
+        assert_eq!(parse_since("24h").unwrap(), chrono::Duration::hours(24));
+        assert_eq!(parse_since("30m").unwrap(), chrono::Duration::minutes(30));
+    }
+
+    #[test]
+    fn test_parse_since_rejects_invalid() {
+        assert!(parse_since("7").is_err());
+        assert!(parse_since("d").is_err());
+        assert!(parse_since("7w").is_err());
+        assert!(parse_since("0d").is_err());
+    }
+
+    #[test]
+    fn test_trend_json_without_data_says_so() {
+        let v = trend_json("7d", None);
+        assert_eq!(v["available"], false);
+        assert!(v["message"].as_str().unwrap().contains("Sin datos"));
+        assert!(v.get("violations").is_none(), "must not emit zeros when there is no data");
+    }
+
+    #[test]
+    fn test_issues_csv_quotes_fields_per_rfc4180() {
+        let issue = |rule: &str, line: Option<usize>, message: &str| ReportIssue {
+            rule: rule.to_string(),
+            level: "warning".to_string(),
+            line,
+            symbol: None,
+            message: message.to_string(),
+        };
+        let data = ReportData {
+            files: vec![FileReport {
+                path: "src/a.ts".to_string(),
+                complexity: 0.0,
+                issues: vec![
+                    issue("DEAD_CODE", Some(3), "'a' no se usa, elimínalo"),
+                    issue("NAMING", None, "usa \"camelCase\"\nen variables"),
+                ],
+            }],
+            ..Default::default()
+        };
+        let csv = issues_csv(&data);
+        let mut rows = csv.split("\r\n");
+        assert_eq!(rows.next(), Some("file,level,rule,line,message"));
+        assert_eq!(rows.next(), Some("src/a.ts,warning,DEAD_CODE,3,\"'a' no se usa, elimínalo\""));
+        assert!(csv.contains("src/a.ts,warning,NAMING,,\"usa \"\"camelCase\"\"\nen variables\"\r\n"));
+        assert_eq!(ReportDestination::resolve(None, Path::new("/repo"), "csv"), ReportDestination::File(PathBuf::from("/repo/sentinel-report.csv")));
+    }
+
+    #[test]
+    fn test_destination_resolution() {
+        let root = Path::new("/repo");
+        assert_eq!(ReportDestination::resolve(Some("-"), root, "json"), ReportDestination::Stdout);
+        assert_eq!(
+            ReportDestination::resolve(None, root, "html"),
+            ReportDestination::File(PathBuf::from("/repo/sentinel-report.html"))
+        );
+        assert_eq!(
+            ReportDestination::resolve(Some("out/r.json"), root, "json"),
+            ReportDestination::File(PathBuf::from("out/r.json"))
+        );
+    }
+
+    #[test]
+    fn test_stdout_destination_writes_to_writer() {
+        let mut buf: Vec<u8> = Vec::new();
+        ReportDestination::Stdout.write("{\"ok\":true}", &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "{\"ok\":true}\n");
+    }
+
+    #[test]
+    fn test_file_destination_creates_nested_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("artifacts/ci/reports/sentinel.json");
+        let mut buf: Vec<u8> = Vec::new();
+        ReportDestination::File(path.clone()).write("{}", &mut buf).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}");
+        assert!(buf.is_empty(), "nothing must go to stdout when writing a file");
+    }
+
+    fn options(format: &str, output: Option<&str>) -> ReportOptions {
+        ReportOptions {
+            format: format.to_string(),
+            since: None,
+            output: output.map(str::to_string),
+            fail_on: super::super::FailOn::Never,
+        }
+    }
+
+    #[test]
+    fn test_handle_report_dash_writes_document_to_sink() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "export const a = 1;\n").unwrap();
+
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        let ctx = AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: root.to_path_buf(),
+            index_db: None,
+        };
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let (mut out, captured) = OutputSink::buffer();
+
+        handle_report(&options("json", Some("-")), &ctx, crate::commands::OutputMode::Quiet, &mut out, &rt);
+
+        let doc: serde_json::Value = serde_json::from_str(&captured.contents()).unwrap();
+        assert_eq!(doc["summary"]["files_analyzed"], 1);
+        assert!(!root.join("sentinel-report.json").exists());
+    }
+
+    #[test]
+    fn test_handle_report_writes_file_and_records_history() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "import { unused } from './b';\nexport const a = 1;\n").unwrap();
+        std::fs::write(root.join("src/b.ts"), "export const unused = 2;\n").unwrap();
+
+        let db = std::sync::Arc::new(crate::index::IndexDb::open(tmp.path().join("index.db")).unwrap());
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        let ctx = AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: root.to_path_buf(),
+            index_db: Some(std::sync::Arc::clone(&db)),
+        };
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        handle_report(&options("json", None), &ctx, crate::commands::OutputMode::Quiet, &mut OutputSink::buffer().0, &rt);
+
+        let written = std::fs::read_to_string(root.join("sentinel-report.json")).unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(doc["summary"]["files_analyzed"], 2);
+        let rows: i64 = db
+            .lock()
+            .query_row("SELECT COUNT(*) FROM quality_history", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rows, 2, "one history row per analyzed file");
+    }
+
+    #[test]
+    fn test_collect_report_data_skips_files_over_max_size() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(root.join("src/bundle.min.ts"), "export const b = 2;\n".repeat(100)).unwrap();
+
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            max_file_bytes: 1_000,
+            ..Default::default()
+        };
+        let ctx = AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: root.to_path_buf(),
+            index_db: None,
+        };
+
+        let data = collect_report_data(&ctx, &crate::commands::OutputMode::Quiet);
+        assert_eq!(data.files_analyzed, 1, "the 2 KB bundle must not be analyzed");
+    }
+
+    #[test]
+    fn test_html_report_embeds_violation_trend_data() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.ts"), "import { unused } from './b';\nexport const a = 1;\n").unwrap();
+
+        let db = std::sync::Arc::new(crate::index::IndexDb::open(tmp.path().join("index.db")).unwrap());
+        let config = crate::config::SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        let ctx = AgentContext {
+            config: std::sync::Arc::new(config),
+            stats: std::sync::Arc::new(std::sync::Mutex::new(crate::stats::SentinelStats::default())),
+            project_root: root.to_path_buf(),
+            index_db: Some(std::sync::Arc::clone(&db)),
+        };
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let report_path = root.join("sentinel-report.html");
+        let output = report_path.to_str().unwrap();
+
+        // Primera ejecución: un único punto, sin gráfico
+        handle_report(&options("html", Some(output)), &ctx, crate::commands::OutputMode::Quiet, &mut OutputSink::buffer().0, &rt);
+        assert!(!std::fs::read_to_string(&report_path).unwrap().contains("violations-data"));
+
+        for _ in 0..2 {
+            handle_report(&options("html", Some(output)), &ctx, crate::commands::OutputMode::Quiet, &mut OutputSink::buffer().0, &rt);
+        }
+        let html = std::fs::read_to_string(&report_path).unwrap();
+        let start = html.find("id=\"violations-data\">").expect("chart data embedded") + "id=\"violations-data\">".len();
+        let end = start + html[start..].find("</script>").unwrap();
+        let points: Vec<serde_json::Value> = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(points.len(), 3, "one point per recorded run");
+        assert!(points.iter().all(|p| p["v"].as_i64() == Some(points[0]["v"].as_i64().unwrap())));
+    }
+}
@@ -17,7 +17,7 @@ pub struct ReviewRecord {
 pub struct ReviewSuggestion {
     pub title: String,
     pub description: String,
-    pub impact: String,
+    pub impact: super::Severity,
     pub action_item: String,
     #[serde(default)]
     pub files_involved: Vec<String>,
@@ -32,6 +32,100 @@ pub fn review_size_mode(file_count: usize) -> ReviewMode {
     else { ReviewMode::Large }
 }
 
+/// Carpetas sin interés para la estructura (dependencias, artefactos): en el árbol se
+/// resumen en una línea y no se muestrean.
+const DIRS_COLAPSADOS: &[&str] = &[
+    "node_modules", "dist", "build", ".next", ".nuxt",
+    "vendor", "target", ".git", "__pycache__", "coverage",
+];
+
+/// Árbol del proyecto para el prompt de review.
+pub struct ProjectTree {
+    pub text: String,
+    /// Entradas recorridas (sin contar el contenido de las carpetas resumidas)
+    pub entries: usize,
+}
+
+/// Tope de `count_files`: un `node_modules` enorme no debe recorrerse entero solo para el árbol.
+const MAX_FILE_COUNT: usize = 10_000;
+
+/// Archivos bajo `dir` (hasta `MAX_FILE_COUNT + 1`); `all` ignora también `.gitignore`
+/// (carpetas de `DIRS_COLAPSADOS`).
+fn count_files(dir: &std::path::Path, all: bool) -> usize {
+    let mut builder = ignore::WalkBuilder::new(dir);
+    if all {
+        builder.standard_filters(false);
+    } else {
+        builder.hidden(false);
+    }
+    builder
+        .build()
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .take(MAX_FILE_COUNT + 1)
+        .count()
+}
+
+/// `n` tal como sale en el árbol: `10000+` si se alcanzó el tope.
+fn format_count(n: usize) -> String {
+    if n > MAX_FILE_COUNT { format!("{}+", MAX_FILE_COUNT) } else { n.to_string() }
+}
+
+/// Árbol de `project_root` hasta `max_depth` niveles y `max_lines` líneas (0 = sin límite).
+/// Las carpetas de `DIRS_COLAPSADOS` (aunque estén en `.gitignore`) y las que llegan al
+/// límite de profundidad salen como una sola línea `nombre/ (+N archivos)`.
+pub fn build_project_tree(project_root: &std::path::Path, max_depth: usize, max_lines: usize) -> ProjectTree {
+    let walker = ignore::WalkBuilder::new(project_root)
+        .hidden(false)
+        .git_ignore(true)
+        .max_depth(Some(max_depth))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(|e| e.depth() == 0 || !DIRS_COLAPSADOS.iter().any(|d| e.file_name() == *d))
+        .build();
+
+    let mut lines = Vec::new();
+    let mut entries = 0;
+    for entry in walker.flatten() {
+        let depth = entry.depth();
+        let indent = "  ".repeat(depth);
+        let name = entry.file_name().to_string_lossy();
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        entries += 1;
+
+        if is_dir && depth > 0 && depth == max_depth {
+            let n = count_files(entry.path(), false);
+            if n > 0 {
+                lines.push(format!("{}{}/ (+{} archivos)", indent, name, format_count(n)));
+                continue;
+            }
+        }
+        lines.push(format!("{}{}", indent, name));
+        if is_dir && depth < max_depth {
+            // `.git` se omite del árbol: no aporta estructura y contarlo es caro
+            for collapsed in DIRS_COLAPSADOS.iter().filter(|d| **d != ".git") {
+                let dir = entry.path().join(collapsed);
+                if dir.is_dir() {
+                    let n = count_files(&dir, true);
+                    lines.push(format!("{}  {}/ (+{} archivos)", indent, collapsed, format_count(n)));
+                }
+            }
+        }
+    }
+
+    let text = if max_lines > 0 && lines.len() > max_lines {
+        format!(
+            "{}\n... (proyecto grande, se muestran primeras {} líneas del árbol)",
+            lines[..max_lines].join("\n"),
+            max_lines
+        )
+    } else {
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    };
+    ProjectTree { text, entries }
+}
+
 pub fn save_review_record(project_root: &std::path::Path, record: &ReviewRecord) -> anyhow::Result<()> {
     let dir = project_root.join(".sentinel").join("reviews");
     std::fs::create_dir_all(&dir)?;
@@ -93,20 +187,149 @@ pub fn diff_reviews(
     (resolved, added, persistent)
 }
 
+/// Argumentos de `pro review` tal como llegan del CLI.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewOptions {
+    pub history: bool,
+    pub diff: bool,
+    /// Sustituye el tope de líneas por archivo de cada modo (`--context-lines`)
+    pub context_lines: Option<usize>,
+    /// `--apply <impact>`: aplica sin menú las sugerencias de ese impacto o superior
+    pub apply: Option<super::Severity>,
+    /// `--yes`: no pide confirmación antes de aplicar
+    pub yes: bool,
+}
+
+/// Separa las sugerencias en (a aplicar, omitidas) según el impacto mínimo de `--apply`.
+pub fn select_by_impact(
+    suggestions: Vec<ReviewSuggestion>,
+    min_impact: super::Severity,
+) -> (Vec<ReviewSuggestion>, Vec<ReviewSuggestion>) {
+    suggestions.into_iter().partition(|s| s.impact >= min_impact)
+}
+
+/// Tarea del FixSuggesterAgent para una sugerencia, con el contenido actual de su primer archivo.
+fn fix_task(suggestion: &ReviewSuggestion, project_root: &std::path::Path) -> Task {
+    let file_context = suggestion.files_involved.first().and_then(|f| {
+        let path = project_root.join(f);
+        std::fs::read_to_string(&path)
+            .ok()
+            .map(|content| format!("CONTENIDO ACTUAL DE {}:\n```\n{}\n```", f, content))
+    });
+
+    Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: format!(
+            "IMPLEMENTACIÓN DE MEJORA ARQUITECTÓNICA\n\n\
+            TÍTULO: {}\n\
+            DESCRIPCIÓN: {}\n\
+            ACCIÓN REQUERIDA: {}\n\n\
+            OBJETIVO: Aplica la mejora al código real adjunto.",
+            suggestion.title, suggestion.description, suggestion.action_item
+        ),
+        task_type: TaskType::Fix,
+        file_path: suggestion.files_involved.first().map(std::path::PathBuf::from),
+        context: file_context,
+    }
+}
+
+/// `--apply <impact>`: desarrolla y aplica sin menú las sugerencias de ese impacto o superior.
+/// Usa los mismos guardas que el modo interactivo (copia en `backup_dir`, rechazo de respuestas
+/// truncadas). Sin `--yes` pide una única confirmación; si no hay terminal no aplica nada.
+fn apply_by_impact(
+    suggestions: Vec<ReviewSuggestion>,
+    min_impact: super::Severity,
+    yes: bool,
+    orchestrator: &AgentOrchestrator,
+    agent_context: &AgentContext,
+    rt: &tokio::runtime::Runtime,
+) {
+    let (selected, below) = select_by_impact(suggestions, min_impact);
+    println!(
+        "\n💡 {} sugerencia(s) con impacto {} o superior ({} por debajo del umbral).",
+        selected.len().to_string().cyan(),
+        min_impact.to_string().to_uppercase(),
+        below.len()
+    );
+    if selected.is_empty() {
+        return;
+    }
+    for s in &selected {
+        println!("   • [{}] {}", s.impact.to_string().to_uppercase(), s.title);
+    }
+
+    if !yes {
+        let confirmed = Confirm::new()
+            .with_prompt("¿Aplicar estas sugerencias automáticamente?")
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !confirmed {
+            println!("\n{} No se aplicó ningún cambio (usa --yes para omitir la confirmación).", "ℹ️".cyan());
+            return;
+        }
+    }
+
+    let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+    let mut applied = Vec::new();
+    let mut failed = Vec::new();
+    for suggestion in &selected {
+        let pb_dev = ui::crear_progreso(&format!("Aplicando mejora: {}...", suggestion.title));
+        let dev_task = fix_task(suggestion, &agent_context.project_root);
+        let dev_result = rt.block_on(orchestrator.execute_task("FixSuggesterAgent", &dev_task, agent_context));
+        pb_dev.finish_and_clear();
+
+        let saved = match dev_result {
+            Ok(d_res) => {
+                let bloques = crate::ai::utils::extraer_todos_bloques(&d_res.output);
+                println!("\n🚀 {}", suggestion.title.cyan().bold());
+                if bloques.is_empty() {
+                    println!("   ⚠️  El agente no devolvió código aplicable.");
+                    0
+                } else {
+                    super::apply::listar_bloques(&bloques);
+                    super::apply::aplicar_bloques(&agent_context.project_root, &bloques, &backups)
+                }
+            }
+            Err(e) => {
+                println!("{} {}: {}", "\n❌ Error al desarrollar".red(), suggestion.title, e);
+                0
+            }
+        };
+        if saved > 0 {
+            let mut s = agent_context.stats.lock().unwrap();
+            s.sugerencias_aplicadas += 1;
+            s.tiempo_estimado_ahorrado_mins += 30;
+            s.guardar(&agent_context.project_root);
+            applied.push(suggestion);
+        } else {
+            failed.push(suggestion);
+        }
+    }
+
+    println!(
+        "\n📋 {} {} aplicada(s), {} sin aplicar, {} omitida(s) por impacto.",
+        "Resumen:".bold(),
+        applied.len().to_string().green(),
+        failed.len().to_string().yellow(),
+        below.len()
+    );
+    for s in &failed {
+        println!("   ✗ {}", s.title.yellow());
+    }
+}
+
 pub fn handle_review(
-    _history: bool,
-    _diff: bool,
+    options: &ReviewOptions,
     _quiet: bool,
     _verbose: bool,
     agent_context: &AgentContext,
     output_mode: crate::commands::OutputMode,
     rt: &tokio::runtime::Runtime,
 ) {
-    if output_mode == crate::commands::OutputMode::Verbose {
-        eprintln!("[DEBUG] Generating review report");
-    }
+    log::debug!("Generating review report");
 
-    if _history {
+    if options.history {
         let records = load_review_records(&agent_context.project_root);
         if records.is_empty() {
             if output_mode != crate::commands::OutputMode::Quiet {
@@ -130,7 +353,7 @@ pub fn handle_review(
         return;
     }
 
-    if _diff {
+    if options.diff {
         let records = load_review_records(&agent_context.project_root);
         if records.len() < 2 {
             if output_mode != crate::commands::OutputMode::Quiet {
@@ -168,55 +391,18 @@ pub fn handle_review(
     let pb = ui::crear_progreso("Analizando estructura del proyecto...");
 
     // 1. Generar mapa del proyecto (Tree)
-    let mut project_tree = String::new();
-    let mut file_count = 0;
-
-    let walker = ignore::WalkBuilder::new(&agent_context.project_root)
-        .hidden(false)
-        .git_ignore(true)
-        .build();
-
-    for result in walker {
-        if let Ok(entry) = result {
-            let path = entry.path();
-            if let Ok(rel) = path.strip_prefix(&agent_context.project_root) {
-                let depth = rel.components().count();
-                if depth > 4 {
-                    continue;
-                } // Limitar profundidad para no saturar
-
-                let indent = "  ".repeat(depth);
-                let name = path.file_name().unwrap_or_default().to_string_lossy();
-
-                project_tree.push_str(&format!("{}{}\n", indent, name));
-                file_count += 1;
-            }
-        }
-    }
+    let ProjectTree { text: project_tree, entries: file_count } = build_project_tree(
+        &agent_context.project_root,
+        agent_context.config.review_tree_depth,
+        agent_context.config.review_tree_max_lines,
+    );
 
     // 2. Leer dependencias
     let deps = crate::files::leer_dependencias(&agent_context.project_root);
     let deps_list = deps.join(", ");
 
-    // Cap del árbol de directorios a 100 líneas
-    let project_tree = {
-        let lines: Vec<&str> = project_tree.lines().collect();
-        if lines.len() > 100 {
-            format!(
-                "{}\n... (proyecto grande, se muestran primeras 100 líneas del árbol)",
-                lines[..100].join("\n")
-            )
-        } else {
-            project_tree
-        }
-    };
-
     // 3. Muestra de archivos fuente reales (máx 8 archivos, 100 líneas c/u)
     // Prioriza src/ y tipos de archivo NestJS/arquitectura relevante.
-    let dirs_ignorados = [
-        "node_modules", "dist", "build", ".next", ".nuxt",
-        "vendor", "target", ".git", "__pycache__", "coverage",
-    ];
     // Recolectar todos los candidatos primero para poder priorizarlos
     let walk_root = {
         let src = agent_context.project_root.join("src");
@@ -230,7 +416,7 @@ pub fn handle_review(
     for entry_result in walker_src {
         if let Ok(entry) = entry_result {
             let p = entry.path();
-            if dirs_ignorados.iter().any(|d| p.components().any(|c| c.as_os_str() == *d)) {
+            if DIRS_COLAPSADOS.iter().any(|d| p.components().any(|c| c.as_os_str() == *d)) {
                 continue;
             }
             if !p.is_file() {
@@ -242,6 +428,7 @@ pub fn handle_review(
             }
         }
     }
+    let mut candidates = super::skip_large_files(candidates, &agent_context.config, &agent_context.project_root, &output_mode);
 
     // Build set of changed files (those matching configured extensions)
     let changed_files = super::render::get_changed_files(&agent_context.project_root);
@@ -276,17 +463,18 @@ pub fn handle_review(
 
     match review_mode {
         ReviewMode::Small => {
-            // < 20 files: top 8 × 100 lines
+            // < 20 files: top 8 × 100 lines (o --context-lines)
             for p in candidates.iter().take(8) {
                 if let Ok(contenido) = std::fs::read_to_string(p) {
-                    let lines: Vec<&str> = contenido.lines().collect();
-                    let preview_lines = lines.len().min(100);
+                    let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+                    let (preview, preview_lines) =
+                        super::sampling::sample_source(&contenido, ext, super::sampling::context_budget(options.context_lines, 100));
                     codigo_muestra.push_str(&format!(
                         "\n\n=== {} ===\n{}",
                         p.strip_prefix(&agent_context.project_root)
                             .map(|r| r.display().to_string())
                             .unwrap_or_else(|_| p.display().to_string()),
-                        lines[..preview_lines].join("\n")
+                        preview
                     ));
                     muestras += 1;
                     total_lines_loaded += preview_lines;
@@ -294,7 +482,7 @@ pub fn handle_review(
             }
         }
         ReviewMode::Medium => {
-            // 20-80 files: centrality-based selection, top 20 × 150 lines
+            // 20-80 files: centrality-based selection, top 20 × 150 lines (o --context-lines)
             let central_files: Vec<std::path::PathBuf> = if let Some(ref db) = agent_context.index_db {
                 let conn = db.lock();
                 let mut stmt = conn.prepare(
@@ -320,14 +508,15 @@ pub fn handle_review(
 
             for p in selected.iter().take(20) {
                 if let Ok(contenido) = std::fs::read_to_string(p) {
-                    let lines: Vec<&str> = contenido.lines().collect();
-                    let preview_lines = lines.len().min(150);
+                    let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+                    let (preview, preview_lines) =
+                        super::sampling::sample_source(&contenido, ext, super::sampling::context_budget(options.context_lines, 150));
                     codigo_muestra.push_str(&format!(
                         "\n\n=== {} ===\n{}",
                         p.strip_prefix(&agent_context.project_root)
                             .map(|r| r.display().to_string())
                             .unwrap_or_else(|_| p.display().to_string()),
-                        lines[..preview_lines].join("\n")
+                        preview
                     ));
                     muestras += 1;
                     total_lines_loaded += preview_lines;
@@ -335,7 +524,7 @@ pub fn handle_review(
             }
         }
         ReviewMode::Large => {
-            // 80+ files: group by top-level subdir, up to 6 groups × 10 files × 80 lines
+            // 80+ files: group by top-level subdir, up to 6 groups × 10 files × 80 lines (o --context-lines)
             use std::collections::HashMap;
             let mut groups: HashMap<String, Vec<std::path::PathBuf>> = HashMap::new();
             for p in &candidates {
@@ -352,14 +541,15 @@ pub fn handle_review(
                 let group_files = &groups[key];
                 for p in group_files.iter().take(10) {
                     if let Ok(contenido) = std::fs::read_to_string(p) {
-                        let lines: Vec<&str> = contenido.lines().collect();
-                        let preview_lines = lines.len().min(80);
+                        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+                        let (preview, preview_lines) =
+                            super::sampling::sample_source(&contenido, ext, super::sampling::context_budget(options.context_lines, 80));
                         codigo_muestra.push_str(&format!(
                             "\n\n=== {} ===\n{}",
                             p.strip_prefix(&agent_context.project_root)
                                 .map(|r| r.display().to_string())
                                 .unwrap_or_else(|_| p.display().to_string()),
-                            lines[..preview_lines].join("\n")
+                            preview
                         ));
                         muestras += 1;
                         total_lines_loaded += preview_lines;
@@ -470,13 +660,17 @@ pub fn handle_review(
                 raw_json
             };
             match serde_json::from_str::<Vec<ReviewSuggestion>>(&json_str) {
+                Ok(suggestions) if !suggestions.is_empty() && options.apply.is_some() => {
+                    let min_impact = options.apply.unwrap_or(super::Severity::High);
+                    apply_by_impact(suggestions, min_impact, options.yes, &orchestrator, agent_context, rt);
+                }
                 Ok(mut suggestions) if !suggestions.is_empty() => {
                     while !suggestions.is_empty() {
                         println!("\n💡 {} sugerencias de mejora detectadas.", suggestions.len().to_string().cyan());
 
                         let mut options: Vec<String> = suggestions.iter()
                             .map(|s| {
-                                let line = format!("[{}] {} — {}", s.impact.to_uppercase(), s.title, s.description);
+                                let line = format!("[{}] {} — {}", s.impact.to_string().to_uppercase(), s.title, s.description);
                                 if line.chars().count() > 90 {
                                     format!("{}…", line.chars().take(89).collect::<String>())
                                 } else {
@@ -501,27 +695,7 @@ pub fn handle_review(
 
                                 let pb_dev = ui::crear_progreso(&format!("Aplicando mejora: {}...", suggestion.title));
 
-                                let file_context = suggestion.files_involved.first().and_then(|f| {
-                                    let path = agent_context.project_root.join(f);
-                                    std::fs::read_to_string(&path)
-                                        .ok()
-                                        .map(|content| format!("CONTENIDO ACTUAL DE {}:\n```\n{}\n```", f, content))
-                                });
-
-                                let dev_task = Task {
-                                    id: uuid::Uuid::new_v4().to_string(),
-                                    description: format!(
-                                        "IMPLEMENTACIÓN DE MEJORA ARQUITECTÓNICA\n\n\
-                                        TÍTULO: {}\n\
-                                        DESCRIPCIÓN: {}\n\
-                                        ACCIÓN REQUERIDA: {}\n\n\
-                                        OBJETIVO: Aplica la mejora al código real adjunto.",
-                                        suggestion.title, suggestion.description, suggestion.action_item
-                                    ),
-                                    task_type: TaskType::Fix,
-                                    file_path: suggestion.files_involved.first().map(|f| std::path::PathBuf::from(f)),
-                                    context: file_context,
-                                };
+                                let dev_task = fix_task(suggestion, &agent_context.project_root);
 
                                 let dev_result = rt.block_on(orchestrator.execute_task("FixSuggesterAgent", &dev_task, &agent_context));
                                 pb_dev.finish_and_clear();
@@ -535,13 +709,7 @@ pub fn handle_review(
                                         if bloques.is_empty() {
                                             println!("{}", d_res.output);
                                         } else {
-                                            println!("\n📂 {} archivo(s) a generar/modificar:", bloques.len().to_string().cyan());
-                                            for (path_opt, _) in &bloques {
-                                                match path_opt {
-                                                    Some(p) => println!("   • {}", p.cyan()),
-                                                    None => println!("   • (sin ruta — se mostrará en consola)"),
-                                                }
-                                            }
+                                            super::apply::listar_bloques(&bloques);
 
                                             let apply = Confirm::new()
                                                 .with_prompt("¿Deseas aplicar estos cambios automáticamente?")
@@ -550,61 +718,8 @@ pub fn handle_review(
                                                 .unwrap_or(false);
 
                                             if apply {
-                                                let mut saved = 0;
-                                                for (path_opt, code) in &bloques {
-                                                    match path_opt {
-                                                        Some(rel_path) => {
-                                                            let target = agent_context.project_root.join(rel_path);
-
-                                                            if target.is_dir() {
-                                                                println!("   ⚠️  '{}' es un directorio, omitido.", rel_path.yellow());
-                                                                continue;
-                                                            }
-
-                                                            if let Some(parent) = target.parent() {
-                                                                let _ = std::fs::create_dir_all(parent);
-                                                            }
-
-                                                            if target.exists() {
-                                                                let original_len = std::fs::metadata(&target)
-                                                                    .map(|m| m.len() as usize)
-                                                                    .unwrap_or(0);
-
-                                                                if original_len > 0 && code.len() < original_len / 3 {
-                                                                    println!(
-                                                                        "   ⚠️  '{}': respuesta truncada ({} chars vs {} original), saltando.",
-                                                                        rel_path, code.len(), original_len
-                                                                    );
-                                                                    continue;
-                                                                }
-
-                                                                let bak = {
-                                                                    let mut p = target.clone();
-                                                                    let mut fname = target.file_name().unwrap_or_default().to_os_string();
-                                                                    fname.push(".bak");
-                                                                    p.set_file_name(fname);
-                                                                    p
-                                                                };
-                                                                if let Err(e) = std::fs::copy(&target, &bak) {
-                                                                    println!("   ⚠️  No se pudo crear backup de '{}': {}", rel_path, e);
-                                                                    continue;
-                                                                }
-                                                            }
-
-                                                            match std::fs::write(&target, code) {
-                                                                Ok(_) => {
-                                                                    println!("   ✅ {}", rel_path.green());
-                                                                    saved += 1;
-                                                                }
-                                                                Err(e) => println!("   ❌ '{}': {}", rel_path, e),
-                                                            }
-                                                        }
-                                                        None => {
-                                                            println!("\n{}", "[Código sin ruta — cópialo manualmente:]".yellow());
-                                                            println!("{}", code);
-                                                        }
-                                                    }
-                                                }
+                                                let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+                                                let saved = super::apply::aplicar_bloques(&agent_context.project_root, &bloques, &backups);
 
                                                 if saved > 0 {
                                                     let mut s = agent_context.stats.lock().unwrap();
@@ -645,6 +760,47 @@ pub fn handle_review(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_project_tree_respects_depth_and_collapses_dependencies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for file in [
+            "src/main.ts",
+            "src/users/user.service.ts",
+            "src/users/dto/create-user.dto.ts",
+            "src/users/dto/update-user.dto.ts",
+            "node_modules/lodash/index.js",
+            "node_modules/lodash/package.json",
+            ".git/HEAD",
+        ] {
+            std::fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let tree = build_project_tree(root, 2, 0);
+        let lines: Vec<&str> = tree.text.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "  node_modules/ (+2 archivos)",
+                "  src",
+                "    main.ts",
+                "    users/ (+3 archivos)",
+            ]
+        );
+        assert_eq!(tree.entries, 4);
+        assert_eq!(format_count(MAX_FILE_COUNT), "10000");
+        assert_eq!(format_count(MAX_FILE_COUNT + 1), "10000+");
+
+        let deep = build_project_tree(root, 4, 0);
+        assert!(deep.text.contains("        create-user.dto.ts"), "{}", deep.text);
+        assert!(!deep.text.contains("lodash"));
+
+        let capped = build_project_tree(root, 4, 3);
+        assert_eq!(capped.text.lines().count(), 4);
+        assert!(capped.text.ends_with("primeras 3 líneas del árbol)"));
+    }
+
     #[test]
     fn test_review_size_thresholds() {
         assert_eq!(review_size_mode(5),   ReviewMode::Small);
@@ -678,6 +834,32 @@ mod tests {
         assert_eq!(loaded[0].suggestions.len(), 1);
     }
 
+    #[test]
+    fn test_select_by_impact_keeps_only_high_for_apply_high() {
+        let suggestion = |title: &str, impact: &str| ReviewSuggestion {
+            title: title.to_string(),
+            description: String::new(),
+            impact: impact.parse().unwrap(),
+            action_item: String::new(),
+            files_involved: vec!["src/app.ts".to_string()],
+        };
+        let suggestions = vec![
+            suggestion("Extraer capa de repositorio", "High"),
+            suggestion("Renombrar helpers", "Low"),
+            suggestion("Cachear consultas", "medium"),
+            suggestion("Validar entrada", "ALTA"),
+        ];
+
+        let (apply, skipped) = select_by_impact(suggestions.clone(), super::super::Severity::High);
+        let titles: Vec<&str> = apply.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Extraer capa de repositorio", "Validar entrada"]);
+        assert_eq!(skipped.len(), 2);
+
+        let (apply, skipped) = select_by_impact(suggestions, super::super::Severity::Medium);
+        assert_eq!((apply.len(), skipped.len()), (3, 1));
+        assert_eq!(skipped[0].title, "Renombrar helpers");
+    }
+
     #[test]
     fn test_review_diff_categorizes_correctly() {
         let old = vec![
@@ -0,0 +1,111 @@
+//! Muestras de código para los prompts de `review` y `audit`.
+//!
+//! Cuando un archivo supera el presupuesto de líneas se corta en un límite entre
+//! funciones (tree-sitter) para que el modelo reciba unidades completas.
+
+use tree_sitter::{Node, Parser};
+
+/// Tope de `--context-lines` para no desbordar el contexto del modelo.
+pub const MAX_CONTEXT_LINES: usize = 600;
+
+/// `--context-lines` efectivo: el valor del usuario (acotado a 1..=MAX) o el default del modo.
+pub fn context_budget(requested: Option<usize>, mode_default: usize) -> usize {
+    requested.map(|n| n.clamp(1, MAX_CONTEXT_LINES)).unwrap_or(mode_default)
+}
+
+fn is_function_node(kind: &str) -> bool {
+    (kind.contains("function") || kind.contains("method"))
+        && !kind.contains("call")
+        && !kind.contains("invocation")
+        && !kind.contains("type")
+        && !kind.contains("signature")
+}
+
+/// Rangos de filas (inicio, fin) de todas las funciones/métodos multilínea.
+fn function_spans(node: Node, spans: &mut Vec<(usize, usize)>) {
+    if is_function_node(node.kind()) && node.end_position().row > node.start_position().row {
+        spans.push((node.start_position().row, node.end_position().row));
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        function_spans(child, spans);
+    }
+}
+
+/// Número de líneas a incluir de `content` (extensión `ext`) con a lo sumo `budget` líneas.
+/// Retrocede hasta el último corte que no deja una función a medias, salvo que eso
+/// desperdicie más de la mitad del presupuesto (p. ej. una sola función enorme).
+pub fn preview_len(content: &str, ext: &str, budget: usize) -> usize {
+    let total = content.lines().count();
+    if total <= budget {
+        return total;
+    }
+    let Some((language, _)) = crate::rules::languages::get_language_and_analyzers(ext) else {
+        return budget;
+    };
+    let mut parser = Parser::new();
+    if parser.set_language(&language).is_err() {
+        return budget;
+    }
+    let Some(tree) = parser.parse(content, None) else {
+        return budget;
+    };
+    let mut spans = Vec::new();
+    function_spans(tree.root_node(), &mut spans);
+
+    // Cortar en `n` incluye las filas 0..n: corta una función si empieza antes y termina después
+    let cuts_function = |n: usize| spans.iter().any(|&(start, end)| start < n && end >= n);
+    (budget / 2..=budget)
+        .rev()
+        .find(|&n| n > 0 && !cuts_function(n))
+        .unwrap_or(budget)
+}
+
+/// Primeras líneas de `content` según `preview_len`, junto con cuántas se incluyeron.
+pub fn sample_source(content: &str, ext: &str, budget: usize) -> (String, usize) {
+    let n = preview_len(content, ext, budget);
+    (content.lines().take(n).collect::<Vec<_>>().join("\n"), n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_FUNCTIONS: &str = "\
+import { db } from './db';
+
+export function first(id: string) {
+  const row = db.find(id);
+  return row;
+}
+
+export function second(items: string[]) {
+  let total = 0;
+  for (const item of items) {
+    total += item.length;
+  }
+  return total;
+}
+";
+
+    #[test]
+    fn test_preview_stops_at_function_boundary() {
+        // 10 líneas cortarían `second` (filas 7–13): se retrocede al final de `first`
+        let (sample, n) = sample_source(TWO_FUNCTIONS, "ts", 10);
+        assert_eq!(n, 7);
+        assert!(sample.trim_end().ends_with('}'));
+        assert!(!sample.contains("second"));
+
+        // Con presupuesto suficiente entran ambas completas
+        assert_eq!(preview_len(TWO_FUNCTIONS, "ts", 14), 14);
+        // Lenguaje sin parser: corte duro
+        assert_eq!(preview_len(TWO_FUNCTIONS, "txt", 10), 10);
+    }
+
+    #[test]
+    fn test_context_budget_is_clamped() {
+        assert_eq!(context_budget(None, 150), 150);
+        assert_eq!(context_budget(Some(40), 150), 40);
+        assert_eq!(context_budget(Some(100_000), 150), MAX_CONTEXT_LINES);
+    }
+}
@@ -0,0 +1,240 @@
+//! `pro score`: una sola puntuación de calidad 0–100 para seguir en el tiempo.
+//!
+//! Combina las violaciones del análisis estático (ponderadas por severidad y normalizadas
+//! por tamaño del proyecto), la complejidad media de las funciones complejas y la cobertura
+//! de `pro coverage`. Los pesos vienen de la sección `[score]` del config.
+
+use crate::config::ScoreConfig;
+use crate::index::quality_history::{QualityHistory, ScoreRecord, run_scope};
+use crate::rules::RuleLevel;
+use colored::*;
+use serde::Serialize;
+
+/// Lo que resta puntos a la puntuación.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ScoreFactor {
+    pub name: String,
+    pub penalty: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScoreResult {
+    #[serde(flatten)]
+    pub record: ScoreRecord,
+    /// Factores ordenados de mayor a menor penalización
+    pub factors: Vec<ScoreFactor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<f64>,
+}
+
+fn redondear(x: f64) -> f64 {
+    (x * 10.0).round() / 10.0
+}
+
+pub fn grade(score: f64) -> &'static str {
+    match score {
+        s if s >= 90.0 => "A",
+        s if s >= 80.0 => "B",
+        s if s >= 70.0 => "C",
+        s if s >= 60.0 => "D",
+        _ => "F",
+    }
+}
+
+/// Aplica la fórmula de `[score]` sobre las métricas de `record` (se ignoran `score`/`grade`).
+pub fn compute_score(record: &ScoreRecord, weights: &ScoreConfig) -> (f64, Vec<ScoreFactor>) {
+    let per_10_files = |count: usize| count as f64 * 10.0 / record.files.max(1) as f64;
+    let mut factors = vec![
+        ScoreFactor { name: "errores".to_string(), penalty: per_10_files(record.errors) * weights.error_weight },
+        ScoreFactor { name: "warnings".to_string(), penalty: per_10_files(record.warnings) * weights.warning_weight },
+        ScoreFactor { name: "infos".to_string(), penalty: per_10_files(record.infos) * weights.info_weight },
+        ScoreFactor {
+            name: "complejidad".to_string(),
+            penalty: (record.avg_complexity - weights.complexity_baseline).max(0.0) * weights.complexity_weight,
+        },
+        ScoreFactor {
+            name: "cobertura".to_string(),
+            penalty: (100.0 - record.coverage).clamp(0.0, 100.0) * weights.coverage_weight,
+        },
+    ];
+    for f in &mut factors {
+        f.penalty = redondear(f.penalty);
+    }
+    let total: f64 = factors.iter().map(|f| f.penalty).sum();
+    factors.retain(|f| f.penalty > 0.0);
+    factors.sort_by(|a, b| b.penalty.total_cmp(&a.penalty));
+    (redondear((100.0 - total).clamp(0.0, 100.0)), factors)
+}
+
+/// Métricas del análisis estático de `files` con los mismos filtros que `pro check`
+/// (supresiones inline, `.sentinel/ignore.json` y umbrales de `[rule_config]`).
+fn collect_metrics(
+    files: &[std::path::PathBuf],
+    agent_context: &crate::agents::base::AgentContext,
+) -> ScoreRecord {
+    let root = &agent_context.project_root;
+    let mut rule_engine = crate::rules::engine::RuleEngine::new()
+        .with_sentinelignore(root)
+        .with_entry_patterns(root, &agent_context.config)
+        .with_secret_allowlist(root, &agent_context.config.rule_config.secret_allowlist)
+        .with_thresholds(&agent_context.config.rule_config);
+    if let Some(ref db) = agent_context.index_db {
+        rule_engine = rule_engine.with_index_db(std::sync::Arc::clone(db));
+    }
+    let rules_path = root.join(".sentinel/rules.yaml");
+    if rules_path.exists() {
+        let _ = rule_engine.load_from_yaml(&rules_path);
+    }
+    let ignore_entries = crate::commands::ignore::load_ignore_entries(root);
+    let rule_cfg = &agent_context.config.rule_config;
+
+    let mut record = ScoreRecord { files: files.len(), ..Default::default() };
+    let mut complexities = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(file).unwrap_or_default();
+        let rel = file.strip_prefix(root).unwrap_or(file).display().to_string();
+        let (violations, _) = rule_engine.validate_file_with_suppressed(file, &content);
+        for v in violations {
+            if v.rule_name == "HIGH_COMPLEXITY"
+                && let Some(c) = v.value
+            {
+                complexities.push(c as f64);
+            }
+            if super::check::is_ignored(&ignore_entries, &v.rule_name, &rel, v.symbol.as_deref())
+                || !super::check::passes_rule_config(rule_cfg, &v.rule_name, v.value)
+            {
+                continue;
+            }
+            match v.level {
+                RuleLevel::Error => record.errors += 1,
+                RuleLevel::Warning => record.warnings += 1,
+                RuleLevel::Info => record.infos += 1,
+            }
+        }
+    }
+    if !complexities.is_empty() {
+        record.avg_complexity = redondear(complexities.iter().sum::<f64>() / complexities.len() as f64);
+    }
+    record
+}
+
+fn colorear(score: f64) -> ColoredString {
+    let text = format!("{:.1}", score);
+    if score >= 80.0 {
+        text.green()
+    } else if score >= 60.0 {
+        text.yellow()
+    } else {
+        text.red()
+    }
+}
+
+pub fn handle_score(
+    target: &str,
+    format: &str,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+) {
+    let (json_mode, _) = super::format_to_mode(format);
+    let root = &agent_context.project_root;
+    let path = root.join(target);
+    if !path.exists() {
+        if json_mode {
+            println!("{{\"error\":\"El destino '{}' no existe\"}}", target);
+        } else {
+            println!("{} El destino '{}' no existe en el proyecto.", "❌".red(), target);
+        }
+        std::process::exit(2);
+    }
+
+    let config = &agent_context.config;
+    let mut files = Vec::new();
+    let walker = ignore::WalkBuilder::new(&path).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if p.is_file() && config.file_extensions.iter().any(|e| e == ext) {
+            files.push(p.to_path_buf());
+        }
+    }
+
+    let mut record = collect_metrics(&files, agent_context);
+    record.coverage =
+        super::coverage::compute_coverage(root, &files, &config.framework, &config.test_patterns).percent;
+    let (score, factors) = compute_score(&record, &config.score);
+    record.score = score;
+    record.grade = grade(score).to_string();
+
+    let mut previous = None;
+    if let Some(ref db) = agent_context.index_db {
+        let history = QualityHistory::new(db);
+        let scope = run_scope(target, &config.rule_config);
+        previous = history.score_history(1, &scope).ok().and_then(|h| h.first().map(|(_, s)| *s));
+        if let Err(e) = history.record_score(&record, &scope) {
+            log::debug!("No se pudo guardar la puntuación: {}", e);
+        }
+    }
+
+    let result = ScoreResult { record, factors, previous };
+    if json_mode {
+        println!("{}", serde_json::to_string_pretty(&result).unwrap_or_default());
+        return;
+    }
+
+    let r = &result.record;
+    println!(
+        "\n🏅 {} {} / 100  ({})",
+        "PUNTUACIÓN DE CALIDAD:".bold(),
+        colorear(r.score).bold(),
+        r.grade.bold()
+    );
+    if let Some(prev) = result.previous {
+        let delta = r.score - prev;
+        let delta_str = format!("{:+.1}", delta);
+        println!(
+            "   Anterior: {:.1} ({})",
+            prev,
+            if delta >= 0.0 { delta_str.green() } else { delta_str.red() }
+        );
+    }
+    if output_mode == crate::commands::OutputMode::Quiet {
+        return;
+    }
+    println!(
+        "   {} archivos · {} errores · {} warnings · {} infos · complejidad media {:.1} · cobertura {:.1}%",
+        r.files, r.errors, r.warnings, r.infos, r.avg_complexity, r.coverage
+    );
+    if !result.factors.is_empty() {
+        println!("\n   {} Lo que más resta:", "📉".yellow());
+        for f in result.factors.iter().take(3) {
+            println!("      -{:>5.1}  {}", f.penalty, f.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_formula_with_default_weights() {
+        let record = ScoreRecord {
+            files: 20,
+            errors: 2, // 1.0 cada 10 archivos × 5.0 = 5.0
+            warnings: 8, // 4.0 × 1.0 = 4.0
+            infos: 5, // 2.5 × 0.2 = 0.5
+            avg_complexity: 13.0, // (13 − 10) × 2.0 = 6.0
+            coverage: 60.0, // 40 × 0.3 = 12.0
+            ..Default::default()
+        };
+        let (score, factors) = compute_score(&record, &ScoreConfig::default());
+        assert_eq!(score, 72.5);
+        assert_eq!(grade(score), "C");
+        let top: Vec<&str> = factors.iter().take(3).map(|f| f.name.as_str()).collect();
+        assert_eq!(top, vec!["cobertura", "complejidad", "errores"]);
+
+        let clean = ScoreRecord { files: 10, coverage: 100.0, avg_complexity: 8.0, ..Default::default() };
+        let (score, factors) = compute_score(&clean, &ScoreConfig::default());
+        assert_eq!((score, factors.len()), (100.0, 0));
+    }
+}
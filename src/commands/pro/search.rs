@@ -0,0 +1,179 @@
+//! `pro search`: búsqueda semántica de código sobre la Knowledge Base (Qdrant),
+//! con fallback a búsqueda por texto cuando la KB no está disponible.
+
+use crate::kb::{SearchHit, VectorDB, VectorStore};
+use colored::*;
+use serde::Serialize;
+use std::path::Path;
+
+/// Líneas de contexto que se muestran por resultado.
+const SNIPPET_LINES: usize = 4;
+
+#[derive(Debug, Serialize)]
+struct SearchOutput<'a> {
+    query: &'a str,
+    /// "semantic" o "grep"
+    mode: &'a str,
+    results: &'a [SearchHit],
+}
+
+/// Vecinos más cercanos, ordenados por score descendente y recortados a `limit`.
+pub fn semantic_search(store: &dyn VectorStore, vector: &[f32], limit: usize) -> anyhow::Result<Vec<SearchHit>> {
+    if store.count()? == 0 {
+        anyhow::bail!("la colección está vacía");
+    }
+    let mut hits = store.search(vector, limit)?;
+    hits.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+/// Fallback: líneas que contienen `query` (sin distinguir mayúsculas).
+pub fn grep_search(root: &Path, extensions: &[String], query: &str, limit: usize) -> Vec<SearchHit> {
+    let needle = query.to_lowercase();
+    let mut hits = Vec::new();
+    let walker = ignore::WalkBuilder::new(root).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if !p.is_file() || !extensions.iter().any(|e| e == ext) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(p) else { continue };
+        let lines: Vec<&str> = content.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            if line.to_lowercase().contains(&needle) {
+                let end = (i + SNIPPET_LINES).min(lines.len());
+                hits.push(SearchHit {
+                    file_path: p.strip_prefix(root).unwrap_or(p).to_string_lossy().replace('\\', "/"),
+                    line: i + 1,
+                    snippet: lines[i..end].join("\n"),
+                    score: None,
+                });
+                if hits.len() >= limit {
+                    return hits;
+                }
+            }
+        }
+    }
+    hits
+}
+
+/// Salida de texto: `file:line` (con score si lo hay) y las primeras líneas del fragmento.
+pub fn format_hits(hits: &[SearchHit]) -> String {
+    let mut out = String::new();
+    for (i, hit) in hits.iter().enumerate() {
+        let score = hit.score.map(|s| format!(" ({:.3})", s)).unwrap_or_default();
+        out.push_str(&format!("{}. {}:{}{}\n", i + 1, hit.file_path, hit.line, score));
+        for line in hit.snippet.lines().take(SNIPPET_LINES) {
+            out.push_str(&format!("   │ {}\n", line));
+        }
+    }
+    out
+}
+
+fn buscar_en_kb(query: &str, limit: usize, agent_context: &crate::agents::base::AgentContext) -> anyhow::Result<Vec<SearchHit>> {
+    let config = &agent_context.config;
+    if !config.features.as_ref().map(|f| f.enable_knowledge_base).unwrap_or(true) {
+        anyhow::bail!("la Knowledge Base está desactivada (features.enable_knowledge_base)");
+    }
+    let vector = crate::ai::obtener_embeddings(vec![query.to_string()], &config.primary_model)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("el proveedor no devolvió embeddings"))?;
+    let db = VectorDB::for_project(&config.project_name);
+    semantic_search(&db, &vector, limit)
+}
+
+pub fn handle_search(
+    query: &str,
+    limit: usize,
+    format: &str,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+) {
+    let (json_mode, _) = super::format_to_mode(format);
+    let limit = limit.max(1);
+
+    let (mode, hits) = match buscar_en_kb(query, limit, agent_context) {
+        Ok(hits) => ("semantic", hits),
+        Err(e) => {
+            // stderr: stdout queda limpio para --format json
+            if output_mode != crate::commands::OutputMode::Quiet {
+                eprintln!(
+                    "{} KB no disponible ({}): usando búsqueda por texto.",
+                    "⚠️".yellow(),
+                    e
+                );
+            }
+            let root = &agent_context.project_root;
+            ("grep", grep_search(root, &agent_context.config.file_extensions, query, limit))
+        }
+    };
+
+    if json_mode {
+        let out = SearchOutput { query, mode, results: &hits };
+        println!("{}", serde_json::to_string_pretty(&out).unwrap_or_default());
+        return;
+    }
+
+    if hits.is_empty() {
+        println!("{} Sin resultados para '{}'.", "ℹ️".cyan(), query);
+        return;
+    }
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!("\n🔎 {} resultado(s) para '{}' ({}):\n", hits.len(), query.cyan(), mode);
+    }
+    print!("{}", format_hits(&hits));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockStore(Vec<SearchHit>);
+
+    impl VectorStore for MockStore {
+        fn count(&self) -> anyhow::Result<u64> {
+            Ok(self.0.len() as u64)
+        }
+        fn search(&self, _vector: &[f32], _limit: usize) -> anyhow::Result<Vec<SearchHit>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn hit(file: &str, line: usize, score: f32, snippet: &str) -> SearchHit {
+        SearchHit { file_path: file.to_string(), line, snippet: snippet.to_string(), score: Some(score) }
+    }
+
+    #[test]
+    fn test_semantic_hits_ranked_and_formatted() {
+        let store = MockStore(vec![
+            hit("src/auth/token.ts", 12, 0.71, "export function refreshToken() {\n  return sign(user);\n}"),
+            hit("src/auth/login.ts", 3, 0.93, "export async function login(user: User) {"),
+            hit("src/users/users.ts", 40, 0.52, "findUser()"),
+        ]);
+
+        let hits = semantic_search(&store, &[0.1, 0.2], 2).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].file_path, "src/auth/login.ts");
+
+        let text = format_hits(&hits);
+        assert_eq!(
+            text,
+            "1. src/auth/login.ts:3 (0.930)\n   │ export async function login(user: User) {\n\
+             2. src/auth/token.ts:12 (0.710)\n   │ export function refreshToken() {\n   │   return sign(user);\n   │ }\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_collection_triggers_fallback() {
+        assert!(semantic_search(&MockStore(vec![]), &[0.1], 5).is_err());
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.ts"), "const x = 1;\nfunction RefreshToken() {}\n").unwrap();
+        let hits = grep_search(tmp.path(), &["ts".to_string()], "refreshtoken", 5);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].file_path.as_str(), hits[0].line, hits[0].score), ("a.ts", 2, None));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::agents::base::{AgentContext, Task, TaskType};
+use crate::agents::orchestrator::AgentOrchestrator;
+use crate::ui;
+use colored::*;
+use dialoguer::Confirm;
+use std::path::Path;
+
+type Bloque = (Option<String>, String);
+
+fn misma_ruta(a: &str, b: &str) -> bool {
+    a.trim_start_matches("./") == b.trim_start_matches("./")
+}
+
+/// Archivo barrel que re-exporta los módulos nuevos desde la ruta del original.
+/// Solo TypeScript/JavaScript y Python tienen una forma idiomática de hacerlo.
+fn contenido_barrel(original: &str, nuevos: &[&str]) -> Option<String> {
+    let dir = Path::new(original).parent().unwrap_or(Path::new(""));
+    let ext = Path::new(original).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let modulos: Vec<String> = nuevos
+        .iter()
+        .map(|n| {
+            let rel = Path::new(n).strip_prefix(dir).unwrap_or(Path::new(n));
+            rel.with_extension("").to_string_lossy().replace('\\', "/")
+        })
+        .collect();
+
+    let lineas: Vec<String> = match ext {
+        "ts" | "tsx" | "js" | "jsx" => modulos.iter().map(|m| format!("export * from './{}';", m)).collect(),
+        "py" => modulos.iter().map(|m| format!("from .{} import *  # noqa: F401,F403", m.replace('/', "."))).collect(),
+        _ => return None,
+    };
+    Some(lineas.join("\n") + "\n")
+}
+
+/// Escribe los archivos propuestos por el SplitterAgent.
+///
+/// Los archivos nuevos y el original (con su bloque TODO) pasan por `aplicar_bloques`
+/// (rutas seguras, sintaxis, truncado y copia en `backups`). Con `barrel`, el original se
+/// reemplaza por un archivo que re-exporta los módulos nuevos; su contenido queda en la copia.
+///
+/// Devuelve las rutas escritas.
+pub fn aplicar_split(
+    root: &Path,
+    original: &str,
+    bloques: &[Bloque],
+    barrel: bool,
+    backups: &super::backup::Backups,
+) -> Vec<String> {
+    let (originales, nuevos): (Vec<Bloque>, Vec<Bloque>) = bloques
+        .iter()
+        .filter(|(path, _)| path.is_some())
+        .cloned()
+        .partition(|(path, _)| path.as_deref().is_some_and(|p| misma_ruta(p, original)));
+
+    let mut escritos = Vec::new();
+    for bloque in &nuevos {
+        if super::apply::aplicar_bloques(root, std::slice::from_ref(bloque), backups) > 0 {
+            escritos.push(bloque.0.clone().unwrap_or_default());
+        }
+    }
+    if escritos.is_empty() {
+        println!("   ⚠️  No se escribió ningún archivo nuevo; el original queda intacto.");
+        return escritos;
+    }
+
+    if barrel {
+        let nuevos_rel: Vec<&str> = escritos.iter().map(String::as_str).collect();
+        let Some(contenido) = contenido_barrel(original, &nuevos_rel) else {
+            println!("   ⚠️  --barrel solo está soportado para TS/JS y Python; el original no se modifica.");
+            return escritos;
+        };
+        let target = match crate::files::secure_join(root, Path::new(original)) {
+            Ok(t) => t,
+            Err(e) => {
+                println!("   ⚠️  '{}' omitido: {}", original.yellow(), e);
+                return escritos;
+            }
+        };
+        // El barrel es corto a propósito: no aplica el guard de truncado, pero sí el backup
+        if let Err(e) = backups.backup_before_write(&target) {
+            println!("   ⚠️  No se pudo crear backup de '{}': {}", original, e);
+            return escritos;
+        }
+        match std::fs::write(&target, contenido) {
+            Ok(_) => {
+                println!("   ✅ {} (barrel)", original.green());
+                escritos.push(original.to_string());
+            }
+            Err(e) => println!("   ❌ '{}': {}", original, e),
+        }
+    } else if super::apply::aplicar_bloques(root, &originales, backups) > 0 {
+        escritos.push(original.to_string());
+    }
+    escritos
+}
+
+pub fn handle_split(
+    file: &str,
+    barrel: bool,
+    agent_context: &AgentContext,
+    orchestrator: &AgentOrchestrator,
+    output_mode: crate::commands::OutputMode,
+    rt: &tokio::runtime::Runtime,
+) {
+    let path = match crate::files::secure_join(&agent_context.project_root, Path::new(file)) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            std::process::exit(2);
+        }
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{} No se pudo leer '{}': {}", "❌".red(), file, e);
+            std::process::exit(1);
+        }
+    };
+
+    if output_mode != crate::commands::OutputMode::Quiet {
+        println!("\n✂️  Dividiendo {} ({} líneas)...", file.cyan(), content.lines().count());
+    }
+
+    let task = Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: format!("Divide '{}' en módulos cohesivos por dominio.", file),
+        task_type: TaskType::Refactor,
+        file_path: Some(std::path::PathBuf::from(file)),
+        context: Some(content),
+    };
+    let pb = ui::crear_progreso("Planificando división (SplitterAgent)...");
+    let result = rt.block_on(orchestrator.execute_task("SplitterAgent", &task, agent_context));
+    pb.finish_and_clear();
+
+    let res = match result {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{} Error al dividir el archivo: {}", "❌".red(), e);
+            std::process::exit(1);
+        }
+    };
+    if !res.success {
+        println!("   ℹ️  {}", res.output);
+        return;
+    }
+
+    let bloques = crate::ai::utils::extraer_todos_bloques(&res.output);
+    if bloques.is_empty() {
+        println!("{}", res.output);
+        println!("\n{} El SplitterAgent no devolvió bloques de código aplicables.", "⚠️".yellow());
+        return;
+    }
+
+    super::apply::listar_bloques(&bloques);
+    let apply = Confirm::new()
+        .with_prompt("¿Deseas escribir los archivos de la división?")
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+    if !apply {
+        println!("   ⏭️  División descartada.");
+        return;
+    }
+
+    let backups = super::backup::Backups::for_config(&agent_context.project_root, &agent_context.config);
+    let escritos = aplicar_split(&agent_context.project_root, file, &bloques, barrel, &backups);
+    if !escritos.is_empty() {
+        let mut s = agent_context.stats.lock().unwrap();
+        s.sugerencias_aplicadas += 1;
+        s.guardar(&agent_context.project_root);
+    }
+    println!("\n✅ {} archivo(s) escritos:", escritos.len().to_string().green());
+    for f in &escritos {
+        println!("   • {}", f.cyan());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::pro::backup::Backups;
+
+    const RESPUESTA: &str = "ARCHIVOS PROPUESTOS:\n  📤 [syncContacts] → contacts.service.ts\n\n\
+```typescript\n// src/crm/contacts.service.ts\nexport class ContactsService {\n  syncContacts() { return 1; }\n}\n```\n\n\
+```typescript\n// src/crm/deals.service.ts\nexport class DealsService {\n  closeDeal() { return 2; }\n}\n```\n\n\
+```typescript\n// src/crm/crm.service.ts\n// TODO: [sentinel] SPLIT\nexport class CrmService {\n  syncContacts() { return 1; }\n  closeDeal() { return 2; }\n}\n```\n";
+
+    fn proyecto() -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("src/crm")).unwrap();
+        std::fs::write(
+            tmp.path().join("src/crm/crm.service.ts"),
+            "export class CrmService {\n  syncContacts() { return 1; }\n  closeDeal() { return 2; }\n}\n",
+        )
+        .unwrap();
+        tmp
+    }
+
+    #[test]
+    fn test_split_blocks_land_at_their_paths() {
+        let tmp = proyecto();
+        let bloques = crate::ai::utils::extraer_todos_bloques(RESPUESTA);
+        let backups = Backups::new(tmp.path(), Path::new(".sentinel/backups"));
+        let escritos = aplicar_split(tmp.path(), "src/crm/crm.service.ts", &bloques, false, &backups);
+
+        assert_eq!(
+            escritos,
+            vec!["src/crm/contacts.service.ts", "src/crm/deals.service.ts", "src/crm/crm.service.ts"]
+        );
+        let deals = std::fs::read_to_string(tmp.path().join("src/crm/deals.service.ts")).unwrap();
+        assert!(deals.contains("export class DealsService"));
+        let original = std::fs::read_to_string(tmp.path().join("src/crm/crm.service.ts")).unwrap();
+        assert!(original.contains("TODO: [sentinel] SPLIT"));
+        assert!(backups.latest(&tmp.path().join("src/crm/crm.service.ts")).is_some());
+    }
+
+    #[test]
+    fn test_split_barrel_replaces_original() {
+        let tmp = proyecto();
+        let bloques = crate::ai::utils::extraer_todos_bloques(RESPUESTA);
+        let backups = Backups::new(tmp.path(), Path::new(".sentinel/backups"));
+        aplicar_split(tmp.path(), "src/crm/crm.service.ts", &bloques, true, &backups);
+
+        let barrel = std::fs::read_to_string(tmp.path().join("src/crm/crm.service.ts")).unwrap();
+        assert_eq!(barrel, "export * from './contacts.service';\nexport * from './deals.service';\n");
+        let bak = std::fs::read_to_string(backups.latest(&tmp.path().join("src/crm/crm.service.ts")).unwrap()).unwrap();
+        assert!(bak.contains("closeDeal"));
+    }
+}
@@ -0,0 +1,341 @@
+//! `pro test-all`: genera con el TesterAgent un test para cada archivo sin test y lo
+//! verifica en el momento.
+//!
+//! Los archivos sin test son los que lista `pro coverage`. Tras escribir cada test se
+//! ejecuta solo ese archivo con el `test_command` del config (`{file}` marca dónde va la
+//! ruta; si no aparece, se añade al final). Si falla, la salida del runner vuelve al agente
+//! hasta `MAX_RETRIES` veces; si ningún intento pasa, queda en disco el de menos fallos.
+
+use crate::agents::base::AgentContext;
+use crate::agents::orchestrator::AgentOrchestrator;
+use crate::agents::tester::{test_task, FailedAttempt};
+use colored::*;
+use std::path::Path;
+
+/// Reintentos tras el primer test generado.
+pub const MAX_RETRIES: usize = 2;
+
+pub struct TestRun {
+    pub passed: bool,
+    pub output: String,
+}
+
+/// Ejecuta un único archivo de test (separado para simular el runner en tests).
+pub trait TestRunner {
+    fn run(&self, test_file: &str) -> TestRun;
+}
+
+/// `test_command` del config con la ruta del test como argumento.
+pub struct CommandRunner<'a> {
+    command: &'a str,
+    project_root: &'a Path,
+}
+
+impl<'a> CommandRunner<'a> {
+    pub fn new(command: &'a str, project_root: &'a Path) -> Self {
+        Self { command, project_root }
+    }
+
+    fn args(&self, test_file: &str) -> Vec<String> {
+        let mut args: Vec<String> = self.command.split_whitespace().map(|a| a.replace("{file}", test_file)).collect();
+        if !self.command.contains("{file}") {
+            args.push(test_file.to_string());
+        }
+        args
+    }
+}
+
+impl TestRunner for CommandRunner<'_> {
+    fn run(&self, test_file: &str) -> TestRun {
+        let args = self.args(test_file);
+        let Some((program, rest)) = args.split_first() else {
+            return TestRun { passed: false, output: "test_command vacío en la configuración".to_string() };
+        };
+        // Mismo sandbox que `tests::ejecutar_tests`: sin secretos del entorno
+        let output = std::process::Command::new(program)
+            .args(rest)
+            .current_dir(self.project_root)
+            .env_clear()
+            .env("PATH", std::env::var("PATH").unwrap_or_default())
+            .env("NODE_ENV", "test")
+            .env("CI", "true")
+            .env("USER", std::env::var("USER").unwrap_or_default())
+            .env("HOME", std::env::var("HOME").unwrap_or_default())
+            .output();
+        match output {
+            Ok(out) => TestRun {
+                passed: out.status.success(),
+                output: format!("{}\n{}", String::from_utf8_lossy(&out.stdout), String::from_utf8_lossy(&out.stderr)),
+            },
+            Err(e) => TestRun { passed: false, output: format!("No se pudo ejecutar '{}': {}", program, e) },
+        }
+    }
+}
+
+/// Tests fallidos según el resumen del runner (`2 failed` en Jest, Vitest y pytest). Sin
+/// resumen (error de compilación, runner desconocido) cuenta como el peor intento.
+fn failure_count(output: &str) -> usize {
+    let re = regex::Regex::new(r"(\d+) failed").unwrap();
+    re.captures_iter(output)
+        .filter_map(|c| c[1].parse::<usize>().ok())
+        .max()
+        .unwrap_or(usize::MAX)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Verification {
+    pub passed: bool,
+    pub attempts: usize,
+}
+
+/// Escribe en `test_path` el test que devuelve `generate` y lo ejecuta con `runner`; si
+/// falla, vuelve a llamar a `generate` con el intento fallido, hasta `MAX_RETRIES` veces.
+/// Si ninguno pasa, deja en `test_path` el intento con menos tests fallidos. Nunca pisa un
+/// test que ya existe: devuelve error sin llamar a `generate`.
+pub fn generate_and_verify<G>(
+    test_path: &Path,
+    test_file: &str,
+    runner: &dyn TestRunner,
+    mut generate: G,
+) -> anyhow::Result<Verification>
+where
+    G: FnMut(Option<&FailedAttempt>) -> anyhow::Result<String>,
+{
+    if test_path.exists() {
+        anyhow::bail!("'{}' ya existe; no se sobrescribe", test_file);
+    }
+    let mut best: Option<(usize, String)> = None;
+    let mut last: Option<(String, String)> = None;
+    let mut attempts = 0;
+    for _ in 0..=MAX_RETRIES {
+        let previous = last.as_ref().map(|(test_code, output)| FailedAttempt { test_code, output });
+        let code = match generate(previous.as_ref()) {
+            Ok(code) => code,
+            Err(e) if best.is_some() => {
+                if !crate::commands::is_quiet() {
+                    println!("   ⚠️  No se pudo regenerar '{}': {}", test_file, e);
+                }
+                break;
+            }
+            Err(e) => return Err(e),
+        };
+        attempts += 1;
+        if let Some(parent) = test_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(test_path, &code)?;
+
+        let run = runner.run(test_file);
+        if run.passed {
+            return Ok(Verification { passed: true, attempts });
+        }
+        let failures = failure_count(&run.output);
+        if best.as_ref().is_none_or(|(fewest, _)| failures < *fewest) {
+            best = Some((failures, code.clone()));
+        }
+        last = Some((code, run.output));
+    }
+
+    if let (Some((_, best_code)), Some((last_code, _))) = (&best, &last)
+        && best_code != last_code
+    {
+        std::fs::write(test_path, best_code)?;
+    }
+    Ok(Verification { passed: false, attempts })
+}
+
+/// Ruta (relativa) del test a generar junto a `source`: `x.spec.ts` en NestJS, `x.test.ts`
+/// en el resto de TS/JS, `test_x.py` y `x_test.go`. `None` en otros lenguajes (en Rust
+/// los tests van inline en el propio archivo).
+pub fn test_file_for(source: &str, framework: &str) -> Option<String> {
+    let path = Path::new(source);
+    let ext = path.extension()?.to_str()?;
+    let stem = path.file_stem()?.to_str()?;
+    let name = match ext {
+        "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => {
+            let kind = if framework.to_lowercase().contains("nest") { "spec" } else { "test" };
+            format!("{}.{}.{}", stem, kind, ext)
+        }
+        "py" => format!("test_{}.py", stem),
+        "go" => format!("{}_test.go", stem),
+        _ => return None,
+    };
+    Some(path.with_file_name(name).to_string_lossy().replace('\\', "/"))
+}
+
+pub fn handle_test_all(
+    target: &str,
+    agent_context: &AgentContext,
+    orchestrator: &AgentOrchestrator,
+    output_mode: crate::commands::OutputMode,
+    rt: &tokio::runtime::Runtime,
+) {
+    let root = &agent_context.project_root;
+    let config = &agent_context.config;
+    let path = root.join(target);
+    if !path.exists() {
+        println!("{} El destino '{}' no existe en el proyecto.", "❌".red(), target);
+        std::process::exit(2);
+    }
+
+    let mut source_files = Vec::new();
+    let walker = ignore::WalkBuilder::new(&path).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if p.is_file() && config.file_extensions.iter().any(|e| e == ext) {
+            source_files.push(p.to_path_buf());
+        }
+    }
+    let (source_files, _) = crate::files::filtrar_por_tamano(source_files, config.max_file_bytes);
+    let untested = super::coverage::compute_coverage(root, &source_files, &config.framework, &config.test_patterns).untested;
+    if untested.is_empty() {
+        println!("{} Todos los archivos de '{}' ya tienen test.", "✅".green(), target);
+        return;
+    }
+
+    let quiet = output_mode == crate::commands::OutputMode::Quiet;
+    if !quiet {
+        println!(
+            "\n🧪 Generando y verificando tests para {} archivo(s) sin test (`{}`)...",
+            untested.len().to_string().cyan(),
+            config.test_command
+        );
+    }
+
+    let runner = CommandRunner::new(&config.test_command, root);
+    let (mut passed, mut failed) = (0, 0);
+    for source_file in &untested {
+        let Some(test_file) = test_file_for(source_file, &config.framework) else {
+            if !quiet {
+                println!("   ⏭️  {}: lenguaje sin test por archivo, omitido.", source_file.dimmed());
+            }
+            continue;
+        };
+        let Ok(source) = std::fs::read_to_string(root.join(source_file)) else { continue };
+        if !quiet {
+            println!("\n   📄 {} → {}", source_file.cyan(), test_file);
+        }
+
+        let generate = |previous: Option<&FailedAttempt>| -> anyhow::Result<String> {
+            if previous.is_some() && !quiet {
+                println!("   🔁 El test falla: reintentando con la salida del runner...");
+            }
+            let task = test_task(source_file, &source, &test_file, previous);
+            let result = rt.block_on(orchestrator.execute_task("TesterAgent", &task, agent_context))?;
+            match result.artifacts.into_iter().next() {
+                Some(code) if !code.trim().is_empty() => Ok(code),
+                _ => anyhow::bail!("el agente no devolvió código"),
+            }
+        };
+        match generate_and_verify(&root.join(&test_file), &test_file, &runner, generate) {
+            Ok(v) if v.passed => {
+                passed += 1;
+                if !quiet {
+                    println!("   ✅ {} pasa ({} intento(s))", test_file.green(), v.attempts);
+                }
+            }
+            Ok(v) => {
+                failed += 1;
+                println!(
+                    "   ❌ {} falla tras {} intento(s); se conserva el de menos fallos",
+                    test_file.red(),
+                    v.attempts
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!("   ❌ {}: {}", test_file.red(), e);
+            }
+        }
+    }
+
+    if !quiet {
+        println!(
+            "\n🧪 {} test(s) generados: {} pasan, {} fallan.",
+            passed + failed,
+            passed.to_string().green(),
+            failed.to_string().red()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Runner simulado: devuelve los resultados en orden y guarda el test que ejecutó.
+    struct MockRunner {
+        results: RefCell<Vec<TestRun>>,
+        seen: RefCell<Vec<String>>,
+        test_path: std::path::PathBuf,
+    }
+
+    impl TestRunner for MockRunner {
+        fn run(&self, _test_file: &str) -> TestRun {
+            self.seen.borrow_mut().push(std::fs::read_to_string(&self.test_path).unwrap());
+            self.results.borrow_mut().remove(0)
+        }
+    }
+
+    fn runner(test_path: &Path, results: Vec<(bool, &str)>) -> MockRunner {
+        MockRunner {
+            results: RefCell::new(results.into_iter().map(|(passed, out)| TestRun { passed, output: out.to_string() }).collect()),
+            seen: RefCell::new(Vec::new()),
+            test_path: test_path.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_retry_loop_feeds_failure_back_until_the_test_passes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let test_path = tmp.path().join("src/sum.test.ts");
+
+        // Falla una vez y pasa en el reintento
+        let mock = runner(&test_path, vec![(false, "Tests: 1 failed, 2 passed"), (true, "Tests: 3 passed")]);
+        let feedback: RefCell<Vec<Option<String>>> = RefCell::new(Vec::new());
+        let generate = |previous: Option<&FailedAttempt>| {
+            feedback.borrow_mut().push(previous.map(|p| format!("{} | {}", p.test_code, p.output)));
+            Ok(format!("test v{}", feedback.borrow().len()))
+        };
+        let v = generate_and_verify(&test_path, "src/sum.test.ts", &mock, generate).unwrap();
+        assert_eq!(v, Verification { passed: true, attempts: 2 });
+        assert_eq!(*feedback.borrow(), vec![None, Some("test v1 | Tests: 1 failed, 2 passed".to_string())]);
+        assert_eq!(*mock.seen.borrow(), vec!["test v1", "test v2"]);
+        assert_eq!(std::fs::read_to_string(&test_path).unwrap(), "test v2");
+
+        // Un test existente no se pisa ni se llega a generar
+        let untouched = runner(&test_path, vec![]);
+        let err = generate_and_verify(&test_path, "src/sum.test.ts", &untouched, |_| Ok("x".to_string())).unwrap_err();
+        assert!(err.to_string().contains("ya existe"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&test_path).unwrap(), "test v2");
+        std::fs::remove_file(&test_path).unwrap();
+
+        // Nunca pasa: 1 + MAX_RETRIES intentos y queda el de menos fallos
+        let mock = runner(
+            &test_path,
+            vec![(false, "Tests: 3 failed"), (false, "Tests: 1 failed, 4 passed"), (false, "SyntaxError")],
+        );
+        let mut n = 0;
+        let generate = |_: Option<&FailedAttempt>| {
+            n += 1;
+            Ok(format!("attempt {}", n))
+        };
+        let v = generate_and_verify(&test_path, "src/sum.test.ts", &mock, generate).unwrap();
+        assert_eq!(v, Verification { passed: false, attempts: 1 + MAX_RETRIES });
+        assert_eq!(std::fs::read_to_string(&test_path).unwrap(), "attempt 2");
+    }
+
+    #[test]
+    fn test_test_file_for_follows_language_conventions() {
+        assert_eq!(test_file_for("src/users/users.service.ts", "NestJS").as_deref(), Some("src/users/users.service.spec.ts"));
+        assert_eq!(test_file_for("src/lib/sum.js", "Express").as_deref(), Some("src/lib/sum.test.js"));
+        assert_eq!(test_file_for("app/models.py", "Django").as_deref(), Some("app/test_models.py"));
+        assert_eq!(test_file_for("pkg/store.go", "Go").as_deref(), Some("pkg/store_test.go"));
+        assert_eq!(test_file_for("src/lib.rs", "Rust"), None);
+        let runner = CommandRunner::new("npx vitest run {file} --silent", Path::new("."));
+        assert_eq!(runner.args("a.test.ts"), vec!["npx", "vitest", "run", "a.test.ts", "--silent"]);
+        assert_eq!(CommandRunner::new("npm run test", Path::new(".")).args("a.test.ts"), vec!["npm", "run", "test", "a.test.ts"]);
+    }
+}
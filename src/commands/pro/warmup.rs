@@ -0,0 +1,188 @@
+//! `pro warmup`: precalcula el índice y la caché de embeddings sin llamar al LLM, para
+//! que los comandos interactivos posteriores no paguen la latencia de la primera vez.
+//! Pensado para un job nocturno de CI.
+
+use crate::index::embeddings::{code_chunks, embed_with_cache, Embedder};
+use crate::index::{IndexDb, ProjectIndexBuilder};
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Fragmentos por llamada al proveedor de embeddings.
+const EMBED_BATCH: usize = 16;
+
+/// Resumen de `warmup_files`.
+#[derive(Debug, Default, PartialEq)]
+pub struct WarmupReport {
+    pub files: usize,
+    /// Archivos (re)indexados; el resto no había cambiado
+    pub indexed: usize,
+    pub chunks: usize,
+    /// Embeddings calculados en esta ejecución
+    pub embedded: usize,
+    /// Embeddings que ya estaban en la caché
+    pub cached: usize,
+}
+
+/// Indexa `files` y, si hay `embed`, guarda en la caché el embedding de cada fragmento.
+/// Si el proveedor falla se desactivan los embeddings y se sigue solo con el índice.
+pub fn warmup_files(
+    root: &Path,
+    files: &[PathBuf],
+    db: &Arc<IndexDb>,
+    model: &str,
+    mut embed: Option<&mut Embedder>,
+    pb: &indicatif::ProgressBar,
+) -> WarmupReport {
+    let builder = ProjectIndexBuilder::new(Arc::clone(db));
+    let mut report = WarmupReport { files: files.len(), ..Default::default() };
+
+    for file in files {
+        let rel = file.strip_prefix(root).unwrap_or(file).display().to_string();
+        pb.set_message(rel.clone());
+        match builder.index_file(file, root) {
+            Ok(true) => report.indexed += 1,
+            Ok(false) => {}
+            Err(e) => log::debug!("warmup: no se pudo indexar {}: {}", rel, e),
+        }
+
+        let mut failed = false;
+        if let Some(embedder) = embed.as_deref_mut()
+            && let Ok(content) = std::fs::read_to_string(file)
+        {
+            let chunks = code_chunks(&content);
+            report.chunks += chunks.len();
+            for batch in chunks.chunks(EMBED_BATCH) {
+                match embed_with_cache(db, model, batch, embedder) {
+                    Ok(out) => {
+                        report.embedded += out.computed;
+                        report.cached += out.cached;
+                    }
+                    Err(e) => {
+                        pb.suspend(|| {
+                            eprintln!("   ⚠️  Embeddings desactivados ({}): se continúa solo con el índice.", e)
+                        });
+                        failed = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if failed {
+            embed = None;
+        }
+        pb.inc(1);
+    }
+    report
+}
+
+pub fn handle_warmup(
+    target: &str,
+    agent_context: &crate::agents::base::AgentContext,
+    output_mode: crate::commands::OutputMode,
+    index_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+) {
+    // La auto-indexación escribe en la misma base: esperar a que termine
+    if let Some(h) = index_handle {
+        let _ = h.join();
+    }
+    let root = &agent_context.project_root;
+    let path = root.join(target);
+    if !path.exists() {
+        println!("{} El destino '{}' no existe en el proyecto.", "❌".red(), target);
+        std::process::exit(2);
+    }
+    let Some(ref db) = agent_context.index_db else {
+        println!("{} No se pudo abrir el índice (.sentinel/index.db).", "❌".red());
+        std::process::exit(1);
+    };
+
+    let config = &agent_context.config;
+    let mut files = Vec::new();
+    let walker = ignore::WalkBuilder::new(&path).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if p.is_file() && config.file_extensions.iter().any(|e| e == ext) {
+            files.push(p.to_path_buf());
+        }
+    }
+
+    let kb_enabled = config.features.as_ref().map(|f| f.enable_knowledge_base).unwrap_or(true);
+    let model_config = config.primary_model.clone();
+    let model = crate::index::embeddings::model_key(&model_config);
+    let mut provider = |texts: Vec<String>| crate::ai::obtener_embeddings(texts, &model_config);
+    let embed: Option<&mut Embedder> = if kb_enabled { Some(&mut provider) } else { None };
+
+    let quiet = output_mode == crate::commands::OutputMode::Quiet;
+    if !quiet {
+        println!("\n🔥 Precalentando {} archivo(s) en '{}'...", files.len().to_string().cyan(), target);
+        if !kb_enabled {
+            println!("   {}", "Knowledge Base desactivada: solo se precalcula el índice.".dimmed());
+        }
+    }
+    let pb = if quiet {
+        indicatif::ProgressBar::hidden()
+    } else {
+        let pb = indicatif::ProgressBar::new(files.len() as u64);
+        pb.set_style(
+            indicatif::ProgressStyle::default_bar()
+                .template("   {bar:30.cyan/blue} {pos}/{len} archivos {msg}")
+                .unwrap(),
+        );
+        pb
+    };
+    let report = warmup_files(root, &files, db, &model, embed, &pb);
+    pb.finish_and_clear();
+
+    if !quiet {
+        println!(
+            "✅ {} archivo(s) precalentados ({} reindexados, {} sin cambios).",
+            report.files.to_string().green(),
+            report.indexed,
+            report.files - report.indexed
+        );
+        if kb_enabled {
+            println!(
+                "   🧠 Embeddings: {} calculados, {} ya en caché ({} fragmentos).",
+                report.embedded.to_string().green(),
+                report.cached,
+                report.chunks
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup_populates_index_and_embedding_cache() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/users.ts"), "export function findUser(id: string) {\n  return id;\n}\n").unwrap();
+        std::fs::write(root.join("src/orders.ts"), "export const total = (a: number) => a * 2;\n").unwrap();
+        let files = vec![root.join("src/users.ts"), root.join("src/orders.ts")];
+        let db = Arc::new(IndexDb::open(root.join(".sentinel/index.db")).unwrap());
+
+        let mut calls = 0;
+        let mut embed = |texts: Vec<String>| -> anyhow::Result<Vec<Vec<f32>>> {
+            calls += 1;
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        };
+        let pb = indicatif::ProgressBar::hidden();
+        let report = warmup_files(root, &files, &db, "local:minilm", Some(&mut embed), &pb);
+
+        assert!(db.is_populated());
+        assert_eq!(db.indexed_file_count(), 2);
+        assert_eq!(db.embedding_cache_count(), 2);
+        assert_eq!(report, WarmupReport { files: 2, indexed: 2, chunks: 2, embedded: 2, cached: 0 });
+
+        // Segunda pasada: todo sale de la caché, sin llamar al proveedor
+        let report = warmup_files(root, &files, &db, "local:minilm", Some(&mut embed), &pb);
+        assert_eq!((report.indexed, report.embedded, report.cached), (0, 0, 2));
+        assert_eq!(calls, 2);
+    }
+}
@@ -45,7 +45,7 @@ pub fn handle_rules_command(project_root: &std::path::Path) {
     if config.is_none() {
         println!("   Info: No se encontro .sentinelrc.toml. Usando valores por defecto.");
     } else {
-        println!("   Info: Para cambiar umbrales, edita la seccion [rule_config] en .sentinelrc.toml:");
+        println!("   Info: Para cambiar umbrales usa `sentinel rules --set-threshold RULE=N` o edita [rule_config] en .sentinelrc.toml:");
     }
     println!("   [rule_config]");
     println!("   complexity_threshold = {}", rule_cfg.complexity_threshold);
@@ -53,3 +53,254 @@ pub fn handle_rules_command(project_root: &std::path::Path) {
     println!("   dead_code_enabled = {}", rule_cfg.dead_code_enabled);
     println!("   unused_imports_enabled = {}", rule_cfg.unused_imports_enabled);
 }
+
+/// Cambio pedido por `sentinel rules --enable/--disable/--set-threshold`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleEdit {
+    Enable(String),
+    Disable(String),
+    SetThreshold(String, usize),
+}
+
+/// Parsea `RULE=N` de `--set-threshold`.
+pub fn parse_threshold(arg: &str) -> anyhow::Result<RuleEdit> {
+    let (rule, value) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Formato inválido '{}': usa RULE=N (ej: HIGH_COMPLEXITY=15)", arg))?;
+    let value: usize = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Umbral inválido en '{}': debe ser un entero positivo", arg))?;
+    Ok(RuleEdit::SetThreshold(rule.trim().to_string(), value))
+}
+
+/// Clave de `[rule_config]` que activa/desactiva una regla built-in.
+fn toggle_key(rule: &str) -> Option<&'static str> {
+    match rule {
+        "DEAD_CODE" | "DEAD_CODE_GLOBAL" => Some("dead_code_enabled"),
+        "UNUSED_IMPORT" => Some("unused_imports_enabled"),
+        _ => None,
+    }
+}
+
+/// Clave de `[rule_config]` con el umbral de una regla built-in.
+fn threshold_key(rule: &str) -> Option<&'static str> {
+    match rule {
+        "HIGH_COMPLEXITY" => Some("complexity_threshold"),
+        "FUNCTION_TOO_LONG" => Some("function_length_threshold"),
+        _ => None,
+    }
+}
+
+/// Fija `key = value` dentro de `[section]` editando solo esa línea, para conservar
+/// el resto del archivo (comentarios incluidos). Crea la clave o la sección si faltan.
+pub fn set_toml_key(content: &str, section: &str, key: &str, value: &str) -> String {
+    let header = format!("[{}]", section);
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+
+    let Some(start) = lines.iter().position(|l| l.trim() == header) else {
+        let mut out = content.trim_end().to_string();
+        out.push_str(&format!("\n\n{}\n{} = {}\n", header, key, value));
+        return out;
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+
+    let existing = (start + 1..end).find(|&i| {
+        lines[i]
+            .trim_start()
+            .strip_prefix(key)
+            .map(|rest| rest.trim_start().starts_with('='))
+            .unwrap_or(false)
+    });
+    match existing {
+        Some(i) => {
+            let indent: String = lines[i].chars().take_while(|c| c.is_whitespace()).collect();
+            let comment = lines[i].find(" #").map(|p| lines[i][p..].to_string()).unwrap_or_default();
+            lines[i] = format!("{}{} = {}{}", indent, key, value, comment);
+        }
+        None => {
+            // Tras la última línea no vacía de la sección
+            let mut insert_at = end;
+            while insert_at > start + 1 && lines[insert_at - 1].trim().is_empty() {
+                insert_at -= 1;
+            }
+            lines.insert(insert_at, format!("{} = {}", key, value));
+        }
+    }
+
+    let mut out = lines.join("\n");
+    if content.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Aplica un cambio de regla sobre `.sentinelrc.toml` (reglas built-in) o
+/// `.sentinel/rules.yaml` (reglas del framework). Devuelve una descripción del cambio.
+pub fn apply_rule_edit(project_root: &std::path::Path, edit: &RuleEdit) -> anyhow::Result<String> {
+    let (rule, enabled) = match edit {
+        RuleEdit::Enable(r) => (r.as_str(), Some(true)),
+        RuleEdit::Disable(r) => (r.as_str(), Some(false)),
+        RuleEdit::SetThreshold(r, _) => (r.as_str(), None),
+    };
+
+    let builtin_key = match edit {
+        RuleEdit::SetThreshold(..) => threshold_key(rule),
+        _ => toggle_key(rule),
+    };
+    if let Some(key) = builtin_key {
+        let config_path = project_root.join(".sentinelrc.toml");
+        let content = std::fs::read_to_string(&config_path).map_err(|_| {
+            anyhow::anyhow!("No se encontró .sentinelrc.toml. Ejecuta `sentinel init` primero.")
+        })?;
+        let value = match edit {
+            RuleEdit::SetThreshold(_, n) => n.to_string(),
+            _ => enabled.unwrap_or(true).to_string(),
+        };
+        let updated = set_toml_key(&content, "rule_config", key, &value);
+        toml::from_str::<toml::Value>(&updated)
+            .map_err(|e| anyhow::anyhow!("El .sentinelrc.toml resultante no es válido: {}", e))?;
+        std::fs::write(&config_path, updated)?;
+        return Ok(format!("{} → [rule_config] {} = {}", rule, key, value));
+    }
+
+    let rules_path = project_root.join(".sentinel/rules.yaml");
+    if rules_path.exists() {
+        let content = std::fs::read_to_string(&rules_path)?;
+        let mut def: crate::rules::FrameworkDefinition = serde_yaml::from_str(&content)?;
+        if let Some(framework_rule) = def.rules.iter_mut().find(|r| r.name == rule) {
+            let Some(enabled) = enabled else {
+                return Err(anyhow::anyhow!(
+                    "La regla '{}' de rules.yaml no tiene umbral configurable", rule
+                ));
+            };
+            framework_rule.enabled = enabled;
+            // serde_yaml no conserva comentarios: se reescribe el archivo completo
+            std::fs::write(&rules_path, serde_yaml::to_string(&def)?)?;
+            return Ok(format!("{} → rules.yaml enabled = {}", rule, enabled));
+        }
+    }
+
+    if crate::rules::catalog::find_rule(rule).is_some() {
+        let what = if enabled.is_some() { "no se puede activar/desactivar" } else { "no tiene umbral configurable" };
+        return Err(anyhow::anyhow!(
+            "La regla '{}' {} (usa `sentinel-ignore-file {}` o `sentinel ignore`)",
+            rule, what, rule
+        ));
+    }
+    Err(anyhow::anyhow!("Regla desconocida: '{}'. Usa `sentinel rules` para ver las disponibles.", rule))
+}
+
+pub fn handle_rules_edit(
+    project_root: &std::path::Path,
+    enable: &[String],
+    disable: &[String],
+    set_threshold: &[String],
+) {
+    let mut edits: Vec<RuleEdit> = Vec::new();
+    edits.extend(enable.iter().map(|r| RuleEdit::Enable(r.clone())));
+    edits.extend(disable.iter().map(|r| RuleEdit::Disable(r.clone())));
+    for arg in set_threshold {
+        match parse_threshold(arg) {
+            Ok(edit) => edits.push(edit),
+            Err(e) => {
+                eprintln!("{} {}", "❌".red(), e);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    for edit in &edits {
+        match apply_rule_edit(project_root, edit) {
+            Ok(summary) => println!("{} {}", "✅".green(), summary),
+            Err(e) => {
+                eprintln!("{} {}", "❌".red(), e);
+                std::process::exit(2);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_with_config() -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = SentinelConfig::create_default(
+            "demo".into(),
+            "npm".into(),
+            "NestJS".into(),
+            vec![],
+            vec!["ts".into()],
+            "typescript".into(),
+            vec![],
+            vec![],
+        );
+        config.save(tmp.path()).unwrap();
+        tmp
+    }
+
+    #[test]
+    fn test_disable_builtin_rule_persists_in_rule_config() {
+        let tmp = project_with_config();
+        let path = tmp.path().join(".sentinelrc.toml");
+        let with_comment = format!("# equipo backend\n{}", std::fs::read_to_string(&path).unwrap());
+        std::fs::write(&path, with_comment).unwrap();
+
+        apply_rule_edit(tmp.path(), &RuleEdit::Disable("DEAD_CODE".into())).unwrap();
+        apply_rule_edit(tmp.path(), &parse_threshold("HIGH_COMPLEXITY=15").unwrap()).unwrap();
+
+        let reloaded = SentinelConfig::load(tmp.path()).unwrap();
+        assert!(!reloaded.rule_config.dead_code_enabled);
+        assert_eq!(reloaded.rule_config.complexity_threshold, 15);
+        assert!(reloaded.rule_config.unused_imports_enabled);
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("# equipo backend\n"));
+    }
+
+    #[test]
+    fn test_disable_framework_rule_in_rules_yaml() {
+        let tmp = project_with_config();
+        std::fs::create_dir_all(tmp.path().join(".sentinel")).unwrap();
+        let yaml = "framework: NestJS\nlanguage: typescript\narchitecture_patterns: []\nrules:\n  - name: NO_CONSOLE\n    description: Evita console.log\n    patterns: []\n    forbidden_patterns: [\"console.log\"]\n    required_imports: []\n    level: warning\n";
+        std::fs::write(tmp.path().join(".sentinel/rules.yaml"), yaml).unwrap();
+
+        apply_rule_edit(tmp.path(), &RuleEdit::Disable("NO_CONSOLE".into())).unwrap();
+
+        let mut engine = crate::rules::RuleEngine::new();
+        engine.load_from_yaml(&tmp.path().join(".sentinel/rules.yaml")).unwrap();
+        let def = engine.framework_def.as_ref().unwrap();
+        assert!(!def.rules[0].enabled);
+        let violations = engine.validate_file(std::path::Path::new("a.txt"), "console.log('x')");
+        assert!(violations.iter().all(|v| v.rule_name != "NO_CONSOLE"));
+
+        assert!(apply_rule_edit(tmp.path(), &parse_threshold("NO_CONSOLE=3").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_unknown_or_non_toggleable_rules_are_rejected() {
+        let tmp = project_with_config();
+        let before = std::fs::read_to_string(tmp.path().join(".sentinelrc.toml")).unwrap();
+        let err = apply_rule_edit(tmp.path(), &RuleEdit::Disable("NOT_A_RULE".into())).unwrap_err();
+        assert!(err.to_string().contains("desconocida"));
+        assert!(apply_rule_edit(tmp.path(), &RuleEdit::Disable("DEFER_IN_LOOP".into())).is_err());
+        assert!(parse_threshold("HIGH_COMPLEXITY").is_err());
+        assert!(parse_threshold("HIGH_COMPLEXITY=-1").is_err());
+        assert_eq!(std::fs::read_to_string(tmp.path().join(".sentinelrc.toml")).unwrap(), before);
+    }
+
+    #[test]
+    fn test_set_toml_key_keeps_comments_and_adds_missing_keys() {
+        let src = "[rule_config]\ncomplexity_threshold = 10 # revisado\n\n[index]\nauto_index = true\n";
+        let out = set_toml_key(src, "rule_config", "complexity_threshold", "12");
+        assert_eq!(out, "[rule_config]\ncomplexity_threshold = 12 # revisado\n\n[index]\nauto_index = true\n");
+        let out = set_toml_key(src, "rule_config", "dead_code_enabled", "false");
+        assert!(out.contains("complexity_threshold = 10 # revisado\ndead_code_enabled = false\n\n[index]"));
+        let out = set_toml_key("name = \"x\"\n", "rule_config", "dead_code_enabled", "false");
+        assert!(out.ends_with("[rule_config]\ndead_code_enabled = false\n"));
+    }
+}
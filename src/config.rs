@@ -1,7 +1,10 @@
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
 
 /// Versión actual de Sentinel (leída desde Cargo.toml en tiempo de compilación)
 pub const SENTINEL_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -17,20 +20,147 @@ pub struct FrameworkDetection {
     pub test_patterns: Vec<String>, // Patrones de ubicación de tests (ej: ["test/{name}/{name}.spec.ts"])
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct ModelConfig {
     pub name: String,
     pub url: String,
     pub api_key: String,
     #[serde(default)]
-    pub provider: String, // "anthropic", "gemini", "ollama", "lm-studio", "local"
+    #[schemars(schema_with = "provider_schema")]
+    pub provider: String, // "anthropic", "gemini", "ollama", "lm-studio", "local", "azure", "openrouter", "bedrock", "vertex"
+    /// Azure OpenAI: nombre del deployment (si falta se usa `name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<String>,
+    /// Azure OpenAI: query param `api-version`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    /// Vertex AI: proyecto GCP (si falta se toma de la URL o de `GOOGLE_CLOUD_PROJECT`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Vertex AI: región (`us-central1`, `europe-west4`, `global`...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Timeout por petición en segundos (default 120; 600 para Ollama, que genera en local)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Tamaño máximo del prompt en tokens; el contexto de review/audit/tests se recorta para caber
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_context_tokens: Option<usize>,
+    /// Ollama: si el modelo no está descargado, lo descarga (`/api/pull`) y reintenta
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_pull: bool,
+    /// Cabeceras extra en cada petición al proveedor (gateways corporativos: `X-Org-Id`,
+    /// centro de coste...). Los valores admiten `env:`/`file:`/`keyring:` como `api_key`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
+pub const DEFAULT_OLLAMA_TIMEOUT_SECS: u64 = 600;
+
+/// Proveedores reconocidos en `ModelConfig::provider` (ver `ai::providers::build_provider`).
+pub const KNOWN_PROVIDERS: &[&str] = &[
+    "anthropic",
+    "gemini",
+    "interactions",
+    "ollama",
+    "openai",
+    "lm-studio",
+    "groq",
+    "kimi",
+    "deepseek",
+    "azure",
+    "openrouter",
+    "bedrock",
+    "vertex",
+    "local",
+];
+
+/// Proveedores que funcionan sin API key.
+pub const KEYLESS_PROVIDERS: &[&str] = &["ollama", "lm-studio", "local", "bedrock", "vertex"];
+
+/// Proveedores que corren en la máquina del usuario o su red: sin los límites de latencia de
+/// una API alojada.
+pub const SELF_HOSTED_PROVIDERS: &[&str] = &["ollama", "lm-studio", "local"];
+
+/// URL por defecto de la API de cada proveedor (vacía si no tiene una pública).
+pub fn default_provider_url(provider: &str) -> &'static str {
+    match provider {
+        "anthropic" => "https://api.anthropic.com",
+        "gemini" => "https://generativelanguage.googleapis.com",
+        "openai" => "https://api.openai.com/v1",
+        "groq" => "https://api.groq.com/openai/v1",
+        "ollama" => "http://localhost:11434",
+        "kimi" => "https://api.moonshot.ai/v1",
+        "deepseek" => "https://api.deepseek.com",
+        "openrouter" => "https://openrouter.ai/api/v1",
+        _ => "",
+    }
+}
+
+/// Modelo sugerido para cada proveedor cuando no se elige uno explícitamente.
+pub fn default_provider_model(provider: &str) -> &'static str {
+    match provider {
+        "anthropic" => "claude-3-5-sonnet-20241022",
+        "gemini" | "vertex" => "gemini-2.0-flash",
+        "openai" => "gpt-4o",
+        "groq" => "llama3-70b-8192",
+        "ollama" => "llama3",
+        "kimi" => "moonshot-v1-8k",
+        "deepseek" => "deepseek-coder",
+        _ => "",
+    }
+}
+
+/// `code_language` inferido de las extensiones del proyecto (fallback sin IA).
+pub fn code_language_for(extensions: &[String]) -> String {
+    let has = |ext: &str| extensions.iter().any(|e| e == ext);
+    let language = if has("ts") {
+        "typescript"
+    } else if has("js") {
+        "javascript"
+    } else if has("py") {
+        "python"
+    } else if has("go") {
+        "go"
+    } else if has("rs") {
+        "rust"
+    } else if has("cs") {
+        "csharp"
+    } else {
+        "code"
+    };
+    language.to_string()
+}
+
+fn provider_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "string",
+        "enum": KNOWN_PROVIDERS,
+        "description": "Proveedor de IA (vacío = se deduce de la URL)",
+    })
 }
 
 impl ModelConfig {
+    /// Timeout efectivo de las peticiones HTTP a este modelo. El proveedor se resuelve como
+    /// al despacharlo (por URL si `provider` está vacío).
+    pub fn timeout(&self) -> std::time::Duration {
+        let secs = self.timeout_secs.unwrap_or(match crate::ai::providers::provider_name(self) {
+            "ollama" => DEFAULT_OLLAMA_TIMEOUT_SECS,
+            _ => DEFAULT_TIMEOUT_SECS,
+        });
+        std::time::Duration::from_secs(secs.max(1))
+    }
+
+    pub fn is_self_hosted(&self) -> bool {
+        SELF_HOSTED_PROVIDERS.contains(&crate::ai::providers::provider_name(self))
+    }
+
     pub fn embedding_dimension(&self) -> u64 {
         match self.provider.as_str() {
             "local" | "anthropic" => 384,   // all-MiniLM-L6-v2 local model
             "openai" | "lm-studio" => 1536, // typical default for OpenAI embeddings
+            "vertex" => 768,                // text-embedding-004
             "ollama" => {
                 if self.name.contains("mxbai") {
                     1024
@@ -45,7 +175,7 @@ impl ModelConfig {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct FeaturesConfig {
     #[serde(default = "default_true")]
     pub enable_ml: bool,
@@ -59,10 +189,37 @@ fn default_true() -> bool {
     true
 }
 
+fn default_monitor_concurrency() -> usize { 2 }
+
+fn default_analysis_cooldown_secs() -> u64 { 10 }
+fn default_coalesce_ms() -> u64 { 500 }
+
+/// Mínimos de `analysis_cooldown_secs` / `coalesce_ms`: por debajo, cada guardado de un
+/// formatter dispararía un análisis de IA propio.
+const MIN_ANALYSIS_COOLDOWN_SECS: u64 = 1;
+const MIN_COALESCE_MS: u64 = 50;
+
+fn default_watch_paths() -> Vec<String> { vec!["src".to_string()] }
+
+fn default_max_file_bytes() -> u64 { 500_000 }
+
+/// Carpeta de copias de seguridad por defecto (relativa a la raíz del proyecto).
+pub const DEFAULT_BACKUP_DIR: &str = ".sentinel/backups";
+
+fn default_backup_dir() -> String { DEFAULT_BACKUP_DIR.to_string() }
+
+fn default_review_tree_depth() -> usize { 4 }
+fn default_review_tree_max_lines() -> usize { 100 }
+
+fn default_package_root_markers() -> Vec<String> {
+    vec!["package.json".to_string(), "go.mod".to_string(), "Cargo.toml".to_string()]
+}
+
 fn default_complexity() -> usize { 10 }
 fn default_function_length() -> usize { 50 }
+fn default_risk_threshold() -> f32 { 0.7 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct RuleConfig {
     #[serde(default = "default_complexity")]
     pub complexity_threshold: usize,
@@ -72,6 +229,12 @@ pub struct RuleConfig {
     pub dead_code_enabled: bool,
     #[serde(default = "default_true")]
     pub unused_imports_enabled: bool,
+    /// `pro check --risk`: score a partir del cual se emite `HIGH_RISK` (0.0–1.0)
+    #[serde(default = "default_risk_threshold")]
+    pub risk_threshold: f32,
+    /// Globs de archivos (fixtures de test con claves falsas) que no revisa `HARDCODED_SECRET`
+    #[serde(default)]
+    pub secret_allowlist: Vec<String>,
 }
 
 impl Default for RuleConfig {
@@ -81,18 +244,90 @@ impl Default for RuleConfig {
             function_length_threshold: 50,
             dead_code_enabled: true,
             unused_imports_enabled: true,
+            risk_threshold: default_risk_threshold(),
+            secret_allowlist: Vec::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+fn default_stale_threshold_pct() -> u8 { 10 }
+
+/// Sección `[index]`: comportamiento del índice del proyecto en los comandos `pro`.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct IndexConfig {
+    /// Indexar en segundo plano si el índice está vacío (desactivar en CI)
+    #[serde(default = "default_true")]
+    pub auto_index: bool,
+    /// % de archivos de diferencia entre disco e índice para avisar de índice desactualizado
+    #[serde(default = "default_stale_threshold_pct")]
+    pub stale_threshold_pct: u8,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            auto_index: true,
+            stale_threshold_pct: default_stale_threshold_pct(),
+        }
+    }
+}
+
+impl IndexConfig {
+    /// Diferencia máxima de archivos tolerada antes de considerar el índice desactualizado
+    /// (nunca menos de 5, para no avisar en proyectos pequeños).
+    pub fn stale_threshold(&self, disk_count: usize) -> usize {
+        5.max(disk_count * self.stale_threshold_pct.min(100) as usize / 100)
+    }
+}
+
+fn default_error_weight() -> f64 { 5.0 }
+fn default_warning_weight() -> f64 { 1.0 }
+fn default_info_weight() -> f64 { 0.2 }
+fn default_complexity_weight() -> f64 { 2.0 }
+fn default_complexity_baseline() -> f64 { 10.0 }
+fn default_coverage_weight() -> f64 { 0.3 }
+
+/// Sección `[score]`: pesos de `pro score`. La puntuación parte de 100 y resta:
+/// violaciones por cada 10 archivos × peso de su severidad, puntos de complejidad
+/// media sobre `complexity_baseline` × `complexity_weight` y % sin test × `coverage_weight`.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct ScoreConfig {
+    #[serde(default = "default_error_weight")]
+    pub error_weight: f64,
+    #[serde(default = "default_warning_weight")]
+    pub warning_weight: f64,
+    #[serde(default = "default_info_weight")]
+    pub info_weight: f64,
+    #[serde(default = "default_complexity_weight")]
+    pub complexity_weight: f64,
+    /// Complejidad media de funciones a partir de la cual se penaliza
+    #[serde(default = "default_complexity_baseline")]
+    pub complexity_baseline: f64,
+    #[serde(default = "default_coverage_weight")]
+    pub coverage_weight: f64,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            error_weight: default_error_weight(),
+            warning_weight: default_warning_weight(),
+            info_weight: default_info_weight(),
+            complexity_weight: default_complexity_weight(),
+            complexity_baseline: default_complexity_baseline(),
+            coverage_weight: default_coverage_weight(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct LocalLlmConfig {
     pub provider: String,
     pub model_path: String,
     pub api_port: u16,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct MlConfig {
     pub models_path: String,
     pub embeddings_model: String,
@@ -106,11 +341,19 @@ impl Default for ModelConfig {
             url: "https://api.anthropic.com".to_string(),
             api_key: "".to_string(),
             provider: "anthropic".to_string(),
+            deployment: None,
+            api_version: None,
+            project: None,
+            location: None,
+            timeout_secs: None,
+            max_context_tokens: None,
+            auto_pull: false,
+            headers: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct SentinelConfig {
     pub version: String,
     pub project_name: String,
@@ -122,6 +365,33 @@ pub struct SentinelConfig {
     pub code_language: String,        // Lenguaje para bloques de código (detectado por IA)
     pub parent_patterns: Vec<String>, // Patrones de archivos padre específicos del framework
     pub test_patterns: Vec<String>, // Patrones de ubicación de tests (usa {name} como placeholder)
+    /// Raíces del análisis de código muerto: decoradores (`@Get`, `@router.*`) o globs de
+    /// archivos (`src/commands/**`). Se suman a los defaults del framework.
+    #[serde(default)]
+    pub entrypoint_patterns: Vec<String>,
+    /// Archivos que marcan la raíz de un paquete en un monorepo; la búsqueda del archivo
+    /// padre de `sentinel monitor` no cruza esa frontera
+    #[serde(default = "default_package_root_markers")]
+    pub package_root_markers: Vec<String>,
+    /// Tamaño máximo (bytes) de los archivos que analizan check/audit/report/review; los
+    /// mayores (bundles minificados, código generado) se omiten. 0 = sin límite
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Carpeta donde fix/audit/split/review/migrate guardan una copia de cada archivo antes
+    /// de sobrescribirlo (relativa a la raíz o absoluta); `sentinel pro restore` la usa
+    #[serde(default = "default_backup_dir")]
+    pub backup_dir: String,
+    /// Si es `false`, fix/audit/split/review/migrate sobrescriben sin copia previa (el
+    /// historial queda a cargo de git); `--no-backup` lo desactiva para una ejecución
+    #[serde(default = "default_true")]
+    pub create_backups: bool,
+    /// Niveles del árbol del proyecto que `pro review` envía al modelo; las carpetas del
+    /// último nivel se resumen como `nombre/ (+N archivos)`
+    #[serde(default = "default_review_tree_depth")]
+    pub review_tree_depth: usize,
+    /// Líneas máximas de ese árbol. 0 = sin límite
+    #[serde(default = "default_review_tree_max_lines")]
+    pub review_tree_max_lines: usize,
     pub ignore_patterns: Vec<String>,
     pub primary_model: ModelConfig,
     pub fallback_model: Option<ModelConfig>,
@@ -141,9 +411,138 @@ pub struct SentinelConfig {
     pub ml: Option<MlConfig>,
     #[serde(default)]
     pub rule_config: RuleConfig,
+    /// Análisis de IA simultáneos en `sentinel monitor` (mínimo 1)
+    #[serde(default = "default_monitor_concurrency")]
+    pub monitor_concurrency: usize,
+    /// Segundos durante los que `sentinel monitor` ignora nuevos cambios de un archivo ya
+    /// analizado (mínimo 1)
+    #[serde(default = "default_analysis_cooldown_secs")]
+    pub analysis_cooldown_secs: u64,
+    /// Milisegundos de espera tras un cambio para agrupar la ráfaga de eventos (mínimo 50)
+    #[serde(default = "default_coalesce_ms")]
+    pub coalesce_ms: u64,
+    /// Carpetas (relativas a la raíz) que vigila `sentinel monitor`
+    #[serde(default = "default_watch_paths")]
+    pub watch_paths: Vec<String>,
+    #[serde(default)]
+    pub index: IndexConfig,
+    #[serde(default)]
+    pub score: ScoreConfig,
+    /// Plantillas de prompt por agente (`ReviewerAgent = ".sentinel/prompts/review.txt"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prompts: BTreeMap<String, String>,
+    /// Precios por modelo en USD por millón de tokens; pisan la tabla de `ai::pricing`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pricing: BTreeMap<String, crate::ai::pricing::ModelPrice>,
+    /// Formato de los commits del monitor: `"free"` (default) o `"conventional"` (`type(scope): subject`)
+    #[serde(default)]
+    pub commit_convention: CommitConvention,
+    /// Qué recibe la IA para redactar el commit: `"diff"` (default; el diff del archivo
+    /// respecto a HEAD, con el archivo completo si no hay diff) o `"file"` (el archivo completo)
+    #[serde(default = "default_commit_context")]
+    pub commit_context: String,
+    /// Overrides por entorno (`[profiles.ci]`, `[profiles.dev]`...) que `--profile` o
+    /// `SENTINEL_PROFILE` mezclan sobre la configuración base (ver `with_profile`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub profiles: BTreeMap<String, toml::Table>,
+}
+
+/// Config vacía (la que usan los comandos si no hay `.sentinelrc.toml`); los campos con
+/// default de serde toman ese mismo valor.
+impl Default for SentinelConfig {
+    fn default() -> Self {
+        Self {
+            version: String::new(),
+            project_name: String::new(),
+            framework: String::new(),
+            manager: String::new(),
+            test_command: String::new(),
+            architecture_rules: Vec::new(),
+            file_extensions: Vec::new(),
+            code_language: String::new(),
+            parent_patterns: Vec::new(),
+            test_patterns: Vec::new(),
+            entrypoint_patterns: Vec::new(),
+            package_root_markers: default_package_root_markers(),
+            max_file_bytes: default_max_file_bytes(),
+            backup_dir: default_backup_dir(),
+            create_backups: default_true(),
+            review_tree_depth: default_review_tree_depth(),
+            review_tree_max_lines: default_review_tree_max_lines(),
+            ignore_patterns: Vec::new(),
+            primary_model: ModelConfig::default(),
+            fallback_model: None,
+            use_cache: false,
+            testing_framework: None,
+            testing_status: None,
+            features: None,
+            local_llm: None,
+            ml: None,
+            rule_config: RuleConfig::default(),
+            monitor_concurrency: default_monitor_concurrency(),
+            analysis_cooldown_secs: default_analysis_cooldown_secs(),
+            coalesce_ms: default_coalesce_ms(),
+            watch_paths: default_watch_paths(),
+            index: IndexConfig::default(),
+            score: ScoreConfig::default(),
+            prompts: BTreeMap::new(),
+            pricing: BTreeMap::new(),
+            commit_convention: CommitConvention::default(),
+            commit_context: default_commit_context(),
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+/// Variable de entorno equivalente a `--profile`.
+pub const PROFILE_ENV: &str = "SENTINEL_PROFILE";
+
+/// Perfil activo: `--profile` tiene prioridad sobre `SENTINEL_PROFILE`.
+pub fn active_profile(cli: Option<&str>) -> Option<String> {
+    cli.map(str::to_string)
+        .or_else(|| std::env::var(PROFILE_ENV).ok())
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// Merge profundo: las tablas se combinan clave a clave, el resto de valores (arrays
+/// incluidos) se reemplaza.
+fn merge_toml(base: &mut toml::Table, patch: &toml::Table) {
+    for (key, value) in patch {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(patch_table)) => {
+                merge_toml(base_table, patch_table)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// `commit_convention`: un valor desconocido es un error al cargar la config.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitConvention {
+    /// Sin validación
+    #[default]
+    Free,
+    /// `type(scope): subject` (ver `git::validar_conventional_commit`)
+    Conventional,
+}
+
+fn default_commit_context() -> String {
+    "diff".to_string()
 }
 
 impl SentinelConfig {
+    /// JSON Schema de `.sentinelrc.toml`, derivado de los structs de configuración.
+    pub fn json_schema() -> serde_json::Value {
+        let schema = schemars::schema_for!(SentinelConfig);
+        serde_json::to_value(&schema).unwrap_or_default()
+    }
+
     pub fn create_default(
         name: String,
         manager: String,
@@ -159,7 +558,16 @@ impl SentinelConfig {
             url: "https://api.anthropic.com".to_string(),
             api_key: "".to_string(),
             provider: "anthropic".to_string(),
+            deployment: None,
+            api_version: None,
+            project: None,
+            location: None,
+            timeout_secs: None,
+            max_context_tokens: None,
+            auto_pull: false,
+            headers: BTreeMap::new(),
         };
+        let entrypoint_patterns = crate::index::entry_points::default_patterns(&framework);
 
         Self {
             version: SENTINEL_VERSION.to_string(),
@@ -172,6 +580,13 @@ impl SentinelConfig {
             code_language,
             parent_patterns,
             test_patterns,
+            entrypoint_patterns,
+            package_root_markers: default_package_root_markers(),
+            max_file_bytes: default_max_file_bytes(),
+            backup_dir: default_backup_dir(),
+            create_backups: true,
+            review_tree_depth: default_review_tree_depth(),
+            review_tree_max_lines: default_review_tree_max_lines(),
             ignore_patterns: vec![
                 "node_modules".to_string(),
                 "dist".to_string(),
@@ -203,6 +618,46 @@ impl SentinelConfig {
                 bug_predictor_model: "bug-predictor-v1".to_string(),
             }),
             rule_config: RuleConfig::default(),
+            monitor_concurrency: default_monitor_concurrency(),
+            analysis_cooldown_secs: default_analysis_cooldown_secs(),
+            coalesce_ms: default_coalesce_ms(),
+            watch_paths: default_watch_paths(),
+            index: IndexConfig::default(),
+            score: ScoreConfig::default(),
+            prompts: BTreeMap::new(),
+            pricing: BTreeMap::new(),
+            commit_convention: CommitConvention::Free,
+            commit_context: default_commit_context(),
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    /// Copia de la configuración con `[profiles.<name>]` aplicado encima. Puede pisar
+    /// cualquier campo (`rule_config`, `primary_model`, `file_extensions`...). Nada se guarda.
+    pub fn with_profile(&self, name: &str) -> anyhow::Result<Self> {
+        let Some(profile) = self.profiles.get(name) else {
+            let available: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            anyhow::bail!(
+                "perfil '{}' no definido en .sentinelrc.toml (disponibles: {})",
+                name,
+                if available.is_empty() { "ninguno".to_string() } else { available.join(", ") }
+            );
+        };
+        let toml::Value::Table(mut merged) = toml::Value::try_from(self)? else {
+            anyhow::bail!("la configuración no se serializa como tabla TOML");
+        };
+        merge_toml(&mut merged, profile);
+        toml::Value::Table(merged)
+            .try_into()
+            .map_err(|e| anyhow::anyhow!("perfil '{}' inválido: {}", name, e))
+    }
+
+    /// Aplica el perfil activo (`--profile` o `SENTINEL_PROFILE`, ver `active_profile`) si
+    /// hay uno. Todos los comandos cargan así la configuración efectiva; nada se guarda.
+    pub fn with_active_profile(self, cli: Option<&str>) -> anyhow::Result<Self> {
+        match active_profile(cli) {
+            Some(profile) => self.with_profile(&profile),
+            None => Ok(self),
         }
     }
 
@@ -266,7 +721,17 @@ impl SentinelConfig {
         let content = fs::read_to_string(&config_path).ok()?;
 
         // Intentar deserializar directamente primero (configuración actual)
-        if let Ok(mut config) = toml::from_str::<SentinelConfig>(&content) {
+        let actual = toml::from_str::<SentinelConfig>(&content);
+        if let Err(ref e) = actual
+            && !e.message().starts_with("missing field")
+            && toml::from_str::<toml::Table>(&content).is_ok_and(|t| t.contains_key("version"))
+        {
+            // Una config actual con un valor inválido (p. ej. `commit_convention`) no es
+            // una config antigua: migrarla la reescribiría con los valores por defecto
+            println!("{}", format!("   ❌ Configuración inválida en .sentinelrc.toml: {}", e.message()).red());
+            return None;
+        }
+        if let Ok(mut config) = actual {
             // Validar y migrar si es necesario
             if config.version != SENTINEL_VERSION {
                 println!(
@@ -335,19 +800,7 @@ impl SentinelConfig {
                 .unwrap_or_else(|| vec!["js".to_string(), "ts".to_string()]);
 
             // Inferir code_language basado en extensiones (fallback)
-            let code_language = if extensions.contains(&"ts".to_string()) {
-                "typescript".to_string()
-            } else if extensions.contains(&"js".to_string()) {
-                "javascript".to_string()
-            } else if extensions.contains(&"py".to_string()) {
-                "python".to_string()
-            } else if extensions.contains(&"go".to_string()) {
-                "go".to_string()
-            } else if extensions.contains(&"rs".to_string()) {
-                "rust".to_string()
-            } else {
-                "code".to_string()
-            };
+            let code_language = code_language_for(&extensions);
 
             // Inferir parent_patterns basados en framework detectado (fallback)
             let parent_patterns = if framework.to_lowercase().contains("nest") {
@@ -448,19 +901,7 @@ impl SentinelConfig {
 
         // Asegurar que exista code_language (fallback basado en extensiones)
         if config.code_language.is_empty() {
-            config.code_language = if config.file_extensions.contains(&"ts".to_string()) {
-                "typescript".to_string()
-            } else if config.file_extensions.contains(&"js".to_string()) {
-                "javascript".to_string()
-            } else if config.file_extensions.contains(&"py".to_string()) {
-                "python".to_string()
-            } else if config.file_extensions.contains(&"go".to_string()) {
-                "go".to_string()
-            } else if config.file_extensions.contains(&"rs".to_string()) {
-                "rust".to_string()
-            } else {
-                "code".to_string()
-            };
+            config.code_language = code_language_for(&config.file_extensions);
         }
 
         // Asegurar que existan parent_patterns (fallback basado en framework/lenguaje)
@@ -542,8 +983,16 @@ impl SentinelConfig {
             if model.provider.is_empty()
                 || (model.provider == "anthropic" && !url.contains("anthropic"))
             {
-                if url.contains("deepseek") {
+                if url.contains("openai.azure.com") {
+                    model.provider = "azure".to_string();
+                } else if url.contains("bedrock-runtime") {
+                    model.provider = "bedrock".to_string();
+                } else if url.contains("openrouter.ai") {
+                    model.provider = "openrouter".to_string();
+                } else if url.contains("deepseek") {
                     model.provider = "deepseek".to_string();
+                } else if url.contains("aiplatform.googleapis.com") {
+                    model.provider = "vertex".to_string();
                 } else if url.contains("googleapis") {
                     model.provider = "gemini".to_string();
                 } else if url.contains("groq") {
@@ -566,7 +1015,31 @@ impl SentinelConfig {
         config
     }
 
-    pub fn debe_ignorar(&self, path: &Path) -> bool {
+    /// Cooldown por archivo del monitor, acotado a `MIN_ANALYSIS_COOLDOWN_SECS`.
+    pub fn analysis_cooldown(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.analysis_cooldown_secs.max(MIN_ANALYSIS_COOLDOWN_SECS))
+    }
+
+    /// Ventana de agrupado de eventos del watcher, acotada a `MIN_COALESCE_MS`.
+    pub fn coalesce_window(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.coalesce_ms.max(MIN_COALESCE_MS))
+    }
+
+    /// Matcher con la semántica de `.gitignore`: el `.gitignore` del proyecto más
+    /// `ignore_patterns` tratados como globs (coinciden por componente, no por substring).
+    pub fn ignore_matcher(&self, project_root: &Path) -> Gitignore {
+        let mut builder = GitignoreBuilder::new(project_root);
+        let gitignore = project_root.join(".gitignore");
+        if gitignore.exists() {
+            let _ = builder.add(gitignore);
+        }
+        for pattern in &self.ignore_patterns {
+            let _ = builder.add_line(None, pattern);
+        }
+        builder.build().unwrap_or_else(|_| Gitignore::empty())
+    }
+
+    pub fn debe_ignorar(&self, matcher: &Gitignore, path: &Path) -> bool {
         let path_str = path.to_str().unwrap_or("");
 
         // 1. Ignorar archivos de tests y sugerencias
@@ -588,10 +1061,17 @@ impl SentinelConfig {
             return true;
         }
 
-        // 3. Filtros personalizados del config (.sentinelrc)
-        self.ignore_patterns
-            .iter()
-            .any(|pattern| path_str.contains(pattern))
+        // 3. .gitignore + filtros personalizados del config (.sentinelrc)
+        let relativa: PathBuf = match path.strip_prefix(matcher.path()) {
+            Ok(rel) => rel.to_path_buf(),
+            // Fuera de la raíz solo aplican los globs sin ancla, evaluados por componente
+            Err(_) => path
+                .components()
+                .filter(|c| matches!(c, std::path::Component::Normal(_)))
+                .collect(),
+        };
+        !relativa.as_os_str().is_empty()
+            && matcher.matched_path_or_any_parents(&relativa, false).is_ignore()
     }
 
     pub fn detectar_gestor(path: &Path) -> String {
@@ -720,3 +1200,137 @@ impl SentinelConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_lists_known_providers() {
+        let schema = SentinelConfig::json_schema();
+        let text = serde_json::to_string(&schema).unwrap();
+        // primary_model referencia la definición de ModelConfig
+        assert!(schema["properties"]["primary_model"].is_object());
+        let model = &schema["$defs"]["ModelConfig"]["properties"]["provider"];
+        let values: Vec<&str> = model["enum"]
+            .as_array()
+            .expect("provider must be an enum")
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        for p in ["anthropic", "gemini", "ollama", "openai", "azure", "openrouter", "local"] {
+            assert!(values.contains(&p), "missing provider {}", p);
+        }
+        // Los defaults de serde se exportan
+        assert_eq!(schema["$defs"]["RuleConfig"]["properties"]["complexity_threshold"]["default"], 10);
+        assert!(text.contains("stale_threshold_pct"));
+    }
+
+    #[test]
+    fn test_debe_ignorar_matches_components_not_substrings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join(".gitignore"), "dist/\n*.generated.ts\n").unwrap();
+        let config = SentinelConfig {
+            file_extensions: vec!["ts".to_string()],
+            ignore_patterns: vec!["target".to_string(), "node_modules".to_string()],
+            ..Default::default()
+        };
+        let matcher = config.ignore_matcher(root);
+
+        assert!(config.debe_ignorar(&matcher, &root.join("target/debug/build.ts")));
+        assert!(config.debe_ignorar(&matcher, &root.join("packages/api/node_modules/x/index.ts")));
+        assert!(!config.debe_ignorar(&matcher, &root.join("src/target-utils.ts")));
+        assert!(!config.debe_ignorar(&matcher, &root.join("src/my-target-file.ts")));
+
+        // Reglas del .gitignore del proyecto
+        assert!(config.debe_ignorar(&matcher, &root.join("dist/main.ts")));
+        assert!(config.debe_ignorar(&matcher, &root.join("src/api.generated.ts")));
+        assert!(!config.debe_ignorar(&matcher, &root.join("src/distance.ts")));
+    }
+
+    #[test]
+    fn test_stale_threshold_by_percentage() {
+        let pct = |p: u8| IndexConfig { stale_threshold_pct: p, ..Default::default() };
+        // Por defecto 10%, con mínimo de 5 archivos
+        assert_eq!(IndexConfig::default().stale_threshold(1000), 100);
+        assert_eq!(IndexConfig::default().stale_threshold(30), 5);
+        assert_eq!(pct(25).stale_threshold(200), 50);
+        assert_eq!(pct(1).stale_threshold(200), 5);
+        assert_eq!(pct(0).stale_threshold(10_000), 5);
+        // Porcentajes > 100 se tratan como 100
+        assert_eq!(pct(250).stale_threshold(40), 40);
+    }
+
+    #[test]
+    fn test_index_section_defaults_when_missing() {
+        let cfg: IndexConfig = toml::from_str("auto_index = false").unwrap();
+        assert!(!cfg.auto_index);
+        assert_eq!(cfg.stale_threshold_pct, 10);
+    }
+
+    #[test]
+    fn test_commit_convention_rejects_unknown_values() {
+        #[derive(Deserialize)]
+        struct Only {
+            #[serde(default)]
+            commit_convention: CommitConvention,
+        }
+        let parse = |s: &str| toml::from_str::<Only>(s).map(|o| o.commit_convention);
+        assert_eq!(parse("").unwrap(), CommitConvention::Free);
+        assert_eq!(parse("commit_convention = \"conventional\"").unwrap(), CommitConvention::Conventional);
+        assert!(parse("commit_convention = \"Conventional Commits\"").is_err());
+
+        // Una config actual inválida no se "migra" (lo que la reescribiría)
+        let tmp = tempfile::tempdir().unwrap();
+        let rc = tmp.path().join(".sentinelrc.toml");
+        let content = format!("version = \"{}\"\nproject_name = \"demo\"\ncommit_convention = \"semantic\"\n", SENTINEL_VERSION);
+        std::fs::write(&rc, &content).unwrap();
+        assert!(SentinelConfig::load(tmp.path()).is_none());
+        assert_eq!(std::fs::read_to_string(&rc).unwrap(), content);
+    }
+
+    #[test]
+    fn test_profile_ci_overrides_complexity_threshold() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = SentinelConfig::create_default(
+            "demo".to_string(),
+            "npm".to_string(),
+            "NestJS".to_string(),
+            vec![],
+            vec!["ts".to_string()],
+            "typescript".to_string(),
+            vec![],
+            vec![],
+        );
+        config.rule_config.complexity_threshold = 15;
+        config.save(tmp.path()).unwrap();
+        let base = std::fs::read_to_string(tmp.path().join(".sentinelrc.toml")).unwrap();
+        std::fs::write(
+            tmp.path().join(".sentinelrc.toml"),
+            format!(
+                "{}\n[profiles.ci.rule_config]\ncomplexity_threshold = 8\n\n[profiles.dev]\nfile_extensions = [\"ts\", \"tsx\"]\n",
+                base
+            ),
+        )
+        .unwrap();
+
+        let loaded = SentinelConfig::load(tmp.path()).unwrap();
+        assert_eq!(loaded.rule_config.complexity_threshold, 15, "base value without a profile");
+
+        let ci = loaded.with_profile("ci").unwrap();
+        assert_eq!(ci.rule_config.complexity_threshold, 8);
+        // Merge profundo: el resto de rule_config y de la config se conserva
+        assert_eq!(ci.rule_config.function_length_threshold, loaded.rule_config.function_length_threshold);
+        assert_eq!(ci.file_extensions, vec!["ts"]);
+        assert_eq!(loaded.with_profile("dev").unwrap().file_extensions, vec!["ts", "tsx"]);
+
+        let err = loaded.with_profile("staging").unwrap_err().to_string();
+        assert!(err.contains("staging") && err.contains("ci, dev"), "{}", err);
+
+        // El loader compartido aplica el perfil pedido y falla con uno inexistente
+        let active = loaded.clone().with_active_profile(Some("ci")).unwrap();
+        assert_eq!(active.rule_config.complexity_threshold, 8);
+        assert!(loaded.with_active_profile(Some("staging")).is_err());
+    }
+}
@@ -37,6 +37,23 @@ pub fn secure_join(project_root: &Path, target: &Path) -> Result<PathBuf, String
     Ok(project_root.join(target))
 }
 
+/// Separa `files` en (a analizar, omitidos con su tamaño) según `max_bytes` (0 = sin límite).
+/// Solo consulta los metadatos: los archivos omitidos nunca se leen en memoria.
+pub fn filtrar_por_tamano(files: Vec<PathBuf>, max_bytes: u64) -> (Vec<PathBuf>, Vec<(PathBuf, u64)>) {
+    if max_bytes == 0 {
+        return (files, Vec::new());
+    }
+    let mut kept = Vec::new();
+    let mut skipped = Vec::new();
+    for file in files {
+        match fs::metadata(&file).map(|m| m.len()) {
+            Ok(size) if size > max_bytes => skipped.push((file, size)),
+            _ => kept.push(file),
+        }
+    }
+    (kept, skipped)
+}
+
 /// Verifica si un archivo es de tipo "padre" según los patrones del framework
 ///
 /// # Argumentos
@@ -60,47 +77,27 @@ pub fn es_archivo_padre(file_name: &str, parent_patterns: &[String]) -> bool {
         .any(|pattern| file_name.ends_with(pattern))
 }
 
-/// Detecta si un archivo es un "hijo" y retorna el nombre del módulo padre
-///
-/// Esta función busca en el mismo directorio del archivo modificado si existe
-/// un archivo padre según los patrones del framework y retorna el nombre
-/// base del módulo. Si hay múltiples padres, usa el de mayor prioridad (primero en la lista).
-///
-/// # Argumentos
-/// * `changed_path` - Path del archivo modificado
-/// * `project_path` - Path raíz del proyecto (no usado directamente, pero útil para validaciones futuras)
-/// * `parent_patterns` - Patrones de archivos padre del framework (detectados por IA)
+/// Raíz del paquete que contiene `path` en un monorepo: el ancestro más cercano con alguno
+/// de los `markers` (`package.json`, `go.mod`, `Cargo.toml`...), sin salir de `project_path`.
 ///
 /// # Retorna
-/// * `Some(nombre_base)` - Si se detecta un padre, retorna el nombre base (ej: "call" para "call.service.ts")
-/// * `None` - Si no se detecta ningún padre
-///
-/// # Ejemplos
-/// ```
-/// // Archivo: src/calls/call-inbound.ts
-/// // Existe: src/calls/call.service.ts
-/// // Retorna: Some("call")
-///
-/// // Archivo: src/users/users.service.ts
-/// // No existe ningún padre (es el padre)
-/// // Retorna: None
-/// ```
-pub fn detectar_archivo_padre(
-    changed_path: &Path,
-    _project_path: &Path,
-    parent_patterns: &[String],
-) -> Option<String> {
-    // Obtener el directorio del archivo modificado
-    let dir = changed_path.parent()?;
+/// * `Some(dir)` - Directorio del paquete (puede ser la propia raíz del proyecto)
+/// * `None` - Si ningún ancestro dentro del proyecto contiene un marcador
+pub fn raiz_de_paquete(path: &Path, project_path: &Path, markers: &[String]) -> Option<PathBuf> {
+    path.ancestors()
+        .take_while(|dir| dir.starts_with(project_path))
+        .find(|dir| markers.iter().any(|m| dir.join(m).is_file()))
+        .map(Path::to_path_buf)
+}
 
-    // Leer todos los archivos en el directorio
+/// Padre de mayor prioridad (menor índice en `parent_patterns`) dentro de `dir`.
+fn padre_en_directorio(dir: &Path, parent_patterns: &[String]) -> Option<String> {
     let entries = fs::read_dir(dir).ok()?;
 
     // Recopilar todos los archivos padres encontrados
     let mut padres: Vec<(String, usize)> = Vec::new();
 
-    for entry in entries {
-        let entry = entry.ok()?;
+    for entry in entries.flatten() {
         let path = entry.path();
 
         // Solo procesar archivos, no directorios
@@ -109,7 +106,9 @@ pub fn detectar_archivo_padre(
         }
 
         // Solo procesar archivos .ts que no sean .spec.ts
-        let file_name = path.file_name()?.to_str()?;
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
 
         if !file_name.ends_with(".ts") || file_name.contains(".spec.") {
             continue;
@@ -132,14 +131,63 @@ pub fn detectar_archivo_padre(
         }
     }
 
-    // Retornar el padre con mayor prioridad (menor índice)
-    if padres.is_empty() {
-        None
-    } else {
-        // Ordenar por prioridad y tomar el primero
-        padres.sort_by_key(|(_, priority)| *priority);
-        Some(padres[0].0.clone())
+    // Retornar el padre con mayor prioridad (menor índice); a igual prioridad, orden alfabético
+    padres.sort_by(|(a, pa), (b, pb)| pa.cmp(pb).then_with(|| a.cmp(b)));
+    padres.into_iter().next().map(|(name, _)| name)
+}
+
+/// Detecta si un archivo es un "hijo" y retorna el nombre del módulo padre
+///
+/// Esta función busca un archivo padre según los patrones del framework, empezando
+/// en el directorio del archivo modificado y subiendo por sus ancestros, y retorna el
+/// nombre base del módulo más cercano. Si hay múltiples padres en un directorio, usa el
+/// de mayor prioridad (primero en la lista). La búsqueda se detiene en el primer directorio
+/// con un padre y nunca sale del paquete del archivo (ver `raiz_de_paquete`), así que en un
+/// monorepo un archivo de `packages/a` no se atribuye a un padre de `packages/b`. Si el
+/// archivo no está en ningún paquete, solo se busca en su propio directorio.
+///
+/// # Argumentos
+/// * `changed_path` - Path del archivo modificado
+/// * `project_path` - Path raíz del proyecto (límite superior de la búsqueda)
+/// * `parent_patterns` - Patrones de archivos padre del framework (detectados por IA)
+/// * `package_markers` - Archivos que marcan la raíz de un paquete (`package_root_markers`)
+///
+/// # Retorna
+/// * `Some(nombre_base)` - Si se detecta un padre, retorna el nombre base (ej: "call" para "call.service.ts")
+/// * `None` - Si no se detecta ningún padre
+///
+/// # Ejemplos
+/// ```
+/// // Archivo: src/calls/call-inbound.ts
+/// // Existe: src/calls/call.service.ts
+/// // Retorna: Some("call")
+///
+/// // Archivo: src/users/users.service.ts
+/// // No existe ningún padre (es el padre)
+/// // Retorna: None
+/// ```
+pub fn detectar_archivo_padre(
+    changed_path: &Path,
+    project_path: &Path,
+    parent_patterns: &[String],
+    package_markers: &[String],
+) -> Option<String> {
+    // Obtener el directorio del archivo modificado
+    let dir = changed_path.parent()?;
+    // Sin paquete conocido no hay límite fiable: solo se mira el propio directorio
+    let Some(limite) = raiz_de_paquete(dir, project_path, package_markers) else {
+        return padre_en_directorio(dir, parent_patterns);
+    };
+
+    for ancestor in dir.ancestors() {
+        if let Some(padre) = padre_en_directorio(ancestor, parent_patterns) {
+            return Some(padre);
+        }
+        if ancestor == limite || !ancestor.starts_with(&limite) {
+            break;
+        }
     }
+    None
 }
 
 /// Busca archivos de test para un módulo usando los patrones del framework
@@ -249,7 +297,8 @@ pub fn sufijos_sin_test_por_framework(framework: &str) -> Vec<&'static str> {
             ".types.ts",
         ]
     } else if fw.contains("rails") || fw.contains("ruby") {
-        vec!["schema.rb", "seeds.rb", "routes.rb"]
+        // `_spec.rb` ya son tests (RSpec)
+        vec!["schema.rb", "seeds.rb", "routes.rb", "_spec.rb"]
     } else {
         vec![]
     }
@@ -271,6 +320,7 @@ pub fn buscar_test_en_directorios(base_name: &str, project_path: &Path) -> bool
         "_test.py",
         "Test.php",
         ".spec.py",
+        "_spec.rb",
     ];
 
     for dir_name in &dirs_a_revisar {
@@ -313,57 +363,148 @@ fn buscar_en_dir_recursivo(base_name: &str, dir: &Path, extensiones: &[&str]) ->
     false
 }
 
-/// Lee las dependencias del proyecto desde package.json o Cargo.toml
-///
-/// # Argumentos
-/// * `project_path` - Path raíz del proyecto
-///
-/// # Retorna
-/// * `Vec<String>` - Lista de dependencias encontradas (ej: ["react", "typescript"])
-pub fn leer_dependencias(project_path: &Path) -> Vec<String> {
-    let mut deps = Vec::new();
+fn deps_package_json(content: &str) -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else { return Vec::new() };
+    ["dependencies", "devDependencies"]
+        .iter()
+        .filter_map(|section| json.get(section).and_then(|v| v.as_object()))
+        .flat_map(|deps| deps.keys().cloned())
+        .collect()
+}
 
-    // Intentar leer package.json
-    let package_json_path = project_path.join("package.json");
-    if package_json_path.exists() {
-        if let Ok(content) = fs::read_to_string(package_json_path) {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                if let Some(dependencies) = json.get("dependencies").and_then(|v| v.as_object()) {
-                    deps.extend(dependencies.keys().cloned());
-                }
-                if let Some(dev_dependencies) =
-                    json.get("devDependencies").and_then(|v| v.as_object())
-                {
-                    deps.extend(dev_dependencies.keys().cloned());
-                }
+/// `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]` (también por target) y
+/// `[workspace.dependencies]`.
+fn deps_cargo_toml(content: &str) -> Vec<String> {
+    let Ok(manifest) = content.parse::<toml::Table>() else { return Vec::new() };
+    let sections = |table: &toml::Table| -> Vec<String> {
+        ["dependencies", "dev-dependencies", "build-dependencies"]
+            .iter()
+            .filter_map(|section| table.get(*section).and_then(|v| v.as_table()))
+            .flat_map(|deps| deps.keys().cloned())
+            .collect()
+    };
+    let mut deps = sections(&manifest);
+    if let Some(targets) = manifest.get("target").and_then(|v| v.as_table()) {
+        for target in targets.values().filter_map(|v| v.as_table()) {
+            deps.extend(sections(target));
+        }
+    }
+    if let Some(workspace) = manifest.get("workspace").and_then(|v| v.as_table()) {
+        deps.extend(sections(workspace));
+    }
+    deps
+}
+
+/// Módulos de `require` (en línea o en bloque), sin los marcados `// indirect`.
+fn deps_go_mod(content: &str) -> Vec<String> {
+    let mut deps = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let spec = if in_block {
+            if trimmed.starts_with(')') {
+                in_block = false;
+                continue;
             }
+            trimmed
+        } else if let Some(rest) = trimmed.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest.starts_with('(') {
+                in_block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+        if spec.is_empty() || spec.starts_with("//") || spec.contains("// indirect") {
+            continue;
+        }
+        if let Some(module) = spec.split_whitespace().next() {
+            deps.push(module.to_string());
         }
     }
+    deps
+}
+
+/// Nombre de un requisito PEP 508 (`requests[socks]>=2.0; python_version<'3.8'` → `requests`).
+fn pep508_name(spec: &str) -> Option<String> {
+    let name: String = spec
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+fn deps_requirements_txt(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        // Opciones de pip (`-r otro.txt`, `-e .`, `--index-url ...`)
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .filter_map(pep508_name)
+        .collect()
+}
 
-    // Intentar leer Cargo.toml
-    let cargo_toml_path = project_path.join("Cargo.toml");
-    if cargo_toml_path.exists() {
-        if let Ok(content) = fs::read_to_string(cargo_toml_path) {
-            // Parser simple para TOML (buscando [dependencies])
-            let mut in_deps = false;
-            for line in content.lines() {
-                let trimmed = line.trim();
-                if trimmed.starts_with("[dependencies]") {
-                    in_deps = true;
-                    continue;
-                } else if trimmed.starts_with("[") {
-                    in_deps = false;
-                }
-
-                if in_deps && !trimmed.is_empty() && !trimmed.starts_with("#") {
-                    if let Some(name) = trimmed.split('=').next() {
-                        deps.push(name.trim().to_string());
-                    }
-                }
+/// `[project] dependencies` / `optional-dependencies` (PEP 621) y las tablas de Poetry.
+fn deps_pyproject(content: &str) -> Vec<String> {
+    let Ok(manifest) = content.parse::<toml::Table>() else { return Vec::new() };
+    let mut deps = Vec::new();
+    if let Some(project) = manifest.get("project").and_then(|v| v.as_table()) {
+        let mut specs: Vec<&toml::Value> = Vec::new();
+        if let Some(list) = project.get("dependencies").and_then(|v| v.as_array()) {
+            specs.extend(list);
+        }
+        if let Some(extras) = project.get("optional-dependencies").and_then(|v| v.as_table()) {
+            specs.extend(extras.values().filter_map(|v| v.as_array()).flatten());
+        }
+        deps.extend(specs.into_iter().filter_map(|v| v.as_str()).filter_map(pep508_name));
+    }
+    if let Some(poetry) = manifest
+        .get("tool")
+        .and_then(|v| v.get("poetry"))
+        .and_then(|v| v.as_table())
+    {
+        for section in ["dependencies", "dev-dependencies"] {
+            if let Some(table) = poetry.get(section).and_then(|v| v.as_table()) {
+                deps.extend(table.keys().filter(|k| k.as_str() != "python").cloned());
             }
         }
     }
+    deps
+}
+
+/// Extrae los nombres de las dependencias del contenido de un manifiesto.
+type ParserDependencias = fn(&str) -> Vec<String>;
 
+/// Manifiestos reconocidos por `leer_dependencias`, en el orden en que se listan sus dependencias.
+const MANIFIESTOS: &[(&str, ParserDependencias)] = &[
+    ("package.json", deps_package_json),
+    ("Cargo.toml", deps_cargo_toml),
+    ("go.mod", deps_go_mod),
+    ("pyproject.toml", deps_pyproject),
+    ("requirements.txt", deps_requirements_txt),
+];
+
+/// Lee las dependencias del proyecto de los manifiestos que haya en la raíz: package.json,
+/// Cargo.toml, go.mod, pyproject.toml y requirements.txt (un proyecto puede tener varios).
+///
+/// # Argumentos
+/// * `project_path` - Path raíz del proyecto
+///
+/// # Retorna
+/// * `Vec<String>` - Nombres de las dependencias, sin repetir (ej: ["react", "typescript"])
+pub fn leer_dependencias(project_path: &Path) -> Vec<String> {
+    let mut deps: Vec<String> = Vec::new();
+    for (manifest, parse) in MANIFIESTOS {
+        let Ok(content) = fs::read_to_string(project_path.join(manifest)) else { continue };
+        for dep in parse(&content) {
+            if !deps.contains(&dep) {
+                deps.push(dep);
+            }
+        }
+    }
     deps
 }
 
@@ -373,6 +514,83 @@ mod test_buscar {
     use std::fs;
     use tempfile::TempDir;
 
+    fn nest_parents() -> Vec<String> {
+        vec![".service.ts".to_string(), ".controller.ts".to_string()]
+    }
+
+    fn markers() -> Vec<String> {
+        vec!["package.json".to_string(), "go.mod".to_string(), "Cargo.toml".to_string()]
+    }
+
+    /// Monorepo con dos paquetes y un padre suelto en `packages/` (fuera de ambos).
+    fn two_package_layout() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for (path, content) in [
+            ("package.json", "{}"),
+            ("packages/shared.service.ts", "export class SharedService {}"),
+            ("packages/a/package.json", "{}"),
+            ("packages/a/src/users/user.service.ts", "export class UserService {}"),
+            ("packages/a/src/users/dto/create-user.dto.ts", "export class CreateUserDto {}"),
+            ("packages/b/go.mod", "module b"),
+            ("packages/b/src/orders/order.controller.ts", "export class OrderController {}"),
+            ("packages/b/src/orders/order.mapper.ts", "export const map = 1;"),
+            ("packages/b/src/lib/helper.ts", "export const helper = 1;"),
+        ] {
+            let file = root.join(path);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, content).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_detectar_archivo_padre_within_each_package() {
+        let temp_dir = two_package_layout();
+        let root = temp_dir.path();
+        let padre = |rel: &str| detectar_archivo_padre(&root.join(rel), root, &nest_parents(), &markers());
+
+        assert_eq!(padre("packages/a/src/users/dto/create-user.dto.ts"), Some("user".to_string()));
+        assert_eq!(padre("packages/b/src/orders/order.mapper.ts"), Some("order".to_string()));
+        // Sin padre en su paquete: no sube a `packages/shared.service.ts`
+        assert_eq!(padre("packages/b/src/lib/helper.ts"), None);
+
+        // Sin marcadores no hay paquete: solo cuenta el propio directorio
+        let sin_marcadores = |rel: &str| detectar_archivo_padre(&root.join(rel), root, &nest_parents(), &[]);
+        assert_eq!(sin_marcadores("packages/b/src/lib/helper.ts"), None);
+        assert_eq!(sin_marcadores("packages/a/src/users/dto/create-user.dto.ts"), None);
+        assert_eq!(sin_marcadores("packages/b/src/orders/order.mapper.ts"), Some("order".to_string()));
+    }
+
+    #[test]
+    fn test_filtrar_por_tamano_excludes_files_over_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let small = temp_dir.path().join("app.ts");
+        let bundle = temp_dir.path().join("vendor.min.js");
+        fs::write(&small, "export const a = 1;\n").unwrap();
+        fs::write(&bundle, "x".repeat(2_000)).unwrap();
+
+        let (kept, skipped) = filtrar_por_tamano(vec![small.clone(), bundle.clone()], 1_000);
+        assert_eq!(kept, vec![small.clone()]);
+        assert_eq!(skipped, vec![(bundle.clone(), 2_000)]);
+
+        // 0 desactiva el límite
+        let (kept, skipped) = filtrar_por_tamano(vec![small, bundle], 0);
+        assert_eq!((kept.len(), skipped.len()), (2, 0));
+    }
+
+    #[test]
+    fn test_raiz_de_paquete_nearest_marker() {
+        let temp_dir = two_package_layout();
+        let root = temp_dir.path();
+        assert_eq!(
+            raiz_de_paquete(&root.join("packages/b/src/orders"), root, &markers()),
+            Some(root.join("packages/b"))
+        );
+        assert_eq!(raiz_de_paquete(&root.join("packages"), root, &markers()), Some(root.to_path_buf()));
+        assert_eq!(raiz_de_paquete(&root.join("packages/a/src"), root, &[]), None);
+    }
+
     #[test]
     fn test_buscar_archivo_test_nestjs() {
         let temp_dir = TempDir::new().unwrap();
@@ -469,3 +687,91 @@ mod test_buscar {
         assert_eq!(result, Some("user_test.go".to_string()));
     }
 }
+
+#[cfg(test)]
+mod test_dependencias {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn deps_of(manifest: &str, content: &str) -> Vec<String> {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(manifest), content).unwrap();
+        leer_dependencias(dir.path())
+    }
+
+    #[test]
+    fn test_package_json() {
+        let deps = deps_of(
+            "package.json",
+            r#"{"dependencies":{"@nestjs/core":"^10.0.0","rxjs":"^7"},"devDependencies":{"typescript":"^5"}}"#,
+        );
+        assert_eq!(deps, vec!["@nestjs/core", "rxjs", "typescript"]);
+    }
+
+    #[test]
+    fn test_cargo_toml() {
+        let deps = deps_of(
+            "Cargo.toml",
+            r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+tokio = "1"
+
+[dependencies.reqwest]
+version = "0.12"
+
+[dev-dependencies]
+tempfile = "3"
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+"#,
+        );
+        assert_eq!(deps, vec!["reqwest", "serde", "tokio", "tempfile", "libc"]);
+    }
+
+    #[test]
+    fn test_go_mod() {
+        let deps = deps_of(
+            "go.mod",
+            "module example.com/api\n\ngo 1.22\n\nrequire github.com/spf13/cobra v1.8.0\n\nrequire (\n\tgithub.com/gin-gonic/gin v1.9.1\n\tgorm.io/gorm v1.25.5\n\tgolang.org/x/sys v0.15.0 // indirect\n)\n",
+        );
+        assert_eq!(deps, vec!["github.com/spf13/cobra", "github.com/gin-gonic/gin", "gorm.io/gorm"]);
+    }
+
+    #[test]
+    fn test_requirements_txt() {
+        let deps = deps_of(
+            "requirements.txt",
+            "# web\nDjango>=4.2,<5\nrequests[socks]==2.31.0  # http\n-r dev.txt\n\nnumpy; python_version >= '3.9'\n",
+        );
+        assert_eq!(deps, vec!["Django", "requests", "numpy"]);
+    }
+
+    #[test]
+    fn test_pyproject_toml() {
+        let pep621 = deps_of(
+            "pyproject.toml",
+            "[project]\nname = \"api\"\ndependencies = [\"fastapi>=0.110\", \"pydantic[email]\"]\n\n[project.optional-dependencies]\ntest = [\"pytest\"]\n",
+        );
+        assert_eq!(pep621, vec!["fastapi", "pydantic", "pytest"]);
+
+        let poetry = deps_of(
+            "pyproject.toml",
+            "[tool.poetry.dependencies]\npython = \"^3.11\"\nflask = \"^3.0\"\n\n[tool.poetry.dev-dependencies]\nblack = \"*\"\n",
+        );
+        assert_eq!(poetry, vec!["flask", "black"]);
+    }
+
+    #[test]
+    fn test_manifests_are_combined_without_duplicates() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("requirements.txt"), "flask\n").unwrap();
+        fs::write(dir.path().join("pyproject.toml"), "[project]\ndependencies = [\"flask\", \"celery\"]\n").unwrap();
+        assert_eq!(leer_dependencias(dir.path()), vec!["flask", "celery"]);
+        assert!(leer_dependencias(&dir.path().join("missing")).is_empty());
+    }
+}
@@ -23,10 +23,54 @@ pub fn obtener_resumen_git(project_path: &Path) -> String {
     String::from_utf8_lossy(&output.stdout).to_string()
 }
 
-/// Genera un mensaje de commit automático siguiendo Conventional Commits.
+/// Diff de `file` respecto a `HEAD` (cambios en stage y sin stage). `None` si no hay repo,
+/// no hay commits o el archivo no tiene cambios rastreados (p. ej. un archivo nuevo).
+pub fn obtener_diff(project_path: &Path, file: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["diff", "--no-color", "HEAD", "--"])
+        .arg(file)
+        .current_dir(project_path)
+        .output()
+        .ok()?;
+    let diff = String::from_utf8_lossy(&output.stdout).into_owned();
+    (output.status.success() && !diff.trim().is_empty()).then_some(diff)
+}
+
+/// Genera un mensaje de commit automático siguiendo Conventional Commits. Con
+/// `commit_context = "diff"` se envía el diff de `file_path`; sin diff, el código completo.
 pub fn generar_mensaje_commit(
     codigo: &str,
-    file_name: &str,
+    file_path: &Path,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: &Path,
+) -> String {
+    generar_mensaje_commit_con(codigo, file_path, None, config, stats, project_path)
+}
+
+/// Vuelve a pedir el mensaje a la IA indicando por qué el anterior no era válido.
+pub fn regenerar_mensaje_commit(
+    codigo: &str,
+    file_path: &Path,
+    invalido: &str,
+    error: &str,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: &Path,
+) -> String {
+    let correccion = format!(
+        "El mensaje anterior \"{}\" no es válido: {}. Usa exactamente el formato `tipo(scope): asunto` con tipo en: {}.",
+        invalido,
+        error,
+        TIPOS_CONVENTIONAL.join(", ")
+    );
+    generar_mensaje_commit_con(codigo, file_path, Some(&correccion), config, stats, project_path)
+}
+
+fn generar_mensaje_commit_con(
+    codigo: &str,
+    file_path: &Path,
+    correccion: Option<&str>,
     config: &SentinelConfig,
     stats: Arc<Mutex<SentinelStats>>,
     project_path: &Path,
@@ -35,10 +79,21 @@ pub fn generar_mensaje_commit(
         "{}",
         "📝 Generando mensaje de commit inteligente...".magenta()
     );
-    let prompt = format!(
-        "Genera un mensaje de commit corto (máximo 50 caracteres) siguiendo 'Conventional Commits' para los cambios en {}. Solo devuelve el texto del mensaje.\n\nCódigo:\n{}",
-        file_name, codigo
+    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+    let diff = (config.commit_context == "diff")
+        .then(|| obtener_diff(project_path, file_path))
+        .flatten();
+    let contexto = match diff {
+        Some(diff) => format!("Diff:\n{}", diff),
+        None => format!("Código:\n{}", codigo),
+    };
+    let mut prompt = format!(
+        "Genera un mensaje de commit corto (máximo 50 caracteres) siguiendo 'Conventional Commits' para los cambios en {}. Solo devuelve el texto del mensaje.\n\n{}",
+        file_name, contexto
     );
+    if let Some(c) = correccion {
+        prompt.push_str(&format!("\n\nIMPORTANTE: {}", c));
+    }
 
     match ai::consultar_ia_dinamico(prompt, ai::TaskType::Light, config, stats, project_path) {
         Ok(msg) => msg.trim().replace('"', ""),
@@ -46,6 +101,71 @@ pub fn generar_mensaje_commit(
     }
 }
 
+/// Tipos admitidos por `commit_convention = "conventional"`.
+pub const TIPOS_CONVENTIONAL: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// Longitud máxima de la primera línea de un commit convencional.
+const MAX_CABECERA_COMMIT: usize = 72;
+
+/// Valida la primera línea contra `tipo(scope)!: asunto`; el scope y el `!` son opcionales.
+/// Devuelve el motivo del rechazo para mostrarlo o reenviarlo a la IA.
+pub fn validar_conventional_commit(mensaje: &str) -> Result<(), String> {
+    let cabecera = mensaje.lines().next().unwrap_or("").trim();
+    let re = regex::Regex::new(r"^([a-z]+)(\(([^()\s][^()]*)\))?(!)?: (.+)$").expect("regex válida");
+    let Some(caps) = re.captures(cabecera) else {
+        return Err("la cabecera debe tener la forma `tipo(scope): asunto`".to_string());
+    };
+    let tipo = &caps[1];
+    if !TIPOS_CONVENTIONAL.contains(&tipo) {
+        return Err(format!("tipo '{}' desconocido (usa {})", tipo, TIPOS_CONVENTIONAL.join(", ")));
+    }
+    let asunto = caps[5].trim();
+    if asunto.is_empty() {
+        return Err("el asunto está vacío".to_string());
+    }
+    if asunto.ends_with('.') {
+        return Err("el asunto no debe terminar en punto".to_string());
+    }
+    if cabecera.chars().count() > MAX_CABECERA_COMMIT {
+        return Err(format!("la cabecera supera {} caracteres", MAX_CABECERA_COMMIT));
+    }
+    if mensaje.lines().nth(1).is_some_and(|l| !l.trim().is_empty()) {
+        return Err("deja una línea en blanco entre la cabecera y el cuerpo".to_string());
+    }
+    Ok(())
+}
+
+/// Abre `$VISUAL` / `$EDITOR` (o `vi` / `notepad`) con `mensaje` y devuelve el texto guardado,
+/// sin las líneas de comentario `#`.
+pub fn editar_mensaje_commit(mensaje: &str) -> anyhow::Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad".to_string() } else { "vi".to_string() });
+    let path = std::env::temp_dir().join(format!("SENTINEL_COMMIT_EDITMSG-{}", uuid::Uuid::new_v4()));
+    fs::write(
+        &path,
+        format!("{}\n\n# Edita el mensaje del commit. Las líneas que empiezan con '#' se ignoran.\n", mensaje),
+    )?;
+
+    // `code --wait`, `subl -w`...: el editor puede traer argumentos
+    let mut partes = editor.split_whitespace();
+    let programa = partes.next().unwrap_or("vi");
+    let estado = Command::new(programa).args(partes).arg(&path).status();
+    let editado = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    if !estado?.success() {
+        anyhow::bail!("el editor '{}' terminó con error", editor);
+    }
+    let editado = editado?;
+    let texto: Vec<&str> = editado.lines().filter(|l| !l.starts_with('#')).collect();
+    Ok(texto.join("\n").trim().to_string())
+}
+
 /// Genera un reporte de productividad diario usando Claude AI.
 pub fn generar_reporte_diario(
     project_path: &Path,
@@ -108,3 +228,62 @@ pub fn preguntar_commit(project_path: &Path, mensaje: &str, respuesta: &str) {
         println!("   ⏭️  Commit omitido.");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obtener_diff_of_modified_tracked_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let git = |args: &[&str]| {
+            let ok = Command::new("git").args(args).current_dir(root).output().unwrap().status.success();
+            assert!(ok, "git {:?}", args);
+        };
+        assert_eq!(obtener_diff(root, &root.join("a.ts")), None, "no repo");
+
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "dev@example.com"]);
+        git(&["config", "user.name", "dev"]);
+        fs::write(root.join("a.ts"), "export const a = 1;\nexport const b = 2;\n").unwrap();
+        git(&["add", "a.ts"]);
+        git(&["commit", "-q", "-m", "init"]);
+        assert_eq!(obtener_diff(root, &root.join("a.ts")), None, "no changes");
+
+        fs::write(root.join("a.ts"), "export const a = 1;\nexport const b = 3;\n").unwrap();
+        let diff = obtener_diff(root, &root.join("a.ts")).unwrap();
+        assert!(diff.contains("-export const b = 2;") && diff.contains("+export const b = 3;"), "{}", diff);
+        // También lo que ya está en stage
+        git(&["add", "a.ts"]);
+        assert!(obtener_diff(root, Path::new("a.ts")).unwrap().contains("+export const b = 3;"));
+
+        fs::write(root.join("new.ts"), "export const n = 0;\n").unwrap();
+        assert_eq!(obtener_diff(root, &root.join("new.ts")), None, "untracked");
+    }
+
+    #[test]
+    fn test_validar_conventional_commit() {
+        for ok in [
+            "feat: add login endpoint",
+            "fix(auth): refresh expired tokens",
+            "refactor(users/service)!: drop legacy lookup",
+            "docs(readme): explain setup\n\nIncluye pasos para Windows.",
+        ] {
+            assert_eq!(validar_conventional_commit(ok), Ok(()), "{}", ok);
+        }
+
+        for bad in [
+            "add login endpoint",
+            "feature: add login",
+            "feat add login",
+            "feat(): add login",
+            "feat: add login endpoint.",
+            "fix: tokens\nsin línea en blanco",
+        ] {
+            assert!(validar_conventional_commit(bad).is_err(), "{}", bad);
+        }
+        let largo = format!("feat: {}", "x".repeat(80));
+        assert!(validar_conventional_commit(&largo).unwrap_err().contains("72"));
+    }
+}
@@ -96,7 +96,7 @@ impl ProjectIndexBuilder {
         let symbol_query_str = r#"
             (function_declaration name: (identifier) @name) @func
             (method_definition name: (property_identifier) @name) @method
-            (class_declaration name: (identifier) @name) @class
+            (class_declaration name: (_) @name) @class
             (variable_declarator name: (identifier) @name) @var
         "#;
         let symbol_query = Query::new(language, symbol_query_str)?;
@@ -108,25 +108,36 @@ impl ProjectIndexBuilder {
         while let Some((m, _)) = captures.next() {
             for capture in m.captures {
                 let name = capture.node.utf8_text(content.as_bytes()).unwrap_or("");
-                let kind = match capture.index {
-                    0 | 1 => "function",
-                    2 | 3 => "method",
-                    4 | 5 => "class",
-                    6 | 7 => "variable",
+                // El índice de captura de @name es el mismo en todos los patrones: el tipo
+                // sale del patrón que hizo match (en TS el nombre de clase es `type_identifier`)
+                let kind = match m.pattern_index {
+                    0 => "function",
+                    1 => "method",
+                    2 => "class",
+                    3 => "variable",
                     _ => "unknown",
                 };
                 
                 // Avoid duplicates by only taking the @name capture for storage
                 if symbol_query.capture_names()[capture.index as usize] == "name" {
                     let range = capture.node.range();
+                    let exported = is_exported(capture.node, name, rel_path);
                     conn.execute(
-                        "INSERT INTO symbols (name, kind, file_path, line_start, line_end) VALUES (?, ?, ?, ?, ?)",
-                        params![name, kind, rel_path, range.start_point.row as i32, range.end_point.row as i32],
+                        "INSERT INTO symbols (name, kind, file_path, line_start, line_end, exported) VALUES (?, ?, ?, ?, ?, ?)",
+                        params![name, kind, rel_path, range.start_point.row as i32, range.end_point.row as i32, exported],
                     )?;
                 }
             }
         }
 
+        // `export { a, b }` / `export default a` sobre símbolos declarados antes
+        for name in exported_names(root_node, content) {
+            conn.execute(
+                "UPDATE symbols SET exported = 1 WHERE file_path = ? AND name = ?",
+                params![rel_path, name],
+            )?;
+        }
+
         // 2. Extraer Grafo de Llamadas (Simplificado)
         let call_query_str = r#"
             (call_expression
@@ -142,10 +153,11 @@ impl ProjectIndexBuilder {
                 if call_query.capture_names()[capture.index as usize] == "callee" {
                     let callee_name = capture.node.utf8_text(content.as_bytes()).unwrap_or("");
                     let range = capture.node.range();
-                    
+                    let caller = enclosing_symbol(capture.node, content).unwrap_or("unknown");
+
                     conn.execute(
                         "INSERT INTO call_graph (caller_file, caller_symbol, callee_symbol, line_number) VALUES (?, ?, ?, ?)",
-                        params![rel_path, "unknown", callee_name, range.start_point.row as i32],
+                        params![rel_path, caller, callee_name, range.start_point.row as i32],
                     )?;
                 }
             }
@@ -163,9 +175,10 @@ impl ProjectIndexBuilder {
         while let Some((m, _)) = captures.next() {
             for capture in m.captures {
                 let import_name = capture.node.utf8_text(content.as_bytes()).unwrap_or("");
+                let import_src = import_source(capture.node, content).unwrap_or("unknown");
                 conn.execute(
                     "INSERT INTO import_usage (file_path, import_name, import_src) VALUES (?, ?, ?)",
-                    params![rel_path, import_name, "unknown"], // src requires more complex parsing
+                    params![rel_path, import_name, import_src],
                 )?;
             }
         }
@@ -173,3 +186,89 @@ impl ProjectIndexBuilder {
         Ok(())
     }
 }
+
+/// La declaración de `name_node` forma parte de la API del módulo: está dentro de un
+/// `export_statement` sin pasar por el cuerpo de una función (TS/JS), o en Go empieza
+/// por mayúscula.
+fn is_exported(name_node: tree_sitter::Node, name: &str, rel_path: &str) -> bool {
+    if rel_path.ends_with(".go") {
+        return name.chars().next().is_some_and(char::is_uppercase);
+    }
+    let mut current = name_node.parent();
+    while let Some(n) = current {
+        match n.kind() {
+            "export_statement" => return true,
+            // Variables locales de una función exportada y miembros de una clase exportada
+            // no son exports: se llega a ellos a través del símbolo que los contiene
+            "statement_block" | "class_body" => return false,
+            _ => current = n.parent(),
+        }
+    }
+    false
+}
+
+/// Función o método que contiene la llamada `node` (la más interna). Las funciones
+/// anónimas asignadas a una variable (`const f = () => …`) toman el nombre de la variable;
+/// `None` si la llamada está en el nivel superior del módulo.
+fn enclosing_symbol<'a>(node: tree_sitter::Node, content: &'a str) -> Option<&'a str> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        let name = match n.kind() {
+            "function_declaration" | "generator_function_declaration" | "method_definition" => {
+                n.child_by_field_name("name")
+            }
+            "arrow_function" | "function_expression" | "function" => n
+                .parent()
+                .filter(|p| p.kind() == "variable_declarator")
+                .and_then(|p| p.child_by_field_name("name")),
+            _ => None,
+        };
+        if let Some(name) = name.and_then(|name| name.utf8_text(content.as_bytes()).ok()) {
+            return Some(name);
+        }
+        current = n.parent();
+    }
+    None
+}
+
+/// Nombres exportados por `export { a, b as c }` y `export default a` (sin `from`: los
+/// re-exports de otro módulo no declaran símbolos en este archivo).
+fn exported_names<'a>(root: tree_sitter::Node, content: &'a str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    let mut cursor = root.walk();
+    for stmt in root.children(&mut cursor) {
+        if stmt.kind() != "export_statement"
+            || stmt.child_by_field_name("declaration").is_some()
+            || stmt.child_by_field_name("source").is_some()
+        {
+            continue;
+        }
+        if let Some(value) = stmt.child_by_field_name("value").filter(|v| v.kind() == "identifier") {
+            names.extend(value.utf8_text(content.as_bytes()).ok());
+        }
+        let mut stmt_cursor = stmt.walk();
+        for clause in stmt.children(&mut stmt_cursor).filter(|c| c.kind() == "export_clause") {
+            let mut clause_cursor = clause.walk();
+            for spec in clause.children(&mut clause_cursor).filter(|s| s.kind() == "export_specifier") {
+                if let Some(name) = spec.child_by_field_name("name") {
+                    names.extend(name.utf8_text(content.as_bytes()).ok());
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Módulo del `import_statement` que contiene `node` (ej: `./users.service`), sin comillas.
+fn import_source<'a>(node: tree_sitter::Node, content: &'a str) -> Option<&'a str> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == "import_statement" {
+            let source = n.child_by_field_name("source")?;
+            let text = source.utf8_text(content.as_bytes()).ok()?;
+            return Some(text.trim_matches(|c| c == '\'' || c == '"' || c == '`'));
+        }
+        current = n.parent();
+    }
+    None
+}
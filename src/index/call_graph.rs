@@ -1,6 +1,44 @@
 use crate::index::db::IndexDb;
 use rusqlite::params;
 
+/// Un punto del código (ruta relativa + línea 1-based si se conoce).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolLocation {
+    pub file_path: String,
+    pub line: Option<usize>,
+}
+
+/// Una definición del símbolo con las llamadas atribuidas a ella.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolDefinitionRefs {
+    pub definition: SymbolLocation,
+    pub kind: String,
+    pub callers: Vec<SymbolLocation>,
+}
+
+/// Resultado de `find_references`. Con varias definiciones, las llamadas desde
+/// otro archivo no se pueden atribuir (el índice no guarda el origen del import)
+/// y quedan en `unresolved`.
+#[derive(Debug, Default)]
+pub struct SymbolRefs {
+    pub definitions: Vec<SymbolDefinitionRefs>,
+    pub unresolved: Vec<SymbolLocation>,
+}
+
+fn to_line(row: Option<i64>) -> Option<usize> {
+    // El builder guarda filas 0-based de tree-sitter
+    row.filter(|r| *r >= 0).map(|r| r as usize + 1)
+}
+
+/// Símbolo exportado sin referencias en el índice (candidato de `pro deadcode`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct UnreferencedExport {
+    pub name: String,
+    pub kind: String,
+    pub file_path: String,
+    pub line: Option<usize>,
+}
+
 pub struct CallGraph<'a> {
     db: &'a IndexDb,
 }
@@ -11,6 +49,7 @@ impl<'a> CallGraph<'a> {
     }
 
     pub fn get_dead_code(&self, file_path: Option<&str>) -> anyhow::Result<Vec<String>> {
+        log::debug!("Index query: get_dead_code({})", file_path.unwrap_or("*"));
         let conn = self.db.lock();
         let mut results = Vec::new();
 
@@ -40,9 +79,102 @@ impl<'a> CallGraph<'a> {
         Ok(results)
     }
 
+    /// Definiciones de `symbol` y las llamadas que lo invocan (call_graph JOIN symbols).
+    pub fn find_references(&self, symbol: &str) -> anyhow::Result<SymbolRefs> {
+        log::debug!("Index query: find_references({})", symbol);
+        let conn = self.db.lock();
+
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT file_path, line_start, kind FROM symbols \
+             WHERE name = ? ORDER BY file_path, line_start",
+        )?;
+        let definitions: Vec<(SymbolLocation, String)> = stmt
+            .query_map(params![symbol], |row| {
+                Ok((
+                    SymbolLocation { file_path: row.get(0)?, line: to_line(row.get(1)?) },
+                    row.get(2)?,
+                ))
+            })?
+            .collect::<Result<_, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT c.caller_file, c.line_number \
+             FROM call_graph c \
+             JOIN symbols s ON c.callee_symbol = s.name \
+             WHERE s.name = ? \
+             GROUP BY c.id \
+             ORDER BY c.caller_file, c.line_number",
+        )?;
+        let callers: Vec<SymbolLocation> = stmt
+            .query_map(params![symbol], |row| {
+                Ok(SymbolLocation { file_path: row.get(0)?, line: to_line(row.get(1)?) })
+            })?
+            .collect::<Result<_, _>>()?;
+
+        let mut refs = SymbolRefs {
+            definitions: definitions
+                .into_iter()
+                .map(|(definition, kind)| SymbolDefinitionRefs { definition, kind, callers: Vec::new() })
+                .collect(),
+            unresolved: Vec::new(),
+        };
+        let ambiguous = refs.definitions.len() > 1;
+        for caller in callers {
+            let owner = if ambiguous {
+                refs.definitions
+                    .iter_mut()
+                    .find(|d| d.definition.file_path == caller.file_path)
+            } else {
+                refs.definitions.first_mut()
+            };
+            match owner {
+                Some(d) => d.callers.push(caller),
+                None => refs.unresolved.push(caller),
+            }
+        }
+        Ok(refs)
+    }
+
+    /// Símbolos exportados que nadie llama (en ningún archivo, incluido el propio) ni importa
+    /// desde otro archivo. No descarta raíces (main, tests, handlers): ver `index::entry_points`.
+    pub fn unreferenced_exports(&self) -> anyhow::Result<Vec<UnreferencedExport>> {
+        log::debug!("Index query: unreferenced_exports");
+        let conn = self.db.lock();
+        let mut stmt = conn.prepare(
+            "SELECT s.name, s.kind, s.file_path, s.line_start FROM symbols s \
+             WHERE s.exported = 1 \
+             AND NOT EXISTS (SELECT 1 FROM call_graph c WHERE c.callee_symbol = s.name) \
+             AND NOT EXISTS (SELECT 1 FROM import_usage i \
+                             WHERE i.import_name = s.name AND i.file_path != s.file_path) \
+             GROUP BY s.file_path, s.name \
+             ORDER BY s.file_path, s.line_start",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(UnreferencedExport {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                file_path: row.get(2)?,
+                line: to_line(row.get(3)?),
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Archivos que importan `symbol` por nombre (`import_usage`), sin repetir.
+    pub fn importers(&self, symbol: &str) -> anyhow::Result<Vec<String>> {
+        log::debug!("Index query: importers({})", symbol);
+        let conn = self.db.lock();
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT file_path FROM import_usage WHERE import_name = ? ORDER BY file_path",
+        )?;
+        let rows = stmt.query_map(params![symbol], |row| row.get(0))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
     /// Returns true if `symbol` is called from any file OTHER than `file_path`.
     /// Used to suppress DEAD_CODE false positives for cross-file symbols.
     pub fn is_called_from_other_file(&self, symbol: &str, file_path: &str) -> bool {
+        log::debug!("Index query: is_called_from_other_file({}, {})", symbol, file_path);
         let conn = self.db.lock();
         let count: i64 = conn.query_row(
             "SELECT COUNT(*) FROM call_graph \
@@ -82,6 +214,59 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    fn seed(db: &IndexDb, symbols: &[(&str, &str, i64)], calls: &[(&str, &str, i64)]) {
+        let conn = db.lock();
+        for (name, file, line) in symbols {
+            conn.execute(
+                "INSERT INTO symbols (name, kind, file_path, line_start) VALUES (?, 'function', ?, ?)",
+                params![name, file, line],
+            ).unwrap();
+        }
+        for (file, callee, line) in calls {
+            conn.execute(
+                "INSERT INTO call_graph (caller_file, caller_symbol, callee_symbol, line_number) VALUES (?, 'unknown', ?, ?)",
+                params![file, callee, line],
+            ).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_references_lists_callers() {
+        let (_f, db) = make_db();
+        seed(
+            &db,
+            &[("createUser", "src/user.service.ts", 9), ("main", "src/main.ts", 0)],
+            &[("src/user.controller.ts", "createUser", 21), ("src/main.ts", "createUser", 4), ("src/main.ts", "other", 5)],
+        );
+        let refs = CallGraph::new(&db).find_references("createUser").unwrap();
+        assert_eq!(refs.definitions.len(), 1);
+        let def = &refs.definitions[0];
+        assert_eq!(def.definition, SymbolLocation { file_path: "src/user.service.ts".into(), line: Some(10) });
+        assert_eq!(
+            def.callers,
+            vec![
+                SymbolLocation { file_path: "src/main.ts".into(), line: Some(5) },
+                SymbolLocation { file_path: "src/user.controller.ts".into(), line: Some(22) },
+            ]
+        );
+        assert!(refs.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_find_references_splits_ambiguous_definitions() {
+        let (_f, db) = make_db();
+        seed(
+            &db,
+            &[("format", "src/a.ts", 0), ("format", "src/b.ts", 2)],
+            &[("src/a.ts", "format", 7), ("src/b.ts", "format", 8), ("src/c.ts", "format", 1)],
+        );
+        let refs = CallGraph::new(&db).find_references("format").unwrap();
+        assert_eq!(refs.definitions.len(), 2);
+        assert_eq!(refs.definitions[0].callers, vec![SymbolLocation { file_path: "src/a.ts".into(), line: Some(8) }]);
+        assert_eq!(refs.definitions[1].callers, vec![SymbolLocation { file_path: "src/b.ts".into(), line: Some(9) }]);
+        assert_eq!(refs.unresolved, vec![SymbolLocation { file_path: "src/c.ts".into(), line: Some(2) }]);
+    }
+
     #[test]
     fn test_is_called_from_other_file_false_when_no_callers() {
         let (_f, db) = make_db();
@@ -83,6 +83,38 @@ impl IndexDb {
             )",
             [],
         )?;
+        // Migración: agrupa los registros por ejecución de `pro check`
+        let has_run_id = conn
+            .prepare("SELECT 1 FROM pragma_table_info('quality_history') WHERE name = 'run_id'")?
+            .exists([])?;
+        if !has_run_id {
+            conn.execute("ALTER TABLE quality_history ADD COLUMN run_id TEXT", [])?;
+        }
+        // 4b. PUNTUACIÓN GLOBAL POR EJECUCIÓN DE `pro score`
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS quality_scores (
+                id              INTEGER PRIMARY KEY,
+                timestamp       DATETIME DEFAULT CURRENT_TIMESTAMP,
+                score           REAL NOT NULL,
+                grade           TEXT NOT NULL,
+                files           INTEGER DEFAULT 0,
+                errors          INTEGER DEFAULT 0,
+                warnings        INTEGER DEFAULT 0,
+                infos           INTEGER DEFAULT 0,
+                avg_complexity  REAL DEFAULT 0.0,
+                coverage        REAL DEFAULT 0.0
+            )",
+            [],
+        )?;
+        // Migración: qué se analizó en cada ejecución (ver `quality_history::run_scope`)
+        for table in ["quality_history", "quality_scores"] {
+            let has_scope = conn
+                .prepare(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = 'scope'", table))?
+                .exists([])?;
+            if !has_scope {
+                conn.execute(&format!("ALTER TABLE {} ADD COLUMN scope TEXT", table), [])?;
+            }
+        }
 
         // 5. ÍNDICE DE ARCHIVOS
         conn.execute(
@@ -95,6 +127,49 @@ impl IndexDb {
             )",
             [],
         )?;
+        // Migración: símbolos exportados (`pro deadcode`). Se vacía `file_index` para que
+        // la próxima indexación vuelva a parsear todo y rellene la columna.
+        let has_exported = conn
+            .prepare("SELECT 1 FROM pragma_table_info('symbols') WHERE name = 'exported'")?
+            .exists([])?;
+        if !has_exported {
+            conn.execute("ALTER TABLE symbols ADD COLUMN exported BOOLEAN DEFAULT 0", [])?;
+            conn.execute("DELETE FROM file_index", [])?;
+        }
+
+        // 6. CACHÉ DE EMBEDDINGS (clave: hash del fragmento + modelo)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS embedding_cache (
+                content_hash    TEXT NOT NULL,
+                model           TEXT NOT NULL,
+                vector          BLOB NOT NULL,
+                created_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (content_hash, model)
+            )",
+            [],
+        )?;
+
+        // 7. FRAGMENTOS PUBLICADOS EN LA KB (Qdrant) Y SINCRONIZACIONES
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS kb_chunks (
+                point_id        INTEGER PRIMARY KEY,
+                file_path       TEXT NOT NULL,
+                line            INTEGER NOT NULL,
+                content_hash    TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS kb_sync_runs (
+                id          INTEGER PRIMARY KEY,
+                timestamp   DATETIME DEFAULT CURRENT_TIMESTAMP,
+                added       INTEGER DEFAULT 0,
+                updated     INTEGER DEFAULT 0,
+                deleted     INTEGER DEFAULT 0,
+                unchanged   INTEGER DEFAULT 0
+            )",
+            [],
+        )?;
 
         // Índices para velocidad
         conn.execute(
@@ -193,7 +268,8 @@ impl IndexDb {
     }
 
     /// Clears index tables (for --rebuild): symbols, call_graph, import_usage, file_index.
-    /// quality_history is intentionally preserved (audit history survives rebuilds).
+    /// quality_history is intentionally preserved (audit history survives rebuilds), and so is
+    /// embedding_cache (keyed by content hash, so its entries never go stale).
     /// Does NOT drop the tables.
     pub fn clear_all(&self) -> rusqlite::Result<()> {
         let conn = self.lock();
@@ -204,6 +280,128 @@ impl IndexDb {
         Ok(())
     }
 
+    /// Resumen del contenido del índice para `sentinel index --stats`.
+    /// `top_symbols`: los `top` símbolos indexados con más llamadas en el call graph.
+    pub fn stats(&self, top: usize) -> IndexStats {
+        let conn = self.lock();
+        let count = |sql: &str| -> usize {
+            conn.query_row(sql, [], |row| row.get::<_, i64>(0)).map(|v| v as usize).unwrap_or(0)
+        };
+        let mut stats = IndexStats {
+            files: count("SELECT COUNT(*) FROM file_index"),
+            symbols: count("SELECT COUNT(*) FROM symbols"),
+            call_edges: count("SELECT COUNT(*) FROM call_graph"),
+            import_edges: count("SELECT COUNT(*) FROM import_usage"),
+            kb_chunks: count("SELECT COUNT(*) FROM kb_chunks"),
+            ..Default::default()
+        };
+        stats.last_kb_sync = conn
+            .query_row(
+                "SELECT added, updated, deleted, unchanged FROM kb_sync_runs ORDER BY id DESC LIMIT 1",
+                [],
+                |row| {
+                    Ok(KbSyncStats {
+                        added: row.get::<_, i64>(0)? as usize,
+                        updated: row.get::<_, i64>(1)? as usize,
+                        deleted: row.get::<_, i64>(2)? as usize,
+                        unchanged: row.get::<_, i64>(3)? as usize,
+                    })
+                },
+            )
+            .ok();
+
+        // La tabla no guarda el lenguaje: se agrupa por extensión del archivo
+        if let Ok(mut stmt) = conn.prepare("SELECT file_path, COUNT(*) FROM symbols GROUP BY file_path") {
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)));
+            for (file, n) in rows.into_iter().flatten().flatten() {
+                let ext = Path::new(&file)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("(sin extensión)")
+                    .to_string();
+                *stats.symbols_by_language.entry(ext).or_insert(0) += n as usize;
+            }
+        }
+
+        if let Ok(mut stmt) = conn.prepare(
+            "SELECT callee_symbol, COUNT(*) AS hits FROM call_graph \
+             WHERE callee_symbol IN (SELECT name FROM symbols) \
+             GROUP BY callee_symbol ORDER BY hits DESC, callee_symbol LIMIT ?1",
+        ) {
+            stats.top_symbols = stmt
+                .query_map(rusqlite::params![top as i64], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+                })
+                .map(|rows| rows.flatten().collect())
+                .unwrap_or_default();
+        }
+        stats
+    }
+
+    /// Cached embedding for a chunk (`content_hash`) computed with `model`.
+    pub fn cached_embedding(&self, content_hash: &str, model: &str) -> Option<Vec<f32>> {
+        let conn = self.lock();
+        let bytes: Vec<u8> = conn
+            .query_row(
+                "SELECT vector FROM embedding_cache WHERE content_hash = ?1 AND model = ?2",
+                rusqlite::params![content_hash, model],
+                |row| row.get(0),
+            )
+            .ok()?;
+        Some(
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+        )
+    }
+
+    pub fn store_embedding(&self, content_hash: &str, model: &str, vector: &[f32]) -> Result<()> {
+        let bytes: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let conn = self.lock();
+        conn.execute(
+            "INSERT OR REPLACE INTO embedding_cache (content_hash, model, vector) VALUES (?1, ?2, ?3)",
+            rusqlite::params![content_hash, model, bytes],
+        )?;
+        Ok(())
+    }
+
+    /// Number of cached embeddings (all models).
+    pub fn embedding_cache_count(&self) -> usize {
+        let conn = self.lock();
+        conn.query_row("SELECT COUNT(*) FROM embedding_cache", [], |row| row.get::<_, i64>(0))
+            .map(|v| v as usize)
+            .unwrap_or(0)
+    }
+
+    /// Published KB chunks: point id → content hash.
+    pub fn kb_chunk_hashes(&self) -> Result<std::collections::HashMap<u64, String>> {
+        let conn = self.lock();
+        let mut stmt = conn.prepare("SELECT point_id, content_hash FROM kb_chunks")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, String>(1)?)))?;
+        rows.collect()
+    }
+
+    /// Applies a KB sync to the ledger (`upserts`: point id, file, line, hash) and records its counts.
+    pub fn apply_kb_sync(&self, upserts: &[(u64, String, usize, String)], deleted: &[u64], stats: &KbSyncStats) -> Result<()> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        for id in deleted {
+            tx.execute("DELETE FROM kb_chunks WHERE point_id = ?1", rusqlite::params![*id as i64])?;
+        }
+        for (id, file, line, hash) in upserts {
+            tx.execute(
+                "INSERT OR REPLACE INTO kb_chunks (point_id, file_path, line, content_hash) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![*id as i64, file, *line as i64, hash],
+            )?;
+        }
+        tx.execute(
+            "INSERT INTO kb_sync_runs (added, updated, deleted, unchanged) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![stats.added as i64, stats.updated as i64, stats.deleted as i64, stats.unchanged as i64],
+        )?;
+        tx.commit()
+    }
+
     /// Number of files currently in the index.
     pub fn indexed_file_count(&self) -> usize {
         let conn = self.lock();
@@ -213,6 +411,32 @@ impl IndexDb {
     }
 }
 
+/// Resultado de `IndexDb::stats`.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct IndexStats {
+    pub files: usize,
+    pub symbols: usize,
+    /// Extensión → número de símbolos
+    pub symbols_by_language: std::collections::BTreeMap<String, usize>,
+    pub call_edges: usize,
+    pub import_edges: usize,
+    /// (símbolo, llamadas recibidas), de más a menos referenciado
+    pub top_symbols: Vec<(String, usize)>,
+    /// Fragmentos publicados en la Knowledge Base
+    pub kb_chunks: usize,
+    /// Resultado de la última sincronización de la KB
+    pub last_kb_sync: Option<KbSyncStats>,
+}
+
+/// Fragmentos de una sincronización incremental de la KB (`kb::KBManager::sync`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct KbSyncStats {
+    pub added: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +468,43 @@ mod tests {
         assert!(db.is_populated());
     }
 
+    #[test]
+    fn test_stats_counts_languages_and_top_symbols() {
+        let (_f, db) = make_db();
+        {
+            let conn = db.lock();
+            for (name, file) in [("getUser", "src/users.ts"), ("saveUser", "src/users.ts"), ("main", "cmd/main.go")] {
+                conn.execute(
+                    "INSERT INTO symbols (name, kind, file_path) VALUES (?, 'function', ?)",
+                    rusqlite::params![name, file],
+                )
+                .unwrap();
+            }
+            for (caller, callee) in [
+                ("a", "getUser"), ("b", "getUser"), ("c", "getUser"),
+                ("a", "saveUser"), ("main", "fmt.Println"),
+            ] {
+                conn.execute(
+                    "INSERT INTO call_graph (caller_file, caller_symbol, callee_symbol) VALUES ('src/app.ts', ?, ?)",
+                    rusqlite::params![caller, callee],
+                )
+                .unwrap();
+            }
+            conn.execute(
+                "INSERT INTO import_usage (file_path, import_name, import_src) VALUES ('src/app.ts', 'getUser', './users')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let stats = db.stats(10);
+        assert_eq!((stats.symbols, stats.call_edges, stats.import_edges), (3, 5, 1));
+        assert_eq!(stats.symbols_by_language.get("ts"), Some(&2));
+        assert_eq!(stats.symbols_by_language.get("go"), Some(&1));
+        // fmt.Println no es un símbolo indexado: no entra en el ranking
+        assert_eq!(stats.top_symbols, vec![("getUser".to_string(), 3), ("saveUser".to_string(), 1)]);
+    }
+
     #[test]
     fn test_clear_all_empties_index() {
         let (_f, db) = make_db();
@@ -0,0 +1,197 @@
+//! Exportación del call graph a Graphviz DOT (`sentinel index --export-dot`).
+//!
+//! Nodos: funciones, métodos y clases del índice (etiquetados con su archivo) más los
+//! símbolos que participan en alguna llamada. Aristas: `call_graph`, con el callee
+//! resuelto a su definición: la del mismo archivo si existe, o la única del proyecto;
+//! las llamadas ambiguas o a código externo (librerías) se omiten. Un subgrafo
+//! `cluster_` por directorio.
+
+use crate::index::db::IndexDb;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Nombre del nodo que agrupa las llamadas hechas fuera de cualquier función.
+const TOP_LEVEL: &str = "unknown";
+
+/// Resultado de `export_dot`.
+#[derive(Debug)]
+pub struct DotGraph {
+    pub dot: String,
+    pub nodes: usize,
+    pub edges: usize,
+}
+
+/// (archivo, símbolo)
+type Node = (String, String);
+
+fn normalize_module(module: &str) -> String {
+    module.trim().trim_start_matches("./").trim_end_matches('/').to_string()
+}
+
+fn in_module(file: &str, module: &str) -> bool {
+    module.is_empty() || file == module || file.starts_with(&format!("{}/", module))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn node_id((file, name): &Node) -> String {
+    format!("\"{}::{}\"", escape(file), escape(name))
+}
+
+fn directory(file: &str) -> &str {
+    file.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+/// Call graph del índice en formato DOT, limitado a `module` (subárbol de directorios) si se indica.
+pub fn export_dot(db: &IndexDb, module: Option<&str>) -> anyhow::Result<DotGraph> {
+    let module = module.map(normalize_module).unwrap_or_default();
+    let mut definitions: HashMap<String, BTreeSet<String>> = HashMap::new();
+    let mut nodes: BTreeSet<Node> = BTreeSet::new();
+    let mut edges: BTreeSet<(Node, Node)> = BTreeSet::new();
+    {
+        let conn = db.lock();
+        let mut stmt = conn.prepare("SELECT name, kind, file_path FROM symbols")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+        for row in rows {
+            let (name, kind, file) = row?;
+            if matches!(kind.as_str(), "function" | "method" | "class") && in_module(&file, &module) {
+                nodes.insert((file.clone(), name.clone()));
+            }
+            definitions.entry(name).or_default().insert(file);
+        }
+
+        let mut stmt = conn.prepare("SELECT DISTINCT caller_file, caller_symbol, callee_symbol FROM call_graph")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+        for row in rows {
+            let (caller_file, caller, callee) = row?;
+            let Some(files) = definitions.get(&callee) else { continue };
+            let callee_file = if files.contains(&caller_file) {
+                caller_file.clone()
+            } else if files.len() == 1 {
+                files.iter().next().cloned().unwrap_or_default()
+            } else {
+                continue;
+            };
+            if !in_module(&caller_file, &module) || !in_module(&callee_file, &module) {
+                continue;
+            }
+            let from = (caller_file, caller);
+            let to = (callee_file, callee);
+            nodes.insert(from.clone());
+            nodes.insert(to.clone());
+            edges.insert((from, to));
+        }
+    }
+
+    let mut clusters: BTreeMap<&str, Vec<&Node>> = BTreeMap::new();
+    for node in &nodes {
+        clusters.entry(directory(&node.0)).or_default().push(node);
+    }
+
+    let mut dot = String::from("digraph sentinel {\n    rankdir=LR;\n    node [shape=box, fontname=\"Helvetica\"];\n");
+    for (dir, members) in &clusters {
+        let label = if dir.is_empty() { "." } else { dir };
+        dot.push_str(&format!("\n    subgraph \"cluster_{}\" {{\n", escape(label)));
+        dot.push_str(&format!("        label=\"{}\";\n", escape(label)));
+        for node in members {
+            let file_name = node.0.rsplit('/').next().unwrap_or(&node.0);
+            let (name, shape) = if node.1 == TOP_LEVEL {
+                ("(top-level)", ", shape=note")
+            } else {
+                (node.1.as_str(), "")
+            };
+            dot.push_str(&format!(
+                "        {} [label=\"{}\\n{}\"{}];\n",
+                node_id(node),
+                escape(name),
+                escape(file_name),
+                shape
+            ));
+        }
+        dot.push_str("    }\n");
+    }
+    if !edges.is_empty() {
+        dot.push('\n');
+    }
+    for (from, to) in &edges {
+        dot.push_str(&format!("    {} -> {};\n", node_id(from), node_id(to)));
+    }
+    dot.push_str("}\n");
+
+    Ok(DotGraph { dot, nodes: nodes.len(), edges: edges.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    fn seed(db: &IndexDb) {
+        let conn = db.lock();
+        for (name, kind, file) in [
+            ("createUser", "method", "src/users/user.service.ts"),
+            ("hashPassword", "function", "src/auth/crypto.ts"),
+            ("create", "method", "src/users/user.controller.ts"),
+            ("bootstrap", "function", "src/main.ts"),
+            ("format", "function", "src/a.ts"),
+            ("format", "function", "src/b.ts"),
+        ] {
+            conn.execute(
+                "INSERT INTO symbols (name, kind, file_path, line_start) VALUES (?, ?, ?, 0)",
+                params![name, kind, file],
+            )
+            .unwrap();
+        }
+        for (file, caller, callee) in [
+            ("src/users/user.controller.ts", "create", "createUser"),
+            ("src/users/user.controller.ts", "create", "createUser"),
+            ("src/users/user.service.ts", "createUser", "hashPassword"),
+            ("src/main.ts", "unknown", "bootstrap"),
+            ("src/main.ts", "bootstrap", "log"),
+            ("src/main.ts", "bootstrap", "format"),
+        ] {
+            conn.execute(
+                "INSERT INTO call_graph (caller_file, caller_symbol, callee_symbol, line_number) VALUES (?, ?, ?, 1)",
+                params![file, caller, callee],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_export_dot_declares_nodes_edges_and_clusters() {
+        let f = tempfile::NamedTempFile::new().unwrap();
+        let db = IndexDb::open(f.path()).unwrap();
+        seed(&db);
+
+        let graph = export_dot(&db, None).unwrap();
+        let dot = &graph.dot;
+        assert!(dot.starts_with("digraph sentinel {"));
+        assert!(dot.contains(
+            "\"src/users/user.service.ts::createUser\" [label=\"createUser\\nuser.service.ts\"];"
+        ));
+        assert!(dot.contains("\"src/main.ts::unknown\" [label=\"(top-level)\\nmain.ts\", shape=note];"));
+        assert!(dot.contains(
+            "\"src/users/user.controller.ts::create\" -> \"src/users/user.service.ts::createUser\";"
+        ));
+        assert!(dot.contains("\"src/users/user.service.ts::createUser\" -> \"src/auth/crypto.ts::hashPassword\";"));
+        assert!(dot.contains("\"src/main.ts::unknown\" -> \"src/main.ts::bootstrap\";"));
+        assert!(dot.contains("subgraph \"cluster_src/users\" {"));
+        assert!(dot.contains("subgraph \"cluster_src\" {"));
+        // Llamadas duplicadas se unen; externas (log) y ambiguas (format) se omiten
+        assert_eq!(graph.edges, 3);
+        assert!(!dot.contains("::log"));
+        assert!(!dot.contains("-> \"src/a.ts::format\""));
+
+        // --module: solo el subárbol, sin aristas que salgan de él
+        let scoped = export_dot(&db, Some("./src/users/")).unwrap();
+        assert_eq!((scoped.nodes, scoped.edges), (2, 1));
+        assert!(!scoped.dot.contains("crypto.ts"));
+        assert!(!scoped.dot.contains("cluster_src\""));
+    }
+}
@@ -0,0 +1,126 @@
+//! Fragmentos de código y caché de embeddings en `index.db`.
+//!
+//! Cada archivo se parte en fragmentos de `CHUNK_LINES` líneas; el embedding de un
+//! fragmento se guarda bajo el SHA-256 de su texto y el modelo que lo calculó, así que
+//! solo se vuelve a pedir al proveedor cuando el fragmento cambia.
+
+use crate::index::db::IndexDb;
+use sha2::{Digest, Sha256};
+
+/// Líneas por fragmento.
+pub const CHUNK_LINES: usize = 60;
+
+/// Función que calcula los embeddings de un lote de textos (proveedor real o mock en tests).
+pub type Embedder<'a> = dyn FnMut(Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> + 'a;
+
+/// Fragmentos no vacíos de `content`, en orden.
+pub fn code_chunks(content: &str) -> Vec<String> {
+    code_chunks_with_lines(content).into_iter().map(|(_, chunk)| chunk).collect()
+}
+
+/// Como `code_chunks`, con la línea (1-based) donde empieza cada fragmento.
+pub fn code_chunks_with_lines(content: &str) -> Vec<(usize, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .chunks(CHUNK_LINES)
+        .enumerate()
+        .map(|(i, chunk)| (i * CHUNK_LINES + 1, chunk.join("\n")))
+        .filter(|(_, chunk)| !chunk.trim().is_empty())
+        .collect()
+}
+
+/// Clave de caché del modelo de embeddings (`proveedor:modelo`).
+pub fn model_key(model: &crate::config::ModelConfig) -> String {
+    format!("{}:{}", model.provider, model.name)
+}
+
+pub fn chunk_hash(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Resultado de `embed_with_cache`.
+#[derive(Debug, Default)]
+pub struct CachedEmbeddings {
+    pub vectors: Vec<Vec<f32>>,
+    /// Fragmentos que hubo que enviar al proveedor
+    pub computed: usize,
+    /// Fragmentos servidos desde la caché
+    pub cached: usize,
+}
+
+/// Embeddings de `texts` (mismo orden): los que ya están en la caché para `model` se
+/// reutilizan; el resto se calcula con `embed` en una sola llamada y se guarda.
+pub fn embed_with_cache(
+    db: &IndexDb,
+    model: &str,
+    texts: &[String],
+    embed: &mut Embedder,
+) -> anyhow::Result<CachedEmbeddings> {
+    let hashes: Vec<String> = texts.iter().map(|t| chunk_hash(t)).collect();
+    let mut vectors: Vec<Option<Vec<f32>>> = hashes.iter().map(|h| db.cached_embedding(h, model)).collect();
+    let missing: Vec<usize> = (0..texts.len()).filter(|&i| vectors[i].is_none()).collect();
+
+    if !missing.is_empty() {
+        let computed = embed(missing.iter().map(|&i| texts[i].clone()).collect())?;
+        if computed.len() != missing.len() {
+            anyhow::bail!(
+                "el proveedor devolvió {} embeddings para {} fragmentos",
+                computed.len(),
+                missing.len()
+            );
+        }
+        for (&i, vector) in missing.iter().zip(computed) {
+            db.store_embedding(&hashes[i], model, &vector)?;
+            vectors[i] = Some(vector);
+        }
+    }
+
+    Ok(CachedEmbeddings {
+        computed: missing.len(),
+        cached: texts.len() - missing.len(),
+        vectors: vectors.into_iter().flatten().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_with_cache_only_computes_missing_chunks() {
+        let f = tempfile::NamedTempFile::new().unwrap();
+        let db = IndexDb::open(f.path()).unwrap();
+        let mut calls: Vec<usize> = Vec::new();
+        let mut embed = |texts: Vec<String>| -> anyhow::Result<Vec<Vec<f32>>> {
+            calls.push(texts.len());
+            Ok(texts.iter().map(|t| vec![t.len() as f32, 0.5]).collect())
+        };
+
+        let first = vec!["fn a() {}".to_string(), "fn bb() {}".to_string()];
+        let out = embed_with_cache(&db, "local:minilm", &first, &mut embed).unwrap();
+        assert_eq!((out.computed, out.cached), (2, 0));
+        assert_eq!(out.vectors[1], vec![10.0, 0.5]);
+
+        let second = vec!["fn bb() {}".to_string(), "fn ccc() {}".to_string()];
+        let out = embed_with_cache(&db, "local:minilm", &second, &mut embed).unwrap();
+        assert_eq!((out.computed, out.cached), (1, 1));
+        assert_eq!(out.vectors, vec![vec![10.0, 0.5], vec![11.0, 0.5]]);
+
+        // Otro modelo no reutiliza vectores ajenos
+        let out = embed_with_cache(&db, "openai:text-embedding-3-small", &second, &mut embed).unwrap();
+        assert_eq!(out.computed, 2);
+        assert_eq!(calls, vec![2, 1, 2]);
+        assert_eq!(db.embedding_cache_count(), 5);
+    }
+
+    #[test]
+    fn test_code_chunks_split_and_skip_blank() {
+        let content = (0..CHUNK_LINES + 5).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
+        let chunks = code_chunks(&content);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[1].starts_with(&format!("line {}", CHUNK_LINES)));
+        assert!(code_chunks("\n\n  \n").is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! Raíces del análisis de código muerto: símbolos que invocan el runtime, el framework o
+//! el runner de tests sin que aparezca una llamada en el índice.
+//!
+//! Además de las convenciones fijas de este módulo, `entrypoint_patterns` en
+//! `.sentinelrc.toml` declara raíces propias (ver `EntryPatterns`).
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::{Node, Parser};
+
+/// Nombres que llama el runtime o el framework: `main`, handlers HTTP de Next.js
+/// (`app/**/route.ts`), data fetching de páginas, `bootstrap` de NestJS...
+const ENTRY_SYMBOLS: &[&str] = &[
+    "main",
+    "default",
+    "handler",
+    "bootstrap",
+    "middleware",
+    "GET",
+    "POST",
+    "PUT",
+    "PATCH",
+    "DELETE",
+    "HEAD",
+    "OPTIONS",
+    "getServerSideProps",
+    "getStaticProps",
+    "getStaticPaths",
+    "generateMetadata",
+    "generateStaticParams",
+];
+
+/// Sufijos de archivos que el framework registra por convención (NestJS).
+const FRAMEWORK_SUFFIXES: &[&str] = &[".controller", ".resolver", ".module", ".gateway"];
+
+/// Archivos de arranque: todo lo que exportan es punto de entrada.
+const ENTRY_STEMS: &[&str] = &["main", "index", "server", "app", "cli"];
+
+/// Archivos que Next.js carga por nombre dentro de `pages/` o `app/`.
+const ROUTE_STEMS: &[&str] = &["page", "route", "layout", "loading", "error", "not-found", "template"];
+
+/// Nombre del archivo sin la última extensión (`user.controller.ts` → `user.controller`).
+fn file_stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name)
+}
+
+/// Tests (`*.test.*`, `*.spec.*`, `*_test.go`, `test_*.py`, `__tests__/`, `tests/`): el runner
+/// los ejecuta, y lo que exportan solo lo usan otros tests.
+pub fn is_test_file(path: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let stem = file_stem(&path);
+    stem.ends_with(".test")
+        || stem.ends_with(".spec")
+        || stem.ends_with("_test")
+        || stem.starts_with("test_")
+        || path.split('/').any(|dir| dir == "__tests__" || dir == "tests" || dir == "test")
+}
+
+fn is_entry_file(path: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let stem = file_stem(&path);
+    let dirs: Vec<&str> = path.split('/').collect();
+    let depth = dirs.len() - 1;
+    // main.ts, src/index.ts... (no cualquier index.ts anidado, que suele ser un barrel)
+    let top_level = depth == 0 || (depth == 1 && dirs[0] == "src");
+    (top_level && ENTRY_STEMS.contains(&stem))
+        || FRAMEWORK_SUFFIXES.iter().any(|s| stem.ends_with(s))
+        || (ROUTE_STEMS.contains(&stem) && dirs.iter().any(|d| *d == "app" || *d == "pages"))
+        || (dirs.contains(&"pages") && !stem.starts_with('_'))
+}
+
+/// `symbol` (definido en `file_path`, relativo a la raíz) se considera alcanzable aunque
+/// el índice no registre ninguna llamada.
+pub fn is_entry_point(file_path: &str, symbol: &str) -> bool {
+    ENTRY_SYMBOLS.contains(&symbol) || is_test_file(file_path) || is_entry_file(file_path)
+}
+
+/// Decoradores que registran código en el framework (`framework` de la config, sin
+/// distinguir mayúsculas). Es el valor inicial de `entrypoint_patterns` y siempre se aplica.
+pub fn default_patterns(framework: &str) -> Vec<String> {
+    let framework = framework.to_lowercase();
+    let patterns: &[&str] = if framework.contains("nest") {
+        &[
+            "@Controller", "@Get", "@Post", "@Put", "@Patch", "@Delete", "@Options", "@Head", "@All",
+            "@Resolver", "@Query", "@Mutation", "@Subscription", "@ResolveField",
+            "@MessagePattern", "@EventPattern", "@SubscribeMessage", "@WebSocketGateway",
+            "@Cron", "@Interval", "@Timeout", "@OnEvent", "@Processor", "@Process", "@Module",
+        ]
+    } else if framework.contains("angular") {
+        &["@Component", "@Directive", "@Pipe", "@NgModule", "@HostListener"]
+    } else if framework.contains("django") {
+        &["@receiver", "@admin.register", "@register.*", "@api_view", "@action"]
+    } else if framework.contains("flask") || framework.contains("fastapi") {
+        &["@app.*", "@*.route", "@router.*", "@*_bp.*", "@pytest.fixture"]
+    } else {
+        &[]
+    };
+    patterns.iter().map(|p| p.to_string()).collect()
+}
+
+/// `*` como comodín (cualquier secuencia); el resto del patrón es literal.
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let regex = format!("^{}$", regex::escape(pattern).replace(r"\*", ".*"));
+    regex::Regex::new(&regex).is_ok_and(|re| re.is_match(value))
+}
+
+/// Raíces declaradas en `entrypoint_patterns`:
+/// - `@Nombre` (decorador, `*` como comodín: `@router.*`): el símbolo decorado es raíz;
+/// - cualquier otro valor es un glob de archivos (`src/commands/**`): todo lo que declaran es raíz.
+#[derive(Default)]
+pub struct EntryPatterns {
+    decorators: Vec<String>,
+    files: Option<Gitignore>,
+}
+
+impl EntryPatterns {
+    /// `project_root` resuelve los globs de archivo relativos.
+    pub fn new(project_root: &Path, patterns: &[String]) -> Self {
+        let mut decorators = Vec::new();
+        let mut builder = GitignoreBuilder::new(project_root);
+        let mut has_files = false;
+        for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            match pattern.strip_prefix('@') {
+                Some(decorator) => decorators.push(decorator.to_string()),
+                None => has_files |= builder.add_line(None, pattern).is_ok(),
+            }
+        }
+        let files = if has_files { builder.build().ok() } else { None };
+        Self { decorators, files }
+    }
+
+    /// `entrypoint_patterns` de la config más los defaults de su framework.
+    pub fn for_config(project_root: &Path, config: &crate::config::SentinelConfig) -> Self {
+        let mut patterns = default_patterns(&config.framework);
+        patterns.extend(config.entrypoint_patterns.iter().cloned());
+        Self::new(project_root, &patterns)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decorators.is_empty() && self.files.is_none()
+    }
+
+    /// `path` (absoluta o relativa a la raíz) coincide con un glob de archivo.
+    pub fn matches_file(&self, path: &Path) -> bool {
+        let Some(ref files) = self.files else { return false };
+        // Gitignore hace panic con rutas absolutas fuera de su raíz
+        if path.has_root() && !path.starts_with(files.path()) {
+            return false;
+        }
+        files.matched_path_or_any_parents(path, false).is_ignore()
+    }
+
+    fn matches_decorator(&self, decorator: &str) -> bool {
+        self.decorators.iter().any(|p| wildcard_match(p, decorator))
+    }
+
+    /// Nombres de los símbolos de `source` (extensión `ext`) con un decorador de la lista.
+    pub fn decorated_roots(&self, ext: &str, source: &str) -> HashSet<String> {
+        let mut roots = HashSet::new();
+        if self.decorators.is_empty() || !source.contains('@') {
+            return roots;
+        }
+        let Some((language, _)) = crate::rules::languages::get_language_and_analyzers(ext) else {
+            return roots;
+        };
+        let mut parser = Parser::new();
+        if parser.set_language(&language).is_err() {
+            return roots;
+        }
+        if let Some(tree) = parser.parse(source, None) {
+            self.collect_roots(tree.root_node(), source, &mut roots);
+        }
+        roots
+    }
+
+    fn collect_roots(&self, node: Node, source: &str, roots: &mut HashSet<String>) {
+        if node.kind() == "decorator"
+            && let Ok(text) = node.utf8_text(source.as_bytes())
+        {
+            // `@Get(':id')` → `Get`; `@app.route("/")` → `app.route`
+            let name: String = text
+                .trim_start_matches('@')
+                .split('(')
+                .next()
+                .unwrap_or("")
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect();
+            if self.matches_decorator(&name)
+                && let Some(target) = decorated_name(node, source)
+            {
+                roots.insert(target.to_string());
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_roots(child, source, roots);
+        }
+    }
+}
+
+/// Nombre de la declaración a la que se aplica `decorator` (TS/JS y Python). `None` para
+/// decoradores de parámetros (`@Body()`), que no declaran nada.
+fn decorated_name<'a>(decorator: Node, source: &'a str) -> Option<&'a str> {
+    let parent = decorator.parent()?;
+    let declaration = match parent.kind() {
+        // Python: @app.route(...) def index(): ...
+        "decorated_definition" => parent.child_by_field_name("definition")?,
+        // @Controller() export class UsersController {}
+        "export_statement" => parent.child_by_field_name("declaration")?,
+        "class_declaration" | "abstract_class_declaration" | "class" => parent,
+        // En el cuerpo de la clase el decorador precede al método como hermano
+        "class_body" => {
+            let mut next = decorator.next_named_sibling();
+            while let Some(n) = next.filter(|n| matches!(n.kind(), "decorator" | "comment")) {
+                next = n.next_named_sibling();
+            }
+            next?
+        }
+        _ => return None,
+    };
+    declaration.child_by_field_name("name")?.utf8_text(source.as_bytes()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_points_cover_main_tests_and_framework_files() {
+        assert!(is_entry_point("src/main.ts", "bootstrapApp"));
+        assert!(is_entry_point("src/users/users.controller.ts", "UsersController"));
+        assert!(is_entry_point("app/api/users/route.ts", "anything"));
+        assert!(is_entry_point("src/users/users.service.spec.ts", "mockRepo"));
+        assert!(is_entry_point("pkg/users/store_test.go", "fixture"));
+        assert!(is_entry_point("src/lib/math.ts", "main"));
+
+        assert!(!is_entry_point("src/users/users.service.ts", "findAll"));
+        assert!(!is_entry_point("src/users/index.ts", "helpers"), "nested barrels are not roots");
+    }
+
+    #[test]
+    fn test_entry_patterns_match_decorators_and_file_globs() {
+        let root = Path::new("/repo");
+        let mut patterns = default_patterns("NestJS");
+        patterns.extend(["@router.*".to_string(), "src/commands/**".to_string()]);
+        let entry = EntryPatterns::new(root, &patterns);
+
+        let ts = "@Controller('users')\nexport class UsersController {\n  @Get(':id')\n  findOne() {}\n\n  @UseGuards(AuthGuard)\n  private helper(@Body() dto: Dto) {}\n}\n";
+        let roots = entry.decorated_roots("ts", ts);
+        assert!(roots.contains("UsersController") && roots.contains("findOne"));
+        assert!(!roots.contains("helper"), "only configured decorators count");
+
+        let py = "@router.get('/items')\ndef list_items():\n    return []\n\ndef helper():\n    pass\n";
+        assert_eq!(entry.decorated_roots("py", py), HashSet::from(["list_items".to_string()]));
+
+        assert!(entry.matches_file(Path::new("src/commands/deploy.ts")));
+        assert!(entry.matches_file(&root.join("src/commands/sub/run.ts")));
+        assert!(!entry.matches_file(Path::new("src/users/users.service.ts")));
+        assert!(default_patterns("typescript").is_empty());
+    }
+}
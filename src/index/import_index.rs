@@ -1,5 +1,16 @@
 use crate::index::db::IndexDb;
 use rusqlite::params;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Longitud máxima (en archivos) de un ciclo reportado; los más largos se ignoran.
+pub const MAX_CYCLE_LEN: usize = 8;
+/// Tope de ciclos devueltos, para no inundar la salida en grafos muy enredados.
+pub const MAX_CYCLES: usize = 50;
+
+const RESOLVE_SUFFIXES: &[&str] = &[
+    "", ".ts", ".tsx", ".js", ".jsx", "/index.ts", "/index.tsx", "/index.js", "/index.jsx",
+];
 
 pub struct ImportIndex<'a> {
     db: &'a IndexDb,
@@ -20,6 +31,7 @@ impl<'a> ImportIndex<'a> {
     }
 
     pub fn get_unused_imports(&self, file_path: &str) -> anyhow::Result<Vec<String>> {
+        log::debug!("Index query: get_unused_imports({})", file_path);
         let conn = self.db.lock();
         let mut stmt = conn.prepare("SELECT import_name FROM import_usage WHERE file_path = ? AND is_used = 0")?;
         let rows = stmt.query_map(params![file_path], |row| row.get(0))?;
@@ -31,3 +43,159 @@ impl<'a> ImportIndex<'a> {
         Ok(results)
     }
 }
+
+/// Resuelve un import relativo (`./x`, `../y`) de `from_file` a un archivo indexado.
+/// Los imports de paquetes (`@nestjs/common`, `lodash`) no forman parte del grafo.
+pub fn resolve_import(from_file: &str, import_src: &str, files: &HashSet<String>) -> Option<String> {
+    if !import_src.starts_with('.') {
+        return None;
+    }
+    let dir = Path::new(from_file).parent().unwrap_or(Path::new(""));
+    let mut normalized = PathBuf::new();
+    for comp in dir.join(import_src).components() {
+        match comp {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    let base = normalized.to_string_lossy().replace('\\', "/");
+    RESOLVE_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{}{}", base, suffix))
+        .find(|candidate| files.contains(candidate))
+}
+
+/// Ciclos elementales del grafo `archivo → archivos importados`.
+///
+/// Cada ciclo empieza por su archivo menor (orden lexicográfico), así se reporta una sola vez.
+/// Se exploran como máximo `max_len` archivos por ciclo y se devuelven hasta `MAX_CYCLES`.
+pub fn find_cycles(graph: &BTreeMap<String, BTreeSet<String>>, max_len: usize) -> Vec<Vec<String>> {
+    fn dfs<'a>(
+        graph: &'a BTreeMap<String, BTreeSet<String>>,
+        start: &'a str,
+        node: &'a str,
+        path: &mut Vec<&'a str>,
+        max_len: usize,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        let Some(next) = graph.get(node) else { return };
+        for n in next {
+            if cycles.len() >= MAX_CYCLES {
+                return;
+            }
+            if n == start {
+                cycles.push(path.iter().map(|s| s.to_string()).collect());
+            } else if n.as_str() > start && path.len() < max_len && !path.contains(&n.as_str()) {
+                path.push(n);
+                dfs(graph, start, n, path, max_len, cycles);
+                path.pop();
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+    for start in graph.keys() {
+        let mut path = vec![start.as_str()];
+        dfs(graph, start, start, &mut path, max_len, &mut cycles);
+    }
+    cycles
+}
+
+impl IndexDb {
+    /// Grafo `archivo → archivos del proyecto que importa` (solo imports relativos resueltos).
+    pub fn import_graph(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let (files, edges) = {
+            let conn = self.lock();
+            let files: HashSet<String> = conn
+                .prepare("SELECT file_path FROM file_index")
+                .and_then(|mut stmt| {
+                    stmt.query_map([], |row| row.get::<_, String>(0))
+                        .map(|rows| rows.flatten().collect())
+                })
+                .unwrap_or_default();
+            let edges: Vec<(String, String)> = conn
+                .prepare("SELECT DISTINCT file_path, import_src FROM import_usage WHERE import_src LIKE '.%'")
+                .and_then(|mut stmt| {
+                    stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+                        .map(|rows| rows.flatten().collect())
+                })
+                .unwrap_or_default();
+            (files, edges)
+        };
+
+        let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (from, src) in edges {
+            if let Some(to) = resolve_import(&from, &src, &files)
+                && to != from
+            {
+                graph.entry(from).or_default().insert(to);
+            }
+        }
+        graph
+    }
+
+    /// Imports circulares entre archivos del proyecto, cada uno como lista ordenada de rutas
+    /// (`a.ts` importa `b.ts`, ..., el último importa `a.ts`).
+    pub fn find_import_cycles(&self) -> Vec<Vec<String>> {
+        log::debug!("Index query: find_import_cycles");
+        find_cycles(&self.import_graph(), MAX_CYCLE_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn seed(db: &IndexDb, imports: &[(&str, &str)]) {
+        let conn = db.lock();
+        for (file, _) in imports {
+            conn.execute(
+                "INSERT OR IGNORE INTO file_index (file_path, content_hash) VALUES (?, ?)",
+                params![file, "h"],
+            )
+            .unwrap();
+        }
+        for (file, src) in imports {
+            conn.execute(
+                "INSERT INTO import_usage (file_path, import_name, import_src) VALUES (?, ?, ?)",
+                params![file, "X", src],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_import_cycles_reports_abc_once() {
+        let f = NamedTempFile::new().unwrap();
+        let db = IndexDb::open(f.path()).unwrap();
+        seed(
+            &db,
+            &[
+                ("src/a.ts", "./b"),
+                ("src/b.ts", "./lib/c"),
+                ("src/lib/c.ts", "../a"),
+                ("src/lib/c.ts", "@nestjs/common"),
+            ],
+        );
+
+        let cycles = db.find_import_cycles();
+        assert_eq!(cycles, vec![vec!["src/a.ts", "src/b.ts", "src/lib/c.ts"]]);
+    }
+
+    #[test]
+    fn test_find_cycles_respects_max_len() {
+        let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let names: Vec<String> = (0..5).map(|i| format!("f{}.ts", i)).collect();
+        for i in 0..5 {
+            graph.entry(names[i].clone()).or_default().insert(names[(i + 1) % 5].clone());
+        }
+        assert_eq!(find_cycles(&graph, 5).len(), 1);
+        assert!(find_cycles(&graph, 4).is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Capas arquitecturales (controller → service → repository) a partir del grafo de imports.
+//!
+//! La capa de un archivo se deduce de su nombre o de sus carpetas (`user.controller.ts`,
+//! `controllers/users.ts`, `UserRepository.java`); los archivos sin capa reconocible no
+//! cuentan. Una dependencia es una violación si sube de capa (un repository que importa un
+//! service) o si se salta una (un controller que importa un repository directamente).
+
+use crate::index::db::IndexDb;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Controller,
+    Service,
+    Repository,
+}
+
+impl Layer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Controller => "controller",
+            Self::Service => "service",
+            Self::Repository => "repository",
+        }
+    }
+}
+
+/// Sufijos de cada capa, en singular y plural; se comparan con cada tramo de la ruta.
+const LAYER_SUFFIXES: &[(&str, Layer)] = &[
+    ("controller", Layer::Controller),
+    ("controllers", Layer::Controller),
+    ("service", Layer::Service),
+    ("services", Layer::Service),
+    ("repository", Layer::Repository),
+    ("repositories", Layer::Repository),
+    ("repo", Layer::Repository),
+    ("repos", Layer::Repository),
+];
+
+/// Capa de `path`. El nombre del archivo manda sobre las carpetas
+/// (`services/user.repository.ts` es un repository).
+pub fn layer_of(path: &str) -> Option<Layer> {
+    let lower = path.to_lowercase();
+    lower.rsplit(|c: char| !c.is_ascii_alphanumeric()).find_map(|part| {
+        LAYER_SUFFIXES.iter().find(|(suffix, _)| part.ends_with(suffix)).map(|(_, layer)| *layer)
+    })
+}
+
+/// Un import entre archivos de capas conocidas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerDependency {
+    pub from: String,
+    pub from_layer: Layer,
+    pub to: String,
+    pub to_layer: Layer,
+}
+
+impl LayerDependency {
+    /// Motivo por el que la dependencia rompe el orden de capas, si lo rompe.
+    pub fn violation(&self) -> Option<String> {
+        let (from, to) = (self.from_layer as usize, self.to_layer as usize);
+        if to < from {
+            Some(format!("dependencia invertida: {} no debería importar {}", self.from_layer.as_str(), self.to_layer.as_str()))
+        } else if to > from + 1 {
+            Some(format!("se salta la capa {}", Layer::Service.as_str()))
+        } else {
+            None
+        }
+    }
+}
+
+impl IndexDb {
+    /// Imports entre archivos de capas distintas, ordenados por archivo de origen.
+    pub fn layer_dependencies(&self) -> Vec<LayerDependency> {
+        log::debug!("Index query: layer_dependencies");
+        let mut deps = Vec::new();
+        for (from, targets) in self.import_graph() {
+            let Some(from_layer) = layer_of(&from) else { continue };
+            for to in targets {
+                if let Some(to_layer) = layer_of(&to)
+                    && to_layer != from_layer
+                {
+                    deps.push(LayerDependency { from: from.clone(), from_layer, to, to_layer });
+                }
+            }
+        }
+        deps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_of_file_name_wins_over_directory() {
+        assert_eq!(layer_of("src/users/user.controller.ts"), Some(Layer::Controller));
+        assert_eq!(layer_of("src/controllers/users.ts"), Some(Layer::Controller));
+        assert_eq!(layer_of("app/services/user_repository.py"), Some(Layer::Repository));
+        assert_eq!(layer_of("src/main/java/UserService.java"), Some(Layer::Service));
+        assert_eq!(layer_of("src/users/user.entity.ts"), None);
+
+        let dep = |from_layer, to_layer| LayerDependency { from: "a".into(), from_layer, to: "b".into(), to_layer };
+        assert!(dep(Layer::Controller, Layer::Service).violation().is_none());
+        assert!(dep(Layer::Controller, Layer::Repository).violation().unwrap().contains("se salta"));
+        assert!(dep(Layer::Repository, Layer::Service).violation().unwrap().contains("invertida"));
+    }
+}
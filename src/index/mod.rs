@@ -2,7 +2,11 @@ pub mod db;
 pub mod builder;
 pub mod symbol_table;
 pub mod call_graph;
+pub mod dot;
+pub mod embeddings;
+pub mod entry_points;
 pub mod import_index;
+pub mod layers;
 pub mod quality_history;
 
 pub use db::IndexDb;
@@ -1,6 +1,19 @@
 use crate::index::db::IndexDb;
 use rusqlite::params;
 
+/// Qué analizó una ejecución: el destino (relativo a la raíz) y los umbrales de complejidad y
+/// longitud. Solo se comparan ejecuciones con el mismo scope: `check src/a` y `check src`
+/// no miden lo mismo.
+pub fn run_scope(target: &str, rule_config: &crate::config::RuleConfig) -> String {
+    let target = target.trim().trim_start_matches("./").trim_end_matches('/');
+    format!(
+        "{}|complexity={}|function_length={}",
+        if target.is_empty() { "." } else { target },
+        rule_config.complexity_threshold,
+        rule_config.function_length_threshold
+    )
+}
+
 pub struct QualityHistory<'a> {
     db: &'a IndexDb,
 }
@@ -27,15 +40,160 @@ impl<'a> QualityHistory<'a> {
         Ok(())
     }
 
+    /// Registra las métricas de todos los archivos de una ejecución bajo un mismo
+    /// `run_id`, que es lo que `top_regressions` compara. Devuelve el id generado.
+    pub fn record_run(&self, metrics: &[FileMetrics], scope: &str) -> anyhow::Result<String> {
+        let run_id = uuid::Uuid::new_v4().to_string();
+        let mut conn = self.db.lock();
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO quality_history (run_id, scope, file_path, dead_functions, unused_imports, complexity_score, violations_count, tests_passing) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )?;
+            for m in metrics {
+                stmt.execute(params![
+                    run_id,
+                    scope,
+                    m.file_path,
+                    m.dead_functions,
+                    m.unused_imports,
+                    m.complexity_score,
+                    m.violations_count,
+                    m.tests_passing
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(run_id)
+    }
+
+    /// Archivos que más empeoraron entre la última ejecución registrada y la anterior con
+    /// el mismo scope (`complexity_score` o `violations_count` al alza). Un archivo presente
+    /// solo en una de las dos cuenta con métricas 0 en la otra. Vacío si no hay dos ejecuciones
+    /// comparables.
+    pub fn top_regressions(&self, limit: usize) -> anyhow::Result<Vec<FileRegression>> {
+        let conn = self.db.lock();
+        let latest: Option<(String, Option<String>)> = conn
+            .prepare("SELECT run_id, scope FROM quality_history WHERE run_id IS NOT NULL ORDER BY id DESC LIMIT 1")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .next()
+            .transpose()?;
+        let Some((latest, scope)) = latest else {
+            return Ok(Vec::new());
+        };
+        let previous: Option<String> = conn
+            .prepare(
+                "SELECT run_id FROM quality_history WHERE run_id IS NOT NULL AND run_id != ? AND scope IS ? \
+                 GROUP BY run_id ORDER BY MAX(id) DESC LIMIT 1",
+            )?
+            .query_map(params![latest, scope], |row| row.get(0))?
+            .next()
+            .transpose()?;
+        let Some(previous) = previous else {
+            return Ok(Vec::new());
+        };
+
+        let load = |run_id: &str| -> anyhow::Result<std::collections::HashMap<String, (f64, i32)>> {
+            let mut stmt = conn.prepare(
+                "SELECT file_path, complexity_score, violations_count FROM quality_history WHERE run_id = ?",
+            )?;
+            let rows = stmt.query_map(params![run_id], |row| {
+                Ok((row.get::<_, String>(0)?, (row.get::<_, f64>(1)?, row.get::<_, i32>(2)?)))
+            })?;
+            Ok(rows.collect::<rusqlite::Result<_>>()?)
+        };
+        let before = load(&previous)?;
+        let after = load(&latest)?;
+
+        let files: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        let mut regressions: Vec<FileRegression> = files
+            .into_iter()
+            .map(|file| {
+                let b = before.get(file);
+                let a = after.get(file);
+                FileRegression {
+                    file_path: file.clone(),
+                    complexity_before: b.map(|m| m.0).unwrap_or(0.0),
+                    complexity_after: a.map(|m| m.0).unwrap_or(0.0),
+                    violations_before: b.map(|m| m.1).unwrap_or(0),
+                    violations_after: a.map(|m| m.1).unwrap_or(0),
+                    status: match (b, a) {
+                        (None, _) => RegressionStatus::New,
+                        (_, None) => RegressionStatus::Deleted,
+                        _ => RegressionStatus::Changed,
+                    },
+                }
+            })
+            .filter(|r| r.complexity_delta() > 0.0 || r.violations_delta() > 0)
+            .collect();
+
+        regressions.sort_by(|x, y| {
+            y.score()
+                .partial_cmp(&x.score())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| x.file_path.cmp(&y.file_path))
+        });
+        regressions.truncate(limit);
+        Ok(regressions)
+    }
+
+    /// Guarda la puntuación global de una ejecución de `pro score` sobre `scope`.
+    pub fn record_score(&self, record: &ScoreRecord, scope: &str) -> anyhow::Result<()> {
+        let conn = self.db.lock();
+        conn.execute(
+            "INSERT INTO quality_scores (scope, score, grade, files, errors, warnings, infos, avg_complexity, coverage) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                scope,
+                record.score,
+                record.grade,
+                record.files as i64,
+                record.errors as i64,
+                record.warnings as i64,
+                record.infos as i64,
+                record.avg_complexity,
+                record.coverage
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Últimas `limit` puntuaciones (timestamp, score) de `scope`, de la más reciente a la
+    /// más antigua.
+    pub fn score_history(&self, limit: usize, scope: &str) -> anyhow::Result<Vec<(String, f64)>> {
+        let conn = self.db.lock();
+        let mut stmt =
+            conn.prepare("SELECT timestamp, score FROM quality_scores WHERE scope = ? ORDER BY id DESC LIMIT ?")?;
+        let rows = stmt.query_map(params![scope, limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Violaciones totales de las últimas `limit` ejecuciones sobre `scope` (timestamp, total),
+    /// de la más antigua a la más reciente. Los registros sin `run_id` o sin scope (previos a
+    /// las migraciones) no cuentan.
+    pub fn violations_per_run(&self, limit: usize, scope: &str) -> anyhow::Result<Vec<(String, i64)>> {
+        let conn = self.db.lock();
+        let mut stmt = conn.prepare(
+            "SELECT MIN(timestamp), SUM(violations_count) FROM quality_history \
+             WHERE run_id IS NOT NULL AND scope = ? GROUP BY run_id ORDER BY MIN(id) DESC LIMIT ?",
+        )?;
+        let rows = stmt.query_map(params![scope, limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        let mut runs: Vec<(String, i64)> = rows.collect::<rusqlite::Result<_>>()?;
+        runs.reverse();
+        Ok(runs)
+    }
+
     pub fn get_history(&self, file_path: &str) -> anyhow::Result<Vec<QualitySnapshot>> {
         let conn = self.db.lock();
-        let mut stmt = conn.prepare("SELECT timestamp, dead_functions, unused_imports, complexity_score FROM quality_history WHERE file_path = ? ORDER BY timestamp DESC")?;
+        let mut stmt = conn.prepare("SELECT timestamp, dead_functions, unused_imports, complexity_score, violations_count FROM quality_history WHERE file_path = ? ORDER BY timestamp DESC")?;
         let rows = stmt.query_map(params![file_path], |row| {
             Ok(QualitySnapshot {
                 timestamp: row.get(0)?,
                 dead_functions: row.get(1)?,
                 unused_imports: row.get(2)?,
                 complexity_score: row.get(3)?,
+                violations_count: row.get(4)?,
             })
         })?;
 
@@ -45,6 +203,75 @@ impl<'a> QualityHistory<'a> {
         }
         Ok(results)
     }
+
+    /// Tendencia de calidad desde `since` hasta ahora.
+    /// Por archivo compara el registro más antiguo de la ventana con el más reciente
+    /// y suma los totales. Devuelve `None` si no hay historial en la ventana.
+    pub fn get_trend(&self, since: chrono::Duration) -> anyhow::Result<Option<QualityTrend>> {
+        self.get_trend_from(chrono::Utc::now() - since)
+    }
+
+    pub fn get_trend_from(&self, cutoff: chrono::DateTime<chrono::Utc>) -> anyhow::Result<Option<QualityTrend>> {
+        // `timestamp` usa CURRENT_TIMESTAMP de SQLite: "YYYY-MM-DD HH:MM:SS" en UTC
+        let cutoff_str = cutoff.format("%Y-%m-%d %H:%M:%S").to_string();
+        let conn = self.db.lock();
+        let mut stmt = conn.prepare(
+            "SELECT file_path, timestamp, dead_functions, unused_imports, violations_count \
+             FROM quality_history WHERE timestamp >= ? ORDER BY timestamp ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![cutoff_str], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i32>(2)?,
+                row.get::<_, i32>(3)?,
+                row.get::<_, i32>(4)?,
+            ))
+        })?;
+
+        // file_path → (primer registro, último registro) como (violations, dead, unused)
+        type Metrics = (i32, i32, i32);
+        let mut per_file: std::collections::HashMap<String, (Metrics, Metrics)> =
+            std::collections::HashMap::new();
+        let mut records = 0usize;
+        let mut oldest: Option<String> = None;
+        let mut newest: Option<String> = None;
+
+        for row in rows {
+            let (file, ts, dead, unused, violations) = row?;
+            let metrics = (violations, dead, unused);
+            per_file
+                .entry(file)
+                .and_modify(|(_, last)| *last = metrics)
+                .or_insert((metrics, metrics));
+            if oldest.is_none() {
+                oldest = Some(ts.clone());
+            }
+            newest = Some(ts);
+            records += 1;
+        }
+
+        let (Some(oldest), Some(newest)) = (oldest, newest) else {
+            return Ok(None);
+        };
+
+        let mut trend = QualityTrend {
+            oldest_timestamp: oldest,
+            newest_timestamp: newest,
+            records,
+            files: per_file.len(),
+            ..Default::default()
+        };
+        for (first, last) in per_file.values() {
+            trend.violations_before += first.0 as i64;
+            trend.violations_after += last.0 as i64;
+            trend.dead_functions_before += first.1 as i64;
+            trend.dead_functions_after += last.1 as i64;
+            trend.unused_imports_before += first.2 as i64;
+            trend.unused_imports_after += last.2 as i64;
+        }
+        Ok(Some(trend))
+    }
 }
 
 pub struct FileMetrics {
@@ -56,9 +283,218 @@ pub struct FileMetrics {
     pub tests_passing: bool,
 }
 
+/// Entradas y resultado de una ejecución de `pro score`.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ScoreRecord {
+    pub score: f64,
+    pub grade: String,
+    pub files: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    pub avg_complexity: f64,
+    pub coverage: f64,
+}
+
 pub struct QualitySnapshot {
     pub timestamp: String,
     pub dead_functions: i32,
     pub unused_imports: i32,
     pub complexity_score: f64,
+    pub violations_count: i32,
+}
+
+/// Totales al inicio y al final de una ventana de tiempo del historial.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct QualityTrend {
+    pub oldest_timestamp: String,
+    pub newest_timestamp: String,
+    pub records: usize,
+    pub files: usize,
+    pub violations_before: i64,
+    pub violations_after: i64,
+    pub dead_functions_before: i64,
+    pub dead_functions_after: i64,
+    pub unused_imports_before: i64,
+    pub unused_imports_after: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegressionStatus {
+    /// Solo aparece en la ejecución más reciente
+    New,
+    /// Solo aparece en la ejecución anterior
+    Deleted,
+    Changed,
+}
+
+/// Métricas de un archivo en las dos últimas ejecuciones.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileRegression {
+    pub file_path: String,
+    pub complexity_before: f64,
+    pub complexity_after: f64,
+    pub violations_before: i32,
+    pub violations_after: i32,
+    pub status: RegressionStatus,
+}
+
+impl FileRegression {
+    pub fn complexity_delta(&self) -> f64 {
+        self.complexity_after - self.complexity_before
+    }
+
+    pub fn violations_delta(&self) -> i32 {
+        self.violations_after - self.violations_before
+    }
+
+    /// Criterio de orden: suma de ambos empeoramientos (las mejoras no compensan).
+    fn score(&self) -> f64 {
+        self.complexity_delta().max(0.0) + self.violations_delta().max(0) as f64
+    }
+}
+
+impl QualityTrend {
+    pub fn violations_delta(&self) -> i64 {
+        self.violations_after - self.violations_before
+    }
+
+    pub fn dead_functions_delta(&self) -> i64 {
+        self.dead_functions_after - self.dead_functions_before
+    }
+
+    pub fn unused_imports_delta(&self) -> i64 {
+        self.unused_imports_after - self.unused_imports_before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn insert_at(db: &IndexDb, ts: &str, file: &str, dead: i32, unused: i32, violations: i32) {
+        db.lock()
+            .execute(
+                "INSERT INTO quality_history (timestamp, file_path, dead_functions, unused_imports, violations_count) \
+                 VALUES (?, ?, ?, ?, ?)",
+                params![ts, file, dead, unused, violations],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_trend_delta_between_two_days() {
+        let tmp = TempDir::new().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        let now = chrono::Utc::now();
+        let fmt = |d: chrono::DateTime<chrono::Utc>| d.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        insert_at(&db, &fmt(now - chrono::Duration::days(2)), "src/a.ts", 4, 3, 10);
+        insert_at(&db, &fmt(now - chrono::Duration::days(1)), "src/a.ts", 1, 2, 6);
+
+        let history = QualityHistory::new(&db);
+        let trend = history
+            .get_trend(chrono::Duration::days(7))
+            .unwrap()
+            .expect("two rows inside the window");
+        assert_eq!(trend.records, 2);
+        assert_eq!(trend.violations_delta(), -4);
+        assert_eq!(trend.dead_functions_delta(), -3);
+        assert_eq!(trend.unused_imports_delta(), -1);
+    }
+
+    #[test]
+    fn test_trend_none_when_window_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        let old = (chrono::Utc::now() - chrono::Duration::days(30))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        insert_at(&db, &old, "src/a.ts", 1, 1, 1);
+
+        let history = QualityHistory::new(&db);
+        assert!(history.get_trend(chrono::Duration::days(7)).unwrap().is_none());
+    }
+
+    fn metrics(file: &str, complexity: f64, violations: i32) -> FileMetrics {
+        FileMetrics {
+            file_path: file.to_string(),
+            dead_functions: 0,
+            unused_imports: 0,
+            complexity_score: complexity,
+            violations_count: violations,
+            tests_passing: true,
+        }
+    }
+
+    #[test]
+    fn test_top_regressions_ranks_files_between_last_two_runs() {
+        let tmp = TempDir::new().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        let history = QualityHistory::new(&db);
+
+        // Una ejecución antigua que no debe participar en la comparación
+        history.record_run(&[metrics("src/a.ts", 100.0, 100)], "src").unwrap();
+        history
+            .record_run(&[
+                metrics("src/a.ts", 10.0, 2),
+                metrics("src/b.ts", 5.0, 1),
+                metrics("src/c.ts", 8.0, 4),
+                metrics("src/gone.ts", 3.0, 3),
+            ], "src")
+            .unwrap();
+        history
+            .record_run(&[
+                metrics("src/a.ts", 12.0, 3),  // +2 complejidad, +1 violación → 3
+                metrics("src/b.ts", 5.0, 7),   // +6 violaciones → 6
+                metrics("src/c.ts", 6.0, 2),   // mejora: fuera del ranking
+                metrics("src/new.ts", 4.0, 0), // nuevo: +4 complejidad → 4
+            ], "src")
+            .unwrap();
+
+        let regressions = history.top_regressions(10).unwrap();
+        let order: Vec<&str> = regressions.iter().map(|r| r.file_path.as_str()).collect();
+        assert_eq!(order, vec!["src/b.ts", "src/new.ts", "src/a.ts"]);
+        assert_eq!(regressions[1].status, RegressionStatus::New);
+        assert_eq!(regressions[1].complexity_before, 0.0);
+        assert_eq!(regressions[2].violations_delta(), 1);
+
+        assert_eq!(history.top_regressions(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_top_regressions_needs_two_runs() {
+        let tmp = TempDir::new().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        let history = QualityHistory::new(&db);
+        // Registros sin run_id (record_metrics) no cuentan como ejecución
+        history.record_metrics(&metrics("src/a.ts", 1.0, 1)).unwrap();
+        history.record_run(&[metrics("src/a.ts", 9.0, 9)], "src").unwrap();
+        assert!(history.top_regressions(5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_top_regressions_ignores_runs_with_another_scope() {
+        let tmp = TempDir::new().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        let history = QualityHistory::new(&db);
+        let src = run_scope("./src/", &crate::config::RuleConfig::default());
+        assert_eq!(src, run_scope("src", &crate::config::RuleConfig::default()));
+
+        history.record_run(&[metrics("src/a.ts", 5.0, 1), metrics("src/b.ts", 5.0, 1)], &src).unwrap();
+        // `check src/a` en medio: no se compara con las ejecuciones de `src`
+        history.record_run(&[metrics("src/a/x.ts", 1.0, 0)], "src/a|complexity=0|function_length=0").unwrap();
+        history.record_run(&[metrics("src/a.ts", 5.0, 1), metrics("src/b.ts", 5.0, 4)], &src).unwrap();
+
+        let regressions = history.top_regressions(10).unwrap();
+        let order: Vec<&str> = regressions.iter().map(|r| r.file_path.as_str()).collect();
+        assert_eq!(order, vec!["src/b.ts"]);
+        assert_eq!(history.violations_per_run(10, &src).unwrap().len(), 2);
+
+        // Cambiar un umbral también cambia el scope
+        let stricter = crate::config::RuleConfig { complexity_threshold: 3, ..Default::default() };
+        assert_ne!(run_scope("src", &stricter), src);
+    }
 }
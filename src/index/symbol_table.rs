@@ -1,5 +1,8 @@
 use crate::index::db::IndexDb;
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
 
 pub struct SymbolTable<'a> {
     db: &'a IndexDb,
@@ -11,6 +14,7 @@ impl<'a> SymbolTable<'a> {
     }
 
     pub fn find_symbol(&self, name: &str) -> anyhow::Result<Vec<SymbolInfo>> {
+        log::debug!("Index query: find_symbol({})", name);
         let conn = self.db.lock();
         let mut stmt = conn.prepare("SELECT name, kind, file_path, line_start FROM symbols WHERE name = ?")?;
         let rows = stmt.query_map(params![name], |row| {
@@ -30,6 +34,7 @@ impl<'a> SymbolTable<'a> {
     }
 
     pub fn get_file_symbols(&self, file_path: &str) -> anyhow::Result<Vec<SymbolInfo>> {
+        log::debug!("Index query: get_file_symbols({})", file_path);
         let conn = self.db.lock();
         let mut stmt = conn.prepare("SELECT name, kind, file_path, line_start FROM symbols WHERE file_path = ?")?;
         let rows = stmt.query_map(params![file_path], |row| {
@@ -47,6 +52,65 @@ impl<'a> SymbolTable<'a> {
         }
         Ok(results)
     }
+
+    /// Escribe la tabla de símbolos completa como JSON (`{"symbols": [...], "summary": {...}}`)
+    /// fila a fila en `writer`, sin cargarla en memoria (`sentinel index --export-symbols`).
+    pub fn export_json<W: Write>(&self, mut writer: W) -> anyhow::Result<SymbolExportSummary> {
+        let conn = self.db.lock();
+        let mut stmt = conn.prepare(
+            "SELECT name, kind, file_path, line_start, COALESCE(exported, 0) FROM symbols \
+             ORDER BY file_path, line_start, name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ExportedSymbol {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                file: row.get(2)?,
+                line: row.get::<_, Option<i64>>(3)?.map(|l| l as usize + 1),
+                exported: row.get(4)?,
+            })
+        })?;
+
+        let mut summary = SymbolExportSummary::default();
+        let mut last_file: Option<String> = None;
+        writer.write_all(b"{\n  \"symbols\": [")?;
+        for row in rows {
+            let symbol = row?;
+            writer.write_all(if summary.symbols == 0 { b"\n    " } else { b",\n    " })?;
+            serde_json::to_writer(&mut writer, &symbol)?;
+            summary.symbols += 1;
+            summary.exported += usize::from(symbol.exported);
+            *summary.by_kind.entry(symbol.kind).or_default() += 1;
+            // Filas ordenadas por archivo: basta con contar los cambios
+            if last_file.as_deref() != Some(symbol.file.as_str()) {
+                summary.files += 1;
+                last_file = Some(symbol.file);
+            }
+        }
+        writer.write_all(if summary.symbols == 0 { b"],\n  \"summary\": " } else { b"\n  ],\n  \"summary\": " })?;
+        serde_json::to_writer(&mut writer, &summary)?;
+        writer.write_all(b"\n}\n")?;
+        writer.flush()?;
+        Ok(summary)
+    }
+}
+
+/// Fila de `symbols` en `--export-symbols` (`line` 1-based).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedSymbol {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub line: Option<usize>,
+    pub exported: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolExportSummary {
+    pub symbols: usize,
+    pub exported: usize,
+    pub files: usize,
+    pub by_kind: BTreeMap<String, usize>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -56,3 +120,65 @@ pub struct SymbolInfo {
     pub file_path: String,
     pub line_start: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Export {
+        symbols: Vec<ExportedSymbol>,
+        summary: SymbolExportSummary,
+    }
+
+    #[test]
+    fn test_export_json_round_trips_symbol_records() {
+        let tmp = tempfile::tempdir().unwrap();
+        let db = IndexDb::open(tmp.path().join("index.db")).unwrap();
+        let empty: Export = serde_json::from_slice(&{
+            let mut buf = Vec::new();
+            SymbolTable::new(&db).export_json(&mut buf).unwrap();
+            buf
+        })
+        .unwrap();
+        assert!(empty.symbols.is_empty());
+        assert_eq!(empty.summary, SymbolExportSummary::default());
+
+        {
+            let conn = db.lock();
+            for (name, kind, file, line, exported) in [
+                ("UserService", "class", "src/users/user.service.ts", 4, true),
+                ("hashPassword", "function", "src/auth/crypto.ts", 0, true),
+                ("salt", "function", "src/auth/crypto.ts", 9, false),
+            ] {
+                conn.execute(
+                    "INSERT INTO symbols (name, kind, file_path, line_start, exported) VALUES (?, ?, ?, ?, ?)",
+                    params![name, kind, file, line, exported],
+                )
+                .unwrap();
+            }
+        }
+
+        let mut buf = Vec::new();
+        let summary = SymbolTable::new(&db).export_json(&mut buf).unwrap();
+        let export: Export = serde_json::from_slice(&buf).unwrap();
+        let symbol = |name: &str, kind: &str, file: &str, line: usize, exported: bool| ExportedSymbol {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            file: file.to_string(),
+            line: Some(line),
+            exported,
+        };
+        assert_eq!(
+            export.symbols,
+            vec![
+                symbol("hashPassword", "function", "src/auth/crypto.ts", 1, true),
+                symbol("salt", "function", "src/auth/crypto.ts", 10, false),
+                symbol("UserService", "class", "src/users/user.service.ts", 5, true),
+            ]
+        );
+        assert_eq!(export.summary, summary);
+        assert_eq!((summary.symbols, summary.exported, summary.files), (3, 2, 2));
+        assert_eq!(summary.by_kind, BTreeMap::from([("class".to_string(), 1), ("function".to_string(), 2)]));
+    }
+}
@@ -0,0 +1,256 @@
+//! Sincronización incremental de la Knowledge Base.
+//!
+//! Cada fragmento (`index::embeddings::code_chunks_with_lines`) es un punto de Qdrant cuyo
+//! id se deriva de (archivo, línea de inicio). `kb_chunks` en `index.db` guarda el hash
+//! del contenido publicado de cada punto, así que una sincronización solo re-embebe y
+//! re-publica los fragmentos cuyo hash cambió y borra los que ya no existen.
+
+use crate::index::db::{IndexDb, KbSyncStats};
+use crate::index::embeddings::{chunk_hash, code_chunks_with_lines, embed_with_cache, model_key, Embedder};
+use crate::kb::vector_db::{ensure_collection, CollectionAdmin, VectorDB};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Fragmentos por llamada al proveedor de embeddings y por upsert.
+const BATCH: usize = 32;
+
+/// Punto a publicar: el payload sigue el formato que lee `VectorDB::search`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KbPoint {
+    pub id: u64,
+    pub vector: Vec<f32>,
+    pub file_path: String,
+    pub line: usize,
+    pub content: String,
+}
+
+/// Escritura de puntos (separado de `VectorStore` para mockear Qdrant en tests).
+pub trait PointStore {
+    fn upsert(&self, points: &[KbPoint]) -> anyhow::Result<()>;
+    fn delete(&self, ids: &[u64]) -> anyhow::Result<()>;
+}
+
+/// Id estable del punto de un fragmento: prefijo de SHA-256, igual entre versiones de Rust
+/// (a diferencia de `DefaultHasher`) y dentro del INTEGER de SQLite.
+pub fn point_id(file_path: &str, line: usize) -> u64 {
+    let digest = Sha256::digest(format!("{}:{}", file_path, line).as_bytes());
+    let prefix: [u8; 8] = digest[..8].try_into().expect("SHA-256 tiene 32 bytes");
+    u64::from_be_bytes(prefix) & (i64::MAX as u64)
+}
+
+struct Chunk {
+    id: u64,
+    file_path: String,
+    line: usize,
+    hash: String,
+    content: String,
+}
+
+pub struct KBManager<'a> {
+    db: &'a IndexDb,
+    collection: String,
+    model: String,
+}
+
+impl<'a> KBManager<'a> {
+    /// `model`: clave del modelo de embeddings (`index::embeddings::model_key`).
+    pub fn new(db: &'a IndexDb, collection: &str, model: &str) -> Self {
+        Self { db, collection: collection.to_string(), model: model.to_string() }
+    }
+
+    /// Publica en `store` los fragmentos de `files` que cambiaron desde la última
+    /// sincronización y borra los que desaparecieron. `files` es el conjunto completo del
+    /// proyecto: cualquier punto publicado fuera de él se considera borrado.
+    pub fn sync<S: CollectionAdmin + PointStore>(
+        &self,
+        store: &S,
+        root: &Path,
+        files: &[PathBuf],
+        embed: &mut Embedder,
+    ) -> anyhow::Result<KbSyncStats> {
+        let published = self.db.kb_chunk_hashes()?;
+
+        let mut current: HashMap<u64, Chunk> = HashMap::new();
+        for file in files {
+            let Ok(content) = std::fs::read_to_string(file) else { continue };
+            let rel = file.strip_prefix(root).unwrap_or(file).to_string_lossy().replace('\\', "/");
+            for (line, text) in code_chunks_with_lines(&content) {
+                let id = point_id(&rel, line);
+                current.insert(id, Chunk { id, file_path: rel.clone(), line, hash: chunk_hash(&text), content: text });
+            }
+        }
+
+        let mut stats = KbSyncStats::default();
+        let mut changed: Vec<&Chunk> = Vec::new();
+        for chunk in current.values() {
+            match published.get(&chunk.id) {
+                Some(hash) if *hash == chunk.hash => stats.unchanged += 1,
+                Some(_) => {
+                    stats.updated += 1;
+                    changed.push(chunk);
+                }
+                None => {
+                    stats.added += 1;
+                    changed.push(chunk);
+                }
+            }
+        }
+        changed.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+        let mut deleted: Vec<u64> = published.keys().filter(|id| !current.contains_key(id)).copied().collect();
+        deleted.sort_unstable();
+        stats.deleted = deleted.len();
+
+        let mut ensured = false;
+        for batch in changed.chunks(BATCH) {
+            let texts: Vec<String> = batch.iter().map(|c| c.content.clone()).collect();
+            let vectors = embed_with_cache(self.db, &self.model, &texts, embed)?.vectors;
+            if !ensured && let Some(first) = vectors.first() {
+                ensure_collection(store, &self.collection, first.len() as u64)?;
+                ensured = true;
+            }
+            let points: Vec<KbPoint> = batch
+                .iter()
+                .zip(vectors)
+                .map(|(c, vector)| KbPoint {
+                    id: c.id,
+                    vector,
+                    file_path: c.file_path.clone(),
+                    line: c.line,
+                    content: c.content.clone(),
+                })
+                .collect();
+            store.upsert(&points)?;
+        }
+        if !deleted.is_empty() {
+            store.delete(&deleted)?;
+        }
+
+        let upserts: Vec<(u64, String, usize, String)> =
+            changed.iter().map(|c| (c.id, c.file_path.clone(), c.line, c.hash.clone())).collect();
+        self.db.apply_kb_sync(&upserts, &deleted, &stats)?;
+        Ok(stats)
+    }
+}
+
+/// Sincroniza la KB del proyecto (colección `sentinel_<proyecto>` en `QDRANT_URL`) con el
+/// modelo de embeddings principal. Usado por la tecla `k` de `sentinel monitor`.
+pub fn sync_project(config: &crate::config::SentinelConfig, root: &Path, db: &IndexDb) -> anyhow::Result<KbSyncStats> {
+    if !config.features.as_ref().map(|f| f.enable_knowledge_base).unwrap_or(true) {
+        anyhow::bail!("la Knowledge Base está desactivada (features.enable_knowledge_base)");
+    }
+    let mut files = Vec::new();
+    let walker = ignore::WalkBuilder::new(root).hidden(false).git_ignore(true).build();
+    for entry in walker.flatten() {
+        let p = entry.path();
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if p.is_file() && config.file_extensions.iter().any(|e| e == ext) {
+            files.push(p.to_path_buf());
+        }
+    }
+    let (files, _) = crate::files::filtrar_por_tamano(files, config.max_file_bytes);
+
+    let store = VectorDB::for_project(&config.project_name);
+    let model = &config.primary_model;
+    let mut embed = |texts: Vec<String>| crate::ai::obtener_embeddings(texts, model);
+    KBManager::new(db, store.collection(), &model_key(model)).sync(&store, root, &files, &mut embed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Qdrant simulado: puntos vigentes y colecciones creadas.
+    #[derive(Default)]
+    struct MockStore {
+        points: RefCell<HashMap<u64, KbPoint>>,
+        created: RefCell<Vec<u64>>,
+    }
+
+    impl CollectionAdmin for MockStore {
+        fn collection_dimension(&self, _name: &str) -> anyhow::Result<Option<u64>> {
+            Ok(self.created.borrow().first().copied())
+        }
+        fn create_collection(&self, _name: &str, dim: u64) -> anyhow::Result<()> {
+            self.created.borrow_mut().push(dim);
+            Ok(())
+        }
+    }
+
+    impl PointStore for MockStore {
+        fn upsert(&self, points: &[KbPoint]) -> anyhow::Result<()> {
+            let mut stored = self.points.borrow_mut();
+            for p in points {
+                stored.insert(p.id, p.clone());
+            }
+            Ok(())
+        }
+        fn delete(&self, ids: &[u64]) -> anyhow::Result<()> {
+            let mut stored = self.points.borrow_mut();
+            for id in ids {
+                stored.remove(id);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sync_only_reembeds_changed_chunks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let write = |rel: &str, body: &str| {
+            let p = root.join(rel);
+            std::fs::create_dir_all(p.parent().unwrap()).unwrap();
+            std::fs::write(&p, body).unwrap();
+            p
+        };
+        let long: String = (0..80).map(|i| format!("export const v{} = {};\n", i, i)).collect();
+        let users = write("src/users.ts", &long);
+        let orders = write("src/orders.ts", "export const total = 1;\n");
+        let db = IndexDb::open(root.join(".sentinel/index.db")).unwrap();
+        let store = MockStore::default();
+        let kb = KBManager::new(&db, "sentinel_test", "local:minilm");
+
+        let embedded: RefCell<Vec<String>> = RefCell::new(Vec::new());
+        let mut embed = |texts: Vec<String>| -> anyhow::Result<Vec<Vec<f32>>> {
+            embedded.borrow_mut().extend(texts.iter().cloned());
+            Ok(texts.iter().map(|t| vec![t.len() as f32, 1.0, 0.0]).collect())
+        };
+
+        // Primera sincronización: users.ts tiene 2 fragmentos (80 líneas), orders.ts 1
+        let first = kb.sync(&store, root, &[users.clone(), orders.clone()], &mut embed).unwrap();
+        assert_eq!(first, KbSyncStats { added: 3, updated: 0, deleted: 0, unchanged: 0 });
+        assert_eq!(*store.created.borrow(), vec![3]);
+        assert_eq!(store.points.borrow().len(), 3);
+
+        // Cambia solo el segundo fragmento de users.ts y orders.ts desaparece
+        embedded.borrow_mut().clear();
+        write("src/users.ts", &long.replace("v70 = 70", "v70 = 700"));
+        let second = kb.sync(&store, root, std::slice::from_ref(&users), &mut embed).unwrap();
+        assert_eq!(second, KbSyncStats { added: 0, updated: 1, deleted: 1, unchanged: 1 });
+        assert_eq!(embedded.borrow().len(), 1, "only the edited chunk is re-embedded");
+        assert!(embedded.borrow()[0].contains("v70 = 700"));
+        let points = store.points.borrow();
+        assert_eq!(points.len(), 2);
+        assert!(points.values().all(|p| p.file_path == "src/users.ts"));
+        assert!(points[&point_id("src/users.ts", 61)].content.contains("v70 = 700"));
+        drop(points);
+
+        // Sin cambios: nada que publicar; --stats ve la última sincronización
+        embedded.borrow_mut().clear();
+        let third = kb.sync(&store, root, &[users], &mut embed).unwrap();
+        assert_eq!(third, KbSyncStats { added: 0, updated: 0, deleted: 0, unchanged: 2 });
+        assert!(embedded.borrow().is_empty());
+        let stats = db.stats(5);
+        assert_eq!((stats.kb_chunks, stats.last_kb_sync), (2, Some(third)));
+        assert_eq!(*store.created.borrow(), vec![3], "collection is created once");
+    }
+
+    #[test]
+    fn test_point_id_is_stable_across_builds() {
+        // Un id distinto tras actualizar el toolchain re-publicaría toda la colección
+        assert_eq!(point_id("src/users.ts", 1), 3313411945479539544);
+        assert_ne!(point_id("src/users.ts", 1), point_id("src/users.ts", 2));
+    }
+}
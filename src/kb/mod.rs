@@ -0,0 +1,8 @@
+//! Knowledge Base (Capa 2): vectores de código en Qdrant para búsqueda semántica.
+
+pub mod manager;
+pub mod vector_db;
+
+pub use crate::index::db::KbSyncStats;
+pub use manager::{KBManager, KbPoint, PointStore, sync_project};
+pub use vector_db::{CollectionAdmin, CollectionStatus, SearchHit, VectorDB, VectorStore, ensure_collection};
@@ -0,0 +1,276 @@
+//! Cliente mínimo de la API REST de Qdrant.
+//!
+//! Cada punto de la colección guarda en su payload `file_path`, `line` y `content`
+//! (el fragmento de código embebido).
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Endpoint de Qdrant cuando `QDRANT_URL` no está definido
+pub const DEFAULT_QDRANT_URL: &str = "http://localhost:6333";
+
+pub fn qdrant_url() -> String {
+    std::env::var("QDRANT_URL").unwrap_or_else(|_| DEFAULT_QDRANT_URL.to_string())
+}
+
+/// Nombre de la colección del proyecto: `sentinel_<proyecto>` en minúsculas.
+pub fn collection_name(project_name: &str) -> String {
+    let slug: String = project_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("sentinel_{}", slug.trim_matches('_'))
+}
+
+/// Resultado de una búsqueda. `score` es `None` cuando viene del fallback por texto.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    pub file_path: String,
+    pub line: usize,
+    pub snippet: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+/// Operaciones de la KB que usan los comandos (permite mockear Qdrant en tests).
+pub trait VectorStore {
+    /// Puntos almacenados en la colección.
+    fn count(&self) -> anyhow::Result<u64>;
+    /// Vecinos más cercanos a `vector`, de mayor a menor score.
+    fn search(&self, vector: &[f32], limit: usize) -> anyhow::Result<Vec<SearchHit>>;
+}
+
+/// Alta y consulta de colecciones (separado de `VectorStore` para mockear Qdrant en tests).
+pub trait CollectionAdmin {
+    /// Dimensión de los vectores de `name`; `None` si la colección no existe.
+    fn collection_dimension(&self, name: &str) -> anyhow::Result<Option<u64>>;
+    /// Crea `name` con vectores de `dim` dimensiones y distancia coseno.
+    fn create_collection(&self, name: &str, dim: u64) -> anyhow::Result<()>;
+}
+
+/// Resultado de `ensure_collection`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollectionStatus {
+    Created,
+    Existing,
+}
+
+/// Crea la colección si no existe; si existe, exige que sus vectores tengan `dim`
+/// dimensiones (`ModelConfig::embedding_dimension()` del modelo de embeddings).
+/// Llamar antes del primer upsert.
+pub fn ensure_collection(admin: &dyn CollectionAdmin, name: &str, dim: u64) -> anyhow::Result<CollectionStatus> {
+    match admin.collection_dimension(name)? {
+        None => {
+            admin.create_collection(name, dim)?;
+            Ok(CollectionStatus::Created)
+        }
+        Some(existing) if existing == dim => Ok(CollectionStatus::Existing),
+        Some(existing) => anyhow::bail!(
+            "la colección '{}' tiene vectores de {} dimensiones pero el modelo de embeddings genera {}: \
+             bórrala en Qdrant o cambia de modelo",
+            name,
+            existing,
+            dim
+        ),
+    }
+}
+
+pub struct VectorDB {
+    url: String,
+    collection: String,
+    client: reqwest::blocking::Client,
+}
+
+#[derive(Deserialize)]
+struct QdrantResponse<T> {
+    result: T,
+}
+
+#[derive(Deserialize)]
+struct CollectionInfo {
+    config: CollectionConfig,
+}
+
+#[derive(Deserialize)]
+struct CollectionConfig {
+    params: CollectionParams,
+}
+
+#[derive(Deserialize)]
+struct CollectionParams {
+    /// `{ "size": N, ... }`, o un mapa de vectores con nombre
+    vectors: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct CountResult {
+    count: u64,
+}
+
+#[derive(Deserialize)]
+struct ScoredPoint {
+    score: f32,
+    #[serde(default)]
+    payload: serde_json::Value,
+}
+
+impl VectorDB {
+    pub fn new(url: &str, collection: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            collection: collection.to_string(),
+            client: crate::ai::providers::http_client(std::time::Duration::from_secs(10)),
+        }
+    }
+
+    /// Colección del proyecto en el Qdrant de `QDRANT_URL`.
+    pub fn for_project(project_name: &str) -> Self {
+        Self::new(&qdrant_url(), &collection_name(project_name))
+    }
+
+    pub fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    fn post<T: for<'de> Deserialize<'de>>(&self, path: &str, body: serde_json::Value) -> anyhow::Result<T> {
+        let url = format!("{}/collections/{}/{}", self.url, self.collection, path);
+        let res = self.client.post(&url).json(&body).send()?;
+        if !res.status().is_success() {
+            anyhow::bail!("Qdrant {} respondió {}", url, res.status());
+        }
+        Ok(res.json::<QdrantResponse<T>>()?.result)
+    }
+}
+
+impl CollectionAdmin for VectorDB {
+    fn collection_dimension(&self, name: &str) -> anyhow::Result<Option<u64>> {
+        let url = format!("{}/collections/{}", self.url, name);
+        let res = self.client.get(&url).send()?;
+        if res.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !res.status().is_success() {
+            anyhow::bail!("Qdrant {} respondió {}", url, res.status());
+        }
+        let vectors = res.json::<QdrantResponse<CollectionInfo>>()?.result.config.params.vectors;
+        // Vectores con nombre: se usa el primero (Sentinel crea colecciones con uno solo)
+        let size = vectors["size"]
+            .as_u64()
+            .or_else(|| vectors.as_object()?.values().find_map(|v| v["size"].as_u64()));
+        match size {
+            Some(size) => Ok(Some(size)),
+            None => anyhow::bail!("no se pudo leer la dimensión de la colección '{}'", name),
+        }
+    }
+
+    fn create_collection(&self, name: &str, dim: u64) -> anyhow::Result<()> {
+        let url = format!("{}/collections/{}", self.url, name);
+        let res = self
+            .client
+            .put(&url)
+            .json(&json!({ "vectors": { "size": dim, "distance": "Cosine" } }))
+            .send()?;
+        if !res.status().is_success() {
+            anyhow::bail!("Qdrant {} respondió {}", url, res.status());
+        }
+        Ok(())
+    }
+}
+
+impl crate::kb::manager::PointStore for VectorDB {
+    fn upsert(&self, points: &[crate::kb::manager::KbPoint]) -> anyhow::Result<()> {
+        let url = format!("{}/collections/{}/points?wait=true", self.url, self.collection);
+        let points: Vec<serde_json::Value> = points
+            .iter()
+            .map(|p| {
+                json!({
+                    "id": p.id,
+                    "vector": p.vector,
+                    "payload": { "file_path": p.file_path, "line": p.line, "content": p.content },
+                })
+            })
+            .collect();
+        let res = self.client.put(&url).json(&json!({ "points": points })).send()?;
+        if !res.status().is_success() {
+            anyhow::bail!("Qdrant {} respondió {}", url, res.status());
+        }
+        Ok(())
+    }
+
+    fn delete(&self, ids: &[u64]) -> anyhow::Result<()> {
+        let _: serde_json::Value = self.post("points/delete?wait=true", json!({ "points": ids }))?;
+        Ok(())
+    }
+}
+
+impl VectorStore for VectorDB {
+    fn count(&self) -> anyhow::Result<u64> {
+        let r: CountResult = self.post("points/count", json!({ "exact": true }))?;
+        Ok(r.count)
+    }
+
+    fn search(&self, vector: &[f32], limit: usize) -> anyhow::Result<Vec<SearchHit>> {
+        let points: Vec<ScoredPoint> = self.post(
+            "points/search",
+            json!({ "vector": vector, "limit": limit, "with_payload": true }),
+        )?;
+        Ok(points
+            .into_iter()
+            .map(|p| SearchHit {
+                file_path: p.payload["file_path"].as_str().unwrap_or("").to_string(),
+                line: p.payload["line"].as_u64().unwrap_or(1) as usize,
+                snippet: p.payload["content"].as_str().unwrap_or("").to_string(),
+                score: Some(p.score),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Qdrant simulado: colecciones existentes (nombre, dimensión) y creaciones recibidas.
+    #[derive(Default)]
+    struct MockAdmin {
+        existing: Vec<(String, u64)>,
+        created: RefCell<Vec<(String, u64)>>,
+    }
+
+    impl CollectionAdmin for MockAdmin {
+        fn collection_dimension(&self, name: &str) -> anyhow::Result<Option<u64>> {
+            Ok(self.existing.iter().find(|(n, _)| n == name).map(|(_, d)| *d))
+        }
+        fn create_collection(&self, name: &str, dim: u64) -> anyhow::Result<()> {
+            self.created.borrow_mut().push((name.to_string(), dim));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_ensure_collection_creates_when_absent_and_checks_dimension() {
+        let model = crate::config::ModelConfig { provider: "ollama".into(), name: "mxbai-embed-large".into(), ..Default::default() };
+        let dim = model.embedding_dimension();
+
+        let absent = MockAdmin::default();
+        assert_eq!(ensure_collection(&absent, "sentinel_crm", dim).unwrap(), CollectionStatus::Created);
+        assert_eq!(*absent.created.borrow(), vec![("sentinel_crm".to_string(), 1024)]);
+
+        let present = MockAdmin { existing: vec![("sentinel_crm".into(), 1024)], ..Default::default() };
+        assert_eq!(ensure_collection(&present, "sentinel_crm", dim).unwrap(), CollectionStatus::Existing);
+        assert!(present.created.borrow().is_empty(), "existing collection must not be recreated");
+
+        let mismatch = MockAdmin { existing: vec![("sentinel_crm".into(), 384)], ..Default::default() };
+        let err = ensure_collection(&mismatch, "sentinel_crm", dim).unwrap_err().to_string();
+        assert!(err.contains("384") && err.contains("1024"), "{}", err);
+        assert!(mismatch.created.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_collection_name_is_sanitized() {
+        assert_eq!(collection_name("My App (API)"), "sentinel_my_app__api");
+        assert_eq!(collection_name("crm"), "sentinel_crm");
+    }
+}
@@ -0,0 +1,71 @@
+//! Logger mínimo sobre el crate `log`, ligado a `--verbose` / `--quiet`.
+//!
+//! Escribe siempre a stderr para que stdout quede limpio en `--format json`.
+//! - Verbose: `debug` (archivos procesados, llamadas a la IA con su duración, consultas al índice)
+//! - Normal: `warn`
+//! - Quiet: solo `error`
+
+use crate::commands::OutputMode;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let tag = match record.level() {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        eprintln!(
+            "[{}] {} {}: {}",
+            tag,
+            chrono::Local::now().format("%H:%M:%S%.3f"),
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn level_for(mode: &OutputMode) -> LevelFilter {
+    match mode {
+        OutputMode::Verbose => LevelFilter::Debug,
+        OutputMode::Normal => LevelFilter::Warn,
+        OutputMode::Quiet => LevelFilter::Error,
+    }
+}
+
+/// Instala el logger (solo la primera vez) y ajusta el nivel al modo de salida.
+pub fn init(mode: &OutputMode) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level_for(mode));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_sets_max_level_from_output_mode() {
+        init(&OutputMode::Verbose);
+        assert_eq!(log::max_level(), LevelFilter::Debug);
+        assert!(log::log_enabled!(Level::Debug));
+
+        init(&OutputMode::Quiet);
+        assert_eq!(log::max_level(), LevelFilter::Error);
+        assert!(!log::log_enabled!(Level::Warn));
+    }
+}
@@ -6,6 +6,8 @@
 
 use clap::Parser;
 use commands::{Cli, Commands};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 // Módulos
 pub mod agents;
@@ -16,18 +18,39 @@ pub mod docs;
 pub mod files;
 pub mod git;
 pub mod index;
+pub mod kb;
+pub mod logging;
 pub mod business_logic_guard;
 pub mod ml;
 pub mod rules;
+pub mod secrets;
 pub mod stats;
 pub mod tests;
 pub mod ui;
 
+/// Handler de SIGINT/SIGTERM (Ctrl-C / Ctrl-Break en Windows) para el monitor.
+/// El primero activa el flag de apagado ordenado; un segundo fuerza la salida.
+fn instalar_apagado() -> Arc<AtomicBool> {
+    let apagado = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&apagado);
+    let resultado = ctrlc::set_handler(move || {
+        if flag.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+        eprintln!("\n🛑 Deteniendo Sentinel: terminando el análisis en curso (Ctrl-C de nuevo para forzar)...");
+    });
+    if let Err(e) = resultado {
+        eprintln!("⚠️  No se pudo instalar el handler de señales: {}", e);
+    }
+    apagado
+}
+
 fn main() {
     let cli = Cli::parse();
+    logging::init(&commands::get_output_mode(cli.quiet, cli.verbose));
 
     match cli.command {
-        Some(Commands::Monitor { daemon, stop, status }) => {
+        Some(Commands::Monitor { daemon, stop, status, interactive }) => {
             let project_root = crate::config::SentinelConfig::find_project_root()
                 .unwrap_or_else(|| std::env::current_dir().unwrap());
 
@@ -47,36 +70,66 @@ fn main() {
                     std::process::exit(1);
                 }
             } else {
-                commands::monitor::start_monitor();
+                commands::monitor::start_monitor(instalar_apagado(), interactive);
             }
         }
-        Some(Commands::Init { force }) => {
+        Some(Commands::Init { print_schema: true, .. }) => {
+            commands::init::print_config_schema();
+        }
+        Some(Commands::Init { force, non_interactive, provider, model, url, .. }) => {
             let project_root = crate::config::SentinelConfig::find_project_root()
                 .unwrap_or_else(|| std::env::current_dir().unwrap());
-            commands::init::handle_init_command(&project_root, force);
+            if non_interactive {
+                let opts = commands::init::NonInteractiveInit { provider, model, url };
+                commands::init::handle_init_non_interactive(&project_root, force, &opts);
+            } else {
+                commands::init::handle_init_command(&project_root, force);
+            }
         }
-        Some(Commands::Ignore { rule, file, symbol, list, clear, show_file }) => {
-            commands::ignore::handle_ignore_command(rule, file, symbol, list, clear, show_file);
+        Some(Commands::Ignore { rule, file, symbol, list, clear, show_file, export, import }) => {
+            let transfer = export
+                .map(commands::ignore::IgnoreTransfer::Export)
+                .or(import.map(commands::ignore::IgnoreTransfer::Import));
+            commands::ignore::handle_ignore_command(rule, file, symbol, list, clear, show_file, transfer);
         }
-        Some(Commands::Index { rebuild, check }) => {
-            commands::index::handle_index_command(rebuild, check);
+        Some(Commands::Index { rebuild, check, trend, refs, cycles, stats, format, export_dot, module, export_symbols }) => {
+            commands::index::handle_index_command(&commands::index::IndexOptions {
+                rebuild,
+                check,
+                trend,
+                refs,
+                cycles,
+                stats,
+                format,
+                export_dot,
+                module,
+                export_symbols,
+            });
         }
-        Some(Commands::Pro { subcommand }) => {
-            commands::pro::handle_pro_command(subcommand, cli.quiet, cli.verbose);
+        Some(Commands::Pro { subcommand, model_override }) => {
+            commands::pro::handle_pro_command(subcommand, cli.quiet, cli.verbose, &model_override);
         }
-        Some(Commands::Doctor) => {
+        Some(Commands::Doctor { format }) => {
             let project_root = crate::config::SentinelConfig::find_project_root()
                 .unwrap_or_else(|| std::env::current_dir().unwrap());
-            commands::doctor::handle_doctor_command(&project_root);
+            if format.eq_ignore_ascii_case("json") {
+                commands::doctor::handle_doctor_json(&project_root);
+            } else {
+                commands::doctor::handle_doctor_command(&project_root);
+            }
         }
-        Some(Commands::Rules) => {
+        Some(Commands::Rules { enable, disable, set_threshold }) => {
             let project_root = crate::config::SentinelConfig::find_project_root()
                 .unwrap_or_else(|| std::env::current_dir().unwrap());
-            commands::rules::handle_rules_command(&project_root);
+            if enable.is_empty() && disable.is_empty() && set_threshold.is_empty() {
+                commands::rules::handle_rules_command(&project_root);
+            } else {
+                commands::rules::handle_rules_edit(&project_root, &enable, &disable, &set_threshold);
+            }
         }
         None => {
             // Comportamiento por defecto (legacy)
-            commands::monitor::start_monitor();
+            commands::monitor::start_monitor(instalar_apagado(), false);
         }
     }
 }
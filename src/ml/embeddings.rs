@@ -107,6 +107,17 @@ impl EmbeddingModel {
         })
     }
 
+    /// Indica si los archivos del modelo ya están en el cache de HF Hub (sin descargar nada).
+    pub fn is_cached() -> bool {
+        let repo = hf_hub::Cache::from_env().repo(Repo::new(
+            "sentence-transformers/all-MiniLM-L6-v2".to_string(),
+            RepoType::Model,
+        ));
+        ["config.json", "tokenizer.json", "model.safetensors"]
+            .iter()
+            .all(|f| repo.get(f).is_some())
+    }
+
     /// Genera embeddings para una lista de textos
     pub fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
         let mut embeddings = Vec::new();
@@ -1,6 +1,20 @@
 use anyhow::Result;
 use std::path::Path;
 
+/// Probabilidad de bug a partir del embedding de un archivo.
+/// Abstrae el modelo para poder sustituirlo en tests.
+pub trait BugRiskPredictor {
+    fn predict_bug_probability(&self, code_embedding: &[f32]) -> Result<f32>;
+}
+
+/// Combina la probabilidad del modelo con la presión de violaciones estáticas
+/// (los errores pesan el doble que los warnings). Resultado en 0.0–1.0.
+pub fn combine_risk(model_probability: f32, errors: usize, warnings: usize) -> f32 {
+    let weighted = (2 * errors + warnings) as f32;
+    let violation_pressure = 1.0 - (-weighted / 10.0).exp();
+    (0.5 * model_probability.clamp(0.0, 1.0) + 0.5 * violation_pressure).clamp(0.0, 1.0)
+}
+
 /// Estructura base para predictores basados en ONNX
 #[allow(dead_code)]
 pub struct OnnxPredictor {
@@ -57,3 +71,9 @@ impl OnnxPredictor {
         Ok(complexity)
     }
 }
+
+impl BugRiskPredictor for OnnxPredictor {
+    fn predict_bug_probability(&self, code_embedding: &[f32]) -> Result<f32> {
+        OnnxPredictor::predict_bug_probability(self, code_embedding)
+    }
+}
@@ -0,0 +1,153 @@
+//! Catálogo de reglas built-in: descripciones y enlace a la documentación.
+//! Lo consumen los emisores que necesitan metadatos por regla (ej: SARIF `driver.rules`,
+//! `pro check --explain`).
+
+/// Página de documentación de reglas; cada regla tiene un anchor con su id en minúsculas.
+pub const RULES_DOC_URL: &str = "https://github.com/sergiogswv/sentinel-pro/blob/main/docs/rules.md";
+
+pub struct RuleInfo {
+    pub name: &'static str,
+    pub short_description: &'static str,
+    pub full_description: &'static str,
+    /// Por qué existe la regla (se antepone a `full_description` en `explanation`)
+    pub rationale: &'static str,
+}
+
+pub static BUILTIN_RULES: &[RuleInfo] = &[
+    RuleInfo {
+        name: "DEAD_CODE",
+        short_description: "Unused function or variable",
+        full_description: "A function or variable is declared but never referenced in the file. Remove it or wire it up where it is needed.",
+        rationale: "Unused code still has to be read, compiled and maintained, and it often hides half-finished refactors.",
+    },
+    RuleInfo {
+        name: "DEAD_CODE_GLOBAL",
+        short_description: "Symbol never called in the project",
+        full_description: "The symbol has no call sites anywhere in the indexed project (call graph). It is likely safe to delete.",
+        rationale: "Code nobody calls keeps costing review and test time, and it misleads readers about what the system actually does.",
+    },
+    RuleInfo {
+        name: "UNUSED_IMPORT",
+        short_description: "Unused import",
+        full_description: "An imported name is never used in the file. Unused imports slow builds and hide real dependencies.",
+        rationale: "Imports are the file's declared dependencies; stale ones make the module look coupled to code it no longer uses.",
+    },
+    RuleInfo {
+        name: "HIGH_COMPLEXITY",
+        short_description: "Cyclomatic complexity above threshold",
+        full_description: "The function has more branches than `rule_config.complexity_threshold`. Split it into smaller functions or simplify its control flow.",
+        rationale: "Every branch is a path that needs a test and a reviewer's attention; highly branched functions are where bugs concentrate.",
+    },
+    RuleInfo {
+        name: "FUNCTION_TOO_LONG",
+        short_description: "Function longer than threshold",
+        full_description: "The function has more lines than `rule_config.function_length_threshold`. Long functions are harder to test and review.",
+        rationale: "Long functions usually do several things at once, which makes them hard to name, test and change safely.",
+    },
+    RuleInfo {
+        name: "NAMING_CONVENTION",
+        short_description: "Framework naming convention violated",
+        full_description: "A class or file name does not follow the naming convention expected by the project framework (e.g. NestJS `*Service`, `*Controller`).",
+        rationale: "Consistent names let teammates and framework tooling find a class's role at a glance.",
+    },
+    RuleInfo {
+        name: "HIGH_RISK",
+        short_description: "High predicted bug risk",
+        full_description: "`pro check --risk` combined the ML bug predictor with the file's static violations and the score is above `rule_config.risk_threshold`. Prioritize this file for review and tests.",
+        rationale: "Files with many issues and a history of changes are statistically the most likely to ship bugs.",
+    },
+    RuleInfo {
+        name: "CIRCULAR_IMPORT",
+        short_description: "Circular import between files",
+        full_description: "The file is part of an import cycle in the project index (`a → b → a`). Move the shared code to a module both can import, or invert one of the dependencies.",
+        rationale: "Cycles make load order fragile, block tree-shaking and mean no file in the loop can be understood or tested on its own.",
+    },
+    RuleInfo {
+        name: "UNCHECKED_ERROR",
+        short_description: "Go error discarded",
+        full_description: "A Go error value is assigned to the blank identifier `_` and never checked.",
+        rationale: "In Go errors are values: discarding one means a failure goes unnoticed and the program continues in a bad state.",
+    },
+    RuleInfo {
+        name: "IGNORED_ERROR",
+        short_description: "Go error return ignored",
+        full_description: "A call keeps its other results but assigns the trailing `error` to `_` (`val, _ := f()`). Handle the error or document why it is safe to drop.",
+        rationale: "The other results are usually invalid when the error is non-nil, so using them without checking is a latent bug.",
+    },
+    RuleInfo {
+        name: "NAMING_CONVENTION_GO",
+        short_description: "Go constant in ALL_CAPS",
+        full_description: "Go constants should use MixedCaps (`maxRetries`, `MaxRetries`) instead of ALL_CAPS.",
+        rationale: "Go's exported/unexported distinction is carried by capitalization, so ALL_CAPS names read as a style from another language.",
+    },
+    RuleInfo {
+        name: "DEFER_IN_LOOP",
+        short_description: "defer inside a for loop",
+        full_description: "`defer` inside a loop only runs when the surrounding function returns, which can leak resources across iterations.",
+        rationale: "Files, locks and connections opened in each iteration stay held until the function ends.",
+    },
+    RuleInfo {
+        name: "EMPTY_CATCH",
+        short_description: "Exception swallowed (Java, C#)",
+        full_description: "A `catch` block has no statements and no comment, so the exception disappears silently. Log it, rethrow it, or explain why it is safe to ignore.",
+        rationale: "A silently swallowed exception turns a clear failure into wrong behavior that is hard to trace later.",
+    },
+    RuleInfo {
+        name: "DEBUG_MACRO",
+        short_description: "Rust debug macro left in code",
+        full_description: "`dbg!`, `println!` or `eprintln!` is used outside `main`, `#[test]` functions and `#[cfg(test)]` modules. Remove it or switch to a logging facade.",
+        rationale: "Debug output left in library code pollutes users' terminals and logs and can leak internal data.",
+    },
+    RuleInfo {
+        name: "HARDCODED_SECRET",
+        short_description: "Secret or key committed in source",
+        full_description: "A line contains an AWS access key, a private-key header, a credential assigned to an `api_key`/`secret`/`token`/`password`-style name, or a long high-entropy string. Move it to an environment variable or a secret manager and rotate it. Test fixtures with fake keys can be listed in `rule_config.secret_allowlist`.",
+        rationale: "A secret in the repository is readable by everyone with access to it, forever, including its history and every fork.",
+    },
+];
+
+pub fn find_rule(name: &str) -> Option<&'static RuleInfo> {
+    BUILTIN_RULES.iter().find(|r| r.name == name)
+}
+
+/// Explicación breve para `pro check --explain`: por qué importa la regla y cómo corregirla.
+/// `None` para reglas fuera del catálogo (las de `.sentinel/rules.yaml`).
+pub fn explanation(name: &str) -> Option<String> {
+    find_rule(name).map(|r| format!("{} {}", r.rationale, r.full_description))
+}
+
+/// Enlace a la sección de la regla dentro de `docs/rules.md`.
+pub fn help_uri(name: &str) -> String {
+    format!("{}#{}", RULES_DOC_URL, name.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_has_unique_names() {
+        let mut names: Vec<&str> = BUILTIN_RULES.iter().map(|r| r.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), BUILTIN_RULES.len());
+    }
+
+    #[test]
+    fn test_help_uri_uses_lowercase_anchor() {
+        assert_eq!(help_uri("DEAD_CODE"), format!("{}#dead_code", RULES_DOC_URL));
+        assert!(find_rule("DEAD_CODE").is_some());
+        assert!(find_rule("CUSTOM_YAML_RULE").is_none());
+    }
+
+    #[test]
+    fn test_every_rule_has_an_explanation() {
+        for rule in BUILTIN_RULES {
+            assert!(!rule.rationale.is_empty(), "{} needs a rationale", rule.name);
+        }
+        let text = explanation("UNUSED_IMPORT").unwrap();
+        assert!(text.starts_with(find_rule("UNUSED_IMPORT").unwrap().rationale));
+        assert!(text.ends_with(find_rule("UNUSED_IMPORT").unwrap().full_description));
+        assert!(explanation("MY_CUSTOM_RULE").is_none());
+    }
+}
@@ -1,12 +1,25 @@
 use crate::rules::{FrameworkDefinition, FrameworkRule, RuleViolation, RuleLevel};
-use crate::rules::static_analysis::NamingAnalyzerWithFramework;
+use crate::rules::static_analysis::{NamingAnalyzerWithFramework, Thresholds};
 use crate::rules::languages;
+use crate::rules::ignore_file::SentinelIgnore;
+use crate::rules::secrets::{SecretScanAnalyzer, SECRET_RULE};
+use crate::rules::suppression::InlineSuppressions;
+use crate::index::entry_points::EntryPatterns;
 use std::fs;
 use std::path::Path;
 
 pub struct RuleEngine {
     pub framework_def: Option<FrameworkDefinition>,
     pub index_db: Option<std::sync::Arc<crate::index::IndexDb>>,
+    pub sentinelignore: SentinelIgnore,
+    /// Reglas seleccionadas con `--only` (vacío = todas)
+    pub only: Vec<String>,
+    /// `entrypoint_patterns`: símbolos que nunca se reportan como código muerto
+    pub entry_patterns: EntryPatterns,
+    /// `HARDCODED_SECRET` en cualquier archivo (texto plano, no AST)
+    pub secret_scan: SecretScanAnalyzer,
+    /// Umbrales de complejidad y longitud con los que corren los analizadores
+    pub thresholds: Thresholds,
 }
 
 impl RuleEngine {
@@ -14,14 +27,86 @@ impl RuleEngine {
         Self {
             framework_def: None,
             index_db: None,
+            sentinelignore: SentinelIgnore::default(),
+            only: Vec::new(),
+            entry_patterns: EntryPatterns::default(),
+            secret_scan: SecretScanAnalyzer::new(Path::new(""), &[]),
+            thresholds: Thresholds::default(),
         }
     }
 
+    /// Restringe el análisis a `rules` (nombres de regla, sin distinguir mayúsculas): solo
+    /// corren los analizadores que pueden emitir alguna de ellas.
+    pub fn with_only(mut self, rules: &[String]) -> Self {
+        self.only = rules.iter().map(|r| r.trim().to_uppercase()).collect();
+        self
+    }
+
+    /// Si `rule` corre en esta ejecución (sin `--only`, todas).
+    pub fn selected(&self, rule: &str) -> bool {
+        self.only.is_empty() || self.only.iter().any(|r| r == rule)
+    }
+
+    /// Reglas que este motor puede emitir: analizadores de todos los lenguajes, naming,
+    /// dead code global, las que calcula `pro check` fuera del motor (`CIRCULAR_IMPORT`,
+    /// `HIGH_RISK`) y las reglas de `.sentinel/rules.yaml` cargadas.
+    pub fn available_rules(&self) -> std::collections::BTreeSet<String> {
+        let mut rules: std::collections::BTreeSet<String> =
+            languages::all_rule_names().into_iter().map(String::from).collect();
+        rules.insert("NAMING_CONVENTION".to_string());
+        rules.insert("DEAD_CODE_GLOBAL".to_string());
+        rules.insert(SECRET_RULE.to_string());
+        rules.insert("CIRCULAR_IMPORT".to_string());
+        rules.insert("HIGH_RISK".to_string());
+        if let Some(ref def) = self.framework_def {
+            rules.extend(def.rules.iter().map(|r| r.name.to_uppercase()));
+        }
+        rules
+    }
+
+    /// Error con la lista de reglas disponibles si alguna regla de `--only` no existe.
+    pub fn check_only(&self) -> anyhow::Result<()> {
+        let available = self.available_rules();
+        let unknown: Vec<&str> = self.only.iter().filter(|r| !available.contains(*r)).map(String::as_str).collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Regla(s) desconocida(s) en --only: {}. Disponibles: {}",
+            unknown.join(", "),
+            available.into_iter().collect::<Vec<_>>().join(", ")
+        )
+    }
+
+    /// Carga `entrypoint_patterns` (más los defaults del framework) de la config.
+    pub fn with_entry_patterns(mut self, project_root: &Path, config: &crate::config::SentinelConfig) -> Self {
+        self.entry_patterns = EntryPatterns::for_config(project_root, config);
+        self
+    }
+
+    /// `rule_config.secret_allowlist`: globs de fixtures que el escaneo de secretos no revisa.
+    pub fn with_secret_allowlist(mut self, project_root: &Path, allowlist: &[String]) -> Self {
+        self.secret_scan = SecretScanAnalyzer::new(project_root, allowlist);
+        self
+    }
+
+    /// Umbrales de `[rule_config]` (ya con los overrides de la CLI) para los analizadores.
+    pub fn with_thresholds(mut self, rule_config: &crate::config::RuleConfig) -> Self {
+        self.thresholds = Thresholds::from_rule_config(rule_config);
+        self
+    }
+
     pub fn with_index_db(mut self, db: std::sync::Arc<crate::index::IndexDb>) -> Self {
         self.index_db = Some(db);
         self
     }
 
+    /// Carga `.sentinelignore` de la raíz del proyecto (exclusiones `glob[:RULE]`).
+    pub fn with_sentinelignore(mut self, project_root: &Path) -> Self {
+        self.sentinelignore = SentinelIgnore::load(project_root);
+        self
+    }
+
     pub fn load_from_yaml(&mut self, yaml_path: &Path) -> anyhow::Result<()> {
         let content = fs::read_to_string(yaml_path)?;
         let def: FrameworkDefinition = serde_yaml::from_str(&content)?;
@@ -29,18 +114,43 @@ impl RuleEngine {
         Ok(())
     }
 
-    pub fn validate_file(&self, _file_path: &Path, content: &str) -> Vec<RuleViolation> {
+    pub fn validate_file(&self, file_path: &Path, content: &str) -> Vec<RuleViolation> {
+        self.validate_file_with_suppressed(file_path, content).0
+    }
+
+    /// Igual que `validate_file`, pero devuelve además cuántas violaciones fueron
+    /// silenciadas por comentarios `sentinel-ignore-next-line` / `sentinel-ignore-file`.
+    pub fn validate_file_with_suppressed(&self, file_path: &Path, content: &str) -> (Vec<RuleViolation>, usize) {
+        if self.sentinelignore.ignores_file(file_path) {
+            return (Vec::new(), 0);
+        }
+        let mut violations = self.collect_violations(file_path, content);
+        if !self.sentinelignore.is_empty() {
+            violations.retain(|v| !self.sentinelignore.ignores(file_path, &v.rule_name));
+        }
+
+        let suppressions = InlineSuppressions::parse(content);
+        if suppressions.is_empty() {
+            return (violations, 0);
+        }
+        let before = violations.len();
+        violations.retain(|v| !suppressions.is_suppressed(v));
+        let suppressed = before - violations.len();
+        (violations, suppressed)
+    }
+
+    fn collect_violations(&self, _file_path: &Path, content: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
 
         // 1. Capa de Análisis Estático (Layer 1 - Automática)
         let ext = _file_path.extension().and_then(|e: &std::ffi::OsStr| e.to_str()).unwrap_or("");
-        if let Some((lang, analyzers)) = languages::get_language_and_analyzers(ext) {
-            for analyzer in &analyzers {
+        if let Some((lang, analyzers)) = languages::get_language_and_analyzers_with(ext, self.thresholds) {
+            for analyzer in analyzers.iter().filter(|a| a.rule_names().iter().any(|r| self.selected(r))) {
                 violations.extend(analyzer.analyze(&lang, content));
             }
 
             // NamingAnalyzer: only for TS/JS (framework naming conventions)
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx") {
+            if matches!(ext, "ts" | "tsx" | "js" | "jsx") && self.selected("NAMING_CONVENTION") {
                 let framework = self.framework_def.as_ref()
                     .map(|f| f.framework.as_str())
                     .unwrap_or("typescript");
@@ -50,6 +160,25 @@ impl RuleEngine {
             }
         }
 
+        // Secretos: sobre el texto, para cualquier lenguaje (incluso sin analizadores)
+        if self.selected(SECRET_RULE) {
+            violations.extend(self.secret_scan.analyze(_file_path, content));
+        }
+
+        // Raíces de `entrypoint_patterns`: registradas por el framework, nunca muertas
+        let is_root_file = self.entry_patterns.matches_file(_file_path);
+        let entry_roots = if self.entry_patterns.is_empty()
+            || !(self.selected("DEAD_CODE") || self.selected("DEAD_CODE_GLOBAL"))
+        {
+            Default::default()
+        } else {
+            self.entry_patterns.decorated_roots(ext, content)
+        };
+        violations.retain(|v| {
+            v.rule_name != "DEAD_CODE"
+                || !(is_root_file || v.symbol.as_ref().is_some_and(|s| entry_roots.contains(s)))
+        });
+
         // --- Análisis de Proyecto Cruzado (SI hay DB disponible) ---
         if let Some(ref db) = self.index_db {
             let rel_path = _file_path.to_string_lossy();
@@ -68,7 +197,12 @@ impl RuleEngine {
             });
 
             // 1. Dead Code de Proyecto (DEAD_CODE_GLOBAL from call graph)
-            if let Ok(dead_symbols) = call_graph.get_dead_code(Some(&rel_path)) {
+            if self.selected("DEAD_CODE_GLOBAL")
+                && let Ok(dead_symbols) = call_graph.get_dead_code(Some(&rel_path))
+            {
+                let dead_symbols = dead_symbols
+                    .into_iter()
+                    .filter(|s| !is_root_file && !entry_roots.contains(s));
                 for symbol in dead_symbols {
                     violations.push(RuleViolation {
                         rule_name: "DEAD_CODE_GLOBAL".to_string(),
@@ -84,7 +218,7 @@ impl RuleEngine {
 
         // 2. Reglas basadas en Patrones (Legacy/Configurable)
         if let Some(ref def) = self.framework_def {
-            for rule in &def.rules {
+            for rule in def.rules.iter().filter(|r| r.enabled && self.selected(&r.name.to_uppercase())) {
                 if self.check_rule(rule, content) {
                     violations.push(RuleViolation {
                         rule_name: rule.name.clone(),
@@ -98,6 +232,10 @@ impl RuleEngine {
             }
         }
 
+        // Un analizador puede emitir varias reglas (complejidad + longitud)
+        if !self.only.is_empty() {
+            violations.retain(|v| self.selected(&v.rule_name.to_uppercase()));
+        }
         violations
     }
 
@@ -117,3 +255,65 @@ impl RuleEngine {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complex_source_with_unused_import() -> String {
+        let branches: String = (0..12).map(|i| format!("  if (x === {}) {{ return {}; }}\n", i, i)).collect();
+        format!("import {{ unused }} from './lib';\n\nexport function pick(x: number) {{\n{}  return -1;\n}}\n", branches)
+    }
+
+    #[test]
+    fn test_only_runs_selected_rules() {
+        let source = complex_source_with_unused_import();
+        let path = Path::new("src/pick.ts");
+
+        let all = RuleEngine::new().validate_file(path, &source);
+        assert!(all.iter().any(|v| v.rule_name == "HIGH_COMPLEXITY"), "fixture must be complex: {:?}", all);
+
+        let only = RuleEngine::new().with_only(&["unused_import".to_string()]);
+        assert!(only.check_only().is_ok());
+        let violations = only.validate_file(path, &source);
+        assert!(!violations.is_empty());
+        assert!(violations.iter().all(|v| v.rule_name == "UNUSED_IMPORT"), "{:?}", violations);
+
+        let err = RuleEngine::new().with_only(&["COMPLEXITY".to_string()]).check_only().unwrap_err().to_string();
+        assert!(err.contains("COMPLEXITY") && err.contains("HIGH_COMPLEXITY"), "{}", err);
+
+        // Reglas que calcula `pro check` fuera del motor: válidas en --only y apagadas sin él
+        let cycles = RuleEngine::new().with_only(&["circular_import".to_string(), "high_risk".to_string()]);
+        assert!(cycles.check_only().is_ok());
+        assert!(cycles.selected("CIRCULAR_IMPORT") && cycles.selected("HIGH_RISK"));
+        assert!(!only.selected("CIRCULAR_IMPORT") && !only.selected("HIGH_RISK"));
+        assert!(crate::rules::catalog::find_rule("CIRCULAR_IMPORT").is_some());
+    }
+
+    #[test]
+    fn test_entry_patterns_suppress_dead_code_for_decorated_symbols() {
+        let source = "@router.get('/items')\ndef list_items():\n    return []\n\ndef unused_helper():\n    pass\n";
+        let path = Path::new("/repo/app/items.py");
+        let dead = |engine: &RuleEngine| -> Vec<String> {
+            engine
+                .validate_file(path, source)
+                .into_iter()
+                .filter(|v| v.rule_name == "DEAD_CODE")
+                .filter_map(|v| v.symbol)
+                .collect()
+        };
+        assert_eq!(dead(&RuleEngine::new()), vec!["list_items", "unused_helper"]);
+
+        let engine = RuleEngine {
+            entry_patterns: EntryPatterns::new(Path::new("/repo"), &["@router.*".to_string()]),
+            ..RuleEngine::new()
+        };
+        assert_eq!(dead(&engine), vec!["unused_helper"]);
+
+        let engine = RuleEngine {
+            entry_patterns: EntryPatterns::new(Path::new("/repo"), &["app/**".to_string()]),
+            ..RuleEngine::new()
+        };
+        assert!(dead(&engine).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+//! `.sentinelignore` en la raíz del proyecto: exclusiones por glob, al estilo `.eslintignore`.
+//!
+//! Cada línea es `glob[:RULE]`:
+//! - `src/generated/**` — excluye esos archivos de todas las reglas
+//! - `src/legacy/**/*.ts:DEAD_CODE` — excluye solo la regla `DEAD_CODE`
+//!
+//! Las líneas con espacios siguen el formato por símbolo (`RULE archivo [símbolo]`)
+//! que procesa `commands::ignore`; aquí se ignoran, así ambas fuentes conviven.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+pub const SENTINELIGNORE_FILE: &str = ".sentinelignore";
+
+pub struct IgnorePattern {
+    pub glob: String,
+    /// `None` = todas las reglas
+    pub rule: Option<String>,
+    matcher: Gitignore,
+}
+
+impl IgnorePattern {
+    fn matches(&self, path: &Path) -> bool {
+        // Gitignore hace panic con rutas absolutas fuera de su raíz
+        if path.has_root() && !path.starts_with(self.matcher.path()) {
+            return false;
+        }
+        self.matcher
+            .matched_path_or_any_parents(path, false)
+            .is_ignore()
+    }
+}
+
+#[derive(Default)]
+pub struct SentinelIgnore {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl SentinelIgnore {
+    /// Carga `<project_root>/.sentinelignore`; vacío si no existe.
+    pub fn load(project_root: &Path) -> Self {
+        std::fs::read_to_string(project_root.join(SENTINELIGNORE_FILE))
+            .map(|content| Self::parse(project_root, &content))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(project_root: &Path, content: &str) -> Self {
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter(|l| !l.contains(char::is_whitespace))
+            .filter_map(|line| {
+                let (glob, rule) = split_rule(line);
+                let mut builder = GitignoreBuilder::new(project_root);
+                builder.add_line(None, glob).ok()?;
+                Some(IgnorePattern {
+                    glob: glob.to_string(),
+                    rule: rule.map(|r| r.to_string()),
+                    matcher: builder.build().ok()?,
+                })
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn patterns(&self) -> &[IgnorePattern] {
+        &self.patterns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// El archivo está excluido de todas las reglas (no hace falta analizarlo).
+    pub fn ignores_file(&self, path: &Path) -> bool {
+        self.patterns.iter().any(|p| p.rule.is_none() && p.matches(path))
+    }
+
+    pub fn ignores(&self, path: &Path, rule: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|p| p.rule.as_deref().is_none_or(|r| r == rule) && p.matches(path))
+    }
+
+    /// Patrones que no coinciden con ninguno de `files` (probablemente obsoletos).
+    pub fn unmatched<'a>(&'a self, files: &[std::path::PathBuf]) -> Vec<&'a IgnorePattern> {
+        self.patterns
+            .iter()
+            .filter(|p| !files.iter().any(|f| p.matches(f)))
+            .collect()
+    }
+}
+
+/// `glob:RULE` → (glob, Some(RULE)); solo se separa si el sufijo parece un id de regla.
+fn split_rule(line: &str) -> (&str, Option<&str>) {
+    match line.rsplit_once(':') {
+        Some((glob, rule))
+            if !glob.is_empty()
+                && !rule.is_empty()
+                && rule.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') =>
+        {
+            (glob, Some(rule))
+        }
+        _ => (line, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(root: &Path) -> SentinelIgnore {
+        SentinelIgnore::parse(
+            root,
+            "# generados\nsrc/generated/**\nsrc/legacy/**/*.ts:DEAD_CODE\n\nDEAD_CODE src/a.ts helper\n",
+        )
+    }
+
+    #[test]
+    fn test_glob_with_rule_only_excludes_that_rule() {
+        let root = Path::new("/repo");
+        let ignore = sample(root);
+        assert_eq!(ignore.patterns().len(), 2, "comments and per-symbol lines are skipped");
+
+        let legacy = root.join("src/legacy/old/user.ts");
+        assert!(ignore.ignores(&legacy, "DEAD_CODE"));
+        assert!(!ignore.ignores(&legacy, "UNUSED_IMPORT"));
+        assert!(!ignore.ignores_file(&legacy));
+        assert!(!ignore.ignores(&root.join("src/app.ts"), "DEAD_CODE"));
+        assert!(!ignore.ignores(Path::new("/elsewhere/src/legacy/a.ts"), "DEAD_CODE"));
+    }
+
+    #[test]
+    fn test_glob_without_rule_excludes_all_rules() {
+        let root = Path::new("/repo");
+        let ignore = sample(root);
+        let generated = root.join("src/generated/api/client.ts");
+        assert!(ignore.ignores_file(&generated));
+        assert!(ignore.ignores(&generated, "HIGH_COMPLEXITY"));
+        assert!(ignore.ignores(&generated, "DEAD_CODE"));
+    }
+
+    #[test]
+    fn test_unmatched_patterns_are_reported() {
+        let root = Path::new("/repo");
+        let ignore = sample(root);
+        let files = vec![root.join("src/generated/x.ts")];
+        let unmatched: Vec<&str> = ignore.unmatched(&files).iter().map(|p| p.glob.as_str()).collect();
+        assert_eq!(unmatched, vec!["src/legacy/**/*.ts"]);
+    }
+}
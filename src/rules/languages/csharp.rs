@@ -0,0 +1,280 @@
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, StreamingIterator};
+use crate::rules::{RuleViolation, RuleLevel};
+use crate::rules::static_analysis::StaticAnalyzer;
+
+/// Namespaces del BCL cuyo uso se reconoce por nombre. Un `using` de namespace solo trae
+/// tipos y métodos de extensión, y sin el compilador no se sabe qué tipo viene de dónde:
+/// por eso solo se marcan los namespaces de esta tabla, cuando ninguno de sus identificadores
+/// aparece en el código. Para los que exponen métodos de extensión (`System.Linq`) la lista
+/// incluye esos métodos, así que `items.Where(...)` cuenta como uso aunque `System.Linq`
+/// nunca se nombre. Los namespaces desconocidos (los del propio proyecto, paquetes NuGet)
+/// nunca se marcan.
+const KNOWN_NAMESPACES: &[(&str, &[&str])] = &[
+    ("System.Text", &["StringBuilder", "Encoding", "Rune", "CompositeFormat"]),
+    (
+        "System.Text.RegularExpressions",
+        &["Regex", "Match", "MatchCollection", "Group", "RegexOptions", "GeneratedRegex"],
+    ),
+    (
+        "System.Text.Json",
+        &["JsonSerializer", "JsonSerializerOptions", "JsonDocument", "JsonElement", "JsonException", "JsonNamingPolicy"],
+    ),
+    (
+        "System.IO",
+        &["File", "Directory", "Path", "Stream", "FileStream", "MemoryStream", "StreamReader", "StreamWriter", "FileInfo", "DirectoryInfo", "IOException", "TextReader", "TextWriter"],
+    ),
+    (
+        "System.Linq",
+        &[
+            "Enumerable", "Queryable", "IQueryable", "IGrouping", "ILookup", "IOrderedEnumerable",
+            "Where", "Select", "SelectMany", "Any", "All", "First", "FirstOrDefault", "Single",
+            "SingleOrDefault", "Last", "LastOrDefault", "ToList", "ToArray", "ToDictionary",
+            "ToHashSet", "ToLookup", "OrderBy", "OrderByDescending", "ThenBy", "ThenByDescending",
+            "GroupBy", "Count", "Sum", "Min", "Max", "Average", "Distinct", "DistinctBy", "Skip",
+            "Take", "Aggregate", "Contains", "Concat", "Zip", "Cast", "OfType", "Except",
+            "Intersect", "Union", "Reverse", "DefaultIfEmpty", "Join", "GroupJoin", "Chunk",
+            "MaxBy", "MinBy", "SequenceEqual", "ElementAt", "Append", "Prepend",
+        ],
+    ),
+    (
+        "System.Collections.Generic",
+        &["List", "Dictionary", "HashSet", "Queue", "Stack", "IEnumerable", "IList", "ICollection", "IDictionary", "IReadOnlyList", "IReadOnlyCollection", "IReadOnlyDictionary", "KeyValuePair", "LinkedList", "SortedDictionary", "SortedSet", "IEnumerator", "IComparer", "IEqualityComparer", "KeyNotFoundException", "PriorityQueue"],
+    ),
+    (
+        "System.Threading.Tasks",
+        &["Task", "ValueTask", "Parallel", "TaskCompletionSource", "TaskScheduler", "TaskFactory"],
+    ),
+    (
+        "System.Threading",
+        &["Thread", "CancellationToken", "CancellationTokenSource", "Interlocked", "Monitor", "SemaphoreSlim", "Mutex", "Timer", "Volatile", "Lock", "ThreadPool", "Timeout"],
+    ),
+    ("System.Net.Http", &["HttpClient", "HttpRequestMessage", "HttpResponseMessage", "HttpMethod", "HttpContent", "StringContent", "HttpRequestException", "IHttpClientFactory"]),
+    ("System.Diagnostics", &["Debug", "Trace", "Stopwatch", "Process", "ProcessStartInfo", "Activity", "ActivitySource", "Debugger", "Conditional"]),
+    ("System.Globalization", &["CultureInfo", "NumberStyles", "DateTimeStyles", "CompareOptions", "Calendar"]),
+];
+
+fn count_word_occurrences(text: &str, word: &str) -> usize {
+    let pattern = format!(r"\b{}\b", regex::escape(word));
+    match regex::Regex::new(&pattern) {
+        Ok(re) => re.find_iter(text).count(),
+        Err(_) => 1,
+    }
+}
+
+/// Source with every `using` directive blanked out, so usages are only counted in code.
+fn code_without_usings(source_code: &str, usings: &[Node]) -> String {
+    let mut bytes = source_code.as_bytes().to_vec();
+    for node in usings {
+        for b in &mut bytes[node.start_byte()..node.end_byte()] {
+            if *b != b'\n' { *b = b' '; }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// `using_directive` del archivo, también los anidados en `namespace { }`.
+fn using_directives<'a>(node: Node<'a>, out: &mut Vec<Node<'a>>) {
+    let mut walker = node.walk();
+    for child in node.children(&mut walker) {
+        match child.kind() {
+            "using_directive" => out.push(child),
+            "namespace_declaration" | "file_scoped_namespace_declaration" | "declaration_list" => using_directives(child, out),
+            _ => {}
+        }
+    }
+}
+
+/// Unused `using` directives. `global using` is never flagged (it serves the whole project);
+/// `using static` is skipped (its members are used unqualified and can't be traced). Aliases
+/// (`using Json = System.Text.Json;`) are flagged when the alias never appears; namespace
+/// usings only when they are in `KNOWN_NAMESPACES` and none of its identifiers appear.
+pub struct CSharpUnusedUsingsAnalyzer;
+
+impl StaticAnalyzer for CSharpUnusedUsingsAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+
+        let mut usings = Vec::new();
+        using_directives(tree.root_node(), &mut usings);
+        let code = code_without_usings(source_code, &usings);
+
+        for using in &usings {
+            let mut c = using.walk();
+            let tokens: Vec<&str> = using.children(&mut c).filter(|n| !n.is_named()).map(|n| n.kind()).collect();
+            if tokens.contains(&"global") || tokens.contains(&"static") { continue; }
+
+            let alias = using.child_by_field_name("name");
+            let mut c = using.walk();
+            let Some(target) = using
+                .named_children(&mut c)
+                .find(|n| Some(n.id()) != alias.map(|a| a.id()) && n.kind() != "comment")
+            else {
+                continue;
+            };
+            let target = target.utf8_text(source_code.as_bytes()).unwrap_or("");
+
+            let unused = match alias {
+                Some(alias) => {
+                    let alias = alias.utf8_text(source_code.as_bytes()).unwrap_or("");
+                    !alias.is_empty() && count_word_occurrences(&code, alias) == 0
+                }
+                None => KNOWN_NAMESPACES
+                    .iter()
+                    .find(|(ns, _)| *ns == target)
+                    .is_some_and(|(_, names)| names.iter().all(|n| count_word_occurrences(&code, n) == 0)),
+            };
+            if unused {
+                violations.push(RuleViolation {
+                    rule_name: "UNUSED_IMPORT".to_string(),
+                    message: format!("El using '{}' no parece usarse en este archivo.", target),
+                    level: RuleLevel::Warning,
+                    line: Some(using.start_position().row + 1),
+                    symbol: Some(target.to_string()),
+                    value: None,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Empty catch: `catch` blocks with no statements and no comment explaining
+/// why the exception can be swallowed.
+pub struct CSharpEmptyCatchAnalyzer;
+
+impl StaticAnalyzer for CSharpEmptyCatchAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["EMPTY_CATCH"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+
+        let query = match Query::new(language, "(catch_clause body: (block) @body) @catch") {
+            Ok(q) => q,
+            Err(_) => return violations,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, tree.root_node(), source_code.as_bytes());
+
+        while let Some(m) = matches.next() {
+            let body = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "body");
+            let catch = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "catch");
+            let (Some(body), Some(catch)) = (body, catch) else { continue };
+
+            // Comments count as content: a documented swallow is intentional
+            if body.node.named_child_count() > 0 { continue; }
+
+            // `catch { }` sin declaración atrapa cualquier excepción
+            let mut c = catch.node.walk();
+            let exception = catch
+                .node
+                .named_children(&mut c)
+                .find(|n| n.kind() == "catch_declaration")
+                .and_then(|d| d.child_by_field_name("type"))
+                .and_then(|t| t.utf8_text(source_code.as_bytes()).ok())
+                .unwrap_or("Exception");
+            violations.push(RuleViolation {
+                rule_name: "EMPTY_CATCH".to_string(),
+                message: format!("Bloque catch vacío: la excepción {} se ignora silenciosamente.", exception),
+                level: RuleLevel::Warning,
+                line: Some(body.node.start_position().row + 1),
+                symbol: Some(exception.to_string()),
+                value: None,
+            });
+        }
+        violations
+    }
+}
+
+/// Returns the set of static analyzers for C# files.
+pub fn analyzers() -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+    vec![
+        Box::new(CSharpUnusedUsingsAnalyzer),
+        Box::new(CSharpEmptyCatchAnalyzer),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cs_lang() -> tree_sitter::Language {
+        tree_sitter_c_sharp::LANGUAGE.into()
+    }
+
+    const FIXTURE: &str = r#"global using System.Net.Http;
+using System.IO;
+using System.Linq;
+using System.Text;
+using MyApp.Domain;
+using Json = System.Text.Json;
+
+namespace MyApp.Orders;
+
+public class OrderService
+{
+    public int[] Load(string path)
+    {
+        try
+        {
+            return File.ReadAllLines(path).Select(int.Parse).Where(n => n > 0).ToArray();
+        }
+        catch (IOException)
+        {
+        }
+        try
+        {
+            return new int[0];
+        }
+        catch
+        {
+            // el archivo es opcional
+        }
+        return new int[0];
+    }
+}
+"#;
+
+    #[test]
+    fn test_csharp_unused_usings_are_conservative() {
+        let violations = CSharpUnusedUsingsAnalyzer.analyze(&cs_lang(), FIXTURE);
+        let symbols: Vec<&str> = violations.iter().filter_map(|v| v.symbol.as_deref()).collect();
+        // global using nunca se marca; System.Linq se usa vía métodos de extensión;
+        // MyApp.Domain es desconocido; el alias Json no aparece
+        assert_eq!(symbols, vec!["System.Text", "System.Text.Json"], "got: {:?}", violations);
+        assert_eq!(violations[0].line, Some(4));
+        assert!(violations.iter().all(|v| v.rule_name == "UNUSED_IMPORT"));
+    }
+
+    #[test]
+    fn test_csharp_empty_catch_flags_only_silent_block() {
+        let violations = CSharpEmptyCatchAnalyzer.analyze(&cs_lang(), FIXTURE);
+        assert_eq!(violations.len(), 1, "got: {:?}", violations);
+        assert_eq!(violations[0].rule_name, "EMPTY_CATCH");
+        assert_eq!(violations[0].symbol.as_deref(), Some("IOException"));
+        assert_eq!(violations[0].line, Some(19));
+    }
+
+    #[test]
+    fn test_csharp_registry_returns_analyzers() {
+        let (_, analyzers) = super::super::get_language_and_analyzers("cs").expect("cs must be registered");
+        assert_eq!(analyzers.len(), 2);
+    }
+}
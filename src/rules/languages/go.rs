@@ -1,6 +1,6 @@
 use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator};
 use crate::rules::{RuleViolation, RuleLevel};
-use crate::rules::static_analysis::StaticAnalyzer;
+use crate::rules::static_analysis::{StaticAnalyzer, Thresholds};
 
 static ALL_CAPS_RE: once_cell::sync::Lazy<regex::Regex> =
     once_cell::sync::Lazy::new(|| regex::Regex::new(r"^[A-Z][A-Z0-9_]+$").unwrap());
@@ -23,6 +23,10 @@ fn find_line_of(source_code: &str, word: &str) -> Option<usize> {
 pub struct GoDeadCodeAnalyzer;
 
 impl StaticAnalyzer for GoDeadCodeAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["DEAD_CODE"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -70,6 +74,10 @@ impl StaticAnalyzer for GoDeadCodeAnalyzer {
 pub struct GoUnusedImportsAnalyzer;
 
 impl StaticAnalyzer for GoUnusedImportsAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -118,9 +126,16 @@ impl StaticAnalyzer for GoUnusedImportsAnalyzer {
 }
 
 /// Complexity + function length analyzer for Go.
-pub struct GoComplexityAnalyzer;
+#[derive(Default)]
+pub struct GoComplexityAnalyzer {
+    pub thresholds: Thresholds,
+}
 
 impl StaticAnalyzer for GoComplexityAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["HIGH_COMPLEXITY", "FUNCTION_TOO_LONG"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -173,12 +188,13 @@ impl StaticAnalyzer for GoComplexityAnalyzer {
                 let mut branches = b_cursor.captures(&branch_query, func_node, source_code.as_bytes());
                 let mut complexity = 1usize;
                 while branches.next().is_some() { complexity += 1; }
-                // NOTE: 5 is the absolute generation floor. The configured complexity_threshold
-                // can suppress violations above this floor but cannot lower it below 5.
-                if complexity > 5 {
+                if self.thresholds.reports_complexity(complexity) {
                     violations.push(RuleViolation {
                         rule_name: "HIGH_COMPLEXITY".to_string(),
-                        message: format!("Función con complejidad ciclomática {} (máximo recomendado: 10).", complexity),
+                        message: format!(
+                            "Función con complejidad ciclomática {} (máximo recomendado: {}).",
+                            complexity, self.thresholds.complexity
+                        ),
                         level: RuleLevel::Error,
                         line: Some(func_node.start_position().row + 1),
                         symbol: None,
@@ -190,13 +206,12 @@ impl StaticAnalyzer for GoComplexityAnalyzer {
                 let start_line = func_node.range().start_point.row;
                 let end_line = func_node.range().end_point.row;
                 let line_count = end_line.saturating_sub(start_line);
-                // NOTE: 10 is the absolute generation floor for function length.
-                if line_count > 10 {
+                if self.thresholds.reports_length(line_count) {
                     violations.push(RuleViolation {
                         rule_name: "FUNCTION_TOO_LONG".to_string(),
                         message: format!(
-                            "Función de {} líneas (máximo recomendado: 50). Considera dividirla.",
-                            line_count
+                            "Función de {} líneas (máximo recomendado: {}). Considera dividirla.",
+                            line_count, self.thresholds.function_length
                         ),
                         level: RuleLevel::Warning,
                         line: Some(start_line + 1),
@@ -214,6 +229,10 @@ impl StaticAnalyzer for GoComplexityAnalyzer {
 pub struct GoUncheckedErrorAnalyzer;
 
 impl StaticAnalyzer for GoUncheckedErrorAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNCHECKED_ERROR"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -267,10 +286,108 @@ impl StaticAnalyzer for GoUncheckedErrorAnalyzer {
     }
 }
 
+/// Ignored error: `val, _ := f()` — the trailing return value (Go's `error` slot)
+/// is discarded while the others are kept. Fully blank assignments are left to
+/// `GoUncheckedErrorAnalyzer`; calls inside `defer` are considered intentional.
+pub struct GoErrorHandlingAnalyzer;
+
+impl GoErrorHandlingAnalyzer {
+    /// Local functions/methods whose declared result list ends in `error`.
+    fn local_error_returns(root: tree_sitter::Node, source_code: &str) -> std::collections::HashMap<String, bool> {
+        let mut funcs = std::collections::HashMap::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if matches!(node.kind(), "function_declaration" | "method_declaration")
+                && let Some(name) = node.child_by_field_name("name")
+            {
+                let name = name.utf8_text(source_code.as_bytes()).unwrap_or("").to_string();
+                let returns_error = node
+                    .child_by_field_name("result")
+                    .and_then(|r| r.utf8_text(source_code.as_bytes()).ok())
+                    .map(|r| r.trim_end_matches(')').trim_end().ends_with("error"))
+                    .unwrap_or(false);
+                funcs.insert(name, returns_error);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        funcs
+    }
+
+    fn inside_defer(node: tree_sitter::Node) -> bool {
+        let mut current = node.parent();
+        while let Some(n) = current {
+            if n.kind() == "defer_statement" { return true; }
+            current = n.parent();
+        }
+        false
+    }
+}
+
+impl StaticAnalyzer for GoErrorHandlingAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["IGNORED_ERROR"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+        let local_funcs = Self::local_error_returns(root, source_code);
+
+        let query_str = r#"
+            (short_var_declaration left: (expression_list) @lhs right: (expression_list (call_expression function: _ @callee)))
+            (assignment_statement left: (expression_list) @lhs right: (expression_list (call_expression function: _ @callee)))
+        "#;
+        let query = match Query::new(language, query_str) {
+            Ok(q) => q,
+            Err(_) => return violations,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, root, source_code.as_bytes());
+
+        while let Some(m) = matches.next() {
+            let lhs = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "lhs");
+            let callee = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "callee");
+            let (Some(lhs), Some(callee)) = (lhs, callee) else { continue };
+
+            let names: Vec<&str> = lhs.node.utf8_text(source_code.as_bytes()).unwrap_or("")
+                .split(',').map(|s| s.trim()).collect();
+            let ignores_last = names.len() >= 2 && names.last() == Some(&"_");
+            if !ignores_last || names.iter().all(|n| *n == "_") { continue; }
+            if Self::inside_defer(lhs.node) { continue; }
+
+            let callee_name = callee.node.utf8_text(source_code.as_bytes()).unwrap_or("unknown");
+            // If the callee is declared here and does not return `error`, the blank is harmless
+            let short_name = callee_name.rsplit('.').next().unwrap_or(callee_name);
+            if local_funcs.get(short_name) == Some(&false) { continue; }
+
+            violations.push(RuleViolation {
+                rule_name: "IGNORED_ERROR".to_string(),
+                message: format!("El error devuelto por {} se asigna a '_' y nunca se comprueba.", callee_name),
+                level: RuleLevel::Warning,
+                line: Some(callee.node.start_position().row + 1),
+                symbol: Some(callee_name.to_string()),
+                value: None,
+            });
+        }
+        violations
+    }
+}
+
 /// Naming convention: detects Go constants in ALL_CAPS format (violates Go naming).
 pub struct GoNamingConventionAnalyzer;
 
 impl StaticAnalyzer for GoNamingConventionAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["NAMING_CONVENTION_GO"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -312,6 +429,10 @@ impl StaticAnalyzer for GoNamingConventionAnalyzer {
 pub struct GoDeferInLoopAnalyzer;
 
 impl StaticAnalyzer for GoDeferInLoopAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["DEFER_IN_LOOP"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -369,12 +490,13 @@ impl StaticAnalyzer for GoDeferInLoopAnalyzer {
 }
 
 /// Returns the set of static analyzers for Go files.
-pub fn analyzers() -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+pub fn analyzers(thresholds: Thresholds) -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
     vec![
         Box::new(GoDeadCodeAnalyzer),
         Box::new(GoUnusedImportsAnalyzer),
-        Box::new(GoComplexityAnalyzer),
+        Box::new(GoComplexityAnalyzer { thresholds }),
         Box::new(GoUncheckedErrorAnalyzer),
+        Box::new(GoErrorHandlingAnalyzer),
         Box::new(GoNamingConventionAnalyzer),
         Box::new(GoDeferInLoopAnalyzer),
     ]
@@ -447,7 +569,7 @@ func complex(x int) int {
     return 0
 }
 "#;
-        let violations = GoComplexityAnalyzer.analyze(&go_lang(), src);
+        let violations = GoComplexityAnalyzer::default().analyze(&go_lang(), src);
         assert!(
             violations.iter().any(|v| v.rule_name == "HIGH_COMPLEXITY"),
             "deeply nested function should have HIGH_COMPLEXITY, got: {:?}", violations
@@ -459,7 +581,7 @@ func complex(x int) int {
         let result = super::super::get_language_and_analyzers("go");
         assert!(result.is_some(), "registry must return analyzers for .go files");
         let (_, analyzers) = result.unwrap();
-        assert_eq!(analyzers.len(), 6, "Go should have 6 analyzers");
+        assert_eq!(analyzers.len(), 7, "Go should have 7 analyzers");
     }
 
     #[test]
@@ -544,9 +666,53 @@ func main() {
         );
     }
 
+    #[test]
+    fn test_go_error_handling_flags_ignored_error_once() {
+        let src = r#"package main
+
+import (
+    "os"
+    "strconv"
+)
+
+func parse(s string) int {
+    n, _ := strconv.Atoi(s)
+    f, err := os.Open(s)
+    if err != nil { return 0 }
+    defer f.Close()
+    return n
+}
+"#;
+        let violations = GoErrorHandlingAnalyzer.analyze(&go_lang(), src);
+        assert_eq!(violations.len(), 1, "got: {:?}", violations);
+        assert_eq!(violations[0].rule_name, "IGNORED_ERROR");
+        assert_eq!(violations[0].symbol.as_deref(), Some("strconv.Atoi"));
+        assert_eq!(violations[0].line, Some(9));
+    }
+
+    #[test]
+    fn test_go_error_handling_skips_defer_and_non_error_locals() {
+        let src = r#"package main
+
+func pair() (int, bool) { return 1, true }
+
+func run() {
+    v, _ := pair()
+    defer func() {
+        _, _ = recover(), 1
+        n, _ := strconv.Atoi("1")
+        println(n)
+    }()
+    println(v)
+}
+"#;
+        let violations = GoErrorHandlingAnalyzer.analyze(&go_lang(), src);
+        assert!(violations.is_empty(), "got: {:?}", violations);
+    }
+
     #[test]
     fn test_go_registry_returns_none_for_unknown() {
-        assert!(super::super::get_language_and_analyzers("rb").is_none());
-        assert!(super::super::get_language_and_analyzers("java").is_none());
+        assert!(super::super::get_language_and_analyzers("swift").is_none());
+        assert!(super::super::get_language_and_analyzers("kt").is_none());
     }
 }
@@ -0,0 +1,201 @@
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, StreamingIterator};
+use crate::rules::{RuleViolation, RuleLevel};
+use crate::rules::static_analysis::StaticAnalyzer;
+
+fn count_word_occurrences(text: &str, word: &str) -> usize {
+    let pattern = format!(r"\b{}\b", regex::escape(word));
+    match regex::Regex::new(&pattern) {
+        Ok(re) => re.find_iter(text).count(),
+        Err(_) => 1,
+    }
+}
+
+/// Unused imports: single-type and static imports whose simple name never appears
+/// outside the import block. Wildcards (`import java.util.*;`) are never flagged.
+pub struct JavaUnusedImportsAnalyzer;
+
+impl JavaUnusedImportsAnalyzer {
+    /// Source with every import declaration blanked out, so usages are only counted in code.
+    fn code_without_imports(source_code: &str, imports: &[Node]) -> String {
+        let mut bytes = source_code.as_bytes().to_vec();
+        for node in imports {
+            for b in &mut bytes[node.start_byte()..node.end_byte()] {
+                if *b != b'\n' { *b = b' '; }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+impl StaticAnalyzer for JavaUnusedImportsAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+
+        let mut walker = root.walk();
+        let imports: Vec<Node> = root
+            .children(&mut walker)
+            .filter(|n| n.kind() == "import_declaration")
+            .collect();
+        let code = Self::code_without_imports(source_code, &imports);
+
+        for import in &imports {
+            let mut c = import.walk();
+            let children: Vec<Node> = import.children(&mut c).collect();
+            if children.iter().any(|n| n.kind() == "asterisk") { continue; }
+            let Some(path) = children
+                .iter()
+                .find(|n| matches!(n.kind(), "scoped_identifier" | "identifier"))
+            else {
+                continue;
+            };
+            let path = path.utf8_text(source_code.as_bytes()).unwrap_or("");
+            let name = path.rsplit('.').next().unwrap_or(path);
+            if name.is_empty() { continue; }
+
+            if count_word_occurrences(&code, name) == 0 {
+                violations.push(RuleViolation {
+                    rule_name: "UNUSED_IMPORT".to_string(),
+                    message: format!("El import '{}' no parece usarse en este archivo.", path),
+                    level: RuleLevel::Warning,
+                    line: Some(import.start_position().row + 1),
+                    symbol: Some(name.to_string()),
+                    value: None,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Empty catch: `catch` blocks with no statements and no comment explaining
+/// why the exception can be swallowed.
+pub struct JavaEmptyCatchAnalyzer;
+
+impl StaticAnalyzer for JavaEmptyCatchAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["EMPTY_CATCH"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+
+        let query_str = r#"(catch_clause (catch_formal_parameter (catch_type) @type) body: (block) @body)"#;
+        let query = match Query::new(language, query_str) {
+            Ok(q) => q,
+            Err(_) => return violations,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, root, source_code.as_bytes());
+
+        while let Some(m) = matches.next() {
+            let ty = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "type");
+            let body = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "body");
+            let (Some(ty), Some(body)) = (ty, body) else { continue };
+
+            // Comments count as content: a documented swallow is intentional
+            if body.node.named_child_count() > 0 { continue; }
+
+            let exception = ty.node.utf8_text(source_code.as_bytes()).unwrap_or("Exception");
+            violations.push(RuleViolation {
+                rule_name: "EMPTY_CATCH".to_string(),
+                message: format!("Bloque catch vacío: la excepción {} se ignora silenciosamente.", exception),
+                level: RuleLevel::Warning,
+                line: Some(body.node.start_position().row + 1),
+                symbol: Some(exception.to_string()),
+                value: None,
+            });
+        }
+        violations
+    }
+}
+
+/// Returns the set of static analyzers for Java files.
+pub fn analyzers() -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+    vec![
+        Box::new(JavaUnusedImportsAnalyzer),
+        Box::new(JavaEmptyCatchAnalyzer),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn java_lang() -> tree_sitter::Language {
+        tree_sitter_java::LANGUAGE.into()
+    }
+
+    const FIXTURE: &str = r#"
+package com.example.orders;
+
+import java.io.IOException;
+import java.util.*;
+import java.util.concurrent.TimeUnit;
+import static java.util.Objects.requireNonNull;
+import static org.junit.Assert.assertEquals;
+
+public class OrderService {
+    public List<String> load(String path) {
+        requireNonNull(path);
+        try {
+            return read(path);
+        } catch (IOException e) {
+        }
+        try {
+            return read(path);
+        } catch (IOException e) {
+            // el archivo es opcional
+        }
+        return new ArrayList<>();
+    }
+
+    private List<String> read(String path) throws IOException {
+        throw new IOException(path);
+    }
+}
+"#;
+
+    #[test]
+    fn test_java_unused_imports_exact() {
+        let violations = JavaUnusedImportsAnalyzer.analyze(&java_lang(), FIXTURE);
+        let symbols: Vec<&str> = violations.iter().filter_map(|v| v.symbol.as_deref()).collect();
+        // java.util.* is a wildcard and requireNonNull is a used static import
+        assert_eq!(symbols, vec!["TimeUnit", "assertEquals"], "got: {:?}", violations);
+        assert_eq!(violations[0].line, Some(6));
+        assert!(violations.iter().all(|v| v.rule_name == "UNUSED_IMPORT"));
+    }
+
+    #[test]
+    fn test_java_empty_catch_flags_only_silent_block() {
+        let violations = JavaEmptyCatchAnalyzer.analyze(&java_lang(), FIXTURE);
+        assert_eq!(violations.len(), 1, "got: {:?}", violations);
+        assert_eq!(violations[0].rule_name, "EMPTY_CATCH");
+        assert_eq!(violations[0].symbol.as_deref(), Some("IOException"));
+        assert_eq!(violations[0].line, Some(15));
+    }
+
+    #[test]
+    fn test_java_registry_returns_analyzers() {
+        let (_, analyzers) = super::super::get_language_and_analyzers("java")
+            .expect("registry must return analyzers for .java files");
+        assert_eq!(analyzers.len(), 2);
+    }
+}
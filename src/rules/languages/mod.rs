@@ -1,31 +1,72 @@
 pub mod typescript;
 pub mod go;
 pub mod python;
+pub mod java;
+pub mod rust;
+pub mod ruby;
+pub mod csharp;
 
 use tree_sitter::Language;
-use crate::rules::static_analysis::StaticAnalyzer;
+use crate::rules::static_analysis::{StaticAnalyzer, Thresholds};
 
-/// Returns the tree-sitter Language and the set of analyzers for the given file extension.
+/// One extension per supported language (JS shares the TypeScript analyzers).
+const LANGUAGE_EXTENSIONS: &[&str] = &["ts", "go", "py", "java", "rs", "rb", "cs"];
+
+/// Every rule name emitted by the per-language analyzers.
+pub fn all_rule_names() -> std::collections::BTreeSet<&'static str> {
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .filter_map(|ext| get_language_and_analyzers(ext))
+        .flat_map(|(_, analyzers)| analyzers.into_iter().flat_map(|a| a.rule_names().iter().copied()))
+        .collect()
+}
+
+/// Returns the tree-sitter Language and the set of analyzers for the given file extension,
+/// with the default complexity/length thresholds.
 /// Returns None for unsupported extensions.
 pub fn get_language_and_analyzers(
     ext: &str,
+) -> Option<(Language, Vec<Box<dyn StaticAnalyzer + Send + Sync>>)> {
+    get_language_and_analyzers_with(ext, Thresholds::default())
+}
+
+/// Same as `get_language_and_analyzers`, with the configured complexity/length thresholds.
+pub fn get_language_and_analyzers_with(
+    ext: &str,
+    thresholds: Thresholds,
 ) -> Option<(Language, Vec<Box<dyn StaticAnalyzer + Send + Sync>>)> {
     match ext {
         "ts" | "tsx" => Some((
             tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
-            typescript::analyzers(),
+            typescript::analyzers(thresholds),
         )),
         "js" | "jsx" => Some((
             tree_sitter_javascript::LANGUAGE.into(),
-            typescript::analyzers(),
+            typescript::analyzers(thresholds),
         )),
         "go" => Some((
             tree_sitter_go::LANGUAGE.into(),
-            go::analyzers(),
+            go::analyzers(thresholds),
         )),
         "py" => Some((
             tree_sitter_python::LANGUAGE.into(),
-            python::analyzers(),
+            python::analyzers(thresholds),
+        )),
+        "java" => Some((
+            tree_sitter_java::LANGUAGE.into(),
+            java::analyzers(),
+        )),
+        "rs" => Some((
+            tree_sitter_rust::LANGUAGE.into(),
+            rust::analyzers(),
+        )),
+        "rb" => Some((
+            tree_sitter_ruby::LANGUAGE.into(),
+            ruby::analyzers(thresholds),
+        )),
+        "cs" => Some((
+            tree_sitter_c_sharp::LANGUAGE.into(),
+            csharp::analyzers(),
         )),
         _ => None,
     }
@@ -1,6 +1,6 @@
 use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator};
 use crate::rules::{RuleViolation, RuleLevel};
-use crate::rules::static_analysis::StaticAnalyzer;
+use crate::rules::static_analysis::{StaticAnalyzer, Thresholds};
 
 fn count_word_occurrences(text: &str, word: &str) -> usize {
     let pattern = format!(r"\b{}\b", regex::escape(word));
@@ -20,6 +20,10 @@ fn find_line_of(source_code: &str, word: &str) -> Option<usize> {
 pub struct PythonDeadCodeAnalyzer;
 
 impl StaticAnalyzer for PythonDeadCodeAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["DEAD_CODE"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -67,6 +71,10 @@ impl StaticAnalyzer for PythonDeadCodeAnalyzer {
 pub struct PythonUnusedImportsAnalyzer;
 
 impl StaticAnalyzer for PythonUnusedImportsAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -139,9 +147,16 @@ impl StaticAnalyzer for PythonUnusedImportsAnalyzer {
 }
 
 /// Complexity + function length analyzer for Python.
-pub struct PythonComplexityAnalyzer;
+#[derive(Default)]
+pub struct PythonComplexityAnalyzer {
+    pub thresholds: Thresholds,
+}
 
 impl StaticAnalyzer for PythonComplexityAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["HIGH_COMPLEXITY", "FUNCTION_TOO_LONG"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -188,12 +203,13 @@ impl StaticAnalyzer for PythonComplexityAnalyzer {
                 let mut branches = b_cursor.captures(&branch_query, func_node, source_code.as_bytes());
                 let mut complexity = 1usize;
                 while branches.next().is_some() { complexity += 1; }
-                // NOTE: 5 is the absolute generation floor. The configured complexity_threshold
-                // can suppress violations above this floor but cannot lower it below 5.
-                if complexity > 5 {
+                if self.thresholds.reports_complexity(complexity) {
                     violations.push(RuleViolation {
                         rule_name: "HIGH_COMPLEXITY".to_string(),
-                        message: format!("Función con complejidad ciclomática {} (máximo recomendado: 10).", complexity),
+                        message: format!(
+                            "Función con complejidad ciclomática {} (máximo recomendado: {}).",
+                            complexity, self.thresholds.complexity
+                        ),
                         level: RuleLevel::Error,
                         line: Some(func_node.start_position().row + 1),
                         symbol: None,
@@ -201,11 +217,13 @@ impl StaticAnalyzer for PythonComplexityAnalyzer {
                     });
                 }
                 let line_count = func_node.range().end_point.row.saturating_sub(func_node.range().start_point.row);
-                // NOTE: 10 is the absolute generation floor for function length.
-                if line_count > 10 {
+                if self.thresholds.reports_length(line_count) {
                     violations.push(RuleViolation {
                         rule_name: "FUNCTION_TOO_LONG".to_string(),
-                        message: format!("Función de {} líneas (máximo recomendado: 50). Considera dividirla.", line_count),
+                        message: format!(
+                            "Función de {} líneas (máximo recomendado: {}). Considera dividirla.",
+                            line_count, self.thresholds.function_length
+                        ),
                         level: RuleLevel::Warning,
                         line: Some(func_node.start_position().row + 1),
                         symbol: None,
@@ -219,11 +237,11 @@ impl StaticAnalyzer for PythonComplexityAnalyzer {
 }
 
 /// Returns the set of static analyzers for Python files.
-pub fn analyzers() -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+pub fn analyzers(thresholds: Thresholds) -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
     vec![
         Box::new(PythonDeadCodeAnalyzer),
         Box::new(PythonUnusedImportsAnalyzer),
-        Box::new(PythonComplexityAnalyzer),
+        Box::new(PythonComplexityAnalyzer { thresholds }),
     ]
 }
 
@@ -304,7 +322,7 @@ def complex_func(x):
                                             return x
     return 0
 "#;
-        let violations = PythonComplexityAnalyzer.analyze(&py_lang(), src);
+        let violations = PythonComplexityAnalyzer::default().analyze(&py_lang(), src);
         assert!(
             violations.iter().any(|v| v.rule_name == "HIGH_COMPLEXITY"),
             "deeply nested should be HIGH_COMPLEXITY, got: {:?}", violations
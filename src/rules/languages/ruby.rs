@@ -0,0 +1,210 @@
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, StreamingIterator};
+use crate::rules::{RuleViolation, RuleLevel};
+use crate::rules::static_analysis::{StaticAnalyzer, Thresholds};
+
+/// `foo_bar` / `FooBar` / `FOO_BAR` → `foobar`, para comparar rutas de require con constantes.
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| *c != '_' && *c != '-').flat_map(char::to_lowercase).collect()
+}
+
+/// Long methods: `def` / `def self.` bodies longer than the generation floor (or the
+/// threshold, if lower). The configured `function_length_threshold` is applied later by
+/// `pro check` via `value`.
+#[derive(Default)]
+pub struct RubyMethodLengthAnalyzer {
+    pub thresholds: Thresholds,
+}
+
+impl StaticAnalyzer for RubyMethodLengthAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["FUNCTION_TOO_LONG"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+
+        let query = match Query::new(language, "(method) @m (singleton_method) @m") {
+            Ok(q) => q,
+            Err(_) => return violations,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(&query, tree.root_node(), source_code.as_bytes());
+
+        while let Some((m, _)) = captures.next() {
+            for capture in m.captures {
+                let node = capture.node;
+                let name = node
+                    .child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source_code.as_bytes()).ok())
+                    .unwrap_or("");
+                let line_count = node.end_position().row.saturating_sub(node.start_position().row);
+                if self.thresholds.reports_length(line_count) {
+                    violations.push(RuleViolation {
+                        rule_name: "FUNCTION_TOO_LONG".to_string(),
+                        message: format!(
+                            "Método '{}' de {} líneas (máximo recomendado: {}). Considera dividirlo.",
+                            name, line_count, self.thresholds.function_length
+                        ),
+                        level: RuleLevel::Warning,
+                        line: Some(node.start_position().row + 1),
+                        symbol: Some(name.to_string()),
+                        value: Some(line_count),
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+/// Unused requires: `require` / `require_relative` whose basename never shows up as a
+/// constant in the file (`require "json"` is used if `JSON` appears).
+/// Requires loaded only for their side effects (monkey patches, class registration) that
+/// define a constant named after the file are therefore never flagged.
+pub struct RubyUnusedRequireAnalyzer;
+
+impl RubyUnusedRequireAnalyzer {
+    /// Path literal of a `require "x"` call, or None for dynamic requires.
+    fn required_path<'s>(call: Node, source: &'s str) -> Option<&'s str> {
+        let args = call.child_by_field_name("arguments")?;
+        let arg = args.named_child(0)?;
+        if arg.kind() != "string" || arg.named_child_count() != 1 {
+            return None; // interpolación u otra expresión
+        }
+        let content = arg.named_child(0)?;
+        if content.kind() != "string_content" { return None; }
+        content.utf8_text(source.as_bytes()).ok()
+    }
+}
+
+impl StaticAnalyzer for RubyUnusedRequireAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+
+        let (Ok(const_query), Ok(call_query)) = (
+            Query::new(language, "(constant) @c"),
+            Query::new(language, "(call method: (identifier) @method) @call"),
+        ) else {
+            return violations;
+        };
+
+        let mut constants = std::collections::HashSet::new();
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(&const_query, root, source_code.as_bytes());
+        while let Some((m, _)) = captures.next() {
+            for capture in m.captures {
+                constants.insert(normalize(capture.node.utf8_text(source_code.as_bytes()).unwrap_or("")));
+            }
+        }
+
+        let method_idx = call_query.capture_index_for_name("method");
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&call_query, root, source_code.as_bytes());
+        while let Some(m) = matches.next() {
+            let Some(method) = m.captures.iter().find(|c| Some(c.index) == method_idx) else { continue };
+            let method_name = method.node.utf8_text(source_code.as_bytes()).unwrap_or("");
+            if method_name != "require" && method_name != "require_relative" { continue; }
+            let call = method.node.parent().unwrap_or(method.node);
+            // Solo requires de primer nivel de la llamada (no `obj.require`)
+            if call.child_by_field_name("receiver").is_some() { continue; }
+            let Some(path) = Self::required_path(call, source_code) else { continue };
+
+            let basename = path.rsplit('/').next().unwrap_or(path).trim_end_matches(".rb");
+            if basename.is_empty() || constants.contains(&normalize(basename)) { continue; }
+            violations.push(RuleViolation {
+                rule_name: "UNUSED_IMPORT".to_string(),
+                message: format!("El require '{}' no parece usarse en este archivo.", path),
+                level: RuleLevel::Warning,
+                line: Some(call.start_position().row + 1),
+                symbol: Some(path.to_string()),
+                value: None,
+            });
+        }
+        violations
+    }
+}
+
+/// Returns the set of static analyzers for Ruby files.
+pub fn analyzers(thresholds: Thresholds) -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+    vec![
+        Box::new(RubyMethodLengthAnalyzer { thresholds }),
+        Box::new(RubyUnusedRequireAnalyzer),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ruby_lang() -> tree_sitter::Language {
+        tree_sitter_ruby::LANGUAGE.into()
+    }
+
+    const SERVICE_FIXTURE: &str = r#"
+require "json"
+require "csv"
+require_relative "concerns/auditable"
+
+class InvoiceExporter
+  include Auditable
+
+  def export(invoices)
+    rows = []
+    invoices.each do |invoice|
+      total = invoice.lines.sum(&:amount)
+      tax = total * 0.21
+      rows << {
+        id: invoice.id,
+        customer: invoice.customer.name,
+        total: total,
+        tax: tax,
+        paid: invoice.paid?
+      }
+    end
+    JSON.generate(rows)
+  end
+
+  def self.short
+    :ok
+  end
+end
+"#;
+
+    #[test]
+    fn test_ruby_long_method_and_unused_require_reported() {
+        let lang = ruby_lang();
+        let length = RubyMethodLengthAnalyzer::default().analyze(&lang, SERVICE_FIXTURE);
+        assert_eq!(length.len(), 1, "got: {:?}", length);
+        assert_eq!(length[0].symbol.as_deref(), Some("export"));
+        assert_eq!(length[0].line, Some(9));
+
+        let requires = RubyUnusedRequireAnalyzer.analyze(&lang, SERVICE_FIXTURE);
+        let flagged: Vec<_> = requires.iter().filter_map(|v| v.symbol.as_deref()).collect();
+        // json → JSON y concerns/auditable → Auditable se usan; csv no
+        assert_eq!(flagged, vec!["csv"]);
+        assert_eq!(requires[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_ruby_registry_returns_two_analyzers() {
+        let (_, analyzers) = super::super::get_language_and_analyzers("rb").expect("rb must be registered");
+        assert_eq!(analyzers.len(), 2);
+    }
+}
@@ -0,0 +1,296 @@
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, StreamingIterator};
+use crate::rules::{RuleViolation, RuleLevel};
+use crate::rules::static_analysis::StaticAnalyzer;
+
+fn count_word_occurrences(text: &str, word: &str) -> usize {
+    let pattern = format!(r"\b{}\b", regex::escape(word));
+    match regex::Regex::new(&pattern) {
+        Ok(re) => re.find_iter(text).count(),
+        Err(_) => 1,
+    }
+}
+
+/// Traits that are usually imported only for their methods (`.write_all()`, `.red()`),
+/// so their name never appears again in the file.
+const METHOD_TRAITS: &[&str] = &[
+    "Write", "Read", "BufRead", "Seek", "FromStr", "Hash", "Hasher", "Digest",
+    "Parser", "Subcommand", "Colorize", "IsTerminal", "Context", "StreamingIterator",
+    "Rng", "Distribution", "Itertools", "FromIterator", "Borrow", "AsRef",
+];
+
+/// Unused imports: names brought in by `use` that never appear outside the `use` items.
+/// Handles grouped imports (`use a::{b, c}`), renames (`use a as b`) and `self`.
+/// `pub use` re-exports, wildcards and `as _` trait imports are never flagged.
+pub struct RustUnusedImportsAnalyzer;
+
+impl RustUnusedImportsAnalyzer {
+    /// Collects `(name, node)` pairs for every binding introduced by a use tree.
+    fn collect_bindings<'t>(node: Node<'t>, source: &str, prefix_last: Option<&str>, out: &mut Vec<(String, Node<'t>)>) {
+        let text = |n: Node| n.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+        match node.kind() {
+            "identifier" => out.push((text(node), node)),
+            "self" => {
+                if let Some(last) = prefix_last { out.push((last.to_string(), node)); }
+            }
+            "scoped_identifier" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    if name.kind() == "self" {
+                        if let Some(path) = node.child_by_field_name("path") {
+                            let path = text(path);
+                            out.push((path.rsplit("::").next().unwrap_or(&path).to_string(), node));
+                        }
+                    } else {
+                        out.push((text(name), node));
+                    }
+                }
+            }
+            "use_as_clause" => {
+                if let Some(alias) = node.child_by_field_name("alias") {
+                    let alias = text(alias);
+                    if alias != "_" { out.push((alias, node)); }
+                }
+            }
+            "scoped_use_list" => {
+                let path = node.child_by_field_name("path").map(text);
+                let last = path.as_deref().map(|p| p.rsplit("::").next().unwrap_or(p).to_string());
+                if let Some(list) = node.child_by_field_name("list") {
+                    Self::collect_bindings(list, source, last.as_deref(), out);
+                }
+            }
+            "use_list" => {
+                let mut c = node.walk();
+                for child in node.named_children(&mut c) {
+                    Self::collect_bindings(child, source, prefix_last, out);
+                }
+            }
+            _ => {} // use_wildcard, crate/super paths without binding
+        }
+    }
+}
+
+impl StaticAnalyzer for RustUnusedImportsAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+
+        let query = match Query::new(language, "(use_declaration) @use") {
+            Ok(q) => q,
+            Err(_) => return violations,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(&query, root, source_code.as_bytes());
+        let mut uses: Vec<Node> = Vec::new();
+        while let Some((m, _)) = captures.next() {
+            for capture in m.captures { uses.push(capture.node); }
+        }
+
+        // Usages are only counted outside the `use` items themselves
+        let mut code = source_code.as_bytes().to_vec();
+        for u in &uses {
+            for b in &mut code[u.start_byte()..u.end_byte()] {
+                if *b != b'\n' { *b = b' '; }
+            }
+        }
+        let code = String::from_utf8_lossy(&code);
+
+        for u in &uses {
+            let mut c = u.walk();
+            if u.children(&mut c).any(|n| n.kind() == "visibility_modifier") { continue; }
+            let Some(argument) = u.child_by_field_name("argument") else { continue };
+
+            let mut bindings = Vec::new();
+            Self::collect_bindings(argument, source_code, None, &mut bindings);
+            for (name, node) in bindings {
+                if name.is_empty() || METHOD_TRAITS.contains(&name.as_str()) { continue; }
+                if count_word_occurrences(&code, &name) == 0 {
+                    violations.push(RuleViolation {
+                        rule_name: "UNUSED_IMPORT".to_string(),
+                        message: format!("El import '{}' no parece usarse en este archivo.", name),
+                        level: RuleLevel::Warning,
+                        line: Some(node.start_position().row + 1),
+                        symbol: Some(name),
+                        value: None,
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+/// Debug macros: `dbg!`, `println!` and `eprintln!` left in library code.
+/// Allowed inside `main`, `#[test]` functions and `#[cfg(test)]` modules.
+pub struct RustDebugMacroAnalyzer;
+
+impl RustDebugMacroAnalyzer {
+    const MACROS: &'static [&'static str] = &["dbg", "println", "eprintln"];
+
+    /// Attributes (`#[...]`) placed right before an item.
+    fn attributes<'s>(item: Node, source: &'s str) -> Vec<&'s str> {
+        let mut attrs = Vec::new();
+        let mut prev = item.prev_named_sibling();
+        while let Some(p) = prev {
+            if p.kind() != "attribute_item" { break; }
+            attrs.push(p.utf8_text(source.as_bytes()).unwrap_or(""));
+            prev = p.prev_named_sibling();
+        }
+        attrs
+    }
+
+    fn is_allowed(node: Node, source: &str) -> bool {
+        let mut current = node.parent();
+        while let Some(n) = current {
+            match n.kind() {
+                "function_item" => {
+                    let name = n.child_by_field_name("name")
+                        .and_then(|id| id.utf8_text(source.as_bytes()).ok());
+                    if name == Some("main") { return true; }
+                    if Self::attributes(n, source).iter().any(|a| a.contains("test]")) { return true; }
+                }
+                "mod_item"
+                    if Self::attributes(n, source).iter().any(|a| a.replace(' ', "").contains("cfg(test)")) =>
+                {
+                    return true;
+                }
+                _ => {}
+            }
+            current = n.parent();
+        }
+        false
+    }
+}
+
+impl StaticAnalyzer for RustDebugMacroAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["DEBUG_MACRO"]
+    }
+
+    fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
+        let mut violations = Vec::new();
+        let mut parser = Parser::new();
+        if parser.set_language(language).is_err() { return violations; }
+        let tree = match parser.parse(source_code, None) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+
+        let query = match Query::new(language, "(macro_invocation macro: (identifier) @name)") {
+            Ok(q) => q,
+            Err(_) => return violations,
+        };
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(&query, root, source_code.as_bytes());
+
+        while let Some((m, _)) = captures.next() {
+            for capture in m.captures {
+                let name = capture.node.utf8_text(source_code.as_bytes()).unwrap_or("");
+                if !Self::MACROS.contains(&name) { continue; }
+                if Self::is_allowed(capture.node, source_code) { continue; }
+                violations.push(RuleViolation {
+                    rule_name: "DEBUG_MACRO".to_string(),
+                    message: format!("'{}!' fuera de tests y de main: usa logging o elimínalo.", name),
+                    level: RuleLevel::Info,
+                    line: Some(capture.node.start_position().row + 1),
+                    symbol: Some(format!("{}!", name)),
+                    value: None,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Returns the set of static analyzers for Rust files.
+pub fn analyzers() -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+    vec![
+        Box::new(RustUnusedImportsAnalyzer),
+        Box::new(RustDebugMacroAnalyzer),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rust_lang() -> tree_sitter::Language {
+        tree_sitter_rust::LANGUAGE.into()
+    }
+
+    const LIB_FIXTURE: &str = r#"
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf as P;
+use std::io::{self, Write};
+pub use crate::rules::RuleLevel;
+
+pub fn load(path: P) -> io::Result<HashMap<String, usize>> {
+    let map = HashMap::new();
+    dbg!(&path);
+    Ok(map)
+}
+"#;
+
+    #[test]
+    fn test_rust_unused_grouped_import_detected() {
+        let violations = RustUnusedImportsAnalyzer.analyze(&rust_lang(), LIB_FIXTURE);
+        let symbols: Vec<&str> = violations.iter().filter_map(|v| v.symbol.as_deref()).collect();
+        // HashMap, P (rename), io (self) are used; Write is a method trait; RuleLevel is a re-export
+        assert_eq!(symbols, vec!["HashSet"], "got: {:?}", violations);
+        assert_eq!(violations[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_rust_stray_dbg_detected() {
+        let violations = RustDebugMacroAnalyzer.analyze(&rust_lang(), LIB_FIXTURE);
+        assert_eq!(violations.len(), 1, "got: {:?}", violations);
+        assert_eq!(violations[0].rule_name, "DEBUG_MACRO");
+        assert_eq!(violations[0].symbol.as_deref(), Some("dbg!"));
+        assert_eq!(violations[0].level, RuleLevel::Info);
+        assert_eq!(violations[0].line, Some(9));
+    }
+
+    #[test]
+    fn test_rust_println_allowed_in_tests_and_main() {
+        let src = r#"
+fn main() {
+    println!("starting");
+}
+
+#[test]
+fn standalone() {
+    eprintln!("debug");
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper() {
+        println!("helper");
+    }
+
+    #[test]
+    fn prints() {
+        dbg!(1 + 1);
+    }
+}
+"#;
+        let violations = RustDebugMacroAnalyzer.analyze(&rust_lang(), src);
+        assert!(violations.is_empty(), "got: {:?}", violations);
+    }
+
+    #[test]
+    fn test_rust_registry_returns_analyzers() {
+        let (_, analyzers) = super::super::get_language_and_analyzers("rs")
+            .expect("registry must return analyzers for .rs files");
+        assert_eq!(analyzers.len(), 2);
+    }
+}
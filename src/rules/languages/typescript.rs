@@ -1,10 +1,10 @@
-use crate::rules::static_analysis::{StaticAnalyzer, DeadCodeAnalyzer, UnusedImportsAnalyzer, ComplexityAnalyzer};
+use crate::rules::static_analysis::{StaticAnalyzer, DeadCodeAnalyzer, UnusedImportsAnalyzer, ComplexityAnalyzer, Thresholds};
 
 /// Returns the set of static analyzers for TypeScript/JavaScript files.
-pub fn analyzers() -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
+pub fn analyzers(thresholds: Thresholds) -> Vec<Box<dyn StaticAnalyzer + Send + Sync>> {
     vec![
         Box::new(DeadCodeAnalyzer::new()),
         Box::new(UnusedImportsAnalyzer::new()),
-        Box::new(ComplexityAnalyzer::new()),
+        Box::new(ComplexityAnalyzer::with_thresholds(thresholds)),
     ]
 }
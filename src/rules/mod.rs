@@ -1,6 +1,11 @@
+pub mod catalog;
 pub mod engine;
+pub mod ignore_file;
 pub mod languages;
+pub mod registry;
+pub mod secrets;
 pub mod static_analysis;
+pub mod suppression;
 
 pub use engine::RuleEngine;
 use serde::{Deserialize, Serialize};
@@ -13,6 +18,17 @@ pub struct FrameworkRule {
     pub forbidden_patterns: Vec<String>,
     pub required_imports: Vec<String>,
     pub level: RuleLevel,
+    /// `sentinel rules --disable NAME` lo pone a false; ausente = activa
+    #[serde(default = "default_rule_enabled", skip_serializing_if = "is_rule_enabled")]
+    pub enabled: bool,
+}
+
+fn default_rule_enabled() -> bool {
+    true
+}
+
+fn is_rule_enabled(enabled: &bool) -> bool {
+    *enabled
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -23,6 +39,17 @@ pub enum RuleLevel {
     Info,
 }
 
+impl RuleLevel {
+    /// Nivel SARIF equivalente (`error` / `warning` / `note`).
+    pub fn sarif_level(&self) -> &'static str {
+        match self {
+            RuleLevel::Error => "error",
+            RuleLevel::Warning => "warning",
+            RuleLevel::Info => "note",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FrameworkDefinition {
     pub framework: String,
@@ -48,3 +75,43 @@ pub struct RuleViolation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<usize>,   // raw numeric value for threshold filtering
 }
+
+/// Comprueba con la gramática tree-sitter de `ext` que `content` parsea sin nodos de error.
+/// Las extensiones sin gramática se consideran válidas (no hay forma barata de verificarlas).
+pub fn is_syntactically_valid(content: &str, ext: &str) -> bool {
+    let Some((language, _)) = languages::get_language_and_analyzers(ext) else {
+        return true;
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return true;
+    }
+    match parser.parse(content, None) {
+        Some(tree) => !tree.root_node().has_error(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_typescript_passes() {
+        let src = "export class UsersService {\n  find(id: number): string {\n    return `user-${id}`;\n  }\n}\n";
+        assert!(is_syntactically_valid(src, "ts"));
+    }
+
+    #[test]
+    fn test_broken_typescript_rejected() {
+        // Respuesta de la IA cortada a mitad de método
+        let src = "export class UsersService {\n  find(id: number): string {\n    return `user-${id}`;\n";
+        assert!(!is_syntactically_valid(src, "ts"));
+        assert!(!is_syntactically_valid("const x = ;", "ts"));
+    }
+
+    #[test]
+    fn test_unknown_extension_is_accepted() {
+        assert!(is_syntactically_valid("{{ not code", "md"));
+    }
+}
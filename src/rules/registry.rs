@@ -0,0 +1,123 @@
+//! Registro de frameworks conocidos.
+//!
+//! Combina definiciones built-in (solo nombre + lenguaje) con la definición del proyecto
+//! en `.sentinel/rules.yaml` (la misma que carga `RuleEngine`), que aporta reglas y patrones.
+
+use crate::rules::FrameworkDefinition;
+use std::path::Path;
+
+/// (framework, lenguaje) soportados sin `rules.yaml`.
+const BUILTIN_FRAMEWORKS: &[(&str, &str)] = &[
+    ("Express", "javascript"),
+    ("Fastify", "typescript"),
+    ("Koa", "javascript"),
+    ("NestJS", "typescript"),
+    ("Next.js", "typescript"),
+    ("React", "typescript"),
+    ("Vue", "typescript"),
+    ("Angular", "typescript"),
+    ("Django", "python"),
+    ("Flask", "python"),
+    ("FastAPI", "python"),
+    ("Gin", "go"),
+    ("Echo", "go"),
+    ("Fiber", "go"),
+    ("Laravel", "php"),
+    ("Spring", "java"),
+    ("Rails", "ruby"),
+];
+
+pub struct FrameworkRegistry {
+    definitions: Vec<FrameworkDefinition>,
+}
+
+/// Clave de comparación: minúsculas, sin espacios, puntos ni guiones ("Next.js" → "nextjs").
+pub fn normalize_framework_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+impl FrameworkRegistry {
+    pub fn builtin() -> Self {
+        let definitions = BUILTIN_FRAMEWORKS
+            .iter()
+            .map(|(name, language)| FrameworkDefinition {
+                framework: name.to_string(),
+                language: language.to_string(),
+                rules: vec![],
+                architecture_patterns: vec![],
+            })
+            .collect();
+        Self { definitions }
+    }
+
+    /// Built-ins + `.sentinel/rules.yaml`, que reemplaza al built-in del mismo nombre.
+    pub fn load(project_root: &Path) -> Self {
+        let mut registry = Self::builtin();
+        let rules_path = project_root.join(".sentinel/rules.yaml");
+        let Ok(content) = std::fs::read_to_string(&rules_path) else {
+            return registry;
+        };
+        match serde_yaml::from_str::<FrameworkDefinition>(&content) {
+            Ok(def) => registry.insert(def),
+            Err(e) => eprintln!("   ⚠️  rules.yaml inválido: {}", e),
+        }
+        registry
+    }
+
+    fn insert(&mut self, def: FrameworkDefinition) {
+        let key = normalize_framework_name(&def.framework);
+        self.definitions
+            .retain(|d| normalize_framework_name(&d.framework) != key);
+        self.definitions.push(def);
+    }
+
+    pub fn find(&self, name: &str) -> Option<&FrameworkDefinition> {
+        let key = normalize_framework_name(name);
+        self.definitions
+            .iter()
+            .find(|d| normalize_framework_name(&d.framework) == key)
+    }
+
+    /// Nombres conocidos, ordenados alfabéticamente.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.definitions.iter().map(|d| d.framework.clone()).collect();
+        names.sort_by_key(|n| n.to_lowercase());
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_is_case_and_punctuation_insensitive() {
+        let registry = FrameworkRegistry::builtin();
+        assert_eq!(registry.find("nestjs").unwrap().framework, "NestJS");
+        assert_eq!(registry.find("next-js").unwrap().framework, "Next.js");
+        assert!(registry.find("cobol-on-cogs").is_none());
+    }
+
+    #[test]
+    fn test_project_rules_override_builtin() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".sentinel");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "framework: NestJS\nlanguage: typescript\nrules:\n  - name: No console\n    description: Usa Logger\n    patterns: []\n    forbidden_patterns: [\"console.log(\"]\n    required_imports: []\n    level: warning\narchitecture_patterns: []\n",
+        )
+        .unwrap();
+
+        let registry = FrameworkRegistry::load(tmp.path());
+        assert_eq!(registry.find("nestjs").unwrap().rules.len(), 1);
+        assert_eq!(
+            registry.names().iter().filter(|n| normalize_framework_name(n) == "nestjs").count(),
+            1
+        );
+    }
+}
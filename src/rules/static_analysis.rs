@@ -20,7 +20,46 @@ fn find_line_of(source_code: &str, word: &str) -> Option<usize> {
         .map(|(i, _)| i + 1)
 }
 
+/// Umbrales de `HIGH_COMPLEXITY` y `FUNCTION_TOO_LONG` (`[rule_config]` o los flags de
+/// `pro check`). Los analizadores emiten desde el menor entre el piso y el umbral: las
+/// métricas de calidad necesitan los valores desde el piso aunque `[rule_config]` oculte
+/// luego los que no superan el umbral.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    pub complexity: usize,
+    pub function_length: usize,
+}
+
+impl Thresholds {
+    pub const COMPLEXITY_FLOOR: usize = 5;
+    pub const FUNCTION_LENGTH_FLOOR: usize = 10;
+
+    pub fn from_rule_config(rule_config: &crate::config::RuleConfig) -> Self {
+        Self {
+            complexity: rule_config.complexity_threshold,
+            function_length: rule_config.function_length_threshold,
+        }
+    }
+
+    pub fn reports_complexity(&self, complexity: usize) -> bool {
+        complexity > self.complexity.min(Self::COMPLEXITY_FLOOR)
+    }
+
+    pub fn reports_length(&self, line_count: usize) -> bool {
+        line_count > self.function_length.min(Self::FUNCTION_LENGTH_FLOOR)
+    }
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self { complexity: 10, function_length: 50 }
+    }
+}
+
 pub trait StaticAnalyzer {
+    /// Reglas (`rule_name`) que puede emitir; `pro check --only` selecciona analizadores por ellas.
+    fn rule_names(&self) -> &'static [&'static str];
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation>;
 }
 
@@ -34,6 +73,10 @@ impl DeadCodeAnalyzer {
 }
 
 impl StaticAnalyzer for DeadCodeAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["DEAD_CODE"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -89,6 +132,10 @@ impl UnusedImportsAnalyzer {
 }
 
 impl StaticAnalyzer for UnusedImportsAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["UNUSED_IMPORT"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -137,15 +184,25 @@ impl StaticAnalyzer for UnusedImportsAnalyzer {
 }
 
 /// Analizador de complejidad ciclomática
-pub struct ComplexityAnalyzer;
+pub struct ComplexityAnalyzer {
+    thresholds: Thresholds,
+}
 
 impl ComplexityAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self { thresholds: Thresholds::default() }
+    }
+
+    pub fn with_thresholds(thresholds: Thresholds) -> Self {
+        Self { thresholds }
     }
 }
 
 impl StaticAnalyzer for ComplexityAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["HIGH_COMPLEXITY", "FUNCTION_TOO_LONG"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         let mut violations = Vec::new();
         let mut parser = Parser::new();
@@ -202,12 +259,13 @@ impl StaticAnalyzer for ComplexityAnalyzer {
                     complexity += 1;
                 }
 
-                // NOTE: 5 is the absolute generation floor. The configured complexity_threshold
-                // can suppress violations above this floor but cannot lower it below 5.
-                if complexity > 5 {
+                if self.thresholds.reports_complexity(complexity) {
                     violations.push(RuleViolation {
                         rule_name: "HIGH_COMPLEXITY".to_string(),
-                        message: format!("La función tiene una complejidad ciclomática de {} (máximo recomendado: 10).", complexity),
+                        message: format!(
+                            "La función tiene una complejidad ciclomática de {} (máximo recomendado: {}).",
+                            complexity, self.thresholds.complexity
+                        ),
                         level: RuleLevel::Error,
                         line: Some(func_node.start_position().row + 1),
                         symbol: None,
@@ -230,13 +288,12 @@ impl StaticAnalyzer for ComplexityAnalyzer {
                 let start_line = node.range().start_point.row;
                 let end_line = node.range().end_point.row;
                 let line_count = end_line.saturating_sub(start_line);
-                // NOTE: 10 is the absolute generation floor for function length.
-                if line_count > 10 {
+                if self.thresholds.reports_length(line_count) {
                     violations.push(RuleViolation {
                         rule_name: "FUNCTION_TOO_LONG".to_string(),
                         message: format!(
-                            "Función de {} líneas (máximo recomendado: 50). Considera dividirla en funciones más pequeñas.",
-                            line_count
+                            "Función de {} líneas (máximo recomendado: {}). Considera dividirla en funciones más pequeñas.",
+                            line_count, self.thresholds.function_length
                         ),
                         level: RuleLevel::Warning,
                         line: Some(start_line + 1),
@@ -339,6 +396,10 @@ impl NamingAnalyzerWithFramework {
 }
 
 impl StaticAnalyzer for NamingAnalyzer {
+    fn rule_names(&self) -> &'static [&'static str] {
+        &["NAMING_CONVENTION"]
+    }
+
     fn analyze(&self, language: &Language, source_code: &str) -> Vec<RuleViolation> {
         // Default: TypeScript/camelCase
         NamingAnalyzerWithFramework::new("typescript").analyze(language, source_code)
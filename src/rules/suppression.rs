@@ -0,0 +1,127 @@
+//! Supresión inline de reglas mediante comentarios en el código fuente.
+//!
+//! - `// sentinel-ignore-next-line DEAD_CODE` — suprime la regla solo en la línea siguiente
+//! - `// sentinel-ignore-file UNUSED_IMPORT` — suprime la regla en todo el archivo
+//!
+//! Se aceptan comentarios `//` (TS/JS/Go) y `#` (Python). Varias reglas pueden
+//! separarse por espacios o comas: `// sentinel-ignore-next-line DEAD_CODE, UNUSED_IMPORT`.
+
+use crate::rules::RuleViolation;
+use std::collections::HashMap;
+
+const NEXT_LINE_DIRECTIVE: &str = "sentinel-ignore-next-line";
+const FILE_DIRECTIVE: &str = "sentinel-ignore-file";
+
+#[derive(Debug, Default)]
+pub struct InlineSuppressions {
+    /// Línea (1-based) → reglas suprimidas en esa línea
+    next_line: HashMap<usize, Vec<String>>,
+    file_wide: Vec<String>,
+}
+
+impl InlineSuppressions {
+    pub fn parse(content: &str) -> Self {
+        let mut suppressions = Self::default();
+
+        for (idx, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let Some(body) = trimmed
+                .strip_prefix("//")
+                .or_else(|| trimmed.strip_prefix('#'))
+            else {
+                continue;
+            };
+            let body = body.trim();
+
+            if let Some(rules) = body.strip_prefix(NEXT_LINE_DIRECTIVE) {
+                let rules = parse_rule_list(rules);
+                if !rules.is_empty() {
+                    // idx es 0-based → la línea del comentario es idx + 1, la siguiente idx + 2
+                    suppressions.next_line.entry(idx + 2).or_default().extend(rules);
+                }
+            } else if let Some(rules) = body.strip_prefix(FILE_DIRECTIVE) {
+                suppressions.file_wide.extend(parse_rule_list(rules));
+            }
+        }
+
+        suppressions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next_line.is_empty() && self.file_wide.is_empty()
+    }
+
+    pub fn is_suppressed(&self, violation: &RuleViolation) -> bool {
+        if self.file_wide.iter().any(|r| r == &violation.rule_name) {
+            return true;
+        }
+        violation
+            .line
+            .and_then(|l| self.next_line.get(&l))
+            .map(|rules| rules.iter().any(|r| r == &violation.rule_name))
+            .unwrap_or(false)
+    }
+}
+
+fn parse_rule_list(raw: &str) -> Vec<String> {
+    // La directiva debe ir seguida de un separador (evita `sentinel-ignore-filex`)
+    if !raw.is_empty() && !raw.starts_with(char::is_whitespace) {
+        return Vec::new();
+    }
+    raw.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|r| !r.is_empty())
+        .map(|r| r.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RuleLevel;
+
+    fn violation(rule: &str, line: Option<usize>) -> RuleViolation {
+        RuleViolation {
+            rule_name: rule.to_string(),
+            message: String::new(),
+            level: RuleLevel::Warning,
+            line,
+            symbol: None,
+            value: None,
+        }
+    }
+
+    #[test]
+    fn test_next_line_only_suppresses_following_line() {
+        let src = "// sentinel-ignore-next-line DEAD_CODE\nfunction a() {}\nfunction b() {}\n";
+        let s = InlineSuppressions::parse(src);
+        assert!(s.is_suppressed(&violation("DEAD_CODE", Some(2))));
+        assert!(!s.is_suppressed(&violation("DEAD_CODE", Some(3))), "line after next must not be suppressed");
+        assert!(!s.is_suppressed(&violation("DEAD_CODE", Some(1))));
+        assert!(!s.is_suppressed(&violation("UNUSED_IMPORT", Some(2))), "other rules must not be suppressed");
+        assert!(!s.is_suppressed(&violation("DEAD_CODE", None)));
+    }
+
+    #[test]
+    fn test_file_wide_suppresses_every_line() {
+        let src = "import x from 'x';\n// sentinel-ignore-file UNUSED_IMPORT\nimport y from 'y';\n";
+        let s = InlineSuppressions::parse(src);
+        assert!(s.is_suppressed(&violation("UNUSED_IMPORT", Some(1))));
+        assert!(s.is_suppressed(&violation("UNUSED_IMPORT", Some(3))));
+        assert!(s.is_suppressed(&violation("UNUSED_IMPORT", None)));
+        assert!(!s.is_suppressed(&violation("DEAD_CODE", Some(1))));
+    }
+
+    #[test]
+    fn test_hash_comments_and_multiple_rules() {
+        let src = "    # sentinel-ignore-next-line DEAD_CODE, HIGH_COMPLEXITY\ndef f():\n    pass\n";
+        let s = InlineSuppressions::parse(src);
+        assert!(s.is_suppressed(&violation("DEAD_CODE", Some(2))));
+        assert!(s.is_suppressed(&violation("HIGH_COMPLEXITY", Some(2))));
+    }
+
+    #[test]
+    fn test_malformed_directive_is_ignored() {
+        let s = InlineSuppressions::parse("// sentinel-ignore-filex DEAD_CODE\n// sentinel-ignore-next-line\nx\n");
+        assert!(s.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! Resolución de secretos de la configuración (`api_key`).
+//!
+//! Además de la clave en texto plano (compatibilidad), se aceptan referencias:
+//! - `env:VAR_NAME` — variable de entorno
+//! - `file:/ruta/al/secreto` — contenido del archivo (sin espacios finales)
+//! - `keyring:servicio/cuenta` — llavero del sistema (Keychain, Credential Manager, keyutils)
+//!
+//! Se resuelven al construir el proveedor, nunca al cargar el config: `.sentinelrc.toml`
+//! solo guarda la referencia.
+
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretSource {
+    Empty,
+    Plain,
+    Env(String),
+    File(PathBuf),
+    Keyring { service: String, account: String },
+}
+
+impl SecretSource {
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        if value.is_empty() {
+            Self::Empty
+        } else if let Some(var) = value.strip_prefix("env:") {
+            Self::Env(var.trim().to_string())
+        } else if let Some(path) = value.strip_prefix("file:") {
+            Self::File(PathBuf::from(path.trim()))
+        } else if let Some(entry) = value.strip_prefix("keyring:") {
+            let (service, account) = entry.split_once('/').unwrap_or((entry, ""));
+            Self::Keyring { service: service.trim().to_string(), account: account.trim().to_string() }
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// Método de resolución, apto para mostrar (nunca incluye el secreto).
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Empty => "none".to_string(),
+            Self::Plain => "plain".to_string(),
+            Self::Env(var) => format!("env:{}", var),
+            Self::File(path) => format!("file:{}", path.display()),
+            Self::Keyring { service, account } => format!("keyring:{}/{}", service, account),
+        }
+    }
+}
+
+/// Valor real de un secreto de la config. Texto plano y vacío se devuelven tal cual.
+pub fn resolve_secret(value: &str) -> anyhow::Result<String> {
+    match SecretSource::parse(value) {
+        SecretSource::Empty => Ok(String::new()),
+        SecretSource::Plain => Ok(value.trim().to_string()),
+        SecretSource::Env(var) => std::env::var(&var)
+            .map_err(|_| anyhow::anyhow!("la variable de entorno '{}' (api_key = \"env:{}\") no está definida", var, var)),
+        SecretSource::File(path) => std::fs::read_to_string(&path)
+            .map(|s| s.trim().to_string())
+            .map_err(|e| anyhow::anyhow!("no se pudo leer el secreto '{}': {}", path.display(), e)),
+        SecretSource::Keyring { service, account } => {
+            if service.is_empty() || account.is_empty() {
+                anyhow::bail!("formato de keyring inválido: usa 'keyring:servicio/cuenta'");
+            }
+            keyring::Entry::new(&service, &account)
+                .and_then(|entry| entry.get_password())
+                .map_err(|e| anyhow::anyhow!("keyring '{}/{}': {}", service, account, e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_secret_resolution() {
+        // SAFETY: variable exclusiva de este test
+        unsafe { std::env::set_var("SENTINEL_TEST_SECRET_KEY", "sk-from-env") };
+        assert_eq!(resolve_secret("env:SENTINEL_TEST_SECRET_KEY").unwrap(), "sk-from-env");
+        assert_eq!(SecretSource::parse("env:SENTINEL_TEST_SECRET_KEY").describe(), "env:SENTINEL_TEST_SECRET_KEY");
+
+        let err = resolve_secret("env:SENTINEL_TEST_SECRET_MISSING").unwrap_err().to_string();
+        assert!(err.contains("SENTINEL_TEST_SECRET_MISSING"));
+    }
+
+    #[test]
+    fn test_file_secret_resolution_and_plain_fallback() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("anthropic.key");
+        std::fs::write(&path, "sk-from-file\n").unwrap();
+        assert_eq!(resolve_secret(&format!("file:{}", path.display())).unwrap(), "sk-from-file");
+        assert!(resolve_secret("file:/nonexistent/sentinel.key").is_err());
+
+        assert_eq!(resolve_secret("sk-plain").unwrap(), "sk-plain");
+        assert_eq!(SecretSource::parse("sk-plain").describe(), "plain");
+        assert_eq!(
+            SecretSource::parse("keyring:sentinel/anthropic"),
+            SecretSource::Keyring { service: "sentinel".into(), account: "anthropic".into() }
+        );
+    }
+}
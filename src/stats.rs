@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -11,6 +12,15 @@ pub struct SentinelStats {
     pub tiempo_estimado_ahorrado_mins: u32,
     pub total_cost_usd: f64,
     pub total_tokens_used: u64,
+    /// Tokens y costo por modelo que efectivamente respondió (incluye fallbacks)
+    #[serde(default)]
+    pub uso_por_modelo: BTreeMap<String, UsoModelo>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct UsoModelo {
+    pub tokens: u64,
+    pub cost_usd: f64,
 }
 
 impl SentinelStats {
@@ -23,6 +33,15 @@ impl SentinelStats {
         }
     }
 
+    /// Acumula tokens/costo en los totales y en el desglose del modelo indicado.
+    pub fn registrar_uso(&mut self, modelo: &str, tokens: u64, cost_usd: f64) {
+        self.total_tokens_used += tokens;
+        self.total_cost_usd += cost_usd;
+        let uso = self.uso_por_modelo.entry(modelo.to_string()).or_default();
+        uso.tokens += tokens;
+        uso.cost_usd += cost_usd;
+    }
+
     pub fn guardar(&self, path: &Path) {
         let stats_path = path.join(".sentinel_stats.json");
         if let Ok(content) = serde_json::to_string_pretty(self) {
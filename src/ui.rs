@@ -137,7 +137,7 @@ pub fn mostrar_ayuda(config: Option<&SentinelConfig>) {
     );
     println!(
         "{}",
-        "  k       Re-intentar conexión Knowledge Base (Qdrant)".dimmed()
+        "  k       Re-indexar y sincronizar Knowledge Base (Qdrant)".dimmed()
     );
 
     // Mostrar comando T solo si hay testing configurado
@@ -286,16 +286,7 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
 
         config.primary_model.provider = provider_str.to_string();
 
-        let default_url = match provider_str {
-            "anthropic" => "https://api.anthropic.com".to_string(),
-            "gemini" => "https://generativelanguage.googleapis.com".to_string(),
-            "openai" => "https://api.openai.com/v1".to_string(),
-            "groq" => "https://api.groq.com/openai/v1".to_string(),
-            "ollama" => "http://localhost:11434".to_string(),
-            "kimi" => "https://api.moonshot.ai/v1".to_string(),
-            "deepseek" => "https://api.deepseek.com".to_string(),
-            _ => "".to_string(),
-        };
+        let default_url = crate::config::default_provider_url(provider_str).to_string();
 
         let env_url = std::env::var(format!("{}_BASE_URL", provider_str.to_uppercase())).ok();
         let env_key = std::env::var(format!("{}_API_KEY", provider_str.to_uppercase())).ok();
@@ -321,16 +312,7 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
                 .interact_text()
                 .unwrap_or_default();
 
-        let default_model = match provider_str {
-            "anthropic" => "claude-3-5-sonnet-20241022".to_string(),
-            "gemini" => "gemini-2.0-flash".to_string(),
-            "openai" => "gpt-4o".to_string(),
-            "groq" => "llama3-70b-8192".to_string(),
-            "ollama" => "llama3".to_string(),
-            "kimi" => "moonshot-v1-8k".to_string(),
-            "deepseek" => "deepseek-coder".to_string(),
-            _ => "".to_string(),
-        };
+        let default_model = crate::config::default_provider_model(provider_str).to_string();
 
         // 2. Intentar obtener modelos disponibles dinámicamente
         println!("🔍 Conectando con {} para obtener modelos...", provider_str);
@@ -408,16 +390,7 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
             };
             fb.provider = fb_provider.to_string();
 
-            let fb_default_url = match fb_provider {
-                "anthropic" => "https://api.anthropic.com".to_string(),
-                "gemini" => "https://generativelanguage.googleapis.com".to_string(),
-                "openai" => "https://api.openai.com/v1".to_string(),
-                "groq" => "https://api.groq.com/openai/v1".to_string(),
-                "ollama" => "http://localhost:11434".to_string(),
-                "kimi" => "https://api.moonshot.ai/v1".to_string(),
-                "deepseek" => "https://api.deepseek.com".to_string(),
-                _ => "".to_string(),
-            };
+            let fb_default_url = crate::config::default_provider_url(fb_provider).to_string();
 
             fb.url = dialoguer::Input::with_theme(&dialoguer::theme::ColorfulTheme::default())
                 .with_prompt(format!("URL de la API para fallback ({})", fb_provider))